@@ -12,6 +12,7 @@ use flowy_database2::entities::*;
 use flowy_database2::event_map::DatabaseEvent;
 use flowy_database2::services::cell::CellBuilder;
 use flowy_database2::services::field::checklist_filter::ChecklistCellInsertChangeset;
+use flowy_database2::services::field::Person;
 use flowy_database2::services::share::csv::CSVFormat;
 use flowy_folder::entities::*;
 use flowy_folder::event_map::FolderEvent;
@@ -277,6 +278,8 @@ impl EventIntegrationTest {
         row_position,
         group_id: None,
         data: data.unwrap_or_default(),
+        sub_group_id: None,
+        template_id: None,
       })
       .async_send()
       .await
@@ -572,12 +575,14 @@ impl EventIntegrationTest {
     &self,
     database_id: String,
     row_ids: Vec<String>,
+    field_ids: Vec<String>,
   ) -> Vec<RelatedRowDataPB> {
     EventBuilder::new(self.clone())
       .event(DatabaseEvent::GetRelatedRowDatas)
       .payload(GetRelatedRowDataPB {
         database_id,
         row_ids,
+        field_ids,
       })
       .async_send()
       .await
@@ -709,6 +714,38 @@ impl<'a> TestRowBuilder<'a> {
     media_field.id.clone()
   }
 
+  pub fn insert_rating_cell(&mut self, stars: &str) -> String {
+    let rating_field = self.field_with_type(&FieldType::Rating);
+    self
+      .cell_build
+      .insert_text_cell(&rating_field.id, stars.to_string());
+    rating_field.id.clone()
+  }
+
+  pub fn insert_email_cell(&mut self, email: &str) -> String {
+    let email_field = self.field_with_type(&FieldType::Email);
+    self
+      .cell_build
+      .insert_text_cell(&email_field.id, email.to_string());
+    email_field.id.clone()
+  }
+
+  pub fn insert_phone_cell(&mut self, phone: &str) -> String {
+    let phone_field = self.field_with_type(&FieldType::Phone);
+    self
+      .cell_build
+      .insert_text_cell(&phone_field.id, phone.to_string());
+    phone_field.id.clone()
+  }
+
+  pub fn insert_person_cell(&mut self, persons: Vec<Person>) -> String {
+    let person_field = self.field_with_type(&FieldType::Person);
+    self
+      .cell_build
+      .insert_person_cell(&person_field.id, persons);
+    person_field.id.clone()
+  }
+
   pub fn field_with_type(&self, field_type: &FieldType) -> Field {
     self
       .fields
@@ -909,9 +909,17 @@ async fn get_detailed_relation_cell_data() {
 
   // using the row ids, get the row data
   let rows = test
-    .get_related_row_data(origin_database.id.clone(), cell.row_ids)
+    .get_related_row_data(
+      origin_database.id.clone(),
+      cell.row_ids,
+      vec![origin_fields.items[0].id.clone()],
+    )
     .await;
 
   assert_eq!(rows.len(), 1);
   assert_eq!(rows[0].name, "hello world");
+  assert_eq!(
+    rows[0].cells.get(&origin_fields.items[0].id).unwrap(),
+    "hello world"
+  );
 }
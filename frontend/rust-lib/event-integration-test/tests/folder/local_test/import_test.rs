@@ -41,6 +41,32 @@ async fn import_10240_row_csv_file_test() {
   assert_eq!(database.rows.len(), 10240);
 }
 
+#[tokio::test]
+async fn import_10240_row_csv_file_from_path_test() {
+  let file_name = "csv_10240r_15c.csv".to_string();
+  let csv_file_path = unzip("./tests/asset", &file_name).unwrap();
+
+  let test = EventIntegrationTest::new_with_name(DEFAULT_NAME).await;
+  test.sign_up_as_anon().await;
+
+  let workspace_id = test.get_current_workspace().await.id;
+  let import_data = ImportPayloadPB {
+    parent_view_id: workspace_id,
+    items: vec![ImportItemPayloadPB {
+      name: file_name,
+      data: None,
+      file_path: Some(csv_file_path.to_str().unwrap().to_string()),
+      view_layout: ViewLayoutPB::Grid,
+      import_type: ImportTypePB::CSV,
+    }],
+  };
+
+  let views = test.import_data(import_data).await;
+  let view_id = views[0].clone().id;
+  let database = test.get_database(&view_id).await;
+  assert_eq!(database.rows.len(), 10240);
+}
+
 fn gen_import_data(file_name: String, csv_string: String, workspace_id: String) -> ImportPayloadPB {
   ImportPayloadPB {
     parent_view_id: workspace_id.clone(),
@@ -1,13 +1,21 @@
+use crate::deps_resolve::database_snapshot_sql::{
+  DatabaseSnapshotMetaRow, DatabaseSnapshotRow, DatabaseSnapshotSql,
+};
 use af_local_ai::ai_ops::{LocalAITranslateItem, LocalAITranslateRowData};
+use collab_document::blocks::DocumentData;
 use collab_integrate::collab_builder::AppFlowyCollabBuilder;
 use collab_integrate::CollabKVDB;
 use flowy_ai::ai_manager::AIManager;
-use flowy_database2::{DatabaseManager, DatabaseUser};
+use flowy_database2::services::snapshot::entities::{DatabaseSnapshotData, DatabaseSnapshotMeta};
+use flowy_database2::{
+  DatabaseManager, DatabaseRowDocumentService, DatabaseSnapshotService, DatabaseUser,
+};
 use flowy_database_pub::cloud::{
   DatabaseAIService, DatabaseCloudService, SummaryRowContent, TranslateRowContent,
   TranslateRowResponse,
 };
-use flowy_error::FlowyError;
+use flowy_document::manager::DocumentManager;
+use flowy_error::{FlowyError, FlowyResult};
 use flowy_user::services::authenticate_user::AuthenticateUser;
 use lib_infra::async_trait::async_trait;
 use lib_infra::priority_task::TaskDispatcher;
@@ -25,8 +33,14 @@ impl DatabaseDepsResolver {
     cloud_service: Arc<dyn DatabaseCloudService>,
     ai_service: Arc<dyn DatabaseAIService>,
     ai_manager: Arc<AIManager>,
+    document_manager: Weak<DocumentManager>,
   ) -> Arc<DatabaseManager> {
-    let user = Arc::new(DatabaseUserImpl(authenticate_user));
+    let user = Arc::new(DatabaseUserImpl(authenticate_user.clone()));
+    let row_document_service = Arc::new(DatabaseRowDocumentImpl {
+      authenticate_user: authenticate_user.clone(),
+      document_manager,
+    });
+    let snapshot_service = Arc::new(DatabaseSnapshotImpl(authenticate_user));
     Arc::new(DatabaseManager::new(
       user,
       task_scheduler,
@@ -36,6 +50,8 @@ impl DatabaseDepsResolver {
         ai_manager,
         ai_service,
       }),
+      snapshot_service,
+      row_document_service,
     ))
   }
 }
@@ -129,4 +145,117 @@ impl DatabaseUser for DatabaseUserImpl {
   fn workspace_database_object_id(&self) -> Result<Uuid, FlowyError> {
     self.upgrade_user()?.workspace_database_object_id()
   }
+
+  fn user_email(&self) -> Result<String, FlowyError> {
+    self.upgrade_user()?.user_email()
+  }
+
+  fn timezone_id(&self) -> Result<String, FlowyError> {
+    Ok(self.upgrade_user()?.timezone_id())
+  }
+}
+
+struct DatabaseSnapshotImpl(Weak<AuthenticateUser>);
+
+impl DatabaseSnapshotImpl {
+  fn upgrade_user(&self) -> Result<Arc<AuthenticateUser>, FlowyError> {
+    let user = self
+      .0
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Unexpected error: UserSession is None"))?;
+    Ok(user)
+  }
+}
+
+impl DatabaseSnapshotService for DatabaseSnapshotImpl {
+  fn create_database_snapshot(
+    &self,
+    database_id: &str,
+    desc: &str,
+    data_hash: &str,
+    data: Vec<u8>,
+    max_snapshots: usize,
+  ) -> FlowyResult<()> {
+    let authenticate_user = self.upgrade_user()?;
+    let uid = authenticate_user.user_id()?;
+    let mut db = authenticate_user.get_sqlite_connection(uid)?;
+    let row = DatabaseSnapshotRow::new(
+      database_id.to_string(),
+      desc.to_string(),
+      data_hash.to_string(),
+      data,
+    );
+    DatabaseSnapshotSql::create(row, max_snapshots, &mut db)
+  }
+
+  fn get_database_snapshot_metas(
+    &self,
+    database_id: &str,
+  ) -> FlowyResult<Vec<DatabaseSnapshotMeta>> {
+    let authenticate_user = self.upgrade_user()?;
+    let uid = authenticate_user.user_id()?;
+    let mut db = authenticate_user.get_sqlite_connection(uid)?;
+    let rows = DatabaseSnapshotSql::get_all_snapshots(database_id, &mut db)?;
+    Ok(rows.into_iter().map(snapshot_meta_from_row).collect())
+  }
+
+  fn get_latest_database_snapshot_meta(
+    &self,
+    database_id: &str,
+  ) -> FlowyResult<Option<DatabaseSnapshotMeta>> {
+    let authenticate_user = self.upgrade_user()?;
+    let uid = authenticate_user.user_id()?;
+    let mut db = authenticate_user.get_sqlite_connection(uid)?;
+    let row = DatabaseSnapshotSql::get_latest_snapshot(database_id, &mut db)?;
+    Ok(row.map(snapshot_meta_from_row))
+  }
+
+  fn get_database_snapshot(&self, snapshot_id: &str) -> FlowyResult<DatabaseSnapshotData> {
+    let authenticate_user = self.upgrade_user()?;
+    let uid = authenticate_user.user_id()?;
+    let mut db = authenticate_user.get_sqlite_connection(uid)?;
+    DatabaseSnapshotSql::get_snapshot(snapshot_id, &mut db)?
+      .map(|row| DatabaseSnapshotData {
+        database_id: row.database_id,
+        data: row.data,
+      })
+      .ok_or(
+        FlowyError::record_not_found().with_context(format!("Snapshot {} not found", snapshot_id)),
+      )
+  }
+}
+
+fn snapshot_meta_from_row(row: DatabaseSnapshotMetaRow) -> DatabaseSnapshotMeta {
+  DatabaseSnapshotMeta {
+    snapshot_id: row.id,
+    database_id: row.database_id,
+    desc: row.desc,
+    data_hash: row.data_hash,
+    created_at: row.timestamp,
+  }
+}
+
+struct DatabaseRowDocumentImpl {
+  authenticate_user: Weak<AuthenticateUser>,
+  document_manager: Weak<DocumentManager>,
+}
+
+#[async_trait]
+impl DatabaseRowDocumentService for DatabaseRowDocumentImpl {
+  async fn create_row_document(&self, document_id: &Uuid, data: &str) -> FlowyResult<()> {
+    let uid = self
+      .authenticate_user
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Unexpected error: UserSession is None"))?
+      .user_id()?;
+    let document_manager = self
+      .document_manager
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Unexpected error: DocumentManager is None"))?;
+    let data = serde_json::from_str::<DocumentData>(data)?;
+    document_manager
+      .create_document(uid, document_id, Some(data))
+      .await?;
+    Ok(())
+  }
 }
@@ -0,0 +1,128 @@
+use diesel::SqliteConnection;
+use flowy_error::FlowyError;
+use flowy_sqlite::{
+  prelude::*,
+  schema::{database_snapshot_table, database_snapshot_table::dsl},
+};
+use lib_infra::util::timestamp;
+use tracing::debug;
+
+/// The local snapshot of a database. Unlike the rows of the `collab_snapshot` table, the data is
+/// not an encoded collab but the json of the database data, so the snapshots are stored in their
+/// own table.
+#[derive(PartialEq, Clone, Debug, Queryable, Identifiable, Insertable)]
+#[diesel(table_name = database_snapshot_table)]
+pub(crate) struct DatabaseSnapshotRow {
+  pub(crate) id: String,
+  pub(crate) database_id: String,
+  pub(crate) desc: String,
+  pub(crate) data_hash: String,
+  pub(crate) timestamp: i64,
+  pub(crate) data: Vec<u8>,
+}
+
+impl DatabaseSnapshotRow {
+  pub fn new(database_id: String, desc: String, data_hash: String, data: Vec<u8>) -> Self {
+    Self {
+      id: uuid::Uuid::new_v4().to_string(),
+      database_id,
+      desc,
+      data_hash,
+      timestamp: timestamp(),
+      data,
+    }
+  }
+}
+
+/// The columns of the [DatabaseSnapshotRow] without the data.
+#[derive(Queryable)]
+pub(crate) struct DatabaseSnapshotMetaRow {
+  pub(crate) id: String,
+  pub(crate) database_id: String,
+  pub(crate) desc: String,
+  pub(crate) data_hash: String,
+  pub(crate) timestamp: i64,
+}
+
+pub(crate) struct DatabaseSnapshotSql;
+impl DatabaseSnapshotSql {
+  /// Inserts the snapshot and deletes the oldest snapshots of the database, so that at most
+  /// `max_snapshots` snapshots are kept per database.
+  pub(crate) fn create(
+    row: DatabaseSnapshotRow,
+    max_snapshots: usize,
+    conn: &mut SqliteConnection,
+  ) -> Result<(), FlowyError> {
+    conn.immediate_transaction::<_, Error, _>(|conn| {
+      let database_id = row.database_id.clone();
+      insert_into(dsl::database_snapshot_table)
+        .values(row)
+        .execute(conn)?;
+
+      let expired_ids: Vec<String> = dsl::database_snapshot_table
+        .filter(dsl::database_id.eq(&database_id))
+        .order(dsl::timestamp.desc())
+        .select(dsl::id)
+        .offset(max_snapshots as i64)
+        .load(conn)?;
+      if !expired_ids.is_empty() {
+        debug!(
+          "Delete {} snapshots of database: {}",
+          expired_ids.len(),
+          database_id
+        );
+        delete(dsl::database_snapshot_table.filter(dsl::id.eq_any(expired_ids))).execute(conn)?;
+      }
+      Ok(())
+    })?;
+    Ok(())
+  }
+
+  pub(crate) fn get_all_snapshots(
+    database_id: &str,
+    conn: &mut SqliteConnection,
+  ) -> Result<Vec<DatabaseSnapshotMetaRow>, FlowyError> {
+    let rows = dsl::database_snapshot_table
+      .filter(dsl::database_id.eq(database_id))
+      .order(dsl::timestamp.desc())
+      .select((
+        dsl::id,
+        dsl::database_id,
+        dsl::desc,
+        dsl::data_hash,
+        dsl::timestamp,
+      ))
+      .load::<DatabaseSnapshotMetaRow>(conn)?;
+    Ok(rows)
+  }
+
+  pub(crate) fn get_latest_snapshot(
+    database_id: &str,
+    conn: &mut SqliteConnection,
+  ) -> Result<Option<DatabaseSnapshotMetaRow>, FlowyError> {
+    let row = dsl::database_snapshot_table
+      .filter(dsl::database_id.eq(database_id))
+      .order(dsl::timestamp.desc())
+      .select((
+        dsl::id,
+        dsl::database_id,
+        dsl::desc,
+        dsl::data_hash,
+        dsl::timestamp,
+      ))
+      .first::<DatabaseSnapshotMetaRow>(conn)
+      .optional()?;
+    Ok(row)
+  }
+
+  pub(crate) fn get_snapshot(
+    snapshot_id: &str,
+    conn: &mut SqliteConnection,
+  ) -> Result<Option<DatabaseSnapshotRow>, FlowyError> {
+    let row = dsl::database_snapshot_table
+      .filter(dsl::id.eq(snapshot_id))
+      .first::<DatabaseSnapshotRow>(conn)
+      .optional()?;
+    Ok(row)
+  }
+}
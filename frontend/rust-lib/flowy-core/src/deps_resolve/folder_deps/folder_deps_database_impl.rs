@@ -303,12 +303,9 @@ impl FolderOperationHandler for DatabaseFolderOperation {
       return Err(FlowyError::record_not_found().with_context("File not found"));
     }
 
-    let data = tokio::fs::read(file_path).await?;
-    let content =
-      String::from_utf8(data).map_err(|e| FlowyError::invalid_data().with_context(e))?;
     let _ = self
       .0
-      .import_csv(view_id.to_string(), content, CSVFormat::Original)
+      .import_csv_from_file(view_id.to_string(), path, HashMap::new())
       .await?;
     Ok(())
   }
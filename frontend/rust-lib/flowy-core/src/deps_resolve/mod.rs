@@ -12,6 +12,7 @@ mod document_deps;
 mod chat_deps;
 mod cloud_service_impl;
 mod database_deps;
+mod database_snapshot_sql;
 pub mod file_storage_deps;
 mod folder_deps;
 pub(crate) mod reminder_deps;
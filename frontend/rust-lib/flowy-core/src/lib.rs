@@ -192,6 +192,13 @@ impl AppFlowyCore {
         folder_query_service.clone(),
       );
 
+      let document_manager = DocumentDepsResolver::resolve(
+        Arc::downgrade(&authenticate_user),
+        collab_builder.clone(),
+        server_provider.clone(),
+        Arc::downgrade(&storage_manager.storage_service),
+      );
+
       let database_manager = DatabaseDepsResolver::resolve(
         Arc::downgrade(&authenticate_user),
         task_dispatcher.clone(),
@@ -199,17 +206,10 @@ impl AppFlowyCore {
         server_provider.clone(),
         server_provider.clone(),
         ai_manager.clone(),
+        Arc::downgrade(&document_manager),
       )
       .await;
 
-      let document_manager = DocumentDepsResolver::resolve(
-        Arc::downgrade(&authenticate_user),
-        &database_manager,
-        collab_builder.clone(),
-        server_provider.clone(),
-        Arc::downgrade(&storage_manager.storage_service),
-      );
-
       let user_manager = UserDepsResolver::resolve(
         authenticate_user.clone(),
         collab_builder.clone(),
@@ -79,3 +79,17 @@ impl CalculationChangesetNotificationPB {
     }
   }
 }
+
+/// The calculations of a group, sent when the rows of the group or the content of their cells
+/// change.
+#[derive(Debug, Default, ProtoBuf, Clone)]
+pub struct GroupCalculationsPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub group_id: String,
+
+  #[pb(index = 3)]
+  pub calculations: Vec<CalculationPB>,
+}
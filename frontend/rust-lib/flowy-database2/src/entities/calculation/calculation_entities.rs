@@ -69,13 +69,22 @@ impl std::convert::From<&Arc<Calculation>> for CalculationPB {
 pub enum CalculationType {
   #[default]
   Average = 0, // Number
-  Max = 1,           // Number
-  Median = 2,        // Number
-  Min = 3,           // Number
-  Sum = 4,           // Number
-  Count = 5,         // All
-  CountEmpty = 6,    // All
-  CountNonEmpty = 7, // All
+  Max = 1,                  // Number
+  Median = 2,               // Number
+  Min = 3,                  // Number
+  Sum = 4,                  // Number
+  Count = 5,                // All
+  CountEmpty = 6,           // All
+  CountNonEmpty = 7,        // All
+  CountUnique = 8,          // All
+  PercentEmpty = 9,         // All
+  PercentNotEmpty = 10,     // All
+  PercentChecked = 11,      // Checkbox
+  PercentUnchecked = 12,    // Checkbox
+  Earliest = 13,            // Date
+  Latest = 14,              // Date
+  DateRange = 15,           // Date
+  ChecklistCompletion = 16, // Checklist
 }
 
 impl Display for CalculationType {
@@ -127,13 +136,50 @@ impl CalculationType {
       | CalculationType::Average
       | CalculationType::Median
       | CalculationType::Sum => {
-        matches!(field_type, FieldType::Number)
+        matches!(
+          field_type,
+          FieldType::Number
+            | FieldType::Formula
+            | FieldType::Rollup
+            | FieldType::Rating
+            | FieldType::Time
+        )
       },
       // Exclude some fields from CountNotEmpty & CountEmpty
-      CalculationType::CountEmpty | CalculationType::CountNonEmpty => !matches!(
+      CalculationType::CountEmpty
+      | CalculationType::CountNonEmpty
+      | CalculationType::PercentEmpty
+      | CalculationType::PercentNotEmpty => !matches!(
         field_type,
-        FieldType::URL | FieldType::Checkbox | FieldType::CreatedTime | FieldType::LastEditedTime
+        FieldType::URL
+          | FieldType::Checkbox
+          | FieldType::CreatedTime
+          | FieldType::LastEditedTime
+          | FieldType::AutoNumber
       ),
+      // The cells of these fields are either always unique or can't be compared
+      CalculationType::CountUnique => !matches!(
+        field_type,
+        FieldType::Checkbox
+          | FieldType::Checklist
+          | FieldType::Media
+          | FieldType::CreatedTime
+          | FieldType::LastEditedTime
+          | FieldType::AutoNumber
+      ),
+      // Checkbox fields only
+      CalculationType::PercentChecked | CalculationType::PercentUnchecked => {
+        field_type == FieldType::Checkbox
+      },
+      // Date fields only
+      CalculationType::Earliest | CalculationType::Latest | CalculationType::DateRange => {
+        matches!(
+          field_type,
+          FieldType::DateTime | FieldType::CreatedTime | FieldType::LastEditedTime
+        )
+      },
+      // Checklist fields only
+      CalculationType::ChecklistCompletion => field_type == FieldType::Checklist,
       // All fields
       CalculationType::Count => true,
     }
@@ -158,6 +158,47 @@ pub struct CellChangesetPB {
   pub cell_changeset: String,
 }
 
+/// Updates many cells of many rows in one batch. The cells are updated like the `UpdateCell`
+/// event, but the filters and sorts of the views are applied once for the whole batch.
+#[derive(Debug, Clone, Default, ProtoBuf, Validate)]
+pub struct RepeatedCellChangesetPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub items: Vec<CellChangesetPB>,
+}
+
+/// Pastes a range of strings, e.g. copied from a spreadsheet, into the cells starting at the
+/// given cell. The rows and the fields of the range follow the order of the view.
+#[derive(Debug, Clone, Default, ProtoBuf, Validate)]
+pub struct PasteCellsPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  /// The row of the top left cell of the range
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub row_id: String,
+
+  /// The field of the top left cell of the range
+  #[pb(index = 3)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub field_id: String,
+
+  #[pb(index = 4)]
+  pub rows: Vec<PastedRowPB>,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct PastedRowPB {
+  /// The strings of the cells from left to right. The empty strings clear the cells.
+  #[pb(index = 1)]
+  pub cells: Vec<String>,
+}
+
 #[derive(Debug, Clone, Default, ProtoBuf)]
 pub struct CellChangesetNotifyPB {
   #[pb(index = 1)]
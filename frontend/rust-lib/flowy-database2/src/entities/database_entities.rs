@@ -172,6 +172,10 @@ pub struct MoveGroupRowPayloadPB {
 
   #[pb(index = 5)]
   pub from_group_id: String,
+
+  /// The sub group the row is moved to if the view is sub grouped
+  #[pb(index = 6, one_of)]
+  pub to_sub_group_id: Option<String>,
 }
 
 pub struct MoveGroupRowParams {
@@ -179,6 +183,7 @@ pub struct MoveGroupRowParams {
   pub from_row_id: RowId,
   pub from_group_id: String,
   pub to_group_id: String,
+  pub to_sub_group_id: Option<String>,
   pub to_row_id: Option<RowId>,
 }
 
@@ -196,6 +201,7 @@ impl TryInto<MoveGroupRowParams> for MoveGroupRowPayloadPB {
       view_id: view_id.0,
       to_group_id: to_group_id.0,
       from_group_id: from_group_id.0,
+      to_sub_group_id: self.to_sub_group_id,
       from_row_id: RowId::from(self.from_row_id),
       to_row_id: self.to_row_id.map(RowId::from),
     })
@@ -313,7 +319,7 @@ pub struct RepeatedDatabaseSnapshotPB {
 #[derive(Debug, Default, ProtoBuf)]
 pub struct DatabaseSnapshotPB {
   #[pb(index = 1)]
-  pub snapshot_id: i64,
+  pub snapshot_id: String,
 
   #[pb(index = 2)]
   pub snapshot_desc: String,
@@ -321,8 +327,37 @@ pub struct DatabaseSnapshotPB {
   #[pb(index = 3)]
   pub created_at: i64,
 
+  /// The encoded collab of the database. Empty for the local snapshots.
   #[pb(index = 4)]
   pub data: Vec<u8>,
+
+  /// Only the local snapshots can be restored with the RestoreDatabaseSnapshot event
+  #[pb(index = 5)]
+  pub is_local: bool,
+}
+
+#[derive(Debug, Default, ProtoBuf, Validate)]
+pub struct RestoreDatabaseSnapshotPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub snapshot_id: String,
+}
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct DatabaseUndoRedoPB {
+  #[pb(index = 1)]
+  pub can_undo: bool,
+
+  #[pb(index = 2)]
+  pub can_redo: bool,
+
+  /// False if there was nothing to undo or to redo
+  #[pb(index = 3)]
+  pub is_success: bool,
 }
 
 #[derive(Debug, Clone, Default, ProtoBuf)]
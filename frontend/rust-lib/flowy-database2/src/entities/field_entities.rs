@@ -441,6 +441,13 @@ pub enum FieldType {
   Translate = 12,
   Time = 13,
   Media = 14,
+  Formula = 15,
+  Rollup = 16,
+  Rating = 17,
+  Email = 18,
+  Phone = 19,
+  AutoNumber = 20,
+  Person = 21,
 }
 
 impl Display for FieldType {
@@ -484,6 +491,13 @@ impl FieldType {
       FieldType::Translate => "Translate",
       FieldType::Time => "Time",
       FieldType::Media => "Media",
+      FieldType::Formula => "Formula",
+      FieldType::Rollup => "Rollup",
+      FieldType::Rating => "Rating",
+      FieldType::Email => "Email",
+      FieldType::Phone => "Phone",
+      FieldType::AutoNumber => "ID",
+      FieldType::Person => "Person",
     };
     s.to_string()
   }
@@ -548,8 +562,36 @@ impl FieldType {
     matches!(self, FieldType::Media)
   }
 
+  pub fn is_formula(&self) -> bool {
+    matches!(self, FieldType::Formula)
+  }
+
+  pub fn is_rollup(&self) -> bool {
+    matches!(self, FieldType::Rollup)
+  }
+
+  pub fn is_rating(&self) -> bool {
+    matches!(self, FieldType::Rating)
+  }
+
+  pub fn is_email(&self) -> bool {
+    matches!(self, FieldType::Email)
+  }
+
+  pub fn is_phone(&self) -> bool {
+    matches!(self, FieldType::Phone)
+  }
+
+  pub fn is_auto_number(&self) -> bool {
+    matches!(self, FieldType::AutoNumber)
+  }
+
+  pub fn is_person(&self) -> bool {
+    matches!(self, FieldType::Person)
+  }
+
   pub fn can_be_group(&self) -> bool {
-    self.is_select_option() || self.is_checkbox() || self.is_url()
+    self.is_select_option() || self.is_checkbox() || self.is_url() || self.is_person()
   }
 
   pub fn is_auto_update(&self) -> bool {
@@ -20,6 +20,16 @@ pub struct DateFilterPB {
 
   #[pb(index = 4, one_of)]
   pub timestamp: Option<i64>,
+
+  /// The range of dates of the [DateFilterConditionPB::DateStartsInRelativeRange] and
+  /// [DateFilterConditionPB::DateEndsInRelativeRange] conditions.
+  #[pb(index = 5, one_of)]
+  pub relative_range: Option<RelativeDateRangePB>,
+
+  /// The number of days of the [RelativeDateRangePB::LastNDays] and
+  /// [RelativeDateRangePB::NextNDays] ranges.
+  #[pb(index = 6, one_of)]
+  pub num_of_days: Option<i64>,
 }
 
 #[derive(Deserialize, Serialize, Default, Clone, Debug)]
@@ -27,6 +37,8 @@ pub struct DateFilterContent {
   pub start: Option<i64>,
   pub end: Option<i64>,
   pub timestamp: Option<i64>,
+  pub relative_range: Option<u8>,
+  pub num_of_days: Option<i64>,
 }
 
 impl DateFilterContent {
@@ -63,6 +75,8 @@ pub enum DateFilterConditionPB {
   DateEndsBetween = 13,
   DateEndIsEmpty = 14,
   DateEndIsNotEmpty = 15,
+  DateStartsInRelativeRange = 16,
+  DateEndsInRelativeRange = 17,
 }
 
 impl DateFilterConditionPB {
@@ -76,7 +90,15 @@ impl DateFilterConditionPB {
         | Self::DateStartsOnOrAfter
         | Self::DateStartsBetween
         | Self::DateStartIsEmpty
-        | Self::DateStartIsNotEmpty,
+        | Self::DateStartIsNotEmpty
+        | Self::DateStartsInRelativeRange,
+    )
+  }
+
+  pub fn is_relative(&self) -> bool {
+    matches!(
+      self,
+      Self::DateStartsInRelativeRange | Self::DateEndsInRelativeRange
     )
   }
 }
@@ -108,6 +130,56 @@ impl std::convert::TryFrom<u8> for DateFilterConditionPB {
       13 => Ok(Self::DateEndsBetween),
       14 => Ok(Self::DateEndIsEmpty),
       15 => Ok(Self::DateEndIsNotEmpty),
+      16 => Ok(Self::DateStartsInRelativeRange),
+      17 => Ok(Self::DateEndsInRelativeRange),
+      _ => Err(ErrorCode::InvalidParams),
+    }
+  }
+}
+
+/// A range of dates relative to the current date, which is evaluated when the rows are filtered.
+/// The weeks start on Monday.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum RelativeDateRangePB {
+  #[default]
+  Today = 0,
+  Yesterday = 1,
+  Tomorrow = 2,
+  ThisWeek = 3,
+  LastWeek = 4,
+  NextWeek = 5,
+  ThisMonth = 6,
+  LastMonth = 7,
+  NextMonth = 8,
+  ThisYear = 9,
+  LastYear = 10,
+  NextYear = 11,
+  /// The last N days, including today.
+  LastNDays = 12,
+  /// The next N days, including today.
+  NextNDays = 13,
+}
+
+impl std::convert::TryFrom<u8> for RelativeDateRangePB {
+  type Error = ErrorCode;
+
+  fn try_from(value: u8) -> Result<Self, Self::Error> {
+    match value {
+      0 => Ok(Self::Today),
+      1 => Ok(Self::Yesterday),
+      2 => Ok(Self::Tomorrow),
+      3 => Ok(Self::ThisWeek),
+      4 => Ok(Self::LastWeek),
+      5 => Ok(Self::NextWeek),
+      6 => Ok(Self::ThisMonth),
+      7 => Ok(Self::LastMonth),
+      8 => Ok(Self::NextMonth),
+      9 => Ok(Self::ThisYear),
+      10 => Ok(Self::LastYear),
+      11 => Ok(Self::NextYear),
+      12 => Ok(Self::LastNDays),
+      13 => Ok(Self::NextNDays),
       _ => Err(ErrorCode::InvalidParams),
     }
   }
@@ -126,6 +198,10 @@ impl ParseFilterData for DateFilterPB {
       date_filter.start = content.start;
       date_filter.end = content.end;
       date_filter.timestamp = content.timestamp;
+      date_filter.relative_range = content
+        .relative_range
+        .and_then(|range| RelativeDateRangePB::try_from(range).ok());
+      date_filter.num_of_days = content.num_of_days;
     };
 
     date_filter
@@ -0,0 +1,65 @@
+use std::cmp::Ordering;
+
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+
+/// Compares the cell of the filtering field with the cell of another field of the same row, e.g.
+/// "Spent is greater than Estimate".
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct FieldComparisonPB {
+  /// The field whose cell is on the right side of the comparison
+  #[pb(index = 1)]
+  pub other_field_id: String,
+
+  #[pb(index = 2)]
+  pub condition: FieldComparisonConditionPB,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum FieldComparisonConditionPB {
+  #[default]
+  Equal = 0,
+  NotEqual = 1,
+  LessThan = 2,
+  LessThanOrEqualTo = 3,
+  GreaterThan = 4,
+  GreaterThanOrEqualTo = 5,
+}
+
+impl FieldComparisonConditionPB {
+  /// Returns true if the ordering of the cell of the filtering field relative to the cell of the
+  /// other field satisfies the condition.
+  pub fn is_satisfied(&self, ordering: Ordering) -> bool {
+    match self {
+      FieldComparisonConditionPB::Equal => ordering.is_eq(),
+      FieldComparisonConditionPB::NotEqual => ordering.is_ne(),
+      FieldComparisonConditionPB::LessThan => ordering.is_lt(),
+      FieldComparisonConditionPB::LessThanOrEqualTo => ordering.is_le(),
+      FieldComparisonConditionPB::GreaterThan => ordering.is_gt(),
+      FieldComparisonConditionPB::GreaterThanOrEqualTo => ordering.is_ge(),
+    }
+  }
+}
+
+impl std::convert::From<FieldComparisonConditionPB> for u32 {
+  fn from(value: FieldComparisonConditionPB) -> Self {
+    value as u32
+  }
+}
+
+impl std::convert::TryFrom<u8> for FieldComparisonConditionPB {
+  type Error = ErrorCode;
+
+  fn try_from(n: u8) -> Result<Self, Self::Error> {
+    match n {
+      0 => Ok(FieldComparisonConditionPB::Equal),
+      1 => Ok(FieldComparisonConditionPB::NotEqual),
+      2 => Ok(FieldComparisonConditionPB::LessThan),
+      3 => Ok(FieldComparisonConditionPB::LessThanOrEqualTo),
+      4 => Ok(FieldComparisonConditionPB::GreaterThan),
+      5 => Ok(FieldComparisonConditionPB::GreaterThanOrEqualTo),
+      _ => Err(ErrorCode::InvalidParams),
+    }
+  }
+}
@@ -1,9 +1,11 @@
 mod checkbox_filter;
 mod checklist_filter;
 mod date_filter;
+mod field_comparison_filter;
 mod filter_changeset;
 mod media_filter;
 mod number_filter;
+mod person_filter;
 mod relation_filter;
 mod select_option_filter;
 mod text_filter;
@@ -13,9 +15,11 @@ mod util;
 pub use checkbox_filter::*;
 pub use checklist_filter::*;
 pub use date_filter::*;
+pub use field_comparison_filter::*;
 pub use filter_changeset::*;
 pub use media_filter::*;
 pub use number_filter::*;
+pub use person_filter::*;
 pub use relation_filter::*;
 pub use select_option_filter::*;
 pub use text_filter::*;
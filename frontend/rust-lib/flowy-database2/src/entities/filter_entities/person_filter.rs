@@ -0,0 +1,71 @@
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+
+use crate::services::filter::ParseFilterData;
+
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct PersonFilterPB {
+  #[pb(index = 1)]
+  pub condition: PersonFilterConditionPB,
+
+  /// The emails of the workspace members. Ignored by [PersonFilterConditionPB::PersonIsMe],
+  /// which is evaluated with the email of the current user.
+  #[pb(index = 2)]
+  pub emails: Vec<String>,
+}
+
+/// [PersonFilterConditionPB::PersonContains] and [PersonFilterConditionPB::PersonDoesNotContain]
+/// check whether the cell contains any of the emails of the filter.
+#[derive(Debug, Default, Clone, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum PersonFilterConditionPB {
+  #[default]
+  PersonIsMe = 0,
+  PersonContains = 1,
+  PersonDoesNotContain = 2,
+  PersonIsEmpty = 3,
+  PersonIsNotEmpty = 4,
+}
+
+impl From<PersonFilterConditionPB> for u32 {
+  fn from(value: PersonFilterConditionPB) -> Self {
+    value as u32
+  }
+}
+
+impl TryFrom<u8> for PersonFilterConditionPB {
+  type Error = ErrorCode;
+
+  fn try_from(value: u8) -> Result<Self, Self::Error> {
+    match value {
+      0 => Ok(PersonFilterConditionPB::PersonIsMe),
+      1 => Ok(PersonFilterConditionPB::PersonContains),
+      2 => Ok(PersonFilterConditionPB::PersonDoesNotContain),
+      3 => Ok(PersonFilterConditionPB::PersonIsEmpty),
+      4 => Ok(PersonFilterConditionPB::PersonIsNotEmpty),
+      _ => Err(ErrorCode::InvalidParams),
+    }
+  }
+}
+
+impl ParseFilterData for PersonFilterPB {
+  fn parse(condition: u8, content: String) -> Self {
+    Self {
+      condition: PersonFilterConditionPB::try_from(condition)
+        .unwrap_or(PersonFilterConditionPB::PersonIsNotEmpty),
+      emails: content
+        .split(',')
+        .map(|email| email.trim())
+        .filter(|email| !email.is_empty())
+        .map(|email| email.to_string())
+        .collect(),
+    }
+  }
+}
+
+impl PersonFilterPB {
+  /// Serializes the emails of the filter into the content that is stored in the filter map.
+  pub fn content(&self) -> String {
+    self.emails.join(",")
+  }
+}
@@ -1,22 +1,73 @@
-use collab_database::{fields::Field, rows::Cell};
-use flowy_derive::ProtoBuf;
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
 
-use crate::services::filter::{ParseFilterData, PreFillCellsWithFilter};
+use crate::services::filter::ParseFilterData;
 
 #[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
 pub struct RelationFilterPB {
   #[pb(index = 1)]
-  pub condition: i64,
+  pub condition: RelationFilterConditionPB,
+
+  #[pb(index = 2)]
+  pub row_ids: Vec<String>,
+}
+
+/// [RelationFilterConditionPB::RelationContains] and
+/// [RelationFilterConditionPB::RelationDoesNotContain] check the first row id of the filter,
+/// [RelationFilterConditionPB::RelationContainsAny] and
+/// [RelationFilterConditionPB::RelationContainsAll] check all of them.
+#[derive(Debug, Default, Clone, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum RelationFilterConditionPB {
+  #[default]
+  RelationIsEmpty = 0,
+  RelationIsNotEmpty = 1,
+  RelationContains = 2,
+  RelationDoesNotContain = 3,
+  RelationContainsAny = 4,
+  RelationContainsAll = 5,
+}
+
+impl From<RelationFilterConditionPB> for u32 {
+  fn from(value: RelationFilterConditionPB) -> Self {
+    value as u32
+  }
+}
+
+impl TryFrom<u8> for RelationFilterConditionPB {
+  type Error = ErrorCode;
+
+  fn try_from(value: u8) -> Result<Self, Self::Error> {
+    match value {
+      0 => Ok(RelationFilterConditionPB::RelationIsEmpty),
+      1 => Ok(RelationFilterConditionPB::RelationIsNotEmpty),
+      2 => Ok(RelationFilterConditionPB::RelationContains),
+      3 => Ok(RelationFilterConditionPB::RelationDoesNotContain),
+      4 => Ok(RelationFilterConditionPB::RelationContainsAny),
+      5 => Ok(RelationFilterConditionPB::RelationContainsAll),
+      _ => Err(ErrorCode::InvalidParams),
+    }
+  }
 }
 
 impl ParseFilterData for RelationFilterPB {
-  fn parse(_condition: u8, _content: String) -> Self {
-    RelationFilterPB { condition: 0 }
+  fn parse(condition: u8, content: String) -> Self {
+    Self {
+      condition: RelationFilterConditionPB::try_from(condition)
+        .unwrap_or(RelationFilterConditionPB::RelationIsNotEmpty),
+      row_ids: content
+        .split(',')
+        .map(|row_id| row_id.trim())
+        .filter(|row_id| !row_id.is_empty())
+        .map(|row_id| row_id.to_string())
+        .collect(),
+    }
   }
 }
 
-impl PreFillCellsWithFilter for RelationFilterPB {
-  fn get_compliant_cell(&self, _field: &Field) -> Option<Cell> {
-    None
+impl RelationFilterPB {
+  /// Serializes the row ids of the filter into the content that is stored in the filter map.
+  pub fn content(&self) -> String {
+    self.row_ids.join(",")
   }
 }
@@ -10,6 +10,18 @@ pub struct TextFilterPB {
 
   #[pb(index = 2)]
   pub content: String,
+
+  /// The filters ignore the case of the letters by default.
+  #[pb(index = 3)]
+  pub case_sensitive: bool,
+
+  /// Ignores the leading and trailing whitespaces of the cells and of the content.
+  #[pb(index = 4)]
+  pub trim_whitespace: bool,
+
+  /// The texts of the [TextFilterConditionPB::TextMatchesAnyOf] condition.
+  #[pb(index = 5)]
+  pub values: Vec<String>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, ProtoBuf_Enum)]
@@ -24,6 +36,10 @@ pub enum TextFilterConditionPB {
   TextEndsWith = 5,
   TextIsEmpty = 6,
   TextIsNotEmpty = 7,
+  /// The content is a regular expression that must match a part of the text
+  TextMatchesRegex = 8,
+  /// The text is equal to one of the values of the filter
+  TextMatchesAnyOf = 9,
 }
 
 impl std::convert::From<TextFilterConditionPB> for u32 {
@@ -45,6 +61,8 @@ impl std::convert::TryFrom<u8> for TextFilterConditionPB {
       5 => Ok(TextFilterConditionPB::TextEndsWith),
       6 => Ok(TextFilterConditionPB::TextIsEmpty),
       7 => Ok(TextFilterConditionPB::TextIsNotEmpty),
+      8 => Ok(TextFilterConditionPB::TextMatchesRegex),
+      9 => Ok(TextFilterConditionPB::TextMatchesAnyOf),
       _ => Err(ErrorCode::InvalidParams),
     }
   }
@@ -52,10 +70,32 @@ impl std::convert::TryFrom<u8> for TextFilterConditionPB {
 
 impl ParseFilterData for TextFilterPB {
   fn parse(condition: u8, content: String) -> Self {
+    let condition =
+      TextFilterConditionPB::try_from(condition).unwrap_or(TextFilterConditionPB::TextIs);
+    if condition == TextFilterConditionPB::TextMatchesAnyOf {
+      return Self {
+        condition,
+        values: serde_json::from_str(&content).unwrap_or_default(),
+        ..Default::default()
+      };
+    }
+
     Self {
-      condition: TextFilterConditionPB::try_from(condition)
-        .unwrap_or(TextFilterConditionPB::TextIs),
+      condition,
       content,
+      ..Default::default()
+    }
+  }
+}
+
+impl TextFilterPB {
+  /// Serializes the filter into the content that is stored in the filter map. The values of the
+  /// [TextFilterConditionPB::TextMatchesAnyOf] condition are stored as a json array.
+  pub fn to_content(&self) -> String {
+    if self.condition == TextFilterConditionPB::TextMatchesAnyOf {
+      serde_json::to_string(&self.values).unwrap_or_default()
+    } else {
+      self.content.clone()
     }
   }
 }
@@ -9,8 +9,8 @@ use protobuf::ProtobufError;
 use validator::Validate;
 
 use crate::entities::{
-  CheckboxFilterPB, ChecklistFilterPB, DateFilterPB, FieldType, NumberFilterPB, RelationFilterPB,
-  SelectOptionFilterPB, TextFilterPB, TimeFilterPB,
+  CheckboxFilterPB, ChecklistFilterPB, DateFilterPB, FieldComparisonPB, FieldType, NumberFilterPB,
+  PersonFilterPB, RelationFilterPB, SelectOptionFilterPB, TextFilterPB, TimeFilterPB,
 };
 use crate::services::filter::{Filter, FilterChangeset, FilterInner};
 
@@ -23,6 +23,8 @@ pub enum FilterType {
   Data = 0,
   And = 1,
   Or = 2,
+  /// Matches the rows that don't match all of its children
+  Not = 3,
 }
 
 impl From<&FilterInner> for FilterType {
@@ -30,7 +32,8 @@ impl From<&FilterInner> for FilterType {
     match value {
       FilterInner::And { .. } => Self::And,
       FilterInner::Or { .. } => Self::Or,
-      FilterInner::Data { .. } => Self::Data,
+      FilterInner::Not { .. } => Self::Not,
+      FilterInner::Data { .. } | FilterInner::FieldComparison { .. } => Self::Data,
     }
   }
 }
@@ -60,17 +63,43 @@ pub struct FilterDataPB {
 
   #[pb(index = 3)]
   pub data: Vec<u8>,
+
+  /// If set, the cell of the field is compared with the cell of another field of the same row and
+  /// `data` is ignored.
+  #[pb(index = 4, one_of)]
+  pub comparison: Option<FieldComparisonPB>,
 }
 
 impl From<&Filter> for FilterPB {
   fn from(filter: &Filter) -> Self {
     match &filter.inner {
-      FilterInner::And { children } | FilterInner::Or { children } => Self {
+      FilterInner::And { children }
+      | FilterInner::Or { children }
+      | FilterInner::Not { children } => Self {
         id: filter.id.clone(),
         filter_type: FilterType::from(&filter.inner),
         children: children.iter().map(FilterPB::from).collect(),
         data: None,
       },
+      FilterInner::FieldComparison {
+        field_id,
+        field_type,
+        other_field_id,
+        condition,
+      } => Self {
+        id: filter.id.clone(),
+        filter_type: FilterType::Data,
+        children: vec![],
+        data: Some(FilterDataPB {
+          field_id: field_id.clone(),
+          field_type: *field_type,
+          data: vec![],
+          comparison: Some(FieldComparisonPB {
+            other_field_id: other_field_id.clone(),
+            condition: *condition,
+          }),
+        }),
+      },
       FilterInner::Data {
         field_id,
         field_type,
@@ -81,7 +110,7 @@ impl From<&Filter> for FilterPB {
             .cloned::<TextFilterPB>()
             .unwrap()
             .try_into(),
-          FieldType::Number => condition_and_content
+          FieldType::Number | FieldType::Rating => condition_and_content
             .cloned::<NumberFilterPB>()
             .unwrap()
             .try_into(),
@@ -123,6 +152,18 @@ impl From<&Filter> for FilterPB {
             .cloned::<MediaFilterPB>()
             .unwrap()
             .try_into(),
+          FieldType::Formula
+          | FieldType::Rollup
+          | FieldType::Email
+          | FieldType::Phone
+          | FieldType::AutoNumber => condition_and_content
+            .cloned::<TextFilterPB>()
+            .unwrap()
+            .try_into(),
+          FieldType::Person => condition_and_content
+            .cloned::<PersonFilterPB>()
+            .unwrap()
+            .try_into(),
         };
 
         Self {
@@ -133,6 +174,7 @@ impl From<&Filter> for FilterPB {
             field_id: field_id.clone(),
             field_type: *field_type,
             data: bytes.unwrap().to_vec(),
+            comparison: None,
           }),
         }
       },
@@ -144,6 +186,18 @@ impl TryFrom<FilterDataPB> for FilterInner {
   type Error = ErrorCode;
 
   fn try_from(value: FilterDataPB) -> Result<Self, Self::Error> {
+    if let Some(comparison) = value.comparison {
+      if comparison.other_field_id.is_empty() {
+        return Err(ErrorCode::FieldIdIsEmpty);
+      }
+      return Ok(Self::FieldComparison {
+        field_id: value.field_id,
+        field_type: value.field_type,
+        other_field_id: comparison.other_field_id,
+        condition: comparison.condition,
+      });
+    }
+
     let bytes: &[u8] = value.data.as_ref();
     let condition_and_content = match value.field_type {
       FieldType::RichText | FieldType::URL => {
@@ -152,7 +206,7 @@ impl TryFrom<FilterDataPB> for FilterInner {
       FieldType::Checkbox => {
         BoxAny::new(CheckboxFilterPB::try_from(bytes).map_err(|_| ErrorCode::ProtobufSerde)?)
       },
-      FieldType::Number => {
+      FieldType::Number | FieldType::Rating => {
         BoxAny::new(NumberFilterPB::try_from(bytes).map_err(|_| ErrorCode::ProtobufSerde)?)
       },
       FieldType::DateTime | FieldType::LastEditedTime | FieldType::CreatedTime => {
@@ -179,6 +233,16 @@ impl TryFrom<FilterDataPB> for FilterInner {
       FieldType::Media => {
         BoxAny::new(MediaFilterPB::try_from(bytes).map_err(|_| ErrorCode::ProtobufSerde)?)
       },
+      FieldType::Formula
+      | FieldType::Rollup
+      | FieldType::Email
+      | FieldType::Phone
+      | FieldType::AutoNumber => {
+        BoxAny::new(TextFilterPB::try_from(bytes).map_err(|_| ErrorCode::ProtobufSerde)?)
+      },
+      FieldType::Person => {
+        BoxAny::new(PersonFilterPB::try_from(bytes).map_err(|_| ErrorCode::ProtobufSerde)?)
+      },
     };
 
     Ok(Self::Data {
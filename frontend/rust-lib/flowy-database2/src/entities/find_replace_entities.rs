@@ -0,0 +1,87 @@
+use flowy_derive::ProtoBuf;
+use lib_infra::validator_fn::required_not_empty_str;
+use validator::Validate;
+
+/// Finds the occurrences of the query in the text of the cells. The whole database is searched
+/// unless `only_in_view` is set, in which case only the rows and the fields of the view are.
+#[derive(Debug, Clone, Default, ProtoBuf, Validate)]
+pub struct FindInDatabasePayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub query: String,
+
+  #[pb(index = 3)]
+  pub is_regex: bool,
+
+  #[pb(index = 4)]
+  pub is_case_sensitive: bool,
+
+  #[pb(index = 5)]
+  pub only_in_view: bool,
+}
+
+/// An occurrence of the query in a cell. The offsets are counted in characters of the text of
+/// the cell, the end is exclusive.
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct CellMatchPB {
+  #[pb(index = 1)]
+  pub row_id: String,
+
+  #[pb(index = 2)]
+  pub field_id: String,
+
+  #[pb(index = 3)]
+  pub start: i32,
+
+  #[pb(index = 4)]
+  pub end: i32,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RepeatedCellMatchPB {
+  #[pb(index = 1)]
+  pub items: Vec<CellMatchPB>,
+}
+
+/// Replaces the occurrences of the query found like [FindInDatabasePayloadPB]. The text of the
+/// text, url, email and phone cells is rewritten, and the select options are renamed, which
+/// renames them in every row of the database.
+#[derive(Debug, Clone, Default, ProtoBuf, Validate)]
+pub struct ReplaceInDatabasePayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub query: String,
+
+  #[pb(index = 3)]
+  pub is_regex: bool,
+
+  #[pb(index = 4)]
+  pub is_case_sensitive: bool,
+
+  #[pb(index = 5)]
+  pub only_in_view: bool,
+
+  #[pb(index = 6)]
+  pub replacement: String,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct ReplaceInDatabaseResultPB {
+  #[pb(index = 1)]
+  pub num_of_replaced_cells: i32,
+
+  #[pb(index = 2)]
+  pub num_of_renamed_options: i32,
+
+  /// The cells that matched but can't be rewritten from text, e.g. the number or date cells
+  #[pb(index = 3)]
+  pub num_of_skipped_cells: i32,
+}
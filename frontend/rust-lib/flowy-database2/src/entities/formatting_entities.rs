@@ -0,0 +1,150 @@
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+use lib_infra::validator_fn::required_not_empty_str;
+use validator::Validate;
+
+use crate::entities::{FilterDataPB, FilterPB, SelectOptionColorPB};
+use crate::services::formatting::{FormattingRule, FormattingRuleChangeset};
+
+/// A rule that colours the rows, or the cells of its field, matching its condition. The condition
+/// is written like the data of a filter, e.g. a [NumberFilterPB](crate::entities::NumberFilterPB)
+/// "is less than 0" for the negative balances.
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct FormattingRulePB {
+  #[pb(index = 1)]
+  pub id: String,
+
+  #[pb(index = 2)]
+  pub condition: FilterDataPB,
+
+  #[pb(index = 3)]
+  pub target: FormattingTargetPB,
+
+  #[pb(index = 4)]
+  pub color: SelectOptionColorPB,
+}
+
+impl std::convert::From<&FormattingRule> for FormattingRulePB {
+  fn from(rule: &FormattingRule) -> Self {
+    Self {
+      id: rule.id.clone(),
+      condition: FilterPB::from(&rule.condition).data.unwrap_or_default(),
+      target: rule.target,
+      color: rule.color.clone(),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum FormattingTargetPB {
+  /// Colours the whole row
+  #[default]
+  Row = 0,
+  /// Colours the cell of the field of the condition
+  Cell = 1,
+}
+
+impl std::convert::TryFrom<u8> for FormattingTargetPB {
+  type Error = ErrorCode;
+
+  fn try_from(n: u8) -> Result<Self, Self::Error> {
+    match n {
+      0 => Ok(FormattingTargetPB::Row),
+      1 => Ok(FormattingTargetPB::Cell),
+      _ => Err(ErrorCode::InvalidParams),
+    }
+  }
+}
+
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct RepeatedFormattingRulePB {
+  #[pb(index = 1)]
+  pub items: Vec<FormattingRulePB>,
+}
+
+impl std::convert::From<&Vec<FormattingRule>> for RepeatedFormattingRulePB {
+  fn from(rules: &Vec<FormattingRule>) -> Self {
+    Self {
+      items: rules.iter().map(FormattingRulePB::from).collect(),
+    }
+  }
+}
+
+#[derive(ProtoBuf, Debug, Default, Clone, Validate)]
+pub struct UpdateFormattingRulePayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  /// Create a new rule if the rule_id is None
+  #[pb(index = 2, one_of)]
+  #[validate(custom(function = "super::utils::validate_filter_id"))]
+  pub rule_id: Option<String>,
+
+  #[pb(index = 3)]
+  pub condition: FilterDataPB,
+
+  #[pb(index = 4)]
+  pub target: FormattingTargetPB,
+
+  #[pb(index = 5)]
+  pub color: SelectOptionColorPB,
+}
+
+impl TryFrom<UpdateFormattingRulePayloadPB> for FormattingRuleChangeset {
+  type Error = ErrorCode;
+
+  fn try_from(value: UpdateFormattingRulePayloadPB) -> Result<Self, Self::Error> {
+    if value.condition.field_id.is_empty() {
+      return Err(ErrorCode::FieldIdIsEmpty);
+    }
+
+    Ok(Self::Upsert {
+      rule_id: value.rule_id,
+      condition: value.condition.try_into()?,
+      target: value.target,
+      color: value.color,
+    })
+  }
+}
+
+#[derive(ProtoBuf, Debug, Default, Clone, Validate)]
+pub struct DeleteFormattingRulePayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "super::utils::validate_filter_id"))]
+  pub rule_id: String,
+}
+
+impl From<DeleteFormattingRulePayloadPB> for FormattingRuleChangeset {
+  fn from(value: DeleteFormattingRulePayloadPB) -> Self {
+    Self::Delete {
+      rule_id: value.rule_id,
+    }
+  }
+}
+
+/// The ids of the formatting rules matched by a row
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct RowFormattingPB {
+  #[pb(index = 1)]
+  pub row_id: String,
+
+  #[pb(index = 2)]
+  pub rule_ids: Vec<String>,
+}
+
+/// Sent with the [DidUpdateRowsFormatting](crate::notification::DatabaseNotification) notification.
+/// It only contains the rows whose matched rules changed.
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct RowsFormattingChangesetPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub rows: Vec<RowFormattingPB>,
+}
@@ -1,6 +1,6 @@
 use crate::{
   entities::FieldType,
-  services::group::{DateCondition, DateGroupConfiguration, Group},
+  services::group::{DateCondition, DateGroupConfiguration, Group, NumberGroupConfiguration},
 };
 use bytes::Bytes;
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
@@ -42,10 +42,32 @@ impl std::convert::From<Group> for GroupRecordPB {
   }
 }
 
-#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+#[derive(PartialEq, ProtoBuf, Debug, Default, Clone)]
 pub struct NumberGroupConfigurationPB {
   #[pb(index = 1)]
-  hide_empty: bool,
+  pub hide_empty: bool,
+
+  /// The width of the range of numbers of each group.
+  #[pb(index = 2)]
+  pub bucket_width: f64,
+}
+
+impl From<NumberGroupConfigurationPB> for NumberGroupConfiguration {
+  fn from(data: NumberGroupConfigurationPB) -> Self {
+    Self {
+      hide_empty: data.hide_empty,
+      bucket_width: data.bucket_width,
+    }
+  }
+}
+
+impl From<NumberGroupConfiguration> for NumberGroupConfigurationPB {
+  fn from(data: NumberGroupConfiguration) -> Self {
+    Self {
+      hide_empty: data.hide_empty,
+      bucket_width: data.bucket_width,
+    }
+  }
 }
 
 #[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
@@ -122,8 +144,12 @@ pub fn group_config_pb_to_json_str<T: Into<Bytes>>(
 ) -> FlowyResult<String> {
   let bytes = bytes.into();
   match field_type {
-    FieldType::DateTime => DateGroupConfigurationPB::try_from(bytes)
-      .map(|pb| DateGroupConfiguration::from(pb).to_json())?,
+    FieldType::DateTime | FieldType::CreatedTime | FieldType::LastEditedTime => {
+      DateGroupConfigurationPB::try_from(bytes)
+        .map(|pb| DateGroupConfiguration::from(pb).to_json())?
+    },
+    FieldType::Number => NumberGroupConfigurationPB::try_from(bytes)
+      .map(|pb| NumberGroupConfiguration::from(pb).to_json())?,
     _ => Ok("".to_string()),
   }
 }
@@ -136,6 +162,20 @@ pub fn group_config_json_to_pb(setting_content: String, field_type: &FieldType)
         .try_into()
         .unwrap()
     },
+    FieldType::CreatedTime | FieldType::LastEditedTime => {
+      let date_group_config =
+        DateGroupConfiguration::from_json(setting_content.as_ref()).unwrap_or_default();
+      DateGroupConfigurationPB::from(date_group_config)
+        .try_into()
+        .unwrap()
+    },
+    FieldType::Number => {
+      let number_group_config =
+        NumberGroupConfiguration::from_json(setting_content.as_ref()).unwrap_or_default();
+      NumberGroupConfigurationPB::from(number_group_config)
+        .try_into()
+        .unwrap()
+    },
     _ => Bytes::new(),
   }
 }
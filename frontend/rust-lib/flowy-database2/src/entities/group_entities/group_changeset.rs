@@ -21,6 +21,40 @@ pub struct GroupRowsNotificationPB {
   pub updated_rows: Vec<RowMetaPB>,
 }
 
+/// The rows that changed in one sub group of a group. The sub group ids are the same in every
+/// group, so both ids are needed to find the rows.
+#[derive(Debug, Default, ProtoBuf)]
+pub struct SubGroupRowsNotificationPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub group_id: String,
+
+  #[pb(index = 3)]
+  pub sub_group_id: String,
+
+  #[pb(index = 4)]
+  pub inserted_rows: Vec<InsertedRowPB>,
+
+  #[pb(index = 5)]
+  pub deleted_rows: Vec<String>,
+
+  #[pb(index = 6)]
+  pub updated_rows: Vec<RowMetaPB>,
+}
+
+impl SubGroupRowsNotificationPB {
+  pub fn new(view_id: String, group_id: String, sub_group_id: String) -> Self {
+    Self {
+      view_id,
+      group_id,
+      sub_group_id,
+      ..Default::default()
+    }
+  }
+}
+
 impl std::fmt::Display for GroupRowsNotificationPB {
   fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
     for inserted_row in &self.inserted_rows {
@@ -19,6 +19,13 @@ macro_rules! impl_into_field_type {
           12 => FieldType::Translate,
           13 => FieldType::Time,
           14 => FieldType::Media,
+          15 => FieldType::Formula,
+          16 => FieldType::Rollup,
+          17 => FieldType::Rating,
+          18 => FieldType::Email,
+          19 => FieldType::Phone,
+          20 => FieldType::AutoNumber,
+          21 => FieldType::Person,
           _ => {
             tracing::error!("🔴Can't parse FieldType from value: {}", ty);
             FieldType::RichText
@@ -62,6 +69,15 @@ macro_rules! impl_into_calculation_type {
           5 => CalculationType::Count,
           6 => CalculationType::CountEmpty,
           7 => CalculationType::CountNonEmpty,
+          8 => CalculationType::CountUnique,
+          9 => CalculationType::PercentEmpty,
+          10 => CalculationType::PercentNotEmpty,
+          11 => CalculationType::PercentChecked,
+          12 => CalculationType::PercentUnchecked,
+          13 => CalculationType::Earliest,
+          14 => CalculationType::Latest,
+          15 => CalculationType::DateRange,
+          16 => CalculationType::ChecklistCompletion,
           _ => {
             tracing::error!("🔴 Can't parse CalculationType from value: {}", ty);
             CalculationType::Average
@@ -7,10 +7,13 @@ mod field_entities;
 mod field_settings_entities;
 pub mod file_entities;
 pub mod filter_entities;
+mod find_replace_entities;
+mod formatting_entities;
 mod group_entities;
 pub mod parser;
 mod position_entities;
 mod row_entities;
+mod row_template_entities;
 pub mod setting_entities;
 mod share_entities;
 mod sort_entities;
@@ -29,9 +32,12 @@ pub use field_entities::*;
 pub use field_settings_entities::*;
 pub use file_entities::*;
 pub use filter_entities::*;
+pub use find_replace_entities::*;
+pub use formatting_entities::*;
 pub use group_entities::*;
 pub use position_entities::*;
 pub use row_entities::*;
+pub use row_template_entities::*;
 pub use setting_entities::*;
 pub use share_entities::*;
 pub use sort_entities::*;
@@ -12,6 +12,7 @@ use validator::Validate;
 
 use crate::entities::parser::NotEmptyStr;
 use crate::entities::position_entities::OrderObjectPositionPB;
+use crate::entities::FilterDataPB;
 use crate::services::database::{InsertedRow, UpdatedRow};
 
 use super::FileUploadTypePB;
@@ -72,6 +73,12 @@ pub struct RowMetaPB {
 
   #[pb(index = 6, one_of)]
   pub cover: Option<RowCoverPB>,
+
+  /// The ids of the formatting rules of the view matched by the row. It's only set when the view
+  /// is opened, the changes are sent with the `DidUpdateRowsFormatting` notification.
+  #[pb(index = 7)]
+  #[serde(default)]
+  pub formatting_rule_ids: Vec<String>,
 }
 
 #[derive(Debug, Default, Clone, ProtoBuf, Serialize, Deserialize)]
@@ -153,6 +160,7 @@ impl From<RowOrder> for RowMetaPB {
       is_document_empty: None,
       attachment_count: None,
       cover: None,
+      formatting_rule_ids: vec![],
     }
   }
 }
@@ -166,6 +174,7 @@ impl From<&Row> for RowMetaPB {
       cover: None,
       is_document_empty: None,
       attachment_count: None,
+      formatting_rule_ids: vec![],
     }
   }
 }
@@ -179,6 +188,7 @@ impl From<Row> for RowMetaPB {
       is_document_empty: None,
       attachment_count: None,
       cover: None,
+      formatting_rule_ids: vec![],
     }
   }
 }
@@ -192,6 +202,7 @@ impl From<RowDetail> for RowMetaPB {
       is_document_empty: Some(row_detail.meta.is_document_empty),
       attachment_count: Some(row_detail.meta.attachment_count),
       cover: row_detail.meta.cover.map(|cover| cover.into()),
+      formatting_rule_ids: vec![],
     }
   }
 }
@@ -205,6 +216,7 @@ impl From<&RowDetail> for RowMetaPB {
       is_document_empty: Some(row_detail.meta.is_document_empty),
       attachment_count: Some(row_detail.meta.attachment_count),
       cover: row_detail.meta.clone().cover.map(|cover| cover.into()),
+      formatting_rule_ids: vec![],
     }
   }
 }
@@ -478,6 +490,47 @@ pub struct CreateRowPayloadPB {
 
   #[pb(index = 4)]
   pub data: HashMap<String, String>,
+
+  /// The sub group of the row if the view is sub grouped
+  #[pb(index = 5, one_of)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub sub_group_id: Option<String>,
+
+  /// The row template of the database whose cells and document prefill the row. The cells of
+  /// `data` take precedence over the cells of the template.
+  #[pb(index = 6, one_of)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub template_id: Option<String>,
+}
+
+/// Creates the rows at the end of the view in one batch
+#[derive(ProtoBuf, Default, Validate)]
+pub struct CreateRowsPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub rows: Vec<RowCellsDataPB>,
+}
+
+#[derive(ProtoBuf, Default)]
+pub struct RowCellsDataPB {
+  /// The content of the cells by field id, like the data of the [CreateRowPayloadPB]
+  #[pb(index = 1)]
+  pub data: HashMap<String, String>,
+}
+
+/// Deletes the rows of the view matching the filter. Only the rows visible in the view are
+/// deleted.
+#[derive(ProtoBuf, Default, Validate)]
+pub struct DeleteRowsByFilterPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub filter: FilterDataPB,
 }
 
 #[derive(Debug, Default, Clone, ProtoBuf)]
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use flowy_derive::ProtoBuf;
+use flowy_error::ErrorCode;
+use lib_infra::validator_fn::required_not_empty_str;
+use validator::Validate;
+
+use crate::services::row_template::{RowTemplate, RowTemplateChangeset};
+
+/// A named set of cells that prefills the rows created from it, see the `template_id` of the
+/// [CreateRowPayloadPB](crate::entities::CreateRowPayloadPB).
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RowTemplatePB {
+  #[pb(index = 1)]
+  pub id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+
+  /// The content of the cells by field id, written like the data of a new row
+  #[pb(index = 3)]
+  pub cells: HashMap<String, String>,
+
+  /// The data of the row document, serialized in json. The document of a row created from the
+  /// template is created from this data.
+  #[pb(index = 4, one_of)]
+  pub document: Option<String>,
+}
+
+impl std::convert::From<&RowTemplate> for RowTemplatePB {
+  fn from(template: &RowTemplate) -> Self {
+    Self {
+      id: template.id.clone(),
+      name: template.name.clone(),
+      cells: template.cells.clone(),
+      document: template.document.clone(),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RepeatedRowTemplatePB {
+  #[pb(index = 1)]
+  pub items: Vec<RowTemplatePB>,
+}
+
+impl std::convert::From<&[RowTemplate]> for RepeatedRowTemplatePB {
+  fn from(templates: &[RowTemplate]) -> Self {
+    Self {
+      items: templates.iter().map(RowTemplatePB::from).collect(),
+    }
+  }
+}
+
+/// Creates a row template if `template_id` is None, otherwise replaces the template
+#[derive(Debug, Clone, Default, ProtoBuf, Validate)]
+pub struct UpdateRowTemplatePayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  #[pb(index = 2, one_of)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub template_id: Option<String>,
+
+  #[pb(index = 3)]
+  pub name: String,
+
+  #[pb(index = 4)]
+  pub cells: HashMap<String, String>,
+
+  #[pb(index = 5, one_of)]
+  pub document: Option<String>,
+}
+
+impl TryFrom<UpdateRowTemplatePayloadPB> for RowTemplateChangeset {
+  type Error = ErrorCode;
+
+  fn try_from(value: UpdateRowTemplatePayloadPB) -> Result<Self, Self::Error> {
+    let name = value.name.trim().to_string();
+    if name.is_empty() {
+      return Err(ErrorCode::InvalidParams);
+    }
+    Ok(Self::Upsert {
+      template_id: value.template_id,
+      name,
+      cells: value.cells,
+      document: value.document,
+    })
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf, Validate)]
+pub struct DeleteRowTemplatePayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub template_id: String,
+}
+
+impl From<DeleteRowTemplatePayloadPB> for RowTemplateChangeset {
+  fn from(value: DeleteRowTemplatePayloadPB) -> Self {
+    Self::Delete {
+      template_id: value.template_id,
+    }
+  }
+}
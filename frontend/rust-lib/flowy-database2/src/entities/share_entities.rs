@@ -7,6 +7,13 @@ pub enum DatabaseExportDataType {
 
   // DatabaseData
   RawDatabaseData = 1,
+
+  JSON = 2,
+
+  Markdown = 3,
+
+  // The data is stored in the bytes of the DatabaseExportDataPB
+  XLSX = 4,
 }
 
 #[derive(Debug, ProtoBuf, Default, Clone)]
@@ -16,4 +23,49 @@ pub struct DatabaseExportDataPB {
 
   #[pb(index = 2)]
   pub data: String,
+
+  /// The content of the binary export types, for example [DatabaseExportDataType::XLSX].
+  #[pb(index = 3)]
+  pub bytes: Vec<u8>,
+}
+
+#[derive(Debug, ProtoBuf, Default, Clone)]
+pub struct ExportViewCSVPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  /// Exports the rows of a board view group by group, each group starts with a header record.
+  #[pb(index = 2)]
+  pub include_group_headers: bool,
+}
+
+#[derive(Debug, ProtoBuf, Default, Clone)]
+pub struct ExportCSVToFilePayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  /// The path of the file that the csv is written to. The file is created if it doesn't exist.
+  #[pb(index = 2)]
+  pub path: String,
+}
+
+/// The progress of a streaming csv import or export. It is sent through the
+/// `DidUpdateCSVProgress` notification with the id of the view being imported or exported.
+#[derive(Debug, ProtoBuf, Default, Clone)]
+pub struct CSVProgressPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub processed_rows: i64,
+
+  /// A value between 0 and 1.
+  #[pb(index = 3)]
+  pub progress: f64,
+
+  #[pb(index = 4)]
+  pub is_finished: bool,
+
+  #[pb(index = 5)]
+  pub is_cancelled: bool,
 }
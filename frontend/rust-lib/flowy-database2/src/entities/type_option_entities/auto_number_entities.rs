@@ -0,0 +1,32 @@
+use flowy_derive::ProtoBuf;
+
+use crate::services::field::AutoNumberTypeOption;
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct AutoNumberTypeOptionPB {
+  /// The text shown before the number, for example `TASK-`.
+  #[pb(index = 1)]
+  pub prefix: String,
+
+  /// The number of the next created row. It's read-only, the counter can't be moved back.
+  #[pb(index = 2)]
+  pub next_number: i64,
+}
+
+impl From<AutoNumberTypeOption> for AutoNumberTypeOptionPB {
+  fn from(value: AutoNumberTypeOption) -> Self {
+    AutoNumberTypeOptionPB {
+      prefix: value.prefix,
+      next_number: value.next_number,
+    }
+  }
+}
+
+impl From<AutoNumberTypeOptionPB> for AutoNumberTypeOption {
+  fn from(value: AutoNumberTypeOptionPB) -> Self {
+    AutoNumberTypeOption {
+      prefix: value.prefix,
+      next_number: value.next_number.max(1),
+    }
+  }
+}
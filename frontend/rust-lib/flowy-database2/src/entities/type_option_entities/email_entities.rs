@@ -0,0 +1,23 @@
+use flowy_derive::ProtoBuf;
+
+use crate::services::field::EmailTypeOption;
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct EmailTypeOptionPB {
+  #[pb(index = 1)]
+  pub dummy: String,
+}
+
+impl From<EmailTypeOption> for EmailTypeOptionPB {
+  fn from(_data: EmailTypeOption) -> Self {
+    Self {
+      dummy: "".to_string(),
+    }
+  }
+}
+
+impl From<EmailTypeOptionPB> for EmailTypeOption {
+  fn from(_data: EmailTypeOptionPB) -> Self {
+    Self
+  }
+}
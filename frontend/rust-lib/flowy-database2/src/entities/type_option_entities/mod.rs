@@ -1,9 +1,16 @@
+mod auto_number_entities;
 mod checkbox_entities;
 mod checklist_entities;
 mod date_entities;
+mod email_entities;
+mod formula_entities;
 mod media_entities;
 mod number_entities;
+mod person_entities;
+mod phone_entities;
+mod rating_entities;
 mod relation_entities;
+mod rollup_entities;
 mod select_option_entities;
 mod summary_entities;
 mod text_entities;
@@ -12,12 +19,19 @@ mod timestamp_entities;
 mod translate_entities;
 mod url_entities;
 
+pub use auto_number_entities::*;
 pub use checkbox_entities::*;
 pub use checklist_entities::*;
 pub use date_entities::*;
+pub use email_entities::*;
+pub use formula_entities::*;
 pub use media_entities::*;
 pub use number_entities::*;
+pub use person_entities::*;
+pub use phone_entities::*;
+pub use rating_entities::*;
 pub use relation_entities::*;
+pub use rollup_entities::*;
 pub use select_option_entities::*;
 pub use summary_entities::*;
 pub use text_entities::*;
@@ -0,0 +1,97 @@
+use flowy_derive::ProtoBuf;
+
+use crate::entities::CellIdPB;
+use crate::services::field::{Person, PersonCellData, PersonTypeOption};
+
+/// A workspace member, as returned by the `GetWorkspaceMembers` event of the user module.
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct PersonPB {
+  #[pb(index = 1)]
+  pub email: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+
+  #[pb(index = 3, one_of)]
+  pub avatar_url: Option<String>,
+}
+
+impl From<Person> for PersonPB {
+  fn from(person: Person) -> Self {
+    Self {
+      email: person.email,
+      name: person.name,
+      avatar_url: person.avatar_url,
+    }
+  }
+}
+
+impl From<PersonPB> for Person {
+  fn from(person: PersonPB) -> Self {
+    Self {
+      email: person.email,
+      name: person.name,
+      avatar_url: person.avatar_url,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct PersonCellDataPB {
+  #[pb(index = 1)]
+  pub persons: Vec<PersonPB>,
+}
+
+impl From<PersonCellData> for PersonCellDataPB {
+  fn from(data: PersonCellData) -> Self {
+    Self {
+      persons: data.persons.into_iter().map(Into::into).collect(),
+    }
+  }
+}
+
+impl From<PersonCellDataPB> for PersonCellData {
+  fn from(data: PersonCellDataPB) -> Self {
+    Self {
+      persons: data.persons.into_iter().map(Into::into).collect(),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct PersonCellChangesetPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub cell_id: CellIdPB,
+
+  #[pb(index = 3)]
+  pub inserted_persons: Vec<PersonPB>,
+
+  #[pb(index = 4)]
+  pub removed_emails: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct PersonTypeOptionPB {
+  /// If true, the cell contains at most one person and inserting a person replaces it.
+  #[pb(index = 1)]
+  pub is_single: bool,
+}
+
+impl From<PersonTypeOption> for PersonTypeOptionPB {
+  fn from(value: PersonTypeOption) -> Self {
+    Self {
+      is_single: value.is_single,
+    }
+  }
+}
+
+impl From<PersonTypeOptionPB> for PersonTypeOption {
+  fn from(value: PersonTypeOptionPB) -> Self {
+    Self {
+      is_single: value.is_single,
+    }
+  }
+}
@@ -0,0 +1,23 @@
+use flowy_derive::ProtoBuf;
+
+use crate::services::field::PhoneTypeOption;
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct PhoneTypeOptionPB {
+  #[pb(index = 1)]
+  pub dummy: String,
+}
+
+impl From<PhoneTypeOption> for PhoneTypeOptionPB {
+  fn from(_data: PhoneTypeOption) -> Self {
+    Self {
+      dummy: "".to_string(),
+    }
+  }
+}
+
+impl From<PhoneTypeOptionPB> for PhoneTypeOption {
+  fn from(_data: PhoneTypeOptionPB) -> Self {
+    Self
+  }
+}
@@ -0,0 +1,24 @@
+use flowy_derive::ProtoBuf;
+
+use crate::services::field::RatingTypeOption;
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RatingTypeOptionPB {
+  /// The maximum number of stars of a cell.
+  #[pb(index = 1)]
+  pub max: i32,
+}
+
+impl From<RatingTypeOption> for RatingTypeOptionPB {
+  fn from(value: RatingTypeOption) -> Self {
+    RatingTypeOptionPB {
+      max: value.max as i32,
+    }
+  }
+}
+
+impl From<RatingTypeOptionPB> for RatingTypeOption {
+  fn from(value: RatingTypeOptionPB) -> Self {
+    RatingTypeOption::new(value.max as i64)
+  }
+}
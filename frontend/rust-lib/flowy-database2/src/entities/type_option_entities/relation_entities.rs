@@ -1,6 +1,7 @@
 use collab_database::fields::relation_type_option::RelationTypeOption;
 use collab_database::template::relation_parse::RelationCellData;
 use flowy_derive::ProtoBuf;
+use std::collections::HashMap;
 
 use crate::entities::CellIdPB;
 
@@ -70,6 +71,10 @@ pub struct RelatedRowDataPB {
 
   #[pb(index = 2)]
   pub name: String,
+
+  /// The cells of the fields requested in [GetRelatedRowDataPB::field_ids], keyed by field id
+  #[pb(index = 3)]
+  pub cells: HashMap<String, String>,
 }
 
 #[derive(Debug, Clone, Default, ProtoBuf)]
@@ -85,4 +90,8 @@ pub struct GetRelatedRowDataPB {
 
   #[pb(index = 2)]
   pub row_ids: Vec<String>,
+
+  /// The fields of the related database whose cells are returned along with the primary field
+  #[pb(index = 3)]
+  pub field_ids: Vec<String>,
 }
@@ -0,0 +1,70 @@
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+
+use crate::services::field::{RollupCalculation, RollupTypeOption};
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RollupTypeOptionPB {
+  #[pb(index = 1)]
+  pub relation_field_id: String,
+
+  #[pb(index = 2)]
+  pub target_field_id: String,
+
+  #[pb(index = 3)]
+  pub calculation: RollupCalculationPB,
+}
+
+impl From<RollupTypeOption> for RollupTypeOptionPB {
+  fn from(value: RollupTypeOption) -> Self {
+    RollupTypeOptionPB {
+      relation_field_id: value.relation_field_id,
+      target_field_id: value.target_field_id,
+      calculation: value.calculation.into(),
+    }
+  }
+}
+
+impl From<RollupTypeOptionPB> for RollupTypeOption {
+  fn from(value: RollupTypeOptionPB) -> Self {
+    RollupTypeOption {
+      relation_field_id: value.relation_field_id,
+      target_field_id: value.target_field_id,
+      calculation: value.calculation.into(),
+    }
+  }
+}
+
+#[derive(Clone, Debug, Copy, ProtoBuf_Enum, Default, PartialEq, Eq)]
+#[repr(i64)]
+pub enum RollupCalculationPB {
+  #[default]
+  ShowOriginal = 0,
+  Sum = 1,
+  Average = 2,
+  Min = 3,
+  Max = 4,
+  Count = 5,
+  CountUnique = 6,
+  PercentChecked = 7,
+}
+
+impl From<RollupCalculation> for RollupCalculationPB {
+  fn from(value: RollupCalculation) -> Self {
+    match value {
+      RollupCalculation::ShowOriginal => RollupCalculationPB::ShowOriginal,
+      RollupCalculation::Sum => RollupCalculationPB::Sum,
+      RollupCalculation::Average => RollupCalculationPB::Average,
+      RollupCalculation::Min => RollupCalculationPB::Min,
+      RollupCalculation::Max => RollupCalculationPB::Max,
+      RollupCalculation::Count => RollupCalculationPB::Count,
+      RollupCalculation::CountUnique => RollupCalculationPB::CountUnique,
+      RollupCalculation::PercentChecked => RollupCalculationPB::PercentChecked,
+    }
+  }
+}
+
+impl From<RollupCalculationPB> for RollupCalculation {
+  fn from(value: RollupCalculationPB) -> Self {
+    RollupCalculation::from(value as i64)
+  }
+}
@@ -13,8 +13,11 @@ use crate::manager::DatabaseManager;
 use crate::services::field::checklist_filter::ChecklistCellChangeset;
 use crate::services::field::date_filter::DateCellChangeset;
 use crate::services::field::{
-  type_option_data_from_pb, RelationCellChangeset, SelectOptionCellChangeset, TypeOptionCellExt,
+  type_option_data_from_pb, PersonCellChangeset, RelationCellChangeset, SelectOptionCellChangeset,
+  TypeOptionCellExt,
 };
+use crate::services::filter::FilterInner;
+use crate::services::find_replace::TextMatcher;
 use crate::services::group::GroupChangeset;
 use crate::services::share::csv::CSVFormat;
 
@@ -291,6 +294,15 @@ pub(crate) async fn update_field_type_option_handler(
     database_editor
       .update_field_type_option(&params.field_id, type_option_data, old_field)
       .await?;
+
+    if field_type.is_rollup() {
+      let database_id = manager
+        .get_database_id_with_view_id(&params.view_id)
+        .await?;
+      manager
+        .update_rollup_cells(&database_id, Some(&params.field_id))
+        .await?;
+    }
   }
   Ok(())
 }
@@ -305,6 +317,9 @@ pub(crate) async fn delete_field_handler(
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
+  manager
+    .create_database_snapshot_before(&params.view_id, "deleting a field")
+    .await;
   database_editor.delete_field(&params.field_id).await?;
   Ok(())
 }
@@ -319,6 +334,9 @@ pub(crate) async fn clear_field_handler(
   let database_editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
+  manager
+    .create_database_snapshot_before(&params.view_id, "clearing a field")
+    .await;
   database_editor
     .clear_field(&params.view_id, &params.field_id)
     .await?;
@@ -344,6 +362,14 @@ pub(crate) async fn switch_to_field_handler(
     )
     .await?;
 
+  if params.field_type.is_rollup() {
+    let database_id = manager
+      .get_database_id_with_view_id(&params.view_id)
+      .await?;
+    manager
+      .update_rollup_cells(&database_id, Some(&params.field_id))
+      .await?;
+  }
   Ok(())
 }
 
@@ -371,13 +397,19 @@ pub(crate) async fn create_field_handler(
 ) -> DataResult<FieldPB, FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params: CreateFieldParams = data.into_inner().try_into()?;
-  let database_editor = manager
-    .get_database_editor_with_view_id(&params.view_id)
-    .await?;
+  let view_id = params.view_id.clone();
+  let database_editor = manager.get_database_editor_with_view_id(&view_id).await?;
   let data = database_editor
     .create_field_with_type_option(params)
     .await?;
 
+  if data.field_type.is_rollup() {
+    let database_id = manager.get_database_id_with_view_id(&view_id).await?;
+    manager
+      .update_rollup_cells(&database_id, Some(&data.id))
+      .await?;
+  }
+
   data_result_ok(data)
 }
 
@@ -474,7 +506,10 @@ pub(crate) async fn delete_rows_handler(
     .into_iter()
     .map(RowId::from)
     .collect::<Vec<_>>();
-  database_editor.delete_rows(&row_ids).await;
+  manager
+    .create_database_snapshot_before(&params.view_id, "deleting rows")
+    .await;
+  database_editor.delete_rows(&params.view_id, &row_ids).await;
   Ok(())
 }
 
@@ -780,6 +815,26 @@ pub(crate) async fn set_group_by_field_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "trace", skip_all, err)]
+pub(crate) async fn set_sub_group_by_field_handler(
+  data: AFPluginData<SubGroupByFieldPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  let params: SubGroupByFieldParams = data.into_inner().try_into()?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+  database_editor
+    .set_sub_group_by_field(
+      &params.view_id,
+      params.field_id.as_deref(),
+      params.setting_content,
+    )
+    .await?;
+  Ok(())
+}
+
 #[tracing::instrument(level = "trace", skip_all, err)]
 pub(crate) async fn update_group_handler(
   data: AFPluginData<UpdateGroupPB>,
@@ -827,6 +882,7 @@ pub(crate) async fn move_group_row_handler(
       &params.view_id,
       &params.from_group_id,
       &params.to_group_id,
+      params.to_sub_group_id.as_deref(),
       params.from_row_id,
       params.to_row_id,
     )
@@ -1029,6 +1085,31 @@ pub(crate) async fn export_csv_handler(
   data_result_ok(DatabaseExportDataPB {
     export_type: DatabaseExportDataType::CSV,
     data,
+    ..Default::default()
+  })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn export_view_csv_handler(
+  data: AFPluginData<ExportViewCSVPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<DatabaseExportDataPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.into_inner();
+  let database = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+  let data = database
+    .export_view_csv(
+      &params.view_id,
+      CSVFormat::Original,
+      params.include_group_headers,
+    )
+    .await?;
+  data_result_ok(DatabaseExportDataPB {
+    export_type: DatabaseExportDataType::CSV,
+    data,
+    ..Default::default()
   })
 }
 
@@ -1043,9 +1124,82 @@ pub(crate) async fn export_raw_database_data_handler(
   data_result_ok(DatabaseExportDataPB {
     export_type: DatabaseExportDataType::RawDatabaseData,
     data,
+    ..Default::default()
   })
 }
 
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn export_json_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<DatabaseExportDataPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner().value;
+  let database = manager.get_database_editor_with_view_id(&view_id).await?;
+  let data = database.export_json().await?;
+  data_result_ok(DatabaseExportDataPB {
+    export_type: DatabaseExportDataType::JSON,
+    data,
+    ..Default::default()
+  })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn export_markdown_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<DatabaseExportDataPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner().value;
+  let database = manager.get_database_editor_with_view_id(&view_id).await?;
+  let data = database.export_markdown(&view_id).await?;
+  data_result_ok(DatabaseExportDataPB {
+    export_type: DatabaseExportDataType::Markdown,
+    data,
+    ..Default::default()
+  })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn export_xlsx_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<DatabaseExportDataPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner().value;
+  let database = manager.get_database_editor_with_view_id(&view_id).await?;
+  let bytes = database.export_xlsx().await?;
+  data_result_ok(DatabaseExportDataPB {
+    export_type: DatabaseExportDataType::XLSX,
+    bytes,
+    ..Default::default()
+  })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn export_csv_to_file_handler(
+  data: AFPluginData<ExportCSVToFilePayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.into_inner();
+  manager
+    .export_csv_to_file(&params.view_id, &params.path, CSVFormat::Original)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn cancel_csv_task_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner().value;
+  manager.cancel_csv_task(&view_id).await;
+  Ok(())
+}
+
 #[tracing::instrument(level = "debug", skip_all, err)]
 pub(crate) async fn get_snapshots_handler(
   data: AFPluginData<DatabaseViewIdPB>,
@@ -1057,6 +1211,19 @@ pub(crate) async fn get_snapshots_handler(
   data_result_ok(RepeatedDatabaseSnapshotPB { items: snapshots })
 }
 
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn restore_snapshot_handler(
+  data: AFPluginData<RestoreDatabaseSnapshotPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  manager
+    .restore_database_snapshot(&params.view_id, &params.snapshot_id)
+    .await?;
+  Ok(())
+}
+
 #[tracing::instrument(level = "debug", skip_all, err)]
 pub(crate) async fn get_field_settings_handler(
   data: AFPluginData<FieldIdsPB>,
@@ -1164,6 +1331,273 @@ pub(crate) async fn remove_calculation_handler(
   Ok(())
 }
 
+pub(crate) async fn get_all_formatting_rules_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedFormattingRulePB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner();
+  let database_editor = manager
+    .get_database_editor_with_view_id(view_id.as_ref())
+    .await?;
+
+  let rules = database_editor
+    .get_all_formatting_rules(view_id.as_ref())
+    .await;
+  data_result_ok(rules)
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn update_formatting_rule_handler(
+  data: AFPluginData<UpdateFormattingRulePayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+
+  let view_id = params.view_id.clone();
+  database_editor
+    .modify_view_formatting_rules(&view_id, params.try_into()?)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn delete_formatting_rule_handler(
+  data: AFPluginData<DeleteFormattingRulePayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+
+  let view_id = params.view_id.clone();
+  database_editor
+    .modify_view_formatting_rules(&view_id, params.into())
+    .await?;
+  Ok(())
+}
+
+pub(crate) async fn undo_database_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<DatabaseUndoRedoPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner();
+  let database_editor = manager
+    .get_database_editor_with_view_id(view_id.as_ref())
+    .await?;
+  let is_success = database_editor.undo().await?;
+  data_result_ok(DatabaseUndoRedoPB {
+    can_undo: database_editor.can_undo(),
+    can_redo: database_editor.can_redo(),
+    is_success,
+  })
+}
+
+pub(crate) async fn redo_database_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<DatabaseUndoRedoPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner();
+  let database_editor = manager
+    .get_database_editor_with_view_id(view_id.as_ref())
+    .await?;
+  let is_success = database_editor.redo().await?;
+  data_result_ok(DatabaseUndoRedoPB {
+    can_undo: database_editor.can_undo(),
+    can_redo: database_editor.can_redo(),
+    is_success,
+  })
+}
+
+pub(crate) async fn can_undo_redo_database_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<DatabaseUndoRedoPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner();
+  let database_editor = manager
+    .get_database_editor_with_view_id(view_id.as_ref())
+    .await?;
+  data_result_ok(DatabaseUndoRedoPB {
+    can_undo: database_editor.can_undo(),
+    can_redo: database_editor.can_redo(),
+    is_success: true,
+  })
+}
+
+pub(crate) async fn update_cells_handler(
+  data: AFPluginData<RepeatedCellChangesetPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+  let changesets = params
+    .items
+    .into_iter()
+    .map(|item| {
+      (
+        RowId::from(item.row_id),
+        item.field_id,
+        BoxAny::new(item.cell_changeset),
+      )
+    })
+    .collect();
+  database_editor
+    .update_cells_with_changesets(&params.view_id, changesets)
+    .await?;
+  Ok(())
+}
+
+pub(crate) async fn create_rows_handler(
+  data: AFPluginData<CreateRowsPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedRowMetaPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+  let rows = params.rows.into_iter().map(|row| row.data).collect();
+  let row_orders = database_editor.create_rows(&params.view_id, rows).await?;
+  data_result_ok(RepeatedRowMetaPB {
+    items: row_orders.into_iter().map(RowMetaPB::from).collect(),
+  })
+}
+
+pub(crate) async fn delete_rows_by_filter_handler(
+  data: AFPluginData<DeleteRowsByFilterPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedRowIdPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+  let filter = FilterInner::try_from(params.filter)?;
+  manager
+    .create_database_snapshot_before(&params.view_id, "deleting rows")
+    .await;
+  let row_ids = database_editor
+    .delete_rows_by_filter(&params.view_id, filter)
+    .await?;
+  data_result_ok(RepeatedRowIdPB {
+    view_id: params.view_id,
+    row_ids: row_ids
+      .into_iter()
+      .map(|row_id| row_id.into_inner())
+      .collect(),
+  })
+}
+
+pub(crate) async fn paste_cells_handler(
+  data: AFPluginData<PasteCellsPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+  database_editor.paste_cells(params).await?;
+  Ok(())
+}
+
+pub(crate) async fn find_in_database_handler(
+  data: AFPluginData<FindInDatabasePayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedCellMatchPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+  let matcher = TextMatcher::new(&params.query, params.is_regex, params.is_case_sensitive)?;
+  let items = database_editor
+    .find_in_database(&params.view_id, &matcher, params.only_in_view)
+    .await?;
+  data_result_ok(RepeatedCellMatchPB { items })
+}
+
+pub(crate) async fn replace_in_database_handler(
+  data: AFPluginData<ReplaceInDatabasePayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<ReplaceInDatabaseResultPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+  let matcher = TextMatcher::new(&params.query, params.is_regex, params.is_case_sensitive)?;
+  manager
+    .create_database_snapshot_before(&params.view_id, "replacing text")
+    .await;
+  let result = database_editor
+    .replace_in_database(
+      &params.view_id,
+      &matcher,
+      &params.replacement,
+      params.only_in_view,
+    )
+    .await?;
+  data_result_ok(result)
+}
+
+pub(crate) async fn get_row_templates_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedRowTemplatePB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner();
+  let database_editor = manager
+    .get_database_editor_with_view_id(view_id.as_ref())
+    .await?;
+  let templates = database_editor.get_row_templates().await;
+  data_result_ok(templates)
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn update_row_template_handler(
+  data: AFPluginData<UpdateRowTemplatePayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+
+  database_editor
+    .modify_row_templates(params.try_into()?)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn delete_row_template_handler(
+  data: AFPluginData<DeleteRowTemplatePayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+
+  database_editor.modify_row_templates(params.into()).await?;
+  Ok(())
+}
+
 pub(crate) async fn get_related_database_ids_handler(
   _data: AFPluginData<DatabaseViewIdPB>,
   _manager: AFPluginState<Weak<DatabaseManager>>,
@@ -1216,6 +1650,38 @@ pub(crate) async fn update_relation_cell_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn update_person_cell_handler(
+  data: AFPluginData<PersonCellChangesetPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  let params: PersonCellChangesetPB = data.into_inner();
+  let view_id = parser::NotEmptyStr::parse(params.view_id)
+    .map_err(|_| flowy_error::ErrorCode::DatabaseViewIdIsEmpty)?
+    .0;
+  let cell_id: CellIdParams = params.cell_id.try_into()?;
+  let changeset = PersonCellChangeset {
+    inserted_persons: params
+      .inserted_persons
+      .into_iter()
+      .map(Into::into)
+      .collect(),
+    removed_emails: params.removed_emails,
+  };
+
+  let database_editor = manager.get_database_editor_with_view_id(&view_id).await?;
+  database_editor
+    .update_cell_with_changeset(
+      &view_id,
+      &cell_id.row_id,
+      &cell_id.field_id,
+      BoxAny::new(changeset),
+    )
+    .await?;
+  Ok(())
+}
+
 #[instrument(level = "debug", skip_all, err)]
 pub(crate) async fn get_related_row_datas_handler(
   data: AFPluginData<GetRelatedRowDataPB>,
@@ -1228,7 +1694,7 @@ pub(crate) async fn get_related_row_datas_handler(
     .await?;
 
   let row_datas = database_editor
-    .get_related_rows(Some(params.row_ids))
+    .get_related_rows(Some(params.row_ids), &params.field_ids)
     .await?;
 
   data_result_ok(RepeatedRelatedRowDataPB { rows: row_datas })
@@ -1247,7 +1713,7 @@ pub(crate) async fn get_related_database_rows_handler(
     database_id
   );
   let database_editor = manager.get_or_init_database_editor(&database_id).await?;
-  let rows = database_editor.get_related_rows(None).await?;
+  let rows = database_editor.get_related_rows(None, &[]).await?;
   data_result_ok(RepeatedRelatedRowDataPB { rows })
 }
 
@@ -56,6 +56,7 @@ pub fn init(database_manager: Weak<DatabaseManager>) -> AFPlugin {
          .event(DatabaseEvent::UpdateDateCell, update_date_cell_handler)
          // Group
          .event(DatabaseEvent::SetGroupByField, set_group_by_field_handler)
+        .event(DatabaseEvent::SetSubGroupByField, set_sub_group_by_field_handler)
          .event(DatabaseEvent::MoveGroup, move_group_handler)
          .event(DatabaseEvent::MoveGroupRow, move_group_row_handler)
          .event(DatabaseEvent::GetGroups, get_groups_handler)
@@ -78,7 +79,14 @@ pub fn init(database_manager: Weak<DatabaseManager>) -> AFPlugin {
          // Export
          .event(DatabaseEvent::ExportCSV, export_csv_handler)
          .event(DatabaseEvent::ExportRawDatabaseData, export_raw_database_data_handler)
+         .event(DatabaseEvent::ExportCSVToFile, export_csv_to_file_handler)
+         .event(DatabaseEvent::CancelCSVTask, cancel_csv_task_handler)
+         .event(DatabaseEvent::ExportJSON, export_json_handler)
+         .event(DatabaseEvent::ExportMarkdown, export_markdown_handler)
+         .event(DatabaseEvent::ExportXLSX, export_xlsx_handler)
+         .event(DatabaseEvent::ExportViewCSV, export_view_csv_handler)
          .event(DatabaseEvent::GetDatabaseSnapshots, get_snapshots_handler)
+         .event(DatabaseEvent::RestoreDatabaseSnapshot, restore_snapshot_handler)
          // Field settings
          .event(DatabaseEvent::GetFieldSettings, get_field_settings_handler)
          .event(DatabaseEvent::GetAllFieldSettings, get_all_field_settings_handler)
@@ -87,11 +95,33 @@ pub fn init(database_manager: Weak<DatabaseManager>) -> AFPlugin {
          .event(DatabaseEvent::GetAllCalculations, get_all_calculations_handler)
          .event(DatabaseEvent::UpdateCalculation, update_calculation_handler)
          .event(DatabaseEvent::RemoveCalculation, remove_calculation_handler)
+         // Formatting
+         .event(DatabaseEvent::GetAllFormattingRules, get_all_formatting_rules_handler)
+         .event(DatabaseEvent::UpdateFormattingRule, update_formatting_rule_handler)
+         .event(DatabaseEvent::DeleteFormattingRule, delete_formatting_rule_handler)
+         // Undo
+         .event(DatabaseEvent::UndoDatabase, undo_database_handler)
+         .event(DatabaseEvent::RedoDatabase, redo_database_handler)
+         .event(DatabaseEvent::CanUndoRedoDatabase, can_undo_redo_database_handler)
+         // Batch
+         .event(DatabaseEvent::UpdateCells, update_cells_handler)
+         .event(DatabaseEvent::CreateRows, create_rows_handler)
+         .event(DatabaseEvent::DeleteRowsByFilter, delete_rows_by_filter_handler)
+         .event(DatabaseEvent::PasteCells, paste_cells_handler)
+         // Find and replace
+         .event(DatabaseEvent::FindInDatabase, find_in_database_handler)
+         .event(DatabaseEvent::ReplaceInDatabase, replace_in_database_handler)
+         // Row templates
+         .event(DatabaseEvent::GetRowTemplates, get_row_templates_handler)
+         .event(DatabaseEvent::UpdateRowTemplate, update_row_template_handler)
+         .event(DatabaseEvent::DeleteRowTemplate, delete_row_template_handler)
          // Relation
          .event(DatabaseEvent::GetRelatedDatabaseIds, get_related_database_ids_handler)
          .event(DatabaseEvent::UpdateRelationCell, update_relation_cell_handler)
          .event(DatabaseEvent::GetRelatedRowDatas, get_related_row_datas_handler)
          .event(DatabaseEvent::GetRelatedDatabaseRows, get_related_database_rows_handler)
+         // Person
+         .event(DatabaseEvent::UpdatePersonCell, update_person_cell_handler)
          // AI
          .event(DatabaseEvent::SummarizeRow, summarize_row_handler)
          .event(DatabaseEvent::TranslateRow, translate_row_handler)
@@ -284,6 +314,11 @@ pub enum DatabaseEvent {
   #[event(input = "GroupByFieldPayloadPB")]
   SetGroupByField = 90,
 
+  /// [SetSubGroupByField] event is used to group the rows of each group of a database view by a
+  /// second field. The sub grouping is removed if the `field_id` is not set.
+  #[event(input = "SubGroupByFieldPayloadPB")]
+  SetSubGroupByField = 91,
+
   #[event(input = "DatabaseViewIdPB", output = "RepeatedGroupPB")]
   GetGroups = 100,
 
@@ -343,6 +378,10 @@ pub enum DatabaseEvent {
   #[event(input = "DatabaseViewIdPB", output = "RepeatedDatabaseSnapshotPB")]
   GetDatabaseSnapshots = 150,
 
+  /// Rolls the database back to one of its local snapshots. The ids of the views don't change.
+  #[event(input = "RestoreDatabaseSnapshotPayloadPB")]
+  RestoreDatabaseSnapshot = 151,
+
   /// Returns the field settings for the provided fields in the given view
   #[event(input = "FieldIdsPB", output = "RepeatedFieldSettingsPB")]
   GetFieldSettings = 160,
@@ -396,9 +435,107 @@ pub enum DatabaseEvent {
   #[event(input = "DatabaseViewIdPB", output = "DatabaseExportDataPB")]
   ExportRawDatabaseData = 178,
 
+  /// Writes the csv of the database to a file row by row. The progress is sent through the
+  /// `DidUpdateCSVProgress` notification.
+  #[event(input = "ExportCSVToFilePayloadPB")]
+  ExportCSVToFile = 179,
+
+  /// Cancels the streaming csv import or export of the view.
+  #[event(input = "DatabaseViewIdPB")]
+  CancelCSVTask = 180,
+
+  /// Exports the rows of the database as json, the cell values are typed by field type.
+  #[event(input = "DatabaseViewIdPB", output = "DatabaseExportDataPB")]
+  ExportJSON = 181,
+
+  /// Exports the view as a Markdown table. The filters, sorts and hidden fields of the view are
+  /// applied.
+  #[event(input = "DatabaseViewIdPB", output = "DatabaseExportDataPB")]
+  ExportMarkdown = 182,
+
+  /// Exports the database as a XLSX workbook with one sheet per view. The workbook is returned
+  /// in the bytes of the [DatabaseExportDataPB].
+  #[event(input = "DatabaseViewIdPB", output = "DatabaseExportDataPB")]
+  ExportXLSX = 183,
+
+  /// Exports the view as csv. Unlike [DatabaseEvent::ExportCSV], the filters, sorts, field
+  /// order and hidden fields of the view are applied.
+  #[event(input = "ExportViewCSVPayloadPB", output = "DatabaseExportDataPB")]
+  ExportViewCSV = 184,
+
+  /// Updates a person cell, assigning or unassigning workspace members. The members are the
+  /// ones returned by the `GetWorkspaceMembers` event of the user module.
+  #[event(input = "PersonCellChangesetPB")]
+  UpdatePersonCell = 185,
+
+  /// Returns the conditional formatting rules of the view
+  #[event(input = "DatabaseViewIdPB", output = "RepeatedFormattingRulePB")]
+  GetAllFormattingRules = 186,
+
+  /// Creates or updates a formatting rule. The rows matching the rules are sent with the
+  /// `DidUpdateRowsFormatting` notification.
+  #[event(input = "UpdateFormattingRulePayloadPB")]
+  UpdateFormattingRule = 187,
+
+  #[event(input = "DeleteFormattingRulePayloadPB")]
+  DeleteFormattingRule = 188,
+
+  /// Reverts the latest edit made by the local user in the database of the view. The changes
+  /// received from the other devices and collaborators are never undone.
+  #[event(input = "DatabaseViewIdPB", output = "DatabaseUndoRedoPB")]
+  UndoDatabase = 189,
+
+  #[event(input = "DatabaseViewIdPB", output = "DatabaseUndoRedoPB")]
+  RedoDatabase = 190,
+
+  #[event(input = "DatabaseViewIdPB", output = "DatabaseUndoRedoPB")]
+  CanUndoRedoDatabase = 191,
+
+  /// Updates many cells at once. The filters, sorts and calculations of the views are applied
+  /// once for the whole batch, which is undone in one step.
+  #[event(input = "RepeatedCellChangesetPB")]
+  UpdateCells = 192,
+
+  /// Creates the rows at the end of the view. Returns the created rows in the same order.
+  #[event(input = "CreateRowsPayloadPB", output = "RepeatedRowMetaPB")]
+  CreateRows = 193,
+
+  /// Deletes the rows of the view matching the filter. Returns the ids of the deleted rows.
+  #[event(input = "DeleteRowsByFilterPayloadPB", output = "RepeatedRowIdPB")]
+  DeleteRowsByFilter = 194,
+
+  /// Pastes a range of strings into the cells of the view. The strings are parsed like the
+  /// content of a csv file, and the missing select options are created.
+  #[event(input = "PasteCellsPayloadPB")]
+  PasteCells = 195,
+
+  /// Finds the occurrences of a plain text or a regex in the text of the cells
+  #[event(input = "FindInDatabasePayloadPB", output = "RepeatedCellMatchPB")]
+  FindInDatabase = 196,
+
+  /// Replaces the occurrences found like [DatabaseEvent::FindInDatabase]. The text cells are
+  /// rewritten and the select options are renamed, which is undone in one step.
+  #[event(
+    input = "ReplaceInDatabasePayloadPB",
+    output = "ReplaceInDatabaseResultPB"
+  )]
+  ReplaceInDatabase = 197,
+
   #[event(input = "MediaCellChangesetPB")]
   UpdateMediaCell = 200,
 
   #[event(input = "RenameMediaChangesetPB")]
   RenameMediaFile = 201,
+
+  /// Returns the row templates of the database of the view
+  #[event(input = "DatabaseViewIdPB", output = "RepeatedRowTemplatePB")]
+  GetRowTemplates = 202,
+
+  /// Creates or updates a row template. The templates are sent with the `DidUpdateRowTemplates`
+  /// notification.
+  #[event(input = "UpdateRowTemplatePayloadPB")]
+  UpdateRowTemplate = 203,
+
+  #[event(input = "DeleteRowTemplatePayloadPB")]
+  DeleteRowTemplate = 204,
 }
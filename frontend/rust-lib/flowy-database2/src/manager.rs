@@ -10,7 +10,6 @@ use collab_database::entity::{CreateDatabaseParams, CreateViewParams, EncodedDat
 use collab_database::error::DatabaseError;
 use collab_database::fields::translate_type_option::TranslateTypeOption;
 use collab_database::rows::RowId;
-use collab_database::template::csv::CSVTemplate;
 use collab_database::views::DatabaseLayout;
 use collab_database::workspace_database::{
   CollabPersistenceImpl, DatabaseCollabPersistenceService, DatabaseCollabService, DatabaseMeta,
@@ -20,10 +19,13 @@ use collab_entity::{CollabObject, CollabType, EncodedCollab};
 use collab_plugins::local_storage::kv::KVTransactionDB;
 use rayon::prelude::*;
 use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
 use std::str::FromStr;
 use std::sync::{Arc, Weak};
 use std::time::Duration;
 use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
 use tracing::{error, info, instrument, trace};
 
 use collab_integrate::collab_builder::{AppFlowyCollabBuilder, CollabBuilderConfig};
@@ -38,10 +40,20 @@ use lib_infra::priority_task::TaskDispatcher;
 
 use crate::entities::{DatabaseLayoutPB, DatabaseSnapshotPB, FieldType, RowMetaPB};
 use crate::services::cell::stringify_cell;
-use crate::services::database::DatabaseEditor;
+use crate::services::database::{
+  observe_rollup_dependencies, update_dependent_rollup_cells, update_rollup_cells, DatabaseEditor,
+  RollupDependencies,
+};
 use crate::services::database_view::DatabaseLayoutDepsResolver;
 use crate::services::field_settings::default_field_settings_by_layout_map;
-use crate::services::share::csv::{CSVFormat, CSVImporter, ImportResult};
+use crate::services::row_template::{
+  database_raw_data_from_json, database_raw_data_to_json, get_row_templates, upsert_row_template,
+};
+use crate::services::share::csv::{
+  CSVExport, CSVFormat, CSVImporter, CSVProgress, ImportResult, CSV_ROWS_PER_BATCH,
+};
+use crate::services::snapshot::entities::{DatabaseSnapshotData, DatabaseSnapshotMeta};
+use crate::services::snapshot::{has_recent_database_snapshot, write_database_snapshot};
 use tokio::sync::RwLock as TokioRwLock;
 use uuid::Uuid;
 
@@ -50,18 +62,62 @@ pub trait DatabaseUser: Send + Sync {
   fn collab_db(&self, uid: i64) -> Result<Weak<CollabKVDB>, FlowyError>;
   fn workspace_id(&self) -> Result<Uuid, FlowyError>;
   fn workspace_database_object_id(&self) -> Result<Uuid, FlowyError>;
+  /// Returns the email of the current user, which identifies the user in the person cells.
+  fn user_email(&self) -> Result<String, FlowyError>;
+  /// Returns the timezone of the date and time settings of the current user, which is empty if
+  /// the timezone of the device is used.
+  fn timezone_id(&self) -> Result<String, FlowyError>;
+}
+
+/// Writes the documents of the rows, which are edited by the document editor.
+#[async_trait]
+pub trait DatabaseRowDocumentService: Send + Sync {
+  /// Creates the document of a row from the json of its document data
+  async fn create_row_document(&self, document_id: &Uuid, data: &str) -> FlowyResult<()>;
+}
+
+/// Stores the local snapshots of the databases, so that they can be restored without the cloud.
+pub trait DatabaseSnapshotService: Send + Sync {
+  /// Stores the snapshot and removes the oldest snapshots of the database, so that at most
+  /// `max_snapshots` snapshots are kept.
+  fn create_database_snapshot(
+    &self,
+    database_id: &str,
+    desc: &str,
+    data_hash: &str,
+    data: Vec<u8>,
+    max_snapshots: usize,
+  ) -> FlowyResult<()>;
+  fn get_database_snapshot_metas(
+    &self,
+    database_id: &str,
+  ) -> FlowyResult<Vec<DatabaseSnapshotMeta>>;
+  fn get_latest_database_snapshot_meta(
+    &self,
+    database_id: &str,
+  ) -> FlowyResult<Option<DatabaseSnapshotMeta>>;
+  fn get_database_snapshot(&self, snapshot_id: &str) -> FlowyResult<DatabaseSnapshotData>;
 }
 
+/// The interval of the snapshots taken while a database is opened
+const DATABASE_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
 pub(crate) type DatabaseEditorMap = HashMap<String, Arc<DatabaseEditor>>;
 pub struct DatabaseManager {
   user: Arc<dyn DatabaseUser>,
   workspace_database_manager: ArcSwapOption<RwLock<WorkspaceDatabaseManager>>,
   task_scheduler: Arc<TokioRwLock<TaskDispatcher>>,
-  pub(crate) editors: Mutex<DatabaseEditorMap>,
+  pub(crate) editors: Arc<Mutex<DatabaseEditorMap>>,
+  /// The rollup fields of the opened databases, indexed by the cells they read
+  rollup_dependencies: Arc<Mutex<RollupDependencies>>,
   removing_editor: Arc<Mutex<HashMap<String, Arc<DatabaseEditor>>>>,
+  /// The cancellation tokens of the streaming csv imports and exports, keyed by view id.
+  csv_tasks: Mutex<HashMap<String, CancellationToken>>,
   collab_builder: Arc<AppFlowyCollabBuilder>,
   cloud_service: Arc<dyn DatabaseCloudService>,
   ai_service: Arc<dyn DatabaseAIService>,
+  snapshot_service: Arc<dyn DatabaseSnapshotService>,
+  row_document_service: Arc<dyn DatabaseRowDocumentService>,
 }
 
 impl DatabaseManager {
@@ -71,16 +127,22 @@ impl DatabaseManager {
     collab_builder: Arc<AppFlowyCollabBuilder>,
     cloud_service: Arc<dyn DatabaseCloudService>,
     ai_service: Arc<dyn DatabaseAIService>,
+    snapshot_service: Arc<dyn DatabaseSnapshotService>,
+    row_document_service: Arc<dyn DatabaseRowDocumentService>,
   ) -> Self {
     Self {
       user: database_user,
       workspace_database_manager: Default::default(),
       task_scheduler,
       editors: Default::default(),
+      rollup_dependencies: Default::default(),
       removing_editor: Default::default(),
+      csv_tasks: Default::default(),
       collab_builder,
       cloud_service,
       ai_service,
+      snapshot_service,
+      row_document_service,
     }
   }
 
@@ -93,6 +155,7 @@ impl DatabaseManager {
       editor.close_all_views().await;
     }
     self.editors.lock().await.clear();
+    *self.rollup_dependencies.lock().await = RollupDependencies::default();
     self.removing_editor.lock().await.clear();
     // 3. Clear the workspace database
     if let Some(old_workspace_database) = self.workspace_database_manager.swap(None) {
@@ -268,6 +331,7 @@ impl DatabaseManager {
       database,
       self.task_scheduler.clone(),
       self.collab_builder.clone(),
+      self.row_document_service.clone(),
     )
     .await?;
 
@@ -276,9 +340,56 @@ impl DatabaseManager {
       .lock()
       .await
       .insert(database_id.to_string(), editor.clone());
+
+    self.observe_database_snapshot(&editor)?;
+    observe_rollup_dependencies(
+      database_id,
+      &editor,
+      Arc::downgrade(&self.editors),
+      Arc::downgrade(&self.rollup_dependencies),
+    )
+    .await;
+    // The related rows might be changed while the database was closed, so refresh the rollup
+    // cells that depend on the opened database.
+    let weak_editors = Arc::downgrade(&self.editors);
+    let weak_rollup_dependencies = Arc::downgrade(&self.rollup_dependencies);
+    let cloned_database_id = database_id.to_string();
+    tokio::spawn(async move {
+      if let (Some(editors), Some(rollup_dependencies)) =
+        (weak_editors.upgrade(), weak_rollup_dependencies.upgrade())
+      {
+        if let Err(err) =
+          update_dependent_rollup_cells(&cloned_database_id, &editors, &rollup_dependencies).await
+        {
+          error!("[Rollup]: refresh rollup cells failed: {}", err);
+        }
+      }
+    });
     Ok(editor)
   }
 
+  /// Recomputes the rollup cells of the database. The databases that the rollup fields relate to
+  /// are opened if they are not opened yet. When `field_id` is provided, only the cells of that
+  /// field are recomputed.
+  pub async fn update_rollup_cells(
+    &self,
+    database_id: &str,
+    field_id: Option<&str>,
+  ) -> FlowyResult<()> {
+    let editor = self.get_or_init_database_editor(database_id).await?;
+    let mut related_editors = DatabaseEditorMap::new();
+    for rollup_field in editor.get_rollup_fields().await {
+      if field_id.map_or(true, |field_id| rollup_field.field.id == field_id) {
+        let related_editor = self
+          .get_or_init_database_editor(&rollup_field.related_database_id)
+          .await?;
+        related_editors.insert(rollup_field.related_database_id, related_editor);
+      }
+    }
+
+    update_rollup_cells(&editor, &related_editors, field_id, None).await
+  }
+
   /// Open the database view
   #[instrument(level = "trace", skip_all, err)]
   pub async fn open_database_view(&self, view_id: &Uuid) -> FlowyResult<()> {
@@ -363,22 +474,34 @@ impl DatabaseManager {
     Ok(data)
   }
 
+  /// Returns the raw data of the database, which is the [DatabaseData] of the database with its
+  /// row templates. See [database_raw_data_to_json].
   pub async fn get_database_json_string(&self, view_id: &str) -> FlowyResult<String> {
-    let lock = self.workspace_database()?;
-    let wdb = lock.read().await;
-    let data = wdb.get_database_data(view_id).await?;
-    let json_string = serde_json::to_string(&data)?;
-    Ok(json_string)
+    let (_, value) = self.get_database_raw_data(view_id).await?;
+    Ok(value.to_string())
+  }
+
+  async fn get_database_raw_data(&self, view_id: &str) -> FlowyResult<(String, serde_json::Value)> {
+    let data = self.get_database_data(view_id).await?;
+    let templates = self
+      .get_database_editor_with_view_id(view_id)
+      .await?
+      .get_row_template_data()
+      .await;
+    let value = database_raw_data_to_json(&data, &templates)?;
+    Ok((data.database_id, value))
   }
 
-  /// Create a new database with the given data that can be deserialized to [DatabaseData].
+  /// Create a new database with the given data that can be deserialized to [DatabaseData]. The
+  /// row templates are created as well if the data was exported by
+  /// [Self::get_database_json_string].
   #[tracing::instrument(level = "trace", skip_all, err)]
   pub async fn create_database_with_data(
     &self,
     new_database_view_id: &str,
     data: Vec<u8>,
   ) -> FlowyResult<EncodedCollab> {
-    let database_data = DatabaseData::from_json_bytes(data)?;
+    let (database_data, templates) = database_raw_data_from_json(data)?;
     if database_data.views.is_empty() {
       return Err(FlowyError::invalid_data().with_context("The database data is empty"));
     }
@@ -396,6 +519,13 @@ impl DatabaseManager {
     let database = wdb.create_database(create_database_params).await?;
     drop(wdb);
 
+    if let Some(templates) = templates {
+      let mut database = database.write().await;
+      for template in templates.iter() {
+        upsert_row_template(&mut database, template);
+      }
+    }
+
     let encoded_collab = database
       .read()
       .await
@@ -412,6 +542,14 @@ impl DatabaseManager {
     database_view_id: &str,
     new_database_view_id: &str,
   ) -> FlowyResult<EncodedCollab> {
+    // The row templates are stored outside of the database data, so they are copied after the
+    // database is duplicated
+    let templates = self
+      .get_database_editor_with_view_id(database_view_id)
+      .await?
+      .get_row_template_data()
+      .await;
+
     let lock = self.workspace_database()?;
     let mut wdb = lock.write().await;
     let database = wdb
@@ -419,6 +557,13 @@ impl DatabaseManager {
       .await?;
     drop(wdb);
 
+    if !templates.is_empty() {
+      let mut database = database.write().await;
+      for template in templates.iter() {
+        upsert_row_template(&mut database, template);
+      }
+    }
+
     let encoded_collab = database
       .read()
       .await
@@ -477,24 +622,32 @@ impl DatabaseManager {
     content: String,
     format: CSVFormat,
   ) -> FlowyResult<ImportResult> {
-    let params = match format {
-      CSVFormat::Original => {
-        let mut csv_template = CSVTemplate::try_from_reader(content.as_bytes(), true, None)?;
-        csv_template.reset_view_id(view_id.clone());
-
-        let database_template = csv_template.try_into_database_template(None).await?;
-        database_template.into_params()
-      },
+    self
+      .import_csv_with_field_types(view_id, content, format, HashMap::new())
+      .await
+  }
 
-      CSVFormat::META => {
-        let cloned_view_id = view_id.clone();
-        tokio::task::spawn_blocking(move || {
-          CSVImporter.import_csv_from_string(cloned_view_id, content, format)
-        })
-        .await
-        .map_err(internal_error)??
-      },
-    };
+  /// Imports the csv content as a new database. In [CSVFormat::Original], the columns listed in
+  /// `field_types` are imported with the given field type and the field type of the other
+  /// columns is inferred from their values.
+  pub async fn import_csv_with_field_types(
+    &self,
+    view_id: String,
+    content: String,
+    format: CSVFormat,
+    field_types: HashMap<String, FieldType>,
+  ) -> FlowyResult<ImportResult> {
+    let cloned_view_id = view_id.clone();
+    let params = tokio::task::spawn_blocking(move || {
+      CSVImporter.import_csv_from_string_with_field_types(
+        cloned_view_id,
+        content,
+        format,
+        &field_types,
+      )
+    })
+    .await
+    .map_err(internal_error)??;
 
     let database_id = params.database_id.clone();
     let database = self.import_database(params).await?;
@@ -517,6 +670,213 @@ impl DatabaseManager {
     database.export_csv(style).await
   }
 
+  /// Imports the csv file as a new database in [CSVFormat::Original], reading the file row by
+  /// row so that large files are imported with bounded memory. Returns the id of the created
+  /// database.
+  ///
+  /// The progress is sent through the `DidUpdateCSVProgress` notification of the view and the
+  /// import can be stopped with [DatabaseManager::cancel_csv_task]. A cancelled or failed
+  /// import deletes the partially imported database and its view.
+  pub async fn import_csv_from_file(
+    &self,
+    view_id: String,
+    path: String,
+    field_types: HashMap<String, FieldType>,
+  ) -> FlowyResult<String> {
+    let progress = self.start_csv_task(&view_id).await;
+    let cloned_view_id = view_id.clone();
+    let (mut file_import, params) = tokio::task::spawn_blocking(move || {
+      CSVImporter.open_csv_file(&cloned_view_id, &path, &field_types)
+    })
+    .await
+    .map_err(internal_error)??;
+
+    let database_id = params.database_id.clone();
+    let mut processed_rows = params.rows.len();
+    let mut num_of_unparsed_cells = 0;
+    let result = match self.import_database(params).await {
+      Ok(database) => {
+        let result: FlowyResult<()> = async {
+          loop {
+            progress.check_cancelled()?;
+            progress.notify(processed_rows, file_import.progress());
+
+            let (returned_file_import, rows) = tokio::task::spawn_blocking(move || {
+              let rows = file_import.next_rows(CSV_ROWS_PER_BATCH);
+              (file_import, rows)
+            })
+            .await
+            .map_err(internal_error)?;
+            file_import = returned_file_import;
+            let rows = rows?;
+            if rows.is_empty() {
+              break;
+            }
+
+            processed_rows += rows.len();
+            let mut database = database.write().await;
+            for row in rows {
+              database.create_row_in_view(&view_id, row).await?;
+            }
+          }
+
+          num_of_unparsed_cells = file_import.num_of_unparsed_cells();
+
+          // Save the select options that only appear after the sampled rows.
+          let mut database = database.write().await;
+          for field in file_import.updated_fields() {
+            let field_type = FieldType::from(field.field_type);
+            if let Some(type_option_data) = field.get_any_type_option(field_type) {
+              database.update_field(&field.id, |update| {
+                update.update_type_options(|type_options_update| {
+                  type_options_update.insert(&field_type.to_string(), type_option_data);
+                });
+              });
+            }
+          }
+          Ok(())
+        }
+        .await;
+
+        if result.is_err() {
+          // Don't leave the partially imported database behind.
+          database.write().await.delete_view(&view_id);
+          if let Ok(lock) = self.workspace_database() {
+            let mut wdb = lock.write().await;
+            wdb.close_database(&database_id);
+            wdb.delete_database(&database_id);
+          }
+        }
+        result
+      },
+      Err(err) => Err(err),
+    };
+    self.csv_tasks.lock().await.remove(&view_id);
+
+    match result {
+      Ok(_) => {
+        progress.finish(processed_rows);
+        info!(
+          "[CSV]: imported {} rows into database:{}, {} values are imported as text",
+          processed_rows, database_id, num_of_unparsed_cells
+        );
+        Ok(database_id)
+      },
+      Err(err) => {
+        if err.is_task_cancelled() {
+          progress.cancelled(processed_rows);
+        }
+        Err(err)
+      },
+    }
+  }
+
+  /// Writes the csv of the database to the file at `path` in batches of [CSV_ROWS_PER_BATCH]
+  /// rows. The database is only locked while a batch is read, and the file is written on the
+  /// blocking thread pool. The progress is sent through the `DidUpdateCSVProgress` notification
+  /// of the view and the export can be stopped with [DatabaseManager::cancel_csv_task]. A
+  /// cancelled or failed export removes the file.
+  pub async fn export_csv_to_file(
+    &self,
+    view_id: &str,
+    path: &str,
+    style: CSVFormat,
+  ) -> FlowyResult<()> {
+    let editor = self.get_database_editor_with_view_id(view_id).await?;
+    let progress = self.start_csv_task(view_id).await;
+    let result: FlowyResult<()> = async {
+      let (inline_view_id, fields, row_ids) = {
+        let database = editor.database.read().await;
+        let inline_view_id = database.get_inline_view_id();
+        let fields = database.get_fields_in_view(&inline_view_id, None);
+        let row_ids = database
+          .get_row_orders_for_view(&inline_view_id)
+          .into_iter()
+          .map(|row_order| row_order.id)
+          .collect::<Vec<RowId>>();
+        (inline_view_id, fields, row_ids)
+      };
+
+      let header = CSVExport.export_field_record(&fields, style)?;
+      let cloned_path = path.to_string();
+      let mut writer = tokio::task::spawn_blocking(move || {
+        let mut writer = BufWriter::new(File::create(cloned_path)?);
+        writer.write_all(&header)?;
+        Ok::<_, FlowyError>(writer)
+      })
+      .await
+      .map_err(internal_error)??;
+
+      let mut written_rows = 0;
+      for batch_row_ids in row_ids.chunks(CSV_ROWS_PER_BATCH) {
+        progress.check_cancelled()?;
+        let rows = {
+          let database = editor.database.read().await;
+          let mut rows = Vec::with_capacity(batch_row_ids.len());
+          for row_id in batch_row_ids {
+            if database.contains_row(&inline_view_id, row_id) {
+              rows.push(Arc::new(database.get_row(row_id).await));
+            }
+          }
+          rows
+        };
+        let records = CSVExport.export_row_records(&fields, &rows, style)?;
+        writer = tokio::task::spawn_blocking(move || {
+          writer.write_all(&records)?;
+          Ok::<_, FlowyError>(writer)
+        })
+        .await
+        .map_err(internal_error)??;
+
+        written_rows += batch_row_ids.len();
+        progress.notify(
+          written_rows,
+          written_rows as f64 / row_ids.len().max(1) as f64,
+        );
+      }
+
+      tokio::task::spawn_blocking(move || writer.flush())
+        .await
+        .map_err(internal_error)??;
+      progress.check_cancelled()?;
+      progress.finish(written_rows);
+      Ok(())
+    }
+    .await;
+    self.csv_tasks.lock().await.remove(view_id);
+
+    if let Err(err) = result {
+      let cloned_path = path.to_string();
+      let _ = tokio::task::spawn_blocking(move || std::fs::remove_file(cloned_path)).await;
+      if err.is_task_cancelled() {
+        progress.cancelled(0);
+      }
+      return Err(err);
+    }
+    Ok(())
+  }
+
+  /// Cancels the streaming csv import or export of the view. Does nothing if there is no
+  /// running task.
+  pub async fn cancel_csv_task(&self, view_id: &str) {
+    if let Some(cancellation) = self.csv_tasks.lock().await.remove(view_id) {
+      cancellation.cancel();
+    }
+  }
+
+  async fn start_csv_task(&self, view_id: &str) -> CSVProgress {
+    let cancellation = CancellationToken::new();
+    if let Some(old) = self
+      .csv_tasks
+      .lock()
+      .await
+      .insert(view_id.to_string(), cancellation.clone())
+    {
+      old.cancel();
+    }
+    CSVProgress::new(view_id, cancellation)
+  }
+
   pub async fn update_database_layout(
     &self,
     view_id: &str,
@@ -528,28 +888,161 @@ impl DatabaseManager {
       .await
   }
 
+  /// Returns the local snapshots of the database of the view, followed by its snapshots in the
+  /// cloud if there is room left.
   pub async fn get_database_snapshots(
     &self,
     view_id: &str,
     limit: usize,
   ) -> FlowyResult<Vec<DatabaseSnapshotPB>> {
-    let database_id = Uuid::from_str(&self.get_database_id_with_view_id(view_id).await?)?;
-    let snapshots = self
-      .cloud_service
-      .get_database_collab_object_snapshots(&database_id, limit)
-      .await?
+    let database_id = self.get_database_id_with_view_id(view_id).await?;
+    let mut snapshots = self
+      .snapshot_service
+      .get_database_snapshot_metas(&database_id)?
       .into_iter()
-      .map(|snapshot| DatabaseSnapshotPB {
-        snapshot_id: snapshot.snapshot_id,
-        snapshot_desc: "".to_string(),
-        created_at: snapshot.created_at,
-        data: snapshot.data,
+      .map(|meta| DatabaseSnapshotPB {
+        snapshot_id: meta.snapshot_id,
+        snapshot_desc: meta.desc,
+        created_at: meta.created_at,
+        data: vec![],
+        is_local: true,
       })
       .collect::<Vec<_>>();
+    snapshots.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    snapshots.truncate(limit);
+
+    if snapshots.len() < limit {
+      let database_id = Uuid::from_str(&database_id)?;
+      match self
+        .cloud_service
+        .get_database_collab_object_snapshots(&database_id, limit - snapshots.len())
+        .await
+      {
+        Ok(remote_snapshots) => {
+          snapshots.extend(
+            remote_snapshots
+              .into_iter()
+              .map(|snapshot| DatabaseSnapshotPB {
+                snapshot_id: snapshot.snapshot_id.to_string(),
+                snapshot_desc: "".to_string(),
+                created_at: snapshot.created_at,
+                data: snapshot.data,
+                is_local: false,
+              }),
+          );
+        },
+        Err(err) => error!("[Database]: get remote snapshots failed: {}", err),
+      }
+    }
 
     Ok(snapshots)
   }
 
+  /// Writes a local snapshot of the database of the view. Nothing is written when the database
+  /// didn't change since its latest snapshot.
+  pub async fn create_database_snapshot(&self, view_id: &str, desc: &str) -> FlowyResult<()> {
+    let (database_id, value) = self.get_database_raw_data(view_id).await?;
+    write_database_snapshot(self.snapshot_service.as_ref(), &database_id, &value, desc)
+  }
+
+  /// Writes a local snapshot before an operation that removes data, e.g. deleting a field, so
+  /// that the operation can be rolled back with [Self::restore_database_snapshot]. No snapshot is
+  /// written if the latest snapshot was taken less than a minute ago. The operation is not
+  /// blocked if the snapshot can't be written.
+  pub async fn create_database_snapshot_before(&self, view_id: &str, operation: &str) {
+    let result: FlowyResult<()> = async {
+      let database_id = self.get_database_id_with_view_id(view_id).await?;
+      if has_recent_database_snapshot(self.snapshot_service.as_ref(), &database_id)? {
+        trace!(
+          "[Database]: skip the snapshot before {}, {} has a recent snapshot",
+          operation,
+          database_id
+        );
+        return Ok(());
+      }
+      let desc = format!("Before {}", operation);
+      self.create_database_snapshot(view_id, &desc).await
+    }
+    .await;
+    if let Err(err) = result {
+      error!(
+        "[Database]: create snapshot before {} failed: {}",
+        operation, err
+      );
+    }
+  }
+
+  /// Rolls the database of the view back to one of its local snapshots. The ids of the views stay
+  /// the same, so the views in the folder keep pointing to the database. The current state is
+  /// saved as a snapshot first, so the restore can be rolled back as well.
+  #[instrument(level = "debug", skip(self), err)]
+  pub async fn restore_database_snapshot(
+    &self,
+    view_id: &str,
+    snapshot_id: &str,
+  ) -> FlowyResult<()> {
+    let database_id = self.get_database_id_with_view_id(view_id).await?;
+    let snapshot = self.snapshot_service.get_database_snapshot(snapshot_id)?;
+    let (data, templates) = database_raw_data_from_json(snapshot.data)?;
+    if data.database_id != database_id {
+      return Err(FlowyError::invalid_data().with_context(format!(
+        "The snapshot {} doesn't belong to the database {}",
+        snapshot_id, database_id
+      )));
+    }
+
+    self
+      .create_database_snapshot(view_id, "Before restoring a snapshot")
+      .await?;
+    let editor = self.get_database_editor_with_view_id(view_id).await?;
+    editor.restore_database_data(data).await?;
+    if let Some(templates) = templates {
+      editor.replace_row_templates(&templates).await;
+    }
+    Ok(())
+  }
+
+  /// Writes a local snapshot of the database every [DATABASE_SNAPSHOT_INTERVAL] until the editor
+  /// is dropped.
+  fn observe_database_snapshot(&self, editor: &Arc<DatabaseEditor>) -> FlowyResult<()> {
+    let weak_editor = Arc::downgrade(editor);
+    let weak_workspace_database = Arc::downgrade(&self.workspace_database()?);
+    let snapshot_service = self.snapshot_service.clone();
+    tokio::spawn(async move {
+      let mut interval = tokio::time::interval(DATABASE_SNAPSHOT_INTERVAL);
+      // The first tick completes immediately
+      interval.tick().await;
+      loop {
+        interval.tick().await;
+        let (view_id, templates) = match weak_editor.upgrade() {
+          Some(editor) => {
+            let database = editor.database.read().await;
+            (database.get_inline_view_id(), get_row_templates(&database))
+          },
+          None => break,
+        };
+        let workspace_database = match weak_workspace_database.upgrade() {
+          Some(workspace_database) => workspace_database,
+          None => break,
+        };
+        let result = workspace_database
+          .read()
+          .await
+          .get_database_data(&view_id)
+          .await
+          .map_err(FlowyError::from)
+          .and_then(|data| {
+            let value = database_raw_data_to_json(&data, &templates)?;
+            write_database_snapshot(snapshot_service.as_ref(), &data.database_id, &value, "")
+          });
+        if let Err(err) = result {
+          error!("[Database]: write periodic snapshot failed: {}", err);
+        }
+      }
+    });
+    Ok(())
+  }
+
   fn workspace_database(&self) -> FlowyResult<Arc<RwLock<WorkspaceDatabaseManager>>> {
     self
       .workspace_database_manager
@@ -52,6 +52,22 @@ pub enum DatabaseNotification {
   DidUpdateFieldSettings = 86,
   // Trigger when Calculation changed
   DidUpdateCalculation = 87,
+  // Trigger when a streaming csv import or export makes progress
+  DidUpdateCSVProgress = 88,
+  // Trigger when the calculations of a group changed
+  DidUpdateGroupCalculations = 89,
+  // Trigger when the sub groups of the view changed or the view is sub grouped by a new field
+  DidUpdateSubGroups = 90,
+  // Trigger after inserting/updating/deleting a formatting rule of the view
+  DidUpdateFormattingRules = 91,
+  // Trigger when the formatting rules matched by some rows changed
+  DidUpdateRowsFormatting = 92,
+  // Trigger after the database is rolled back to a snapshot, the views should be reloaded
+  DidRestoreDatabaseSnapshot = 93,
+  // Trigger after inserting/updating/deleting a row template of the view
+  DidUpdateRowTemplates = 94,
+  // Trigger when the rows of a sub group of a group changed
+  DidUpdateSubGroupRows = 95,
 }
 
 impl std::convert::From<DatabaseNotification> for i32 {
@@ -84,6 +100,14 @@ impl std::convert::From<i32> for DatabaseNotification {
       84 => DatabaseNotification::DidMoveDatabaseViewToTrash,
       86 => DatabaseNotification::DidUpdateFieldSettings,
       87 => DatabaseNotification::DidUpdateCalculation,
+      88 => DatabaseNotification::DidUpdateCSVProgress,
+      89 => DatabaseNotification::DidUpdateGroupCalculations,
+      90 => DatabaseNotification::DidUpdateSubGroups,
+      91 => DatabaseNotification::DidUpdateFormattingRules,
+      92 => DatabaseNotification::DidUpdateRowsFormatting,
+      93 => DatabaseNotification::DidRestoreDatabaseSnapshot,
+      94 => DatabaseNotification::DidUpdateRowTemplates,
+      95 => DatabaseNotification::DidUpdateSubGroupRows,
       _ => DatabaseNotification::Unknown,
     }
   }
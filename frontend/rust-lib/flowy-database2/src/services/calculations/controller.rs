@@ -13,9 +13,12 @@ use tracing::{error, instrument, trace};
 
 use crate::entities::{
   CalculationChangesetNotificationPB, CalculationPB, CalculationType, FieldType,
+  GroupCalculationsPB,
 };
 use crate::services::calculations::CalculationsByFieldIdCache;
+use crate::services::cell::CellCache;
 use crate::services::database_view::{DatabaseViewChanged, DatabaseViewChangedNotifier};
+use crate::services::share::get_exported_cell;
 use crate::utils::cache::AnyTypeCache;
 
 use super::{Calculation, CalculationChangeset, CalculationsService};
@@ -37,6 +40,8 @@ pub struct CalculationsController {
   calculations_by_field_cache: CalculationsByFieldIdCache,
   task_scheduler: Arc<TokioRwLock<TaskDispatcher>>,
   calculations_service: CalculationsService,
+  /// The last calculations of each group that were sent to the client, keyed by group id
+  group_calculations: DashMap<String, Vec<CalculationPB>>,
   notifier: DatabaseViewChangedNotifier,
 }
 
@@ -54,6 +59,7 @@ impl CalculationsController {
     calculations: Vec<Arc<Calculation>>,
     task_scheduler: Arc<TokioRwLock<TaskDispatcher>>,
     notifier: DatabaseViewChangedNotifier,
+    cell_cache: CellCache,
   ) -> Self
   where
     T: CalculationsDelegate + 'static,
@@ -64,7 +70,8 @@ impl CalculationsController {
       delegate: Box::new(delegate),
       calculations_by_field_cache: AnyTypeCache::<String>::new(),
       task_scheduler,
-      calculations_service: CalculationsService::new(),
+      calculations_service: CalculationsService::new(cell_cache),
+      group_calculations: DashMap::new(),
       notifier,
     };
     this.update_cache(calculations);
@@ -139,6 +146,7 @@ impl CalculationsController {
         .delegate
         .remove_calculation(&self.view_id, &calculation.id)
         .await;
+      self.calculations_by_field_cache.remove(&field_id);
 
       let notification = CalculationChangesetNotificationPB::from_delete(
         &self.view_id,
@@ -175,6 +183,7 @@ impl CalculationsController {
           .delegate
           .remove_calculation(&self.view_id, &calculation.id)
           .await;
+        self.calculations_by_field_cache.remove(&field_id);
 
         let notification = CalculationChangesetNotificationPB::from_delete(
           &self.view_id,
@@ -356,8 +365,13 @@ impl CalculationsController {
       .calculations_service
       .calculate(field, calculation.calculation_type, cells);
 
-    if value != calculation.value {
-      return Some(calculation.with_value(value));
+    // Keep the latest value of the calculation in the cache
+    let update = calculation.with_value(value);
+    self
+      .calculations_by_field_cache
+      .insert(&update.field_id, Arc::new(update.clone()));
+    if update.value != calculation.value {
+      return Some(update);
     }
 
     None
@@ -380,6 +394,9 @@ impl CalculationsController {
       let value = self
         .calculations_service
         .calculate(&field, insert.calculation_type, cells);
+      self
+        .calculations_by_field_cache
+        .insert(&insert.field_id, Arc::new(insert.with_value(value.clone())));
 
       notification = Some(CalculationChangesetNotificationPB::from_insert(
         &self.view_id,
@@ -393,6 +410,7 @@ impl CalculationsController {
     }
 
     if let Some(delete) = &changeset.delete_calculation {
+      self.calculations_by_field_cache.remove(&delete.field_id);
       notification = Some(CalculationChangesetNotificationPB::from_delete(
         &self.view_id,
         vec![CalculationPB {
@@ -407,6 +425,61 @@ impl CalculationsController {
     notification
   }
 
+  pub async fn has_calculations(&self) -> bool {
+    !self
+      .delegate
+      .get_all_calculations(&self.view_id)
+      .await
+      .is_empty()
+  }
+
+  /// Calculates the calculations of the view over the rows of a group.
+  pub async fn calculate_group(&self, group_id: &str, rows: &[Arc<Row>]) -> Vec<CalculationPB> {
+    let mut calculations = vec![];
+    for calculation in self.delegate.get_all_calculations(&self.view_id).await {
+      if let Some(field) = self.delegate.get_field(&calculation.field_id).await {
+        let cells = rows
+          .iter()
+          .filter_map(|row| get_exported_cell(row, &field).map(Arc::new))
+          .collect::<Vec<_>>();
+        let value =
+          self
+            .calculations_service
+            .calculate(&field, calculation.calculation_type, cells);
+        calculations.push(CalculationPB::from(&calculation.with_value(value)));
+      }
+    }
+
+    self
+      .group_calculations
+      .insert(group_id.to_string(), calculations.clone());
+    calculations
+  }
+
+  /// Recalculates the calculations of the groups after their rows changed. Only the groups whose
+  /// values are different from the last ones are returned.
+  pub async fn did_receive_groups_changed(
+    &self,
+    groups: Vec<(String, Vec<Arc<Row>>)>,
+  ) -> Vec<GroupCalculationsPB> {
+    let mut changed_groups = vec![];
+    for (group_id, rows) in groups {
+      let old_calculations = self
+        .group_calculations
+        .get(&group_id)
+        .map(|calculations| calculations.clone());
+      let calculations = self.calculate_group(&group_id, &rows).await;
+      if old_calculations.as_ref() != Some(&calculations) {
+        changed_groups.push(GroupCalculationsPB {
+          view_id: self.view_id.clone(),
+          group_id,
+          calculations,
+        });
+      }
+    }
+    changed_groups
+  }
+
   fn update_cache(&self, calculations: Vec<Arc<Calculation>>) {
     for calculation in calculations {
       let field_id = &calculation.field_id;
@@ -1,16 +1,33 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 
+use collab_database::fields::date_type_option::{DateCellData, DateTypeOption};
+use collab_database::fields::timestamp_type_option::TimestampTypeOption;
 use collab_database::fields::Field;
 use collab_database::rows::Cell;
+use collab_database::template::check_list_parse::ChecklistCellData;
+use collab_database::template::timestamp_parse::TimestampCellData;
 
-use crate::entities::CalculationType;
-use crate::services::field::TypeOptionCellExt;
+use crate::entities::{CalculationType, CheckboxCellDataPB, FieldType};
+use crate::services::cell::CellCache;
+use crate::services::field::{TypeOptionCellDataHandler, TypeOptionCellExt};
 use rayon::prelude::*;
 
-pub struct CalculationsService;
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Calculates the values of the calculations. The decoded cell data is kept in the cell cache,
+/// so only the cells that changed since the last calculation are decoded again.
+pub struct CalculationsService {
+  cell_cache: CellCache,
+}
+
 impl CalculationsService {
-  pub fn new() -> Self {
-    Self
+  pub fn new(cell_cache: CellCache) -> Self {
+    Self { cell_cache }
+  }
+
+  fn get_handler(&self, field: &Field) -> Option<Box<dyn TypeOptionCellDataHandler>> {
+    TypeOptionCellExt::new(field, Some(self.cell_cache.clone())).get_type_option_cell_data_handler()
   }
 
   pub fn calculate(&self, field: &Field, calculation_type: i64, cells: Vec<Arc<Cell>>) -> String {
@@ -25,11 +42,20 @@ impl CalculationsService {
       CalculationType::Count => self.calculate_count(cells),
       CalculationType::CountEmpty => self.calculate_count_empty(field, cells),
       CalculationType::CountNonEmpty => self.calculate_count_non_empty(field, cells),
+      CalculationType::CountUnique => self.calculate_count_unique(field, cells),
+      CalculationType::PercentEmpty => self.calculate_percent_empty(field, cells, true),
+      CalculationType::PercentNotEmpty => self.calculate_percent_empty(field, cells, false),
+      CalculationType::PercentChecked => self.calculate_percent_checked(field, cells, true),
+      CalculationType::PercentUnchecked => self.calculate_percent_checked(field, cells, false),
+      CalculationType::Earliest => self.calculate_earliest(field, cells),
+      CalculationType::Latest => self.calculate_latest(field, cells),
+      CalculationType::DateRange => self.calculate_date_range(field, cells),
+      CalculationType::ChecklistCompletion => self.calculate_checklist_completion(field, cells),
     }
   }
 
   fn calculate_average(&self, field: &Field, cells: Vec<Arc<Cell>>) -> String {
-    if let Some(handler) = TypeOptionCellExt::new(field, None).get_type_option_cell_data_handler() {
+    if let Some(handler) = self.get_handler(field) {
       let (sum, len): (f64, usize) = cells
         .par_iter()
         .filter_map(|cell| handler.handle_numeric_cell(cell))
@@ -92,7 +118,7 @@ impl CalculationsService {
   }
 
   fn calculate_count_empty(&self, field: &Field, cells: Vec<Arc<Cell>>) -> String {
-    if let Some(handler) = TypeOptionCellExt::new(field, None).get_type_option_cell_data_handler() {
+    if let Some(handler) = self.get_handler(field) {
       let empty_count = cells
         .par_iter()
         .filter(|cell| handler.handle_is_empty(cell, field))
@@ -104,7 +130,7 @@ impl CalculationsService {
   }
 
   fn calculate_count_non_empty(&self, field: &Field, cells: Vec<Arc<Cell>>) -> String {
-    if let Some(handler) = TypeOptionCellExt::new(field, None).get_type_option_cell_data_handler() {
+    if let Some(handler) = self.get_handler(field) {
       let non_empty_count = cells
         .par_iter()
         .filter(|cell| !handler.handle_is_empty(cell, field))
@@ -115,8 +141,172 @@ impl CalculationsService {
     }
   }
 
+  fn calculate_count_unique(&self, field: &Field, cells: Vec<Arc<Cell>>) -> String {
+    if let Some(handler) = self.get_handler(field) {
+      let unique_values = cells
+        .par_iter()
+        .map(|cell| handler.handle_stringify_cell(cell, field))
+        .filter(|value| !value.is_empty())
+        .collect::<HashSet<_>>();
+      unique_values.len().to_string()
+    } else {
+      "".to_string()
+    }
+  }
+
+  fn calculate_percent_empty(&self, field: &Field, cells: Vec<Arc<Cell>>, empty: bool) -> String {
+    if cells.is_empty() {
+      return String::new();
+    }
+
+    if let Some(handler) = self.get_handler(field) {
+      let count = cells
+        .par_iter()
+        .filter(|cell| handler.handle_is_empty(cell, field) == empty)
+        .count();
+      Self::percent(count, cells.len())
+    } else {
+      String::new()
+    }
+  }
+
+  fn calculate_percent_checked(
+    &self,
+    field: &Field,
+    cells: Vec<Arc<Cell>>,
+    checked: bool,
+  ) -> String {
+    if cells.is_empty() {
+      return String::new();
+    }
+
+    if let Some(handler) = self.get_handler(field) {
+      let count = cells
+        .par_iter()
+        .filter(|cell| {
+          let is_checked = handler
+            .handle_get_boxed_cell_data(cell, field)
+            .and_then(|cell_data| cell_data.unbox_or_none::<CheckboxCellDataPB>())
+            .map(|cell_data| cell_data.is_checked)
+            .unwrap_or(false);
+          is_checked == checked
+        })
+        .count();
+      Self::percent(count, cells.len())
+    } else {
+      String::new()
+    }
+  }
+
+  fn calculate_earliest(&self, field: &Field, cells: Vec<Arc<Cell>>) -> String {
+    let earliest = self
+      .reduce_date_ranges(field, cells)
+      .into_iter()
+      .map(|(start, _)| start)
+      .min();
+    self.format_timestamp(field, earliest)
+  }
+
+  fn calculate_latest(&self, field: &Field, cells: Vec<Arc<Cell>>) -> String {
+    let latest = self
+      .reduce_date_ranges(field, cells)
+      .into_iter()
+      .map(|(_, end)| end)
+      .max();
+    self.format_timestamp(field, latest)
+  }
+
+  /// The number of days between the earliest and the latest date.
+  fn calculate_date_range(&self, field: &Field, cells: Vec<Arc<Cell>>) -> String {
+    let ranges = self.reduce_date_ranges(field, cells);
+    let earliest = ranges.iter().map(|(start, _)| *start).min();
+    let latest = ranges.iter().map(|(_, end)| *end).max();
+    match (earliest, latest) {
+      (Some(earliest), Some(latest)) => ((latest - earliest) / SECONDS_PER_DAY).to_string(),
+      _ => String::new(),
+    }
+  }
+
+  /// The percentage of the completed tasks among the tasks of all the cells.
+  fn calculate_checklist_completion(&self, field: &Field, cells: Vec<Arc<Cell>>) -> String {
+    if let Some(handler) = self.get_handler(field) {
+      let (completed, total): (usize, usize) = cells
+        .par_iter()
+        .filter_map(|cell| {
+          handler
+            .handle_get_boxed_cell_data(cell, field)
+            .and_then(|cell_data| cell_data.unbox_or_none::<ChecklistCellData>())
+        })
+        .map(|cell_data| (cell_data.selected_option_ids.len(), cell_data.options.len()))
+        .reduce(
+          || (0, 0),
+          |(completed1, total1), (completed2, total2)| (completed1 + completed2, total1 + total2),
+        );
+
+      if total > 0 {
+        Self::percent(completed, total)
+      } else {
+        String::new()
+      }
+    } else {
+      String::new()
+    }
+  }
+
+  /// Returns the start and end timestamps of the dates of the cells. The end is the same as the
+  /// start unless the date is a range.
+  fn reduce_date_ranges(&self, field: &Field, cells: Vec<Arc<Cell>>) -> Vec<(i64, i64)> {
+    let handler = match self.get_handler(field) {
+      None => return vec![],
+      Some(handler) => handler,
+    };
+
+    let field_type = FieldType::from(field.field_type);
+    cells
+      .par_iter()
+      .filter_map(|cell| {
+        let cell_data = handler.handle_get_boxed_cell_data(cell, field)?;
+        match field_type {
+          FieldType::DateTime => {
+            let cell_data = cell_data.unbox_or_none::<DateCellData>()?;
+            let start = cell_data.timestamp?;
+            let end = if cell_data.is_range {
+              cell_data.end_timestamp.unwrap_or(start)
+            } else {
+              start
+            };
+            Some((start, end))
+          },
+          FieldType::CreatedTime | FieldType::LastEditedTime => {
+            let timestamp = cell_data.unbox_or_none::<TimestampCellData>()?.timestamp?;
+            Some((timestamp, timestamp))
+          },
+          _ => None,
+        }
+      })
+      .collect()
+  }
+
+  fn format_timestamp(&self, field: &Field, timestamp: Option<i64>) -> String {
+    if timestamp.is_none() {
+      return String::new();
+    }
+
+    match FieldType::from(field.field_type) {
+      FieldType::DateTime => field
+        .get_type_option::<DateTypeOption>(field.field_type)
+        .map(|type_option| type_option.formatted_date_time_from_timestamp(&timestamp).0)
+        .unwrap_or_default(),
+      FieldType::CreatedTime | FieldType::LastEditedTime => field
+        .get_type_option::<TimestampTypeOption>(field.field_type)
+        .map(|type_option| type_option.formatted_date_time_from_timestamp(&timestamp).0)
+        .unwrap_or_default(),
+      _ => String::new(),
+    }
+  }
+
   fn reduce_values_f64(&self, field: &Field, row_cells: Vec<Arc<Cell>>) -> Vec<f64> {
-    if let Some(handler) = TypeOptionCellExt::new(field, None).get_type_option_cell_data_handler() {
+    if let Some(handler) = self.get_handler(field) {
       row_cells
         .par_iter()
         .filter_map(|cell| handler.handle_numeric_cell(cell))
@@ -126,6 +316,10 @@ impl CalculationsService {
     }
   }
 
+  fn percent(count: usize, total: usize) -> String {
+    format!("{:.2}", count as f64 / total as f64 * 100.0)
+  }
+
   fn median(array: &[f64]) -> f64 {
     if array.len() % 2 == 0 {
       let left = array.len() / 2 - 1;
@@ -4,7 +4,7 @@ use std::str::FromStr;
 use collab_database::fields::media_type_option::MediaCellData;
 use collab_database::fields::select_type_option::SelectOptionIds;
 use collab_database::fields::Field;
-use collab_database::rows::{get_field_type_from_cell, Cell, Cells};
+use collab_database::rows::{get_field_type_from_cell, Cell, Cells, RowId};
 use collab_database::template::relation_parse::RelationCellData;
 use flowy_error::{FlowyError, FlowyResult};
 use lib_infra::box_any::BoxAny;
@@ -140,6 +140,18 @@ pub fn stringify_cell(cell: &Cell, field: &Field) -> String {
   }
 }
 
+/// Returns the typed json value of the cell. See [TypeOptionCellDataHandler::handle_json_cell].
+pub fn json_cell(cell: &Cell, field: &Field) -> serde_json::Value {
+  if let Some(field_type_of_cell) = get_field_type_from_cell::<FieldType>(cell) {
+    TypeOptionCellExt::new(field, None)
+      .get_type_option_cell_data_handler_with_field_type(field_type_of_cell)
+      .map(|handler| handler.handle_json_cell(cell))
+      .unwrap_or_default()
+  } else {
+    serde_json::Value::Null
+  }
+}
+
 pub fn insert_text_cell(s: String, field: &Field) -> Cell {
   apply_cell_changeset(BoxAny::new(s), None, field, None).unwrap()
 }
@@ -191,6 +203,22 @@ pub fn insert_select_option_cell(option_ids: Vec<String>, field: &Field) -> Cell
   apply_cell_changeset(BoxAny::new(changeset), None, field, None).unwrap()
 }
 
+pub fn insert_person_cell(persons: Vec<Person>, field: &Field) -> Cell {
+  let changeset = PersonCellChangeset {
+    inserted_persons: persons,
+    removed_emails: vec![],
+  };
+  apply_cell_changeset(BoxAny::new(changeset), None, field, None).unwrap()
+}
+
+pub fn insert_relation_cell(row_ids: Vec<RowId>, field: &Field) -> Cell {
+  let changeset = RelationCellChangeset {
+    inserted_row_ids: row_ids,
+    removed_row_ids: vec![],
+  };
+  apply_cell_changeset(BoxAny::new(changeset), None, field, None).unwrap()
+}
+
 pub fn insert_checklist_cell(
   insert_options: Vec<ChecklistCellInsertChangeset>,
   field: &Field,
@@ -245,6 +273,21 @@ impl<'a> CellBuilder<'a> {
           FieldType::LastEditedTime | FieldType::CreatedTime => {
             tracing::warn!("Shouldn't insert cell data to cell whose field type is LastEditedTime or CreatedTime");
           },
+          FieldType::Formula => {
+            tracing::warn!("Shouldn't insert cell data to cell whose field type is Formula");
+          },
+          FieldType::Rollup => {
+            tracing::warn!("Shouldn't insert cell data to cell whose field type is Rollup");
+          },
+          FieldType::AutoNumber => {
+            tracing::warn!("Shouldn't insert cell data to cell whose field type is AutoNumber");
+          },
+          FieldType::Rating | FieldType::Email | FieldType::Phone => {
+            // Invalid email addresses and phone numbers are skipped.
+            if let Ok(cell) = apply_cell_changeset(BoxAny::new(cell_str), None, field, None) {
+              cells.insert(field_id, cell);
+            }
+          },
           FieldType::SingleSelect | FieldType::MultiSelect => {
             if let Ok(ids) = SelectOptionIds::from_str(&cell_str) {
               cells.insert(field_id, insert_select_option_cell(ids.into_inner(), field));
@@ -273,6 +316,11 @@ impl<'a> CellBuilder<'a> {
               cells.insert(field_id, cell_data.into());
             }
           },
+          FieldType::Person => {
+            if let Ok(cell_data) = PersonCellData::from_str(&cell_str) {
+              cells.insert(field_id, insert_person_cell(cell_data.persons, field));
+            }
+          },
         }
       }
     }
@@ -306,6 +354,17 @@ impl<'a> CellBuilder<'a> {
     }
   }
 
+  pub fn insert_person_cell(&mut self, field_id: &str, persons: Vec<Person>) {
+    match self.field_maps.get(field_id) {
+      None => tracing::warn!("Can't find the person field with id: {}", field_id),
+      Some(field) => {
+        self
+          .cells
+          .insert(field_id.to_owned(), insert_person_cell(persons, field));
+      },
+    }
+  }
+
   pub fn insert_number_cell(&mut self, field_id: &str, num: i64) {
     match self.field_maps.get(field_id) {
       None => tracing::warn!("Can't find the number field with id: {}", field_id),
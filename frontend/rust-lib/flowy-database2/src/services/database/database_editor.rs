@@ -1,35 +1,61 @@
 use crate::entities::*;
 use crate::notification::{database_notification_builder, DatabaseNotification};
 use crate::services::calculations::Calculation;
-use crate::services::cell::{apply_cell_changeset, get_cell_protobuf, CellCache};
+use crate::services::cell::{apply_cell_changeset, get_cell_protobuf, stringify_cell, CellCache};
 use crate::services::database::database_observe::*;
 use crate::services::database::util::database_view_setting_pb_from_view;
+use crate::services::database::{
+  DatabaseEdit, DatabaseUndoManager, FieldViewSettings, RollupField,
+};
 use crate::services::database_view::{
-  DatabaseViewChanged, DatabaseViewEditor, DatabaseViewOperation, DatabaseViews, EditorByViewId,
+  notify_did_update_row_templates, DatabaseViewChanged, DatabaseViewEditor, DatabaseViewOperation,
+  DatabaseViews, EditorByViewId,
 };
 use crate::services::field::checklist_filter::ChecklistCellChangeset;
 use crate::services::field::type_option_transform::transform_type_option;
 use crate::services::field::{
-  default_type_option_data_from_type, select_type_option_from_field, type_option_data_from_pb,
-  SelectOptionCellChangeset, StringCellData, TypeOptionCellDataHandler, TypeOptionCellExt,
+  compute_formula_cells, default_type_option_data_from_type, has_formula_field,
+  is_referenced_by_formula, rename_field_references, select_type_option_from_field,
+  type_option_data_from_pb, AutoNumberCellData, AutoNumberTypeOption, FormulaTypeOption,
+  RollupSourceValue, RollupTypeOption, SelectOptionCellChangeset, StringCellData,
+  TypeOptionCellDataHandler, TypeOptionCellExt, CELL_DATA,
 };
 use crate::services::field_settings::{default_field_settings_by_layout_map, FieldSettings};
-use crate::services::filter::{Filter, FilterChangeset};
-use crate::services::group::{default_group_setting, GroupChangeset, GroupSetting};
-use crate::services::share::csv::{CSVExport, CSVFormat};
+use crate::services::filter::{apply_filter, Filter, FilterChangeset, FilterContext, FilterInner};
+use crate::services::find_replace::{
+  is_replaceable_field_type, rename_select_options, TextMatcher,
+};
+use crate::services::formatting::{
+  get_formatting_rules, remove_formatting_rules, save_formatting_rules, FormattingRule,
+  FormattingRuleChangeset,
+};
+use crate::services::group::{
+  default_group_setting, GroupChangeset, GroupSetting, GROUP_SETTING_INDEX, SUB_GROUP_SETTING_INDEX,
+};
+use crate::services::row_template::{
+  delete_row_template, get_row_template, get_row_templates, upsert_row_template, RowTemplate,
+  RowTemplateChangeset,
+};
+use crate::services::share::csv::{
+  insert_missing_select_options, pasted_cell_changeset, CSVExport, CSVFormat, CSVGroup,
+};
+use crate::services::share::{JSONExport, MarkdownExport, XLSXCell, XLSXExport, XLSXSheet};
 use crate::services::sort::Sort;
 use crate::utils::cache::AnyTypeCache;
-use crate::DatabaseUser;
+use crate::{DatabaseRowDocumentService, DatabaseUser};
 use arc_swap::ArcSwapOption;
 use async_trait::async_trait;
 use collab::core::collab_plugin::CollabPluginType;
 use collab::lock::RwLock;
-use collab_database::database::Database;
+use collab_database::database::{gen_database_filter_id, Database, DatabaseData};
 use collab_database::entity::DatabaseView;
 use collab_database::fields::media_type_option::MediaCellData;
 use collab_database::fields::relation_type_option::RelationTypeOption;
 use collab_database::fields::{Field, TypeOptionData};
-use collab_database::rows::{Cell, Cells, DatabaseRow, Row, RowCell, RowDetail, RowId, RowUpdate};
+use collab_database::rows::{
+  Cell, Cells, CreateRowParams, DatabaseRow, Row, RowCell, RowDetail, RowId, RowUpdate,
+};
+use collab_database::template::relation_parse::RelationCellData;
 use collab_database::template::timestamp_parse::TimestampCellData;
 use collab_database::views::{
   DatabaseLayout, FilterMap, LayoutSetting, OrderObjectPosition, RowOrder,
@@ -43,7 +69,7 @@ use futures::{pin_mut, StreamExt};
 use lib_infra::box_any::BoxAny;
 use lib_infra::priority_task::TaskDispatcher;
 use lib_infra::util::timestamp;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 use std::sync::{Arc, Weak};
 use std::time::Duration;
@@ -72,6 +98,9 @@ pub struct DatabaseEditor {
   database_cancellation: Arc<RwLock<Option<CancellationToken>>>,
   un_finalized_rows_cancellation: Arc<ArcSwapOption<CancellationToken>>,
   finalized_rows: Arc<moka::future::Cache<String, Weak<RwLock<DatabaseRow>>>>,
+  undo_manager: DatabaseUndoManager,
+  computed_cells_tx: broadcast::Sender<Vec<RowId>>,
+  row_document_service: Arc<dyn DatabaseRowDocumentService>,
 }
 
 impl DatabaseEditor {
@@ -80,6 +109,7 @@ impl DatabaseEditor {
     database: Arc<RwLock<Database>>,
     task_scheduler: Arc<TokioRwLock<TaskDispatcher>>,
     collab_builder: Arc<AppFlowyCollabBuilder>,
+    row_document_service: Arc<dyn DatabaseRowDocumentService>,
   ) -> FlowyResult<Arc<Self>> {
     let finalized_rows: moka::future::Cache<String, Weak<RwLock<DatabaseRow>>> =
       moka::future::Cache::builder()
@@ -102,6 +132,7 @@ impl DatabaseEditor {
     // Used to cache the view of the database for fast access.
     let editor_by_view_id = Arc::new(RwLock::new(EditorByViewId::default()));
     let view_operation = Arc::new(DatabaseViewOperationImpl {
+      user: user.clone(),
       database: database.clone(),
       task_scheduler: task_scheduler.clone(),
       cell_cache: cell_cache.clone(),
@@ -144,9 +175,13 @@ impl DatabaseEditor {
       database_cancellation,
       un_finalized_rows_cancellation: Arc::new(Default::default()),
       finalized_rows: Arc::new(finalized_rows),
+      undo_manager: DatabaseUndoManager::default(),
+      computed_cells_tx: broadcast::channel(100).0,
+      row_document_service,
     });
     observe_block_event(&database_id, &this).await;
     observe_view_change(&database_id, &this).await;
+    observe_formula_source_cells(&database_id, &this).await;
     Ok(this)
   }
 
@@ -221,6 +256,7 @@ impl DatabaseEditor {
   ) -> FlowyResult<()> {
     let old_group_settings: Vec<GroupSetting>;
     let mut setting_content = "".to_string();
+    let mut is_sub_grouping_removed = false;
     {
       let mut database = self.database.write().await;
       let field = database.get_field(field_id);
@@ -230,8 +266,23 @@ impl DatabaseEditor {
         setting_content = group_config_pb_to_json_str(data, &field_type)?;
         let mut group_setting = default_group_setting(&field);
         group_setting.content.clone_from(&setting_content);
+
+        // Keep the sub grouping unless the view is now grouped by the sub grouping field
+        let mut group_settings = vec![group_setting];
+        if let Some(sub_group_setting) = old_group_settings.get(SUB_GROUP_SETTING_INDEX) {
+          if sub_group_setting.field_id == field_id {
+            is_sub_grouping_removed = true;
+          } else {
+            group_settings.push(sub_group_setting.clone());
+          }
+        }
         database.update_database_view(view_id, |view| {
-          view.set_groups(vec![group_setting.into()]);
+          view.set_groups(
+            group_settings
+              .into_iter()
+              .map(|group_setting| group_setting.into())
+              .collect(),
+          );
         });
       }
     }
@@ -244,6 +295,60 @@ impl DatabaseEditor {
     if !view_editor.is_grouping_field(field_id).await || !has_same_content {
       view_editor.v_initialize_new_group(field_id).await?;
     }
+    if is_sub_grouping_removed {
+      view_editor.v_sub_group_by_field().await?;
+    }
+    Ok(())
+  }
+
+  /// Groups the rows of each group of the view by a second field. The view must already be
+  /// grouped. The sub grouping is removed if the `field_id` is None.
+  pub async fn set_sub_group_by_field(
+    &self,
+    view_id: &str,
+    field_id: Option<&str>,
+    data: Vec<u8>,
+  ) -> FlowyResult<()> {
+    {
+      let mut database = self.database.write().await;
+      let old_group_settings: Vec<GroupSetting> = database.get_all_group_setting(view_id);
+      let group_setting = old_group_settings
+        .get(GROUP_SETTING_INDEX)
+        .cloned()
+        .ok_or_else(|| {
+          FlowyError::invalid_data().with_context("The view must be grouped before sub grouping")
+        })?;
+
+      let mut group_settings = vec![group_setting];
+      if let Some(field_id) = field_id {
+        if field_id == group_settings[GROUP_SETTING_INDEX].field_id {
+          return Err(
+            FlowyError::invalid_data()
+              .with_context("The sub grouping field must be different from the grouping field"),
+          );
+        }
+        let field = database.get_field(field_id).ok_or_else(|| {
+          FlowyError::record_not_found()
+            .with_context(format!("Field with id:{} not found", field_id))
+        })?;
+        let field_type = FieldType::from(field.field_type);
+        let mut sub_group_setting = default_group_setting(&field);
+        sub_group_setting.content = group_config_pb_to_json_str(data, &field_type)?;
+        group_settings.push(sub_group_setting);
+      }
+
+      database.update_database_view(view_id, |view| {
+        view.set_groups(
+          group_settings
+            .into_iter()
+            .map(|group_setting| group_setting.into())
+            .collect(),
+        );
+      });
+    }
+
+    let view_editor = self.database_views.get_or_init_view_editor(view_id).await?;
+    view_editor.v_sub_group_by_field().await?;
     Ok(())
   }
 
@@ -269,7 +374,12 @@ impl DatabaseEditor {
   /// will be the reference view ids and the inline view id. Otherwise, the return value will
   /// be the view id.
   pub async fn delete_database_view(&self, view_id: &str) -> FlowyResult<Vec<String>> {
-    Ok(self.database.write().await.delete_view(view_id))
+    let mut database = self.database.write().await;
+    let deleted_view_ids = database.delete_view(view_id);
+    for deleted_view_id in deleted_view_ids.iter() {
+      remove_formatting_rules(&mut database, deleted_view_id);
+    }
+    Ok(deleted_view_ids)
   }
 
   pub async fn update_group(
@@ -361,6 +471,107 @@ impl DatabaseEditor {
       None
     }
   }
+  pub async fn get_all_formatting_rules(&self, view_id: &str) -> RepeatedFormattingRulePB {
+    if let Ok(view_editor) = self.database_views.get_or_init_view_editor(view_id).await {
+      view_editor.v_get_all_formatting_rules().await
+    } else {
+      RepeatedFormattingRulePB { items: vec![] }
+    }
+  }
+
+  pub async fn modify_view_formatting_rules(
+    &self,
+    view_id: &str,
+    changeset: FormattingRuleChangeset,
+  ) -> FlowyResult<()> {
+    let view_editor = self.database_views.get_or_init_view_editor(view_id).await?;
+    view_editor.v_modify_formatting_rules(changeset).await;
+    Ok(())
+  }
+
+  /// Returns the row templates of the database, which are shared by all of its views
+  pub async fn get_row_templates(&self) -> RepeatedRowTemplatePB {
+    self.get_row_template_data().await.as_slice().into()
+  }
+
+  pub async fn get_row_template_data(&self) -> Vec<RowTemplate> {
+    get_row_templates(&self.database.read().await)
+  }
+
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub async fn modify_row_templates(&self, changeset: RowTemplateChangeset) -> FlowyResult<()> {
+    let mut database = self.database.write().await;
+    match changeset {
+      RowTemplateChangeset::Upsert {
+        template_id,
+        name,
+        cells,
+        document,
+      } => {
+        let template = match template_id {
+          Some(template_id) => {
+            let mut template = get_row_template(&database, &template_id).ok_or_else(|| {
+              FlowyError::record_not_found()
+                .with_context(format!("Row template:{} not found", template_id))
+            })?;
+            template.name = name;
+            template.cells = cells;
+            template.document = document;
+            template
+          },
+          None => {
+            let mut template = RowTemplate::new(name, cells, document);
+            // Keeps the order of the templates created within the same millisecond
+            if let Some(last_template) = get_row_templates(&database).last() {
+              template.created_at = template.created_at.max(last_template.created_at + 1);
+            }
+            template
+          },
+        };
+        upsert_row_template(&mut database, &template);
+      },
+      RowTemplateChangeset::Delete { template_id } => {
+        delete_row_template(&mut database, &template_id);
+      },
+    }
+    let templates = get_row_templates(&database);
+    drop(database);
+
+    self.notify_row_templates(&templates).await;
+    Ok(())
+  }
+
+  /// Replaces all the row templates of the database, used when the database is created from
+  /// exported data or restored from a snapshot
+  pub async fn replace_row_templates(&self, templates: &[RowTemplate]) {
+    let mut database = self.database.write().await;
+    for template in get_row_templates(&database) {
+      if templates.iter().all(|t| t.id != template.id) {
+        delete_row_template(&mut database, &template.id);
+      }
+    }
+    for template in templates {
+      upsert_row_template(&mut database, template);
+    }
+    drop(database);
+
+    self.notify_row_templates(templates).await;
+  }
+
+  async fn notify_row_templates(&self, templates: &[RowTemplate]) {
+    let templates = RepeatedRowTemplatePB::from(templates);
+    for view_editor in self.database_views.editors().await {
+      notify_did_update_row_templates(&view_editor.view_id, templates.clone()).await;
+    }
+  }
+
+  /// Returns the row template with the given id
+  async fn get_row_template(&self, template_id: &str) -> FlowyResult<RowTemplate> {
+    get_row_template(&self.database.read().await, template_id).ok_or_else(|| {
+      FlowyError::record_not_found().with_context(format!("Row template:{} not found", template_id))
+    })
+  }
+
   pub async fn get_all_sorts(&self, view_id: &str) -> RepeatedSortPB {
     if let Ok(view_editor) = self.database_views.get_or_init_view_editor(view_id).await {
       view_editor.v_get_all_sorts().await.into()
@@ -392,16 +603,40 @@ impl DatabaseEditor {
 
   pub async fn update_field(&self, params: FieldChangesetPB) -> FlowyResult<()> {
     let mut database = self.database.write().await;
+    let old_name = database.get_field(&params.field_id).map(|field| field.name);
+    let new_name = params.name.clone();
     database.update_field(&params.field_id, |update| {
       update
         .set_name_if_not_none(params.name)
         .set_icon_if_not_none(params.icon);
     });
     notify_did_update_database_field(&database, &params.field_id)?;
+
+    if let (Some(old_name), Some(new_name)) = (old_name, new_name) {
+      if old_name != new_name {
+        rename_formula_references(&mut database, &old_name, &new_name);
+      }
+    }
     Ok(())
   }
 
   pub async fn delete_field(&self, field_id: &str) -> FlowyResult<()> {
+    let deleted_field = self.get_field_with_position(field_id).await;
+    let view_settings = self.get_field_view_settings(field_id).await;
+    self.remove_field(field_id).await?;
+    if let Some((field, position)) = deleted_field {
+      self.undo_manager.record(vec![DatabaseEdit::DeletedField {
+        field,
+        position,
+        view_settings,
+      }]);
+    }
+    Ok(())
+  }
+
+  /// Deletes the field without recording it, used to delete the field when its creation is
+  /// undone.
+  async fn remove_field(&self, field_id: &str) -> FlowyResult<()> {
     let is_primary = self
       .database
       .write()
@@ -417,10 +652,11 @@ impl DatabaseEditor {
       ));
     }
 
-    let database_id = {
+    let (database_id, should_update_formula_cells) = {
       let mut database = self.database.write().await;
+      let is_referenced = is_referenced_by_formula(&database.get_fields(None), field_id);
       database.delete_field(field_id);
-      database.get_database_id()
+      (database.get_database_id(), is_referenced)
     };
     let notified_changeset =
       DatabaseFieldChangesetPB::delete(&database_id, vec![FieldIdPB::from(field_id)]);
@@ -430,6 +666,9 @@ impl DatabaseEditor {
       view.v_did_delete_field(field_id).await;
     }
 
+    if should_update_formula_cells {
+      self.update_all_formula_cells().await?;
+    }
     Ok(())
   }
 
@@ -450,10 +689,22 @@ impl DatabaseEditor {
       ));
     }
 
+    // The cells are restored together when the clearing is undone
     let cells: Vec<RowCell> = self.get_cells_for_field(view_id, field_id).await;
+    let mut edits = vec![];
     for row_cell in cells {
-      self.clear_cell(view_id, row_cell.row_id, field_id).await?;
+      self
+        .write_cell(view_id, &row_cell.row_id, field_id, None)
+        .await?;
+      if row_cell.cell.is_some() {
+        edits.push(DatabaseEdit::UpdatedCells {
+          view_id: view_id.to_string(),
+          row_id: row_cell.row_id,
+          old_cells: vec![(field_id.to_string(), row_cell.cell)],
+        });
+      }
     }
+    self.undo_manager.record(edits);
 
     Ok(())
   }
@@ -462,22 +713,45 @@ impl DatabaseEditor {
   /// Do nothing if the [TypeOptionData] is empty.
   pub async fn update_field_type_option(
     &self,
-    _field_id: &str,
+    field_id: &str,
     type_option_data: TypeOptionData,
     old_field: Field,
   ) -> FlowyResult<()> {
+    let field_type = FieldType::from(old_field.field_type);
+    if field_type.is_formula() {
+      FormulaTypeOption::from(type_option_data.clone())
+        .parse_expression()
+        .map_err(|err| FlowyError::invalid_data().with_context(err))?;
+    }
+
+    let is_updated = !type_option_data.is_empty();
     let view_editors = self.database_views.editors().await;
-    {
+    let should_update_formula_cells = {
       let mut database = self.database.write().await;
+      let type_option_data = if field_type.is_auto_number() && !type_option_data.is_empty() {
+        keep_auto_number_counter(&database, field_id, type_option_data)
+      } else {
+        type_option_data
+      };
       update_field_type_option_fn(&mut database, type_option_data, &old_field).await?;
-      drop(database);
-    }
+      field_type.is_formula() || is_referenced_by_formula(&database.get_fields(None), field_id)
+    };
 
     for view_editor in view_editors {
       view_editor
         .v_did_update_field_type_option(&old_field)
         .await?;
     }
+
+    if should_update_formula_cells {
+      self.update_all_formula_cells().await?;
+    }
+
+    if is_updated {
+      self
+        .undo_manager
+        .record(vec![DatabaseEdit::UpdatedField { old_field }]);
+    }
     Ok(())
   }
 
@@ -497,6 +771,7 @@ impl DatabaseEditor {
         ));
       }
 
+      let old_field = field.clone();
       let old_field_type = FieldType::from(field.field_type);
       let old_type_option_data = field.get_any_type_option(old_field_type);
       let new_type_option_data = field
@@ -530,6 +805,18 @@ impl DatabaseEditor {
       let database = self.database.read().await;
 
       notify_did_update_database_field(&database, field_id)?;
+      let should_update_formula_cells = old_field_type.is_formula()
+        || new_field_type.is_formula()
+        || is_referenced_by_formula(&database.get_fields(None), field_id);
+      drop(database);
+
+      if should_update_formula_cells {
+        self.update_all_formula_cells().await?;
+      }
+
+      self
+        .undo_manager
+        .record(vec![DatabaseEdit::UpdatedField { old_field }]);
     }
 
     Ok(())
@@ -563,20 +850,27 @@ impl DatabaseEditor {
       for cell in cells {
         if let Some(new_cell) = cell.cell.clone() {
           self
-            .update_cell(view_id, &cell.row_id, &new_field_id, new_cell)
+            .write_cell(view_id, &cell.row_id, &new_field_id, Some(new_cell))
             .await?;
         }
       }
+
+      // The copied cells are removed with the field when the duplication is undone
+      self.undo_manager.record(vec![DatabaseEdit::CreatedField {
+        field_id: new_field_id,
+      }]);
     }
     Ok(())
   }
 
   pub async fn duplicate_row(&self, view_id: &str, row_id: &RowId) -> FlowyResult<()> {
     let mut database = self.database.write().await;
-    let params = database
+    let mut params = database
       .duplicate_row(row_id)
       .await
       .ok_or_else(|| FlowyError::internal().with_context("error while copying row"))?;
+    // The duplicated row gets new numbers instead of the numbers of the original row
+    insert_auto_number_cells(&mut database, &mut params.cells);
     let (index, row_order) = database.create_row_in_view(view_id, params).await?;
 
     let row_meta = database.get_row_meta(row_id).await;
@@ -601,6 +895,10 @@ impl DatabaseEditor {
       row_order
     );
 
+    self.undo_manager.record(vec![DatabaseEdit::CreatedRows {
+      view_id: view_id.to_string(),
+      row_ids: vec![row_order.id],
+    }]);
     Ok(())
   }
 
@@ -611,11 +909,20 @@ impl DatabaseEditor {
     from_row_id: RowId,
     to_row_id: RowId,
   ) -> FlowyResult<()> {
+    let old_index = self.get_row_index(view_id, &from_row_id).await;
     let mut database = self.database.write().await;
     database.update_database_view(view_id, |view| {
       view.move_row_order(&from_row_id, &to_row_id);
     });
+    drop(database);
 
+    if let Some(old_index) = old_index {
+      self.undo_manager.record(vec![DatabaseEdit::MovedRow {
+        view_id: view_id.to_string(),
+        row_id: from_row_id,
+        old_index,
+      }]);
+    }
     Ok(())
   }
 
@@ -625,6 +932,7 @@ impl DatabaseEditor {
     view_id: &str,
     from_group: &str,
     to_group: &str,
+    to_sub_group: Option<&str>,
     from_row: RowId,
     to_row: Option<RowId>,
   ) -> FlowyResult<()> {
@@ -639,15 +947,33 @@ impl DatabaseEditor {
       to_group
     );
 
+    // The cells and the position of the row are restored together when the move is undone
+    let mut edits = vec![];
+    if let Some(old_index) = self.get_row_index(view_id, &from_row).await {
+      edits.push(DatabaseEdit::MovedRow {
+        view_id: view_id.to_string(),
+        row_id: from_row.clone(),
+        old_index,
+      });
+    }
+
     // when moving row between groups, the cells of the row should be updated
     // if the updated cells is not empty, we need to update cells for given row
     let updated_cells = self
       .database_views
       .get_or_init_view_editor(view_id)
       .await?
-      .v_move_group_row(&row, to_group, to_row.clone())
+      .v_move_group_row(&row, to_group, to_sub_group, to_row.clone())
       .await;
     if !updated_cells.is_empty() {
+      edits.push(DatabaseEdit::UpdatedCells {
+        view_id: view_id.to_string(),
+        row_id: row.id.clone(),
+        old_cells: updated_cells
+          .keys()
+          .map(|field_id| (field_id.clone(), row.cells.get(field_id).cloned()))
+          .collect(),
+      });
       self
         .update_row(row.id, |row| {
           row
@@ -669,29 +995,75 @@ impl DatabaseEditor {
         .map(|row| row.id.clone())
     };
 
-    if let Some(to_row_id) = to_row.clone() {
-      self.move_row(view_id, from_row.clone(), to_row_id).await?;
+    if let Some(to_row_id) = to_row {
+      self
+        .database
+        .write()
+        .await
+        .update_database_view(view_id, |view| {
+          view.move_row_order(&from_row, &to_row_id);
+        });
     }
 
+    self.undo_manager.record(edits);
     Ok(())
   }
 
-  pub async fn create_row(&self, params: CreateRowPayloadPB) -> FlowyResult<Option<RowDetail>> {
+  pub async fn create_row(&self, mut params: CreateRowPayloadPB) -> FlowyResult<Option<RowDetail>> {
     let view_editor = self
       .database_views
       .get_or_init_view_editor(&params.view_id)
       .await?;
 
-    let params = view_editor.v_will_create_row(params).await?;
+    // fill in cells from the template, the cells from the frontend take precedence
+    let mut template_document = None;
+    if let Some(template_id) = params.template_id.take() {
+      let template = self.get_row_template(&template_id).await?;
+      for (field_id, content) in template.cells {
+        params.data.entry(field_id).or_insert(content);
+      }
+      template_document = template.document;
+    }
+
+    let mut params = view_editor.v_will_create_row(params).await?;
 
     let mut database = self.database.write().await;
+    insert_auto_number_cells(&mut database, &mut params.cells);
     let (index, row_order) = database
       .create_row_in_view(&view_editor.view_id, params)
       .await?;
-    let row_detail = database.get_row_detail(&row_order.id).await;
+    let row_document_id = database.get_row_document_id(&row_order.id);
     drop(database);
 
+    if let (Some(document), Some(document_id)) = (template_document, row_document_id) {
+      let document_id = Uuid::from_str(&document_id)?;
+      if let Err(err) = self
+        .row_document_service
+        .create_row_document(&document_id, &document)
+        .await
+      {
+        error!(
+          "Failed to create the document of row:{}: {}",
+          row_order.id, err
+        );
+      }
+    }
+
+    self
+      .update_formula_cells(&view_editor.view_id, std::slice::from_ref(&row_order.id))
+      .await?;
+    let row_detail = self
+      .database
+      .read()
+      .await
+      .get_row_detail(&row_order.id)
+      .await;
+
     trace!("[Database]: did create row: {} at {}", row_order.id, index);
+    self.undo_manager.record(vec![DatabaseEdit::CreatedRows {
+      view_id: view_editor.view_id.clone(),
+      row_ids: vec![row_order.id],
+    }]);
     if let Some(row_detail) = row_detail {
       trace!("created row: {:?} at {}", row_detail, index);
       return Ok(Some(row_detail));
@@ -731,6 +1103,16 @@ impl DatabaseEditor {
       .notify_did_insert_database_field(field.clone(), index)
       .await;
 
+    if params.field_type.is_formula() {
+      self.update_all_formula_cells().await?;
+    }
+    if params.field_type.is_auto_number() {
+      self.update_auto_number_cells().await?;
+    }
+
+    self.undo_manager.record(vec![DatabaseEdit::CreatedField {
+      field_id: field.id.clone(),
+    }]);
     Ok(FieldPB::new(field))
   }
 
@@ -845,6 +1227,7 @@ impl DatabaseEditor {
         is_document_empty: Some(row_meta.is_document_empty),
         attachment_count: Some(row_meta.attachment_count),
         cover: row_meta.cover.map(|cover| cover.into()),
+        formatting_rule_ids: vec![],
       })
     } else {
       warn!(
@@ -856,8 +1239,17 @@ impl DatabaseEditor {
     }
   }
 
-  pub async fn delete_rows(&self, row_ids: &[RowId]) {
+  /// Deletes the rows, the rows are put back at their position in the view when the deletion is
+  /// undone.
+  pub async fn delete_rows(&self, view_id: &str, row_ids: &[RowId]) {
+    let deleted_rows = self.get_rows_with_position(view_id, row_ids).await;
     let _ = self.database.write().await.remove_rows(row_ids).await;
+    if !deleted_rows.is_empty() {
+      self.undo_manager.record(vec![DatabaseEdit::DeletedRows {
+        view_id: view_id.to_string(),
+        rows: deleted_rows,
+      }]);
+    }
   }
 
   #[tracing::instrument(level = "trace", skip_all)]
@@ -968,6 +1360,19 @@ impl DatabaseEditor {
     field_id: &str,
     cell_changeset: BoxAny,
   ) -> FlowyResult<()> {
+    let new_cell = self
+      .cell_from_changeset(row_id, field_id, cell_changeset)
+      .await?;
+    self.update_cell(view_id, row_id, field_id, new_cell).await
+  }
+
+  /// Applies the changeset to the current cell and returns the new cell
+  async fn cell_from_changeset(
+    &self,
+    row_id: &RowId,
+    field_id: &str,
+    cell_changeset: BoxAny,
+  ) -> FlowyResult<Cell> {
     let (field, cell) = {
       let database = self.database.read().await;
       let field = match database.get_field(field_id) {
@@ -980,9 +1385,7 @@ impl DatabaseEditor {
       (field, database.get_cell(field_id, row_id).await.cell)
     };
 
-    let new_cell =
-      apply_cell_changeset(cell_changeset, cell, &field, Some(self.cell_cache.clone()))?;
-    self.update_cell(view_id, row_id, field_id, new_cell).await
+    apply_cell_changeset(cell_changeset, cell, &field, Some(self.cell_cache.clone()))
   }
 
   /// Update a cell in the database.
@@ -994,17 +1397,45 @@ impl DatabaseEditor {
     row_id: &RowId,
     field_id: &str,
     new_cell: Cell,
+  ) -> FlowyResult<()> {
+    let old_cells = self.get_cells_of_row(row_id, &[field_id]).await;
+    self
+      .write_cell(view_id, row_id, field_id, Some(new_cell))
+      .await?;
+    self.undo_manager.record(vec![DatabaseEdit::UpdatedCells {
+      view_id: view_id.to_string(),
+      row_id: row_id.clone(),
+      old_cells,
+    }]);
+    Ok(())
+  }
+
+  /// Writes the cell, or clears it if the cell is `None`, without recording the edit in the undo
+  /// manager.
+  async fn write_cell(
+    &self,
+    view_id: &str,
+    row_id: &RowId,
+    field_id: &str,
+    new_cell: Option<Cell>,
   ) -> FlowyResult<()> {
     // Get the old row before updating the cell. It would be better to get the old cell
     let old_row = self.get_row(view_id, row_id).await;
     trace!("[Database Row]: update cell: {:?}", new_cell);
     self
-      .update_row(row_id.clone(), |row_update| {
-        row_update
-          .set_last_modified(timestamp())
-          .update_cells(|cell_update| {
-            cell_update.insert(field_id, new_cell);
+      .update_row(row_id.clone(), |row_update| match new_cell {
+        Some(new_cell) => {
+          row_update
+            .set_last_modified(timestamp())
+            .update_cells(|cell_update| {
+              cell_update.insert(field_id, new_cell);
+            });
+        },
+        None => {
+          row_update.update_cells(|cell_update| {
+            cell_update.clear(field_id);
           });
+        },
       })
       .await?;
 
@@ -1015,6 +1446,19 @@ impl DatabaseEditor {
     Ok(())
   }
 
+  /// Returns the cells of the row stored in the database, `None` for the empty cells
+  async fn get_cells_of_row(
+    &self,
+    row_id: &RowId,
+    field_ids: &[&str],
+  ) -> Vec<(String, Option<Cell>)> {
+    let row = self.database.read().await.get_row(row_id).await;
+    field_ids
+      .iter()
+      .map(|field_id| (field_id.to_string(), row.cells.get(*field_id).cloned()))
+      .collect()
+  }
+
   pub async fn update_row<F>(&self, row_id: RowId, modify: F) -> FlowyResult<()>
   where
     F: FnOnce(RowUpdate),
@@ -1031,21 +1475,447 @@ impl DatabaseEditor {
   }
 
   pub async fn clear_cell(&self, view_id: &str, row_id: RowId, field_id: &str) -> FlowyResult<()> {
-    // Get the old row before updating the cell. It would be better to get the old cell
-    let old_row = self.get_row(view_id, &row_id).await;
+    let old_cells = self.get_cells_of_row(&row_id, &[field_id]).await;
+    self.write_cell(view_id, &row_id, field_id, None).await?;
+    self.undo_manager.record(vec![DatabaseEdit::UpdatedCells {
+      view_id: view_id.to_string(),
+      row_id,
+      old_cells,
+    }]);
+    Ok(())
+  }
+
+  /// Applies the changesets to the cells in one batch. The changesets are (row id, field id,
+  /// changeset), the views refresh their filters, sorts and calculations once for the batch.
+  #[instrument(level = "trace", skip_all)]
+  pub async fn update_cells_with_changesets(
+    &self,
+    view_id: &str,
+    changesets: Vec<(RowId, String, BoxAny)>,
+  ) -> FlowyResult<()> {
+    let changesets = changesets
+      .into_iter()
+      .map(|(row_id, field_id, changeset)| (row_id, field_id, Some(changeset)))
+      .collect();
+    let cells_by_row_id = self.cells_from_changesets(changesets).await?;
     self
-      .update_row(row_id.clone(), |row_update| {
-        row_update.update_cells(|cell_update| {
-          cell_update.clear(field_id);
+      .write_cells_in_batch(view_id, cells_by_row_id, vec![])
+      .await
+  }
+
+  /// Applies each changeset to the current cell with the [CellDataChangeset] of its field. A
+  /// `None` changeset clears the cell. Nothing is returned if any of the changesets fails, so
+  /// the batch is either written as a whole or not at all.
+  async fn cells_from_changesets(
+    &self,
+    changesets: Vec<(RowId, String, Option<BoxAny>)>,
+  ) -> FlowyResult<HashMap<RowId, Vec<(String, Option<Cell>)>>> {
+    let database = self.database.read().await;
+    let mut fields: HashMap<String, Field> = HashMap::new();
+    let mut rows: HashMap<RowId, Row> = HashMap::new();
+    let mut cells_by_row_id: HashMap<RowId, Vec<(String, Option<Cell>)>> = HashMap::new();
+    for (row_id, field_id, cell_changeset) in changesets {
+      let new_cell = match cell_changeset {
+        Some(cell_changeset) => {
+          if !fields.contains_key(&field_id) {
+            let field = database.get_field(&field_id).ok_or_else(|| {
+              FlowyError::internal().with_context(format!("Field with id:{} not found", field_id))
+            })?;
+            fields.insert(field_id.clone(), field);
+          }
+          if !rows.contains_key(&row_id) {
+            rows.insert(row_id.clone(), database.get_row(&row_id).await);
+          }
+          let old_cell = rows
+            .get(&row_id)
+            .and_then(|row| row.cells.get(&field_id).cloned());
+          Some(apply_cell_changeset(
+            cell_changeset,
+            old_cell,
+            &fields[&field_id],
+            Some(self.cell_cache.clone()),
+          )?)
+        },
+        None => None,
+      };
+      cells_by_row_id
+        .entry(row_id)
+        .or_default()
+        .push((field_id, new_cell));
+    }
+    Ok(cells_by_row_id)
+  }
+
+  /// Writes the cells of many rows and notifies the views once. Each row is a separate collab, so
+  /// the rows are updated in one transaction each while the database is locked. The cells that
+  /// are `None` are cleared. The written cells are recorded as one step with the given edits.
+  async fn write_cells_in_batch(
+    &self,
+    view_id: &str,
+    cells_by_row_id: HashMap<RowId, Vec<(String, Option<Cell>)>>,
+    mut edits: Vec<DatabaseEdit>,
+  ) -> FlowyResult<()> {
+    if cells_by_row_id.is_empty() {
+      self.undo_manager.record(edits);
+      return Ok(());
+    }
+
+    let mut old_rows = HashMap::with_capacity(cells_by_row_id.len());
+    for row_id in cells_by_row_id.keys() {
+      if self.finalized_rows.get(row_id.as_str()).await.is_none() {
+        self.init_database_row(row_id).await?;
+      }
+      old_rows.insert(row_id.clone(), self.get_row(view_id, row_id).await);
+    }
+
+    let mut updated_field_ids = HashSet::new();
+    let mut database = self.database.write().await;
+    for (row_id, cells) in cells_by_row_id {
+      let old_row = database.get_row(&row_id).await;
+      let old_cells = cells
+        .iter()
+        .map(|(field_id, _)| (field_id.clone(), old_row.cells.get(field_id).cloned()))
+        .collect::<Vec<(String, Option<Cell>)>>();
+      updated_field_ids.extend(cells.iter().map(|(field_id, _)| field_id.clone()));
+
+      database
+        .update_row(row_id.clone(), |row_update| {
+          row_update
+            .set_last_modified(timestamp())
+            .update_cells(|cell_update| {
+              cells
+                .into_iter()
+                .fold(cell_update, |cell_update, (field_id, cell)| match cell {
+                  Some(cell) => cell_update.insert(&field_id, cell),
+                  None => cell_update.clear(&field_id),
+                });
+            });
+        })
+        .await;
+      edits.push(DatabaseEdit::UpdatedCells {
+        view_id: view_id.to_string(),
+        row_id,
+        old_cells,
+      });
+    }
+    drop(database);
+
+    let mut updated_rows = Vec::with_capacity(old_rows.len());
+    for (row_id, old_row) in old_rows {
+      if let Some(row) = self.get_row(view_id, &row_id).await {
+        updated_rows.push((old_row, row));
+      }
+    }
+    let updated_field_ids = updated_field_ids.into_iter().collect::<Vec<String>>();
+    if !updated_rows.is_empty() {
+      for view in self.database_views.editors().await {
+        view
+          .v_did_update_rows(&updated_rows, updated_field_ids.clone())
+          .await;
+      }
+    }
+
+    self.undo_manager.record(edits);
+    Ok(())
+  }
+
+  /// Creates the rows at the end of the view. The cells of each row are prefilled like the cells
+  /// of a row created with [Self::create_row], and the rows are undone in one step.
+  pub async fn create_rows(
+    &self,
+    view_id: &str,
+    rows: Vec<HashMap<String, String>>,
+  ) -> FlowyResult<Vec<RowOrder>> {
+    let view_editor = self.database_views.get_or_init_view_editor(view_id).await?;
+    let mut params_list = Vec::with_capacity(rows.len());
+    for data in rows {
+      let params = view_editor
+        .v_will_create_row(CreateRowPayloadPB {
+          view_id: view_id.to_string(),
+          data,
+          ..Default::default()
+        })
+        .await?;
+      params_list.push(params);
+    }
+
+    let mut row_orders = Vec::with_capacity(params_list.len());
+    let mut database = self.database.write().await;
+    for mut params in params_list {
+      insert_auto_number_cells(&mut database, &mut params.cells);
+      let row_order = database.body.block.create_new_row(params).await?;
+      row_orders.push(row_order);
+    }
+    // The row orders are inserted in one transaction, so each view receives one change with all
+    // the created rows
+    {
+      let mut txn = database.collab.transact_mut();
+      database
+        .body
+        .views
+        .update_all_views_with_txn(&mut txn, |_, update| {
+          row_orders.iter().fold(update, |update, row_order| {
+            update.insert_row_order(row_order, &OrderObjectPosition::End)
+          });
         });
+    }
+    drop(database);
+
+    let row_ids = row_orders
+      .iter()
+      .map(|row_order| row_order.id.clone())
+      .collect::<Vec<RowId>>();
+    self.update_formula_cells(view_id, &row_ids).await?;
+
+    trace!("[Database]: did create {} rows", row_orders.len());
+    self.undo_manager.record(vec![DatabaseEdit::CreatedRows {
+      view_id: view_id.to_string(),
+      row_ids: row_orders
+        .iter()
+        .map(|row_order| row_order.id.clone())
+        .collect(),
+    }]);
+    Ok(row_orders)
+  }
+
+  /// Deletes the rows of the view matching the filter, the rows hidden by the filters of the view
+  /// are kept. Returns the ids of the deleted rows.
+  pub async fn delete_rows_by_filter(
+    &self,
+    view_id: &str,
+    filter: FilterInner,
+  ) -> FlowyResult<Vec<RowId>> {
+    let view_editor = self.database_views.get_or_init_view_editor(view_id).await?;
+    let rows = view_editor.v_get_all_rows().await;
+    let field_by_field_id = self
+      .database
+      .read()
+      .await
+      .get_fields(None)
+      .into_iter()
+      .map(|field| (field.id.clone(), field))
+      .collect::<HashMap<String, Field>>();
+    let filter = Filter {
+      id: gen_database_filter_id(),
+      inner: filter,
+    };
+    let context = FilterContext::new(self.user.user_email().ok(), self.user.timezone_id().ok());
+
+    let row_ids = rows
+      .iter()
+      .filter(|row| {
+        apply_filter(row, &field_by_field_id, &self.cell_cache, &filter, &context) == Some(true)
       })
-      .await?;
+      .map(|row| row.id.clone())
+      .collect::<Vec<RowId>>();
+    if !row_ids.is_empty() {
+      self.delete_rows(view_id, &row_ids).await;
+    }
+    Ok(row_ids)
+  }
 
+  /// Pastes the range of strings into the cells of the view, starting from the given cell. The
+  /// range is cut at the last row and the last field of the view. The select options missing from
+  /// the select fields are created, and the field updates are undone with the pasted cells.
+  #[instrument(level = "trace", skip_all, err)]
+  pub async fn paste_cells(&self, params: PasteCellsPayloadPB) -> FlowyResult<()> {
+    let view_id = params.view_id.as_str();
+    let view_editor = self.database_views.get_or_init_view_editor(view_id).await?;
+    let rows = view_editor.v_get_all_rows().await;
+    let fields = self.get_fields(view_id, None).await;
+    let row_index = rows
+      .iter()
+      .position(|row| row.id.as_str() == params.row_id)
+      .ok_or_else(|| {
+        FlowyError::record_not_found()
+          .with_context(format!("The row:{} in view not found", params.row_id))
+      })?;
+    let field_index = fields
+      .iter()
+      .position(|field| field.id == params.field_id)
+      .ok_or_else(|| {
+        FlowyError::record_not_found()
+          .with_context(format!("Field with id:{} not found", params.field_id))
+      })?;
+
+    // Create the missing select options before parsing the select cells
+    let mut fields = fields.into_iter().skip(field_index).collect::<Vec<Field>>();
+    let mut edits = vec![];
+    for (column, field) in fields.iter_mut().enumerate() {
+      let field_type = FieldType::from(field.field_type);
+      if !field_type.is_select_option() {
+        continue;
+      }
+      let old_field = field.clone();
+      let mut is_updated = false;
+      for pasted_row in params.rows.iter() {
+        if let Some(content) = pasted_row.cells.get(column) {
+          is_updated |= insert_missing_select_options(field, field_type, content);
+        }
+      }
+      if !is_updated {
+        continue;
+      }
+
+      if let Some(type_option_data) = field.get_any_type_option(field_type) {
+        let mut database = self.database.write().await;
+        update_field_type_option_fn(&mut database, type_option_data, &old_field).await?;
+      }
+      for view_editor in self.database_views.editors().await {
+        view_editor
+          .v_did_update_field_type_option(&old_field)
+          .await?;
+      }
+      edits.push(DatabaseEdit::UpdatedField { old_field });
+    }
+
+    let mut changesets = vec![];
+    for (pasted_row, row) in params.rows.iter().zip(rows.iter().skip(row_index)) {
+      for (content, field) in pasted_row.cells.iter().zip(fields.iter()) {
+        if let Some(changeset) = pasted_cell_changeset(content, field) {
+          changesets.push((row.id.clone(), field.id.clone(), changeset));
+        }
+      }
+    }
+
+    let cells_by_row_id = self.cells_from_changesets(changesets).await?;
     self
-      .did_update_row(view_id, &row_id, field_id, old_row)
-      .await;
+      .write_cells_in_batch(view_id, cells_by_row_id, edits)
+      .await
+  }
 
-    Ok(())
+  /// Returns the rows and the fields searched by find and replace. The rows of the whole database
+  /// are in the order of the inline view.
+  async fn get_searchable_rows_and_fields(
+    &self,
+    view_id: &str,
+    only_in_view: bool,
+  ) -> FlowyResult<(Vec<Arc<Row>>, Vec<Field>)> {
+    if only_in_view {
+      let rows = self.get_all_rows(view_id).await?;
+      let fields = self.get_fields(view_id, None).await;
+      return Ok((rows, fields));
+    }
+
+    let database = self.database.read().await;
+    let inline_view_id = database.get_inline_view_id();
+    let mut rows = vec![];
+    for row_order in database.get_row_orders_for_view(&inline_view_id) {
+      rows.push(Arc::new(database.get_row(&row_order.id).await));
+    }
+    let fields = database.get_fields_in_view(&inline_view_id, None);
+    Ok((rows, fields))
+  }
+
+  /// Finds the occurrences of the matcher in the text of the cells, row by row.
+  pub async fn find_in_database(
+    &self,
+    view_id: &str,
+    matcher: &TextMatcher,
+    only_in_view: bool,
+  ) -> FlowyResult<Vec<CellMatchPB>> {
+    let (rows, fields) = self
+      .get_searchable_rows_and_fields(view_id, only_in_view)
+      .await?;
+    let mut matches = vec![];
+    for row in rows.iter() {
+      for field in fields.iter() {
+        if let Some(cell) = row.cells.get(&field.id) {
+          let text = stringify_cell(cell, field);
+          matches.extend(
+            matcher
+              .find(&text)
+              .into_iter()
+              .map(|(start, end)| CellMatchPB {
+                row_id: row.id.to_string(),
+                field_id: field.id.clone(),
+                start: start as i32,
+                end: end as i32,
+              }),
+          );
+        }
+      }
+    }
+    Ok(matches)
+  }
+
+  /// Replaces the occurrences of the matcher. The select options are renamed through their type
+  /// option, and the cells of the text like fields are rewritten. The replacement is undone in
+  /// one step.
+  #[instrument(level = "trace", skip_all, err)]
+  pub async fn replace_in_database(
+    &self,
+    view_id: &str,
+    matcher: &TextMatcher,
+    replacement: &str,
+    only_in_view: bool,
+  ) -> FlowyResult<ReplaceInDatabaseResultPB> {
+    let (rows, fields) = self
+      .get_searchable_rows_and_fields(view_id, only_in_view)
+      .await?;
+    let mut result = ReplaceInDatabaseResultPB::default();
+    let mut edits = vec![];
+    for old_field in fields.iter() {
+      if !FieldType::from(old_field.field_type).is_select_option() {
+        continue;
+      }
+      let mut type_option = select_type_option_from_field(old_field)?;
+      let num_of_renamed_options =
+        rename_select_options(type_option.mut_options(), matcher, replacement);
+      if num_of_renamed_options == 0 {
+        continue;
+      }
+
+      let mut database = self.database.write().await;
+      update_field_type_option_fn(&mut database, type_option.to_type_option_data(), old_field)
+        .await?;
+      drop(database);
+      for view_editor in self.database_views.editors().await {
+        view_editor
+          .v_did_update_field_type_option(old_field)
+          .await?;
+      }
+      edits.push(DatabaseEdit::UpdatedField {
+        old_field: old_field.clone(),
+      });
+      result.num_of_renamed_options += num_of_renamed_options as i32;
+    }
+
+    let mut changesets = vec![];
+    for row in rows.iter() {
+      for field in fields.iter() {
+        let field_type = FieldType::from(field.field_type);
+        if field_type.is_select_option() {
+          continue;
+        }
+        let text = match row.cells.get(&field.id) {
+          Some(cell) => stringify_cell(cell, field),
+          None => continue,
+        };
+        if !matcher.is_match(&text) {
+          continue;
+        }
+
+        let changeset = if is_replaceable_field_type(&field_type) {
+          pasted_cell_changeset(&matcher.replace(&text, replacement), field)
+        } else {
+          None
+        };
+        match changeset {
+          Some(changeset) => {
+            changesets.push((row.id.clone(), field.id.clone(), changeset));
+            result.num_of_replaced_cells += 1;
+          },
+          None => result.num_of_skipped_cells += 1,
+        }
+      }
+    }
+
+    let cells_by_row_id = self.cells_from_changesets(changesets).await?;
+    self
+      .write_cells_in_batch(view_id, cells_by_row_id, edits)
+      .await?;
+    Ok(result)
   }
 
   async fn did_update_row(
@@ -1085,6 +1955,316 @@ impl DatabaseEditor {
     }
   }
 
+  /// Recomputes the formula cells of the rows whose cells referenced by a formula have changed.
+  /// Called by the row change observer, so the edits received from the other devices are
+  /// recomputed like the local ones.
+  pub(crate) async fn did_update_formula_source_cells(
+    &self,
+    changed_field_ids_by_row_id: HashMap<RowId, HashSet<String>>,
+  ) -> FlowyResult<()> {
+    let (view_id, fields) = {
+      let database = self.database.read().await;
+      (database.get_inline_view_id(), database.get_fields(None))
+    };
+    let row_ids = changed_field_ids_by_row_id
+      .into_iter()
+      .filter(|(_, field_ids)| {
+        field_ids
+          .iter()
+          .any(|field_id| is_referenced_by_formula(&fields, field_id))
+      })
+      .map(|(row_id, _)| row_id)
+      .collect::<Vec<RowId>>();
+    if row_ids.is_empty() {
+      return Ok(());
+    }
+
+    self.update_formula_cells(&view_id, &row_ids).await
+  }
+
+  /// Recomputes the formula cells of the rows
+  async fn update_formula_cells(&self, view_id: &str, row_ids: &[RowId]) -> FlowyResult<()> {
+    let fields = self.database.read().await.get_fields(None);
+    if !has_formula_field(&fields) {
+      return Ok(());
+    }
+
+    let mut computed_rows = Vec::with_capacity(row_ids.len());
+    for row_id in row_ids {
+      if let Some(row) = self.get_row(view_id, row_id).await {
+        let formula_cells = compute_formula_cells(&row, &fields);
+        computed_rows.push((row, formula_cells));
+      }
+    }
+    self.write_computed_cells(view_id, computed_rows).await
+  }
+
+  /// Writes the cells computed from other cells, for example the rollup cells, to the row. The
+  /// formula cells that reference the written cells are recomputed.
+  pub(crate) async fn update_computed_cells(
+    &self,
+    view_id: &str,
+    row_id: &RowId,
+    cells: HashMap<String, Cell>,
+  ) -> FlowyResult<()> {
+    let old_row = match self.get_row(view_id, row_id).await {
+      None => return Ok(()),
+      Some(row) => row,
+    };
+    let fields = self.database.read().await.get_fields(None);
+    let should_update_formula_cells = cells
+      .keys()
+      .any(|field_id| is_referenced_by_formula(&fields, field_id));
+    self
+      .write_computed_cells(view_id, vec![(old_row, cells)])
+      .await?;
+
+    if should_update_formula_cells {
+      self
+        .update_formula_cells(view_id, std::slice::from_ref(row_id))
+        .await?;
+    }
+    Ok(())
+  }
+
+  /// Writes the computed cells of the rows. Only the cells whose value has changed are written,
+  /// and the views are notified once for all the rows, so the filters, sorts, groups and
+  /// calculations that depend on the cells are refreshed once per field.
+  async fn write_computed_cells(
+    &self,
+    view_id: &str,
+    computed_rows: Vec<(Row, HashMap<String, Cell>)>,
+  ) -> FlowyResult<()> {
+    let mut updated_field_ids = HashSet::new();
+    let mut changed_rows = vec![];
+    for (old_row, mut cells) in computed_rows {
+      cells.retain(|field_id, cell| {
+        let old_cell = old_row.cells.get(field_id);
+        old_cell.and_then(|old_cell| old_cell.get(CELL_DATA)) != cell.get(CELL_DATA)
+      });
+      if cells.is_empty() {
+        continue;
+      }
+
+      if self.finalized_rows.get(old_row.id.as_str()).await.is_none() {
+        self.init_database_row(&old_row.id).await?;
+      }
+      updated_field_ids.extend(cells.keys().cloned());
+      changed_rows.push((old_row, cells));
+    }
+    if changed_rows.is_empty() {
+      return Ok(());
+    }
+
+    let mut database = self.database.write().await;
+    for (old_row, cells) in changed_rows.iter() {
+      database
+        .update_row(old_row.id.clone(), |row_update| {
+          row_update.set_cells(Cells::from(cells.clone()));
+        })
+        .await;
+    }
+    drop(database);
+
+    let mut updated_rows = Vec::with_capacity(changed_rows.len());
+    for (old_row, _) in changed_rows {
+      if let Some(row) = self.get_row(view_id, &old_row.id).await {
+        updated_rows.push((Some(old_row), row));
+      }
+    }
+    let updated_field_ids = updated_field_ids.into_iter().collect::<Vec<String>>();
+    for view in self.database_views.editors().await {
+      view
+        .v_did_update_rows(&updated_rows, updated_field_ids.clone())
+        .await;
+    }
+
+    let row_ids = updated_rows
+      .into_iter()
+      .map(|(_, row)| row.id)
+      .collect::<Vec<RowId>>();
+    let _ = self.computed_cells_tx.send(row_ids);
+    Ok(())
+  }
+
+  /// Subscribes to the ids of the rows whose computed cells, the formula and rollup cells, have
+  /// been written.
+  pub fn subscribe_computed_cells(&self) -> broadcast::Receiver<Vec<RowId>> {
+    self.computed_cells_tx.subscribe()
+  }
+
+  /// Recomputes the formula cells of all the rows in the database.
+  async fn update_all_formula_cells(&self) -> FlowyResult<()> {
+    let (view_id, row_ids) = {
+      let database = self.database.read().await;
+      if !has_formula_field(&database.get_fields(None)) {
+        return Ok(());
+      }
+      let view_id = database.get_inline_view_id();
+      let row_ids = database
+        .get_row_orders_for_view(&view_id)
+        .into_iter()
+        .map(|row_order| row_order.id)
+        .collect::<Vec<RowId>>();
+      (view_id, row_ids)
+    };
+
+    self.update_formula_cells(&view_id, &row_ids).await
+  }
+
+  /// Gives a number to the rows whose auto number is missing or also used by another row. The
+  /// rows created before the auto number field don't have a number, and the rows created
+  /// offline on different devices may have the same number after they are merged. The counters
+  /// are also advanced past the numbers of the rows merged from the other devices.
+  pub(crate) async fn update_auto_number_cells(&self) -> FlowyResult<()> {
+    let (view_id, fields) = {
+      let database = self.database.read().await;
+      let fields = database
+        .get_fields(None)
+        .into_iter()
+        .filter(|field| FieldType::from(field.field_type).is_auto_number())
+        .collect::<Vec<Field>>();
+      (database.get_inline_view_id(), fields)
+    };
+    if fields.is_empty() {
+      return Ok(());
+    }
+
+    let rows = self.get_all_rows(&view_id).await?;
+    for field in fields {
+      let numbered_rows = {
+        let mut database = self.database.write().await;
+        let mut type_option = database
+          .get_field(&field.id)
+          .and_then(|field| field.get_type_option::<AutoNumberTypeOption>(FieldType::AutoNumber))
+          .unwrap_or_default();
+        let next_number = type_option.next_number;
+        let numbered_rows = type_option.number_rows(&field.id, rows.iter().map(|row| row.as_ref()));
+        if type_option.next_number != next_number {
+          database.update_field(&field.id, |update| {
+            update.update_type_options(|type_options_update| {
+              type_options_update.insert(&FieldType::AutoNumber.to_string(), type_option.into());
+            });
+          });
+        }
+        numbered_rows
+      };
+
+      for (row_id, cell_data) in numbered_rows {
+        trace!(
+          "[AutoNumber]: assign number:{} to row:{}",
+          cell_data.0,
+          row_id
+        );
+        self
+          .write_cell(&view_id, &row_id, &field.id, Some(Cell::from(cell_data)))
+          .await?;
+      }
+    }
+    Ok(())
+  }
+
+  /// Returns the configured rollup fields of the database.
+  pub async fn get_rollup_fields(&self) -> Vec<RollupField> {
+    let database = self.database.read().await;
+    database
+      .get_fields(None)
+      .into_iter()
+      .filter(|field| FieldType::from(field.field_type).is_rollup())
+      .filter_map(|field| {
+        let type_option = field
+          .get_type_option::<RollupTypeOption>(FieldType::Rollup)
+          .unwrap_or_default();
+        if !type_option.is_configured() {
+          return None;
+        }
+
+        let related_database_id = database
+          .get_field(&type_option.relation_field_id)?
+          .get_type_option::<RelationTypeOption>(FieldType::Relation)?
+          .database_id;
+        if related_database_id.is_empty() {
+          return None;
+        }
+        Some(RollupField {
+          field,
+          type_option,
+          related_database_id,
+        })
+      })
+      .collect()
+  }
+
+  /// Returns the values of the target field in the given rows. It's used by the rollup fields
+  /// whose relation field links to this database. The rows that don't exist are skipped.
+  pub async fn get_rollup_source_values(
+    &self,
+    row_ids: &[RowId],
+    target_field_id: &str,
+  ) -> Vec<RollupSourceValue> {
+    let (view_id, field) = {
+      let database = self.database.read().await;
+      (
+        database.get_inline_view_id(),
+        database.get_field(target_field_id),
+      )
+    };
+    let field = match field {
+      None => return vec![],
+      Some(field) => field,
+    };
+    let field_type = FieldType::from(field.field_type);
+    let handler = TypeOptionCellExt::new(&field, Some(self.cell_cache.clone()))
+      .get_type_option_cell_data_handler();
+
+    let mut values = vec![];
+    for row_id in row_ids {
+      if !self.database.read().await.contains_row(&view_id, row_id) {
+        continue;
+      }
+
+      let value = match self.get_cell(target_field_id, row_id).await {
+        None => RollupSourceValue::default(),
+        Some(cell) => RollupSourceValue {
+          content: stringify_cell(&cell, &field),
+          number: handler
+            .as_ref()
+            .and_then(|handler| handler.handle_numeric_cell(&cell)),
+          is_checked: field_type.is_checkbox() && CheckboxCellDataPB::from(&cell).is_checked,
+        },
+      };
+      values.push(value);
+    }
+    values
+  }
+
+  /// Returns the rows whose relation cell of the `relation_field_id` field contains the
+  /// `related_row_id`.
+  pub async fn get_row_ids_related_to(
+    &self,
+    relation_field_id: &str,
+    related_row_id: &RowId,
+  ) -> Vec<RowId> {
+    let view_id = self.database.read().await.get_inline_view_id();
+    self
+      .get_cells_for_field(&view_id, relation_field_id)
+      .await
+      .into_iter()
+      .filter(|row_cell| {
+        row_cell
+          .cell
+          .as_ref()
+          .map(|cell| {
+            RelationCellData::from(cell)
+              .row_ids
+              .contains(related_row_id)
+          })
+          .unwrap_or(false)
+      })
+      .map(|row_cell| row_cell.row_id)
+      .collect()
+  }
+
   async fn did_update_attachments(
     &self,
     view_id: &str,
@@ -1226,9 +2406,8 @@ impl DatabaseEditor {
 
     // Insert the options into the cell
     self
-      .update_cell_with_changeset(view_id, &row_id, field_id, BoxAny::new(cell_changeset))
-      .await?;
-    Ok(())
+      .update_select_option_cell(view_id, row_id, field, cell_changeset)
+      .await
   }
 
   pub async fn delete_select_options(
@@ -1267,8 +2446,34 @@ impl DatabaseEditor {
     }
 
     self
-      .update_cell_with_changeset(view_id, &row_id, field_id, BoxAny::new(cell_changeset))
+      .update_select_option_cell(view_id, row_id, field, cell_changeset)
+      .await
+  }
+
+  /// Updates the cell after the options of the field were edited. The options and the cell are
+  /// restored together when the edit is undone.
+  async fn update_select_option_cell(
+    &self,
+    view_id: &str,
+    row_id: RowId,
+    old_field: Field,
+    cell_changeset: SelectOptionCellChangeset,
+  ) -> FlowyResult<()> {
+    let old_cells = self.get_cells_of_row(&row_id, &[&old_field.id]).await;
+    let new_cell = self
+      .cell_from_changeset(&row_id, &old_field.id, BoxAny::new(cell_changeset))
+      .await?;
+    self
+      .write_cell(view_id, &row_id, &old_field.id, Some(new_cell))
       .await?;
+    self.undo_manager.record(vec![
+      DatabaseEdit::UpdatedField { old_field },
+      DatabaseEdit::UpdatedCells {
+        view_id: view_id.to_string(),
+        row_id,
+        old_cells,
+      },
+    ]);
     Ok(())
   }
 
@@ -1436,6 +2641,525 @@ impl DatabaseEditor {
     Ok(database_view_setting_pb_from_view(view))
   }
 
+  /// Rolls the database back to the given data, e.g. a local snapshot. The fields, the rows and
+  /// the filters, sorts, groups and field settings of the views are replaced in place, so the ids
+  /// of the views don't change. The views that don't exist in the data are left untouched.
+  #[tracing::instrument(level = "debug", skip_all, err)]
+  pub async fn restore_database_data(&self, data: DatabaseData) -> FlowyResult<()> {
+    // The view editors cache the settings and the rows of the views, so they are opened again
+    // after restoring.
+    let opened_view_ids = self
+      .database_views
+      .editors()
+      .await
+      .into_iter()
+      .map(|editor| editor.view_id.clone())
+      .collect::<Vec<_>>();
+    for view_id in opened_view_ids.iter() {
+      self.database_views.remove_view(view_id).await;
+    }
+
+    let mut updated_rows = vec![];
+    {
+      let mut database = self.database.write().await;
+
+      // Fields
+      let field_ids = data
+        .fields
+        .iter()
+        .map(|field| field.id.as_str())
+        .collect::<HashSet<_>>();
+      for field in database.get_fields(None) {
+        if !field.is_primary && !field_ids.contains(field.id.as_str()) {
+          database.delete_field(&field.id);
+        }
+      }
+      let mut position = OrderObjectPosition::Start;
+      for field in data.fields.iter() {
+        if database.get_field(&field.id).is_some() {
+          database.update_field(&field.id, |update| {
+            update
+              .set_name_if_not_none(Some(field.name.clone()))
+              .set_icon_if_not_none(Some(field.icon.clone()))
+              .set_field_type(field.field_type)
+              .update_type_options(|type_options_update| {
+                for (field_type, type_option) in field.type_options.iter() {
+                  type_options_update.insert(field_type, type_option.clone());
+                }
+              });
+          });
+        } else {
+          database.create_field(
+            None,
+            field.clone(),
+            &position,
+            default_field_settings_by_layout_map(),
+          );
+        }
+        position = OrderObjectPosition::After(field.id.clone());
+      }
+
+      // Settings of the views
+      for view in data.views.iter() {
+        if database.get_view(&view.id).is_none() {
+          continue;
+        }
+        database.update_database_view(&view.id, |update| {
+          update
+            .set_filters(view.filters.clone())
+            .set_sorts(view.sorts.clone())
+            .set_groups(view.group_settings.clone());
+        });
+        for (field_id, field_settings) in view.field_settings.iter() {
+          database.update_field_settings(
+            &view.id,
+            Some(vec![field_id.clone()]),
+            field_settings.clone(),
+          );
+        }
+      }
+
+      // Rows
+      let row_ids = data
+        .rows
+        .iter()
+        .map(|row| row.id.clone())
+        .collect::<HashSet<_>>();
+      let current_row_ids = database
+        .get_all_row_orders()
+        .await
+        .into_iter()
+        .map(|row_order| row_order.id)
+        .collect::<HashSet<_>>();
+      let deleted_row_ids = current_row_ids
+        .iter()
+        .filter(|row_id| !row_ids.contains(*row_id))
+        .cloned()
+        .collect::<Vec<_>>();
+      let _ = database.remove_rows(&deleted_row_ids).await;
+
+      let inline_view_id = database.get_inline_view_id();
+      let mut position = OrderObjectPosition::Start;
+      for row in data.rows {
+        let row_id = row.id.to_string();
+        if current_row_ids.contains(&row.id) {
+          updated_rows.push(row);
+        } else {
+          let params = CreateRowParams {
+            id: row.id,
+            database_id: row.database_id,
+            cells: row.cells,
+            height: row.height,
+            visibility: row.visibility,
+            row_position: position,
+            created_at: row.created_at,
+            modified_at: row.modified_at,
+          };
+          database.create_row_in_view(&inline_view_id, params).await?;
+        }
+        position = OrderObjectPosition::After(row_id);
+      }
+    }
+
+    for row in updated_rows {
+      let current_row = self.database.read().await.get_row(&row.id).await;
+      if *current_row.cells == *row.cells {
+        continue;
+      }
+      let cleared_field_ids = current_row
+        .cells
+        .keys()
+        .filter(|field_id| !row.cells.contains_key(*field_id))
+        .cloned()
+        .collect::<Vec<_>>();
+      self
+        .update_row(row.id.clone(), |row_update| {
+          row_update.set_cells(row.cells).update_cells(|cell_update| {
+            cleared_field_ids
+              .iter()
+              .fold(cell_update, |cell_update, field_id| {
+                cell_update.clear(field_id)
+              });
+          });
+        })
+        .await?;
+    }
+
+    for view_id in opened_view_ids.iter() {
+      self.database_views.get_or_init_view_editor(view_id).await?;
+    }
+    // The recorded edits may not apply to the restored database
+    self.undo_manager.clear();
+    let views = self.database.read().await.get_all_database_views_meta();
+    for view in views {
+      database_notification_builder(&view.id, DatabaseNotification::DidRestoreDatabaseSnapshot)
+        .send();
+    }
+    Ok(())
+  }
+
+  pub fn can_undo(&self) -> bool {
+    self.undo_manager.can_undo()
+  }
+
+  pub fn can_redo(&self) -> bool {
+    self.undo_manager.can_redo()
+  }
+
+  /// Reverts the latest edit of the local user. Returns false if there is nothing to undo.
+  pub async fn undo(&self) -> FlowyResult<bool> {
+    match self.undo_manager.start_undo() {
+      None => Ok(false),
+      Some(edits) => {
+        self.revert_step(true, edits).await?;
+        Ok(true)
+      },
+    }
+  }
+
+  /// Applies the latest undone edit again. Returns false if there is nothing to redo.
+  pub async fn redo(&self) -> FlowyResult<bool> {
+    match self.undo_manager.start_redo() {
+      None => Ok(false),
+      Some(edits) => {
+        self.revert_step(false, edits).await?;
+        Ok(true)
+      },
+    }
+  }
+
+  /// Reverts the edits of the step in the reverse order. The edits reverting them are kept in the
+  /// order they are applied, so reverting them in the reverse order applies the step again.
+  /// If an edit fails to be reverted, the edits that are not reverted yet are pushed back to the
+  /// stack they were taken from.
+  async fn revert_step(&self, is_undo: bool, mut edits: Vec<DatabaseEdit>) -> FlowyResult<()> {
+    let mut reverted_edits = vec![];
+    while let Some(edit) = edits.pop() {
+      match self.revert_edit(edit.clone()).await {
+        Ok(reverted_edit) => reverted_edits.push(reverted_edit),
+        Err(err) => {
+          error!("[Database]: revert edit failed: {}", err);
+          edits.push(edit);
+          self
+            .undo_manager
+            .finish_reverting(is_undo, reverted_edits, edits);
+          return Err(err);
+        },
+      }
+    }
+    self
+      .undo_manager
+      .finish_reverting(is_undo, reverted_edits, vec![]);
+    Ok(())
+  }
+
+  /// Reverts the edit and returns the edit that reverts it again
+  async fn revert_edit(&self, edit: DatabaseEdit) -> FlowyResult<DatabaseEdit> {
+    trace!("[Database]: revert edit: {:?}", edit);
+    match edit {
+      DatabaseEdit::UpdatedCells {
+        view_id,
+        row_id,
+        old_cells,
+      } => {
+        let field_ids = old_cells
+          .iter()
+          .map(|(field_id, _)| field_id.as_str())
+          .collect::<Vec<_>>();
+        let current_cells = self.get_cells_of_row(&row_id, &field_ids).await;
+        for (field_id, cell) in old_cells {
+          self.write_cell(&view_id, &row_id, &field_id, cell).await?;
+        }
+        Ok(DatabaseEdit::UpdatedCells {
+          view_id,
+          row_id,
+          old_cells: current_cells,
+        })
+      },
+      DatabaseEdit::CreatedRows { view_id, row_ids } => {
+        let rows = self.get_rows_with_position(&view_id, &row_ids).await;
+        let _ = self.database.write().await.remove_rows(&row_ids).await;
+        Ok(DatabaseEdit::DeletedRows { view_id, rows })
+      },
+      DatabaseEdit::DeletedRows { view_id, rows } => {
+        let mut database = self.database.write().await;
+        let mut row_ids = vec![];
+        for (row, position) in rows {
+          row_ids.push(row.id.clone());
+          let params = CreateRowParams {
+            id: row.id,
+            database_id: row.database_id,
+            cells: row.cells,
+            height: row.height,
+            visibility: row.visibility,
+            row_position: position,
+            created_at: row.created_at,
+            modified_at: row.modified_at,
+          };
+          database.create_row_in_view(&view_id, params).await?;
+        }
+        Ok(DatabaseEdit::CreatedRows { view_id, row_ids })
+      },
+      DatabaseEdit::MovedRow {
+        view_id,
+        row_id,
+        old_index,
+      } => {
+        let current_index = self.get_row_index(&view_id, &row_id).await.ok_or_else(|| {
+          FlowyError::record_not_found().with_context(format!("Can not find the row:{}", row_id))
+        })?;
+        if let Some(row_order) = self
+          .get_row_order_at_index(&view_id, old_index as u32)
+          .await
+        {
+          self
+            .database
+            .write()
+            .await
+            .update_database_view(&view_id, |view| {
+              view.move_row_order(&row_id, &row_order.id);
+            });
+        }
+        Ok(DatabaseEdit::MovedRow {
+          view_id,
+          row_id,
+          old_index: current_index,
+        })
+      },
+      DatabaseEdit::CreatedField { field_id } => {
+        let (field, position) = self
+          .get_field_with_position(&field_id)
+          .await
+          .ok_or_else(|| {
+            FlowyError::record_not_found()
+              .with_context(format!("Field with id:{} not found", field_id))
+          })?;
+        let view_settings = self.get_field_view_settings(&field_id).await;
+        self.remove_field(&field_id).await?;
+        Ok(DatabaseEdit::DeletedField {
+          field,
+          position,
+          view_settings,
+        })
+      },
+      DatabaseEdit::DeletedField {
+        field,
+        position,
+        view_settings,
+      } => {
+        let field_id = field.id.clone();
+        let (index, should_update_formula_cells) = {
+          let mut database = self.database.write().await;
+          database.create_field(
+            None,
+            field.clone(),
+            &position,
+            default_field_settings_by_layout_map(),
+          );
+          let index = database
+            .index_of_field(&database.get_inline_view_id(), &field_id)
+            .unwrap_or_default();
+          let should_update_formula_cells = FieldType::from(field.field_type).is_formula()
+            || is_referenced_by_formula(&database.get_fields(None), &field_id);
+          (index, should_update_formula_cells)
+        };
+        let _ = self.notify_did_insert_database_field(field, index).await;
+        self.restore_field_view_settings(view_settings).await?;
+
+        if should_update_formula_cells {
+          self.update_all_formula_cells().await?;
+        }
+        Ok(DatabaseEdit::CreatedField { field_id })
+      },
+      DatabaseEdit::UpdatedField { old_field } => {
+        let current_field = self.get_field(&old_field.id).await.ok_or_else(|| {
+          FlowyError::record_not_found()
+            .with_context(format!("Field with id:{} not found", old_field.id))
+        })?;
+        let old_field_type = FieldType::from(old_field.field_type);
+        let current_field_type = FieldType::from(current_field.field_type);
+        let should_update_formula_cells = {
+          let mut database = self.database.write().await;
+          database.update_field(&old_field.id, |update| {
+            update
+              .set_name_if_not_none(Some(old_field.name.clone()))
+              .set_field_type(old_field.field_type)
+              .update_type_options(|type_options_update| {
+                for (field_type, type_option) in old_field.type_options.iter() {
+                  type_options_update.insert(field_type, type_option.clone());
+                }
+              });
+          });
+          notify_did_update_database_field(&database, &old_field.id)?;
+          old_field_type.is_formula()
+            || current_field_type.is_formula()
+            || is_referenced_by_formula(&database.get_fields(None), &old_field.id)
+        };
+
+        for view in self.database_views.editors().await {
+          if old_field_type != current_field_type {
+            view
+              .v_did_update_field_type(&old_field.id, old_field_type)
+              .await;
+          } else {
+            view.v_did_update_field_type_option(&current_field).await?;
+          }
+        }
+
+        if should_update_formula_cells {
+          self.update_all_formula_cells().await?;
+        }
+        Ok(DatabaseEdit::UpdatedField {
+          old_field: current_field,
+        })
+      },
+    }
+  }
+
+  /// Returns the rows and their position in the view, in the order of the view
+  async fn get_rows_with_position(
+    &self,
+    view_id: &str,
+    row_ids: &[RowId],
+  ) -> Vec<(Row, OrderObjectPosition)> {
+    let database = self.database.read().await;
+    let mut rows = vec![];
+    let mut position = OrderObjectPosition::Start;
+    for row_order in database.get_row_orders_for_view(view_id) {
+      if row_ids.contains(&row_order.id) {
+        let row = database.get_row(&row_order.id).await;
+        rows.push((row, position));
+      }
+      position = OrderObjectPosition::After(row_order.id.to_string());
+    }
+    rows
+  }
+
+  /// Returns the field and its position in the inline view
+  async fn get_field_with_position(&self, field_id: &str) -> Option<(Field, OrderObjectPosition)> {
+    let database = self.database.read().await;
+    let field = database.get_field(field_id)?;
+    let mut position = OrderObjectPosition::Start;
+    for field in database.get_fields_in_view(&database.get_inline_view_id(), None) {
+      if field.id == field_id {
+        break;
+      }
+      position = OrderObjectPosition::After(field.id);
+    }
+    Some((field, position))
+  }
+
+  /// Returns the settings of the field in each view, and the filters, sorts and group settings
+  /// of the views that are removed with the field when it is deleted
+  async fn get_field_view_settings(&self, field_id: &str) -> Vec<FieldViewSettings> {
+    let database = self.database.read().await;
+    let field_ids = vec![field_id.to_string()];
+    let mut view_settings = vec![];
+    for view in database.get_all_database_views_meta() {
+      let view_id = view.id;
+      let layout = database.get_database_view_layout(&view_id);
+      let field_settings = database
+        .get_field_settings(&view_id, Some(field_ids.as_slice()))
+        .get(field_id)
+        .map(|field_settings| FieldSettings::from_any_map(field_id, layout, field_settings));
+
+      let all_filters: Vec<Filter> = database.get_all_filters(&view_id);
+      let filters = all_filters
+        .iter()
+        .enumerate()
+        .filter(|(_, filter)| {
+          let mut filter_ids = vec![];
+          filter.find_all_filters_with_field_id(field_id, &mut filter_ids);
+          !filter_ids.is_empty()
+        })
+        .map(|(index, filter)| (index, FilterMap::from(filter)))
+        .collect::<Vec<_>>();
+
+      let sorts = database
+        .get_all_sorts::<Sort>(&view_id)
+        .into_iter()
+        .enumerate()
+        .filter(|(_, sort)| sort.field_id == field_id)
+        .collect::<Vec<_>>();
+
+      let mut group_settings: Vec<GroupSetting> = database.get_all_group_setting(&view_id);
+      if !group_settings
+        .iter()
+        .any(|group_setting| group_setting.field_id == field_id)
+      {
+        group_settings.clear();
+      }
+
+      view_settings.push(FieldViewSettings {
+        view_id,
+        field_settings,
+        filters,
+        sorts,
+        group_settings,
+      });
+    }
+    view_settings
+  }
+
+  /// Puts back the settings of a field in each view after the field is created again
+  async fn restore_field_view_settings(
+    &self,
+    view_settings: Vec<FieldViewSettings>,
+  ) -> FlowyResult<()> {
+    for settings in view_settings {
+      let view_id = settings.view_id;
+      if let Some(field_settings) = settings.field_settings {
+        self.database.write().await.update_field_settings(
+          &view_id,
+          Some(vec![field_settings.field_id.clone()]),
+          field_settings.clone(),
+        );
+        database_notification_builder(&view_id, DatabaseNotification::DidUpdateFieldSettings)
+          .payload(FieldSettingsPB::from(field_settings))
+          .send();
+      }
+
+      let filters = settings
+        .filters
+        .into_iter()
+        .filter_map(|(index, filter)| Filter::try_from(filter).ok().map(|filter| (index, filter)))
+        .collect::<Vec<_>>();
+      if filters.is_empty() && settings.sorts.is_empty() && settings.group_settings.is_empty() {
+        continue;
+      }
+
+      let view_editor = self
+        .database_views
+        .get_or_init_view_editor(&view_id)
+        .await?;
+      view_editor
+        .v_restore_filters_and_sorts(filters, settings.sorts)
+        .await;
+
+      if let Some(group_setting) = settings.group_settings.get(GROUP_SETTING_INDEX) {
+        let grouping_field_id = group_setting.field_id.clone();
+        self
+          .database
+          .write()
+          .await
+          .update_database_view(&view_id, |view| {
+            view.set_groups(
+              settings
+                .group_settings
+                .into_iter()
+                .map(|group_setting| group_setting.into())
+                .collect(),
+            );
+          });
+        view_editor
+          .v_initialize_new_group(&grouping_field_id)
+          .await?;
+        view_editor.v_sub_group_by_field().await?;
+      }
+    }
+    Ok(())
+  }
+
   pub async fn close_database(&self) {
     info!("[Database]: {} close", self.database_id);
     let token = CancellationToken::new();
@@ -1536,14 +3260,25 @@ impl DatabaseEditor {
 
       let (tx, rx) = oneshot::channel();
       self
-        .async_load_rows(view_editor, Some(tx), new_token, blocking_read, row_orders)
+        .async_load_rows(
+          view_editor.clone(),
+          Some(tx),
+          new_token,
+          blocking_read,
+          row_orders,
+        )
         .await;
       if blocking_read {
         // the rows returned here are applied with filters and sorts
         if let Ok(rows) = rx.await {
+          let mut rule_ids_by_row_id = view_editor.v_get_rows_formatting(&rows).await;
           order_rows = rows
             .into_iter()
-            .map(|row| RowMetaPB::from(row.as_ref()))
+            .map(|row| {
+              let mut row_meta = RowMetaPB::from(row.as_ref());
+              row_meta.formatting_rule_ids = rule_ids_by_row_id.remove(&row.id).unwrap_or_default();
+              row_meta
+            })
             .collect();
         }
       }
@@ -1690,6 +3425,10 @@ impl DatabaseEditor {
         let _ = notify_finish.send(loaded_rows);
       }
       tokio::spawn(async move {
+        // The formatting of the rows returned when opening the view is set in their metas
+        if !blocking_read {
+          view_editor.v_format_rows_and_notify(&calculate_rows).await;
+        }
         let _ = view_editor.v_calculate_rows(fields, calculate_rows).await;
       });
     });
@@ -1705,6 +3444,131 @@ impl DatabaseEditor {
     Ok(csv)
   }
 
+  /// Exports the view as csv. Only the visible fields are exported, in the order of the view,
+  /// and the rows are filtered and sorted by the view. If `include_group_headers` is true and
+  /// the view is a board, the rows are exported group by group.
+  pub async fn export_view_csv(
+    &self,
+    view_id: &str,
+    style: CSVFormat,
+    include_group_headers: bool,
+  ) -> FlowyResult<String> {
+    let fields = self.get_visible_fields(view_id).await?;
+    let rows = self.get_all_rows(view_id).await?;
+    let layout = self.database.read().await.get_database_view_layout(view_id);
+    if !include_group_headers || !layout.is_board() {
+      return CSVExport.export_rows(&fields, &rows, style);
+    }
+
+    let groups = self.load_groups(view_id).await?.items;
+    let group_field = match groups.first() {
+      None => return CSVExport.export_rows(&fields, &rows, style),
+      Some(group) => self.get_field(&group.field_id).await,
+    };
+    let csv_groups = groups
+      .into_iter()
+      .filter(|group| group.is_visible)
+      .map(|group| {
+        // Keep the order of the sorted rows, the rows of a group are not sorted.
+        let row_ids = group
+          .rows
+          .iter()
+          .map(|row_meta| row_meta.id.as_str())
+          .collect::<HashSet<&str>>();
+        let group_rows = rows
+          .iter()
+          .filter(|row| row_ids.contains(row.id.as_str()))
+          .cloned()
+          .collect();
+        CSVGroup {
+          name: group_name(&group, group_field.as_ref()),
+          rows: group_rows,
+        }
+      })
+      .collect::<Vec<CSVGroup>>();
+    CSVExport.export_groups(&fields, &csv_groups, style)
+  }
+
+  /// Exports all the fields and rows of the database as json, regardless of the settings of
+  /// its views.
+  pub async fn export_json(&self) -> FlowyResult<String> {
+    let database = self.database.read().await;
+    let inline_view_id = database.get_inline_view_id();
+    let fields = database.get_fields_in_view(&inline_view_id, None);
+    let rows = database
+      .get_rows_for_view(&inline_view_id, 20, None)
+      .await
+      .filter_map(|result| async { result.ok().map(Arc::new) })
+      .collect::<Vec<Arc<Row>>>()
+      .await;
+    JSONExport.export_rows(&database.get_database_id(), &fields, &rows)
+  }
+
+  /// Exports the view as a Markdown table. Only the visible fields and the rows that match the
+  /// filters of the view are exported, in the order of the view.
+  pub async fn export_markdown(&self, view_id: &str) -> FlowyResult<String> {
+    let fields = self.get_visible_fields(view_id).await?;
+    let rows = self.get_all_rows(view_id).await?;
+    Ok(MarkdownExport.export_rows(&fields, &rows))
+  }
+
+  /// Exports the database as a XLSX workbook. Each view is exported as a sheet that contains
+  /// the visible fields and the filtered, sorted rows of the view.
+  pub async fn export_xlsx(&self) -> FlowyResult<Vec<u8>> {
+    let views = self.database.read().await.get_all_database_views_meta();
+    let mut sheets = Vec::with_capacity(views.len());
+    for view in views {
+      let name = self
+        .database
+        .read()
+        .await
+        .get_view(&view.id)
+        .map(|view| view.name)
+        .unwrap_or_default();
+      let fields = self.get_visible_fields(&view.id).await?;
+      let rows = self.get_all_rows(&view.id).await?;
+
+      let mut sheet_rows = Vec::with_capacity(rows.len() + 1);
+      sheet_rows.push(
+        fields
+          .iter()
+          .map(|field| XLSXCell::Text(field.name.clone()))
+          .collect(),
+      );
+      for row in rows.iter() {
+        sheet_rows.push(
+          fields
+            .iter()
+            .map(|field| XLSXCell::from_row(row, field))
+            .collect(),
+        );
+      }
+      sheets.push(XLSXSheet {
+        name,
+        rows: sheet_rows,
+      });
+    }
+    XLSXExport.export_sheets(sheets)
+  }
+
+  /// Returns the fields of the view that are not hidden, in the order of the view.
+  async fn get_visible_fields(&self, view_id: &str) -> FlowyResult<Vec<Field>> {
+    let hidden_field_ids = self
+      .get_all_field_settings(view_id)
+      .await?
+      .into_iter()
+      .filter(|field_settings| field_settings.visibility == FieldVisibility::AlwaysHidden)
+      .map(|field_settings| field_settings.field_id)
+      .collect::<Vec<String>>();
+    let fields = self
+      .get_fields(view_id, None)
+      .await
+      .into_iter()
+      .filter(|field| !hidden_field_ids.contains(&field.id))
+      .collect();
+    Ok(fields)
+  }
+
   pub async fn get_field_settings(
     &self,
     view_id: &str,
@@ -1773,9 +3637,12 @@ impl DatabaseEditor {
       .await
   }
 
+  /// Returns the primary field text of the rows, and the cells of the `field_ids` fields as
+  /// strings. All the rows of the database are returned when `row_ids` is None.
   pub async fn get_related_rows(
     &self,
     row_ids: Option<Vec<String>>,
+    field_ids: &[String],
   ) -> FlowyResult<Vec<RelatedRowDataPB>> {
     let database = self.database.read().await;
     let primary_field = Arc::new(
@@ -1783,6 +3650,11 @@ impl DatabaseEditor {
         .get_primary_field()
         .ok_or_else(|| FlowyError::internal().with_context("Primary field is not exist"))?,
     );
+    let fields = Arc::new(if field_ids.is_empty() {
+      vec![]
+    } else {
+      database.get_fields(Some(field_ids.to_vec()))
+    });
 
     let handler = Arc::new(
       TypeOptionCellExt::new(&primary_field, Some(self.cell_cache.clone()))
@@ -1808,6 +3680,7 @@ impl DatabaseEditor {
             row_data.push(RelatedRowDataPB {
               row_id: row.id.to_string(),
               name: title.0,
+              cells: stringify_related_cells(&fields, |field_id| row.cells.get(field_id).cloned()),
             });
           }
         }
@@ -1826,11 +3699,11 @@ impl DatabaseEditor {
         let row_data_futures = database_rows.into_iter().map(|database_row| {
           let handler = handler.clone();
           let cloned_primary_field = primary_field.clone();
+          let fields = fields.clone();
           async move {
-            let row_id = database_row.read().await.row_id.to_string();
+            let database_row = database_row.read().await;
+            let row_id = database_row.row_id.to_string();
             let title = database_row
-              .read()
-              .await
               .get_cell(&cloned_primary_field.id)
               .and_then(|cell| handler.handle_get_boxed_cell_data(&cell, &cloned_primary_field))
               .and_then(|cell_data| cell_data.unbox_or_none())
@@ -1839,6 +3712,7 @@ impl DatabaseEditor {
             RelatedRowDataPB {
               row_id,
               name: title.0,
+              cells: stringify_related_cells(&fields, |field_id| database_row.get_cell(field_id)),
             }
           }
         });
@@ -1867,6 +3741,7 @@ impl DatabaseEditor {
 }
 
 struct DatabaseViewOperationImpl {
+  user: Arc<dyn DatabaseUser>,
   database: Arc<RwLock<Database>>,
   task_scheduler: Arc<TokioRwLock<TaskDispatcher>>,
   cell_cache: CellCache,
@@ -2106,6 +3981,14 @@ impl DatabaseViewOperation for DatabaseViewOperationImpl {
       .save_filters::<Filter, FilterMap>(view_id, filters);
   }
 
+  async fn get_all_formatting_rules(&self, view_id: &str) -> Vec<FormattingRule> {
+    get_formatting_rules(&self.database.read().await, view_id)
+  }
+
+  async fn save_formatting_rules(&self, view_id: &str, rules: &[FormattingRule]) {
+    save_formatting_rules(&mut self.database.write().await, view_id, rules);
+  }
+
   async fn get_filter(&self, view_id: &str, filter_id: &str) -> Option<Filter> {
     self
       .database
@@ -2151,6 +4034,14 @@ impl DatabaseViewOperation for DatabaseViewOperationImpl {
     self.task_scheduler.clone()
   }
 
+  fn get_user_email(&self) -> Option<String> {
+    self.user.user_email().ok()
+  }
+
+  fn get_timezone_id(&self) -> Option<String> {
+    self.user.timezone_id().ok()
+  }
+
   fn get_type_option_cell_handler(
     &self,
     field: &Field,
@@ -2284,6 +4175,103 @@ pub async fn update_field_type_option_fn(
   Ok(())
 }
 
+/// Gives the next number of every auto number field to the cells of a new row. The counters
+/// are advanced while the database is locked, so a number is never given to two rows created
+/// on the same device.
+fn insert_auto_number_cells(database: &mut Database, cells: &mut Cells) {
+  let auto_number_fields = database
+    .get_fields(None)
+    .into_iter()
+    .filter(|field| FieldType::from(field.field_type).is_auto_number())
+    .collect::<Vec<Field>>();
+
+  for field in auto_number_fields {
+    let mut type_option = field
+      .get_type_option::<AutoNumberTypeOption>(FieldType::AutoNumber)
+      .unwrap_or_default();
+    let number = type_option.take_next_number();
+    database.update_field(&field.id, |update| {
+      update.update_type_options(|type_options_update| {
+        type_options_update.insert(&FieldType::AutoNumber.to_string(), type_option.into());
+      });
+    });
+    cells.insert(field.id, Cell::from(AutoNumberCellData(number)));
+  }
+}
+
+/// Keeps the counter of the auto number field when its type option is updated, the counter
+/// can't be moved back because the numbers would be reused.
+fn keep_auto_number_counter(
+  database: &Database,
+  field_id: &str,
+  type_option_data: TypeOptionData,
+) -> TypeOptionData {
+  let next_number = database
+    .get_field(field_id)
+    .and_then(|field| field.get_type_option::<AutoNumberTypeOption>(FieldType::AutoNumber))
+    .map(|type_option| type_option.next_number)
+    .unwrap_or(1);
+  let mut type_option = AutoNumberTypeOption::from(type_option_data);
+  type_option.reserve_number(next_number - 1);
+  type_option.into()
+}
+
+/// Stringifies the cells of the `fields` in a related row, keyed by field id
+fn stringify_related_cells<F>(fields: &[Field], get_cell: F) -> HashMap<String, String>
+where
+  F: Fn(&str) -> Option<Cell>,
+{
+  fields
+    .iter()
+    .map(|field| {
+      let text = get_cell(&field.id)
+        .map(|cell| stringify_cell(&cell, field))
+        .unwrap_or_default();
+      (field.id.clone(), text)
+    })
+    .collect()
+}
+
+/// Rewrites the `{old_name}` references of the formula fields after a field is renamed.
+fn rename_formula_references(database: &mut Database, old_name: &str, new_name: &str) {
+  let formula_fields = database
+    .get_fields(None)
+    .into_iter()
+    .filter(|field| FieldType::from(field.field_type).is_formula())
+    .collect::<Vec<Field>>();
+
+  for field in formula_fields {
+    let mut type_option = field
+      .get_type_option::<FormulaTypeOption>(FieldType::Formula)
+      .unwrap_or_default();
+    if !type_option
+      .referenced_field_names()
+      .iter()
+      .any(|name| name == old_name)
+    {
+      continue;
+    }
+
+    type_option.expression =
+      match rename_field_references(&type_option.expression, old_name, new_name) {
+        Ok(expression) => expression,
+        Err(err) => {
+          warn!(
+            "[Formula]: can't rename the references of {}: {}",
+            field.id, err
+          );
+          continue;
+        },
+      };
+    database.update_field(&field.id, |update| {
+      update.update_type_options(|type_options_update| {
+        type_options_update.insert(&FieldType::Formula.to_string(), type_option.into());
+      });
+    });
+    let _ = notify_did_update_database_field(database, &field.id);
+  }
+}
+
 #[tracing::instrument(level = "trace", skip_all, err)]
 fn notify_did_update_database_field(database: &Database, field_id: &str) -> FlowyResult<()> {
   let (database_id, field, views) = {
@@ -2327,3 +4315,26 @@ async fn remove_row_sync_plugin(row_id: &str, row: Weak<RwLock<DatabaseRow>>) {
     }
   }
 }
+
+/// Returns the name of the group that is shown on the board. The ids of the select option
+/// groups are the option ids, the other groups are identified by their content.
+fn group_name(group: &GroupPB, field: Option<&Field>) -> String {
+  let field = match field {
+    None => return group.group_id.clone(),
+    Some(field) => field,
+  };
+  if group.is_default {
+    return format!("No {}", field.name);
+  }
+
+  select_type_option_from_field(field)
+    .ok()
+    .and_then(|type_option| {
+      type_option
+        .options()
+        .iter()
+        .find(|option| option.id == group.group_id)
+        .map(|option| option.name.clone())
+    })
+    .unwrap_or_else(|| group.group_id.clone())
+}
@@ -14,7 +14,9 @@ use dashmap::DashMap;
 use flowy_notification::{DebounceNotificationSender, NotificationBuilder};
 use futures::StreamExt;
 
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
 use tracing::{error, trace, warn};
 use uuid::Uuid;
 
@@ -85,6 +87,65 @@ pub(crate) async fn observe_rows_change(
     });
   }
 }
+/// Recomputes the formula cells when a cell referenced by a formula changes. The row changes
+/// are emitted for the local edits and for the edits received from the other devices.
+pub(crate) async fn observe_formula_source_cells(
+  database_id: &Uuid,
+  database_editor: &Arc<DatabaseEditor>,
+) {
+  let database_id = database_id.to_string();
+  let weak_database_editor = Arc::downgrade(database_editor);
+  let sub = database_editor.database.read().await.subscribe_row_change();
+  if let Some(mut row_change_rx) = sub {
+    tokio::spawn(async move {
+      loop {
+        let row_change = match row_change_rx.recv().await {
+          Ok(row_change) => row_change,
+          Err(RecvError::Lagged(_)) => continue,
+          Err(RecvError::Closed) => break,
+        };
+
+        // Take the pending changes as well, so the cells updated together are recomputed in
+        // one batch.
+        let mut changed_field_ids_by_row_id: HashMap<RowId, HashSet<String>> = HashMap::new();
+        let mut row_changes = vec![row_change];
+        while let Ok(row_change) = row_change_rx.try_recv() {
+          row_changes.push(row_change);
+        }
+        for row_change in row_changes {
+          if let RowChange::DidUpdateCell {
+            field_id, row_id, ..
+          } = row_change
+          {
+            changed_field_ids_by_row_id
+              .entry(row_id)
+              .or_default()
+              .insert(field_id);
+          }
+        }
+        if changed_field_ids_by_row_id.is_empty() {
+          continue;
+        }
+
+        match weak_database_editor.upgrade() {
+          None => break,
+          Some(database_editor) => {
+            if let Err(err) = database_editor
+              .did_update_formula_source_cells(changed_field_ids_by_row_id)
+              .await
+            {
+              error!(
+                "[Formula]: {} update formula cells failed: {}",
+                database_id, err
+              );
+            }
+          },
+        }
+      }
+    });
+  }
+}
+
 #[allow(dead_code)]
 pub(crate) async fn observe_field_change(database_id: &str, database: &Arc<RwLock<Database>>) {
   let database_id = database_id.to_string();
@@ -199,7 +260,10 @@ async fn handle_did_update_row_orders(
   // Delete row indexes: [0]
   // Final state after delete: [b, a, c]
   let row_changes = DashMap::new();
-  // 1. handle insert row orders
+  // The rows created offline on other devices may have the same auto numbers as the local rows
+  let should_update_auto_numbers = !is_local_change && !insert_row_orders.is_empty();
+  // 1. handle insert row orders. The rows inserted by one change are passed to the view at once
+  let mut created_rows = vec![];
   for (row_order, index) in insert_row_orders {
     let row = match database_editor.init_database_row(&row_order.id).await {
       Ok(database_row) => database_row.read().await.get_row().map(Arc::new),
@@ -226,18 +290,19 @@ async fn handle_did_update_row_orders(
 
       let is_move_row = is_move_row(&view_editor, &row_order, &delete_row_indexes).await;
       if let Some((index, row_detail)) = view_editor.v_get_row(&row_order.id).await {
-        view_editor
-          .v_did_create_row(
-            &row_detail,
-            index as u32,
-            is_move_row,
-            is_local_change,
-            &row_changes,
-          )
-          .await;
+        created_rows.push((row_detail.as_ref().clone(), index as u32, is_move_row));
       }
     }
   }
+  if let Some(view_editor) = database_editor
+    .database_views
+    .get_view_editor(view_id)
+    .await
+  {
+    view_editor
+      .v_did_create_rows(&created_rows, is_local_change, &row_changes)
+      .await;
+  }
 
   // handle delete row orders
   for index in delete_row_indexes {
@@ -288,6 +353,12 @@ async fn handle_did_update_row_orders(
       .payload(changes)
       .send();
   }
+
+  if should_update_auto_numbers {
+    if let Err(err) = database_editor.update_auto_number_cells().await {
+      error!("[AutoNumber]: update auto number cells failed: {}", err);
+    }
+  }
 }
 
 pub(crate) async fn observe_block_event(database_id: &Uuid, database_editor: &Arc<DatabaseEditor>) {
@@ -0,0 +1,342 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Weak};
+
+use collab_database::rows::{Cell, RowChange, RowId};
+use collab_database::template::relation_parse::RelationCellData;
+use flowy_error::FlowyResult;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::Mutex;
+use tracing::{error, trace};
+
+use crate::manager::DatabaseEditorMap;
+use crate::services::database::{DatabaseEditor, RollupField};
+use crate::services::field::RollupCellData;
+
+/// Recomputes the rollup cells of the `editor`. The databases that the rollup fields relate to
+/// are looked up in `editors`, the rollup fields whose related database is not opened are
+/// skipped.
+///
+/// When `field_id` is provided, only the rollup cells of that field are recomputed. When
+/// `row_ids` is provided, only the rollup cells of those rows are recomputed.
+pub(crate) async fn update_rollup_cells(
+  editor: &DatabaseEditor,
+  editors: &DatabaseEditorMap,
+  field_id: Option<&str>,
+  row_ids: Option<Vec<RowId>>,
+) -> FlowyResult<()> {
+  let rollup_fields = editor
+    .get_rollup_fields()
+    .await
+    .into_iter()
+    .filter(|rollup_field| field_id.map_or(true, |field_id| rollup_field.field.id == field_id))
+    .collect::<Vec<RollupField>>();
+  if rollup_fields.is_empty() {
+    return Ok(());
+  }
+
+  let row_ids = match row_ids {
+    None => editor.get_row_ids().await,
+    Some(row_ids) => row_ids,
+  };
+  let view_id = editor.database.read().await.get_inline_view_id();
+  for row_id in row_ids {
+    let row = match editor.get_row(&view_id, &row_id).await {
+      None => continue,
+      Some(row) => row,
+    };
+
+    let mut cells = HashMap::<String, Cell>::new();
+    for rollup_field in rollup_fields.iter() {
+      let related_editor = match editors.get(&rollup_field.related_database_id) {
+        None => {
+          trace!(
+            "[Rollup]: related database:{} of field:{} is not opened",
+            rollup_field.related_database_id,
+            rollup_field.field.id
+          );
+          continue;
+        },
+        Some(related_editor) => related_editor,
+      };
+
+      let related_row_ids = row
+        .cells
+        .get(&rollup_field.type_option.relation_field_id)
+        .map(|cell| RelationCellData::from(cell).row_ids)
+        .unwrap_or_default();
+      let values = related_editor
+        .get_rollup_source_values(&related_row_ids, &rollup_field.type_option.target_field_id)
+        .await;
+      let cell_data = RollupCellData(rollup_field.type_option.calculation.aggregate(&values));
+
+      let old_cell_data = row
+        .cells
+        .get(&rollup_field.field.id)
+        .map(RollupCellData::from)
+        .unwrap_or_default();
+      if old_cell_data != cell_data {
+        cells.insert(rollup_field.field.id.clone(), Cell::from(cell_data));
+      }
+    }
+
+    if !cells.is_empty() {
+      editor
+        .update_computed_cells(&view_id, &row_id, cells)
+        .await?;
+    }
+  }
+  Ok(())
+}
+
+/// Where a rollup field reads the changed cell from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RollupSource {
+  /// The relation field of the rollup field. The changed row is the row of the rollup cell.
+  Relation,
+  /// The target field in the related database. The changed row is one of the related rows.
+  Target,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct RollupDependent {
+  pub database_id: String,
+  pub field_id: String,
+  pub related_database_id: String,
+  pub relation_field_id: String,
+  pub source: RollupSource,
+}
+
+/// Indexes the rollup fields of the opened databases by the database and the field of the cells
+/// they read, so a cell change only looks up the rollup fields that depend on it.
+#[derive(Default)]
+pub(crate) struct RollupDependencies {
+  dependents: HashMap<(String, String), Vec<RollupDependent>>,
+}
+
+impl RollupDependencies {
+  /// Replaces the indexed rollup fields of the database with `database_id`
+  pub(crate) fn set_rollup_fields(&mut self, database_id: &str, rollup_fields: &[RollupField]) {
+    self.remove_database(database_id);
+    for rollup_field in rollup_fields {
+      let type_option = &rollup_field.type_option;
+      let dependent = |source| RollupDependent {
+        database_id: database_id.to_string(),
+        field_id: rollup_field.field.id.clone(),
+        related_database_id: rollup_field.related_database_id.clone(),
+        relation_field_id: type_option.relation_field_id.clone(),
+        source,
+      };
+      self
+        .dependents
+        .entry((
+          database_id.to_string(),
+          type_option.relation_field_id.clone(),
+        ))
+        .or_default()
+        .push(dependent(RollupSource::Relation));
+      self
+        .dependents
+        .entry((
+          rollup_field.related_database_id.clone(),
+          type_option.target_field_id.clone(),
+        ))
+        .or_default()
+        .push(dependent(RollupSource::Target));
+    }
+  }
+
+  pub(crate) fn remove_database(&mut self, database_id: &str) {
+    self.dependents.retain(|_, dependents| {
+      dependents.retain(|dependent| dependent.database_id != database_id);
+      !dependents.is_empty()
+    });
+  }
+
+  /// Returns the rollup fields that read the cells of the field
+  pub(crate) fn get_dependents(&self, database_id: &str, field_id: &str) -> Vec<RollupDependent> {
+    self
+      .dependents
+      .get(&(database_id.to_string(), field_id.to_string()))
+      .cloned()
+      .unwrap_or_default()
+  }
+
+  /// Returns the rollup fields of the database and the rollup fields that read the database
+  pub(crate) fn get_dependents_of_database(&self, database_id: &str) -> Vec<RollupDependent> {
+    let mut field_ids = HashSet::new();
+    self
+      .dependents
+      .values()
+      .flatten()
+      .filter(|dependent| {
+        dependent.database_id == database_id || dependent.related_database_id == database_id
+      })
+      .filter(|dependent| {
+        field_ids.insert((dependent.database_id.clone(), dependent.field_id.clone()))
+      })
+      .cloned()
+      .collect()
+  }
+}
+
+/// Returns the editor of the database that owns the rollup field, and the editor of the related
+/// database keyed by its id. Returns None if one of them is not opened.
+async fn get_rollup_editors(
+  editors: &Mutex<DatabaseEditorMap>,
+  dependent: &RollupDependent,
+) -> Option<(Arc<DatabaseEditor>, DatabaseEditorMap)> {
+  let editors = editors.lock().await;
+  let editor = editors.get(&dependent.database_id)?.clone();
+  let related_editor = editors.get(&dependent.related_database_id)?.clone();
+  let related_editors = HashMap::from([(dependent.related_database_id.clone(), related_editor)]);
+  Some((editor, related_editors))
+}
+
+/// Recomputes the rollup cells that depend on the database with `database_id`, including the
+/// rollup fields of the database itself. Only the cells whose value is stale are written.
+pub(crate) async fn update_dependent_rollup_cells(
+  database_id: &str,
+  editors: &Mutex<DatabaseEditorMap>,
+  dependencies: &Mutex<RollupDependencies>,
+) -> FlowyResult<()> {
+  let dependents = dependencies
+    .lock()
+    .await
+    .get_dependents_of_database(database_id);
+  for dependent in dependents {
+    if let Some((editor, related_editors)) = get_rollup_editors(editors, &dependent).await {
+      update_rollup_cells(&editor, &related_editors, Some(&dependent.field_id), None).await?;
+    }
+  }
+  Ok(())
+}
+
+/// Keeps the rollup cells that depend on the database up to date. The rollup fields of the
+/// database are indexed in `dependencies`, and indexed again when its fields change. When a cell
+/// of the database is updated, only the rollup fields that read the cell are recomputed.
+pub(crate) async fn observe_rollup_dependencies(
+  database_id: &str,
+  editor: &Arc<DatabaseEditor>,
+  editors: Weak<Mutex<DatabaseEditorMap>>,
+  dependencies: Weak<Mutex<RollupDependencies>>,
+) {
+  if let Some(dependencies) = dependencies.upgrade() {
+    let rollup_fields = editor.get_rollup_fields().await;
+    dependencies
+      .lock()
+      .await
+      .set_rollup_fields(database_id, &rollup_fields);
+  }
+
+  let database_id = database_id.to_string();
+  let weak_editor = Arc::downgrade(editor);
+  let (field_change_rx, row_change_rx) = {
+    let database = editor.database.read().await;
+    (
+      database.subscribe_field_change(),
+      database.subscribe_row_change(),
+    )
+  };
+  let (mut field_change_rx, mut row_change_rx) = match (field_change_rx, row_change_rx) {
+    (Some(field_change_rx), Some(row_change_rx)) => (field_change_rx, row_change_rx),
+    _ => return,
+  };
+
+  tokio::spawn(async move {
+    loop {
+      // The field changes are handled first, so a cell updated right after its rollup field is
+      // configured is looked up in the new index.
+      let row_change = tokio::select! {
+        biased;
+        field_change = field_change_rx.recv() => {
+          if let Err(RecvError::Closed) = field_change {
+            break;
+          }
+          match (weak_editor.upgrade(), dependencies.upgrade()) {
+            (Some(editor), Some(dependencies)) => {
+              let rollup_fields = editor.get_rollup_fields().await;
+              dependencies
+                .lock()
+                .await
+                .set_rollup_fields(&database_id, &rollup_fields);
+            },
+            _ => break,
+          }
+          continue;
+        },
+        row_change = row_change_rx.recv() => match row_change {
+          Ok(row_change) => row_change,
+          Err(RecvError::Lagged(_)) => continue,
+          Err(RecvError::Closed) => break,
+        },
+      };
+
+      if let RowChange::DidUpdateCell {
+        field_id, row_id, ..
+      } = row_change
+      {
+        let (editors, dependencies) = match (editors.upgrade(), dependencies.upgrade()) {
+          (Some(editors), Some(dependencies)) => (editors, dependencies),
+          _ => break,
+        };
+        if let Err(err) =
+          did_update_rollup_source_cell(&database_id, &field_id, &row_id, &editors, &dependencies)
+            .await
+        {
+          error!(
+            "[Rollup]: update rollup cells after {}:{} changed failed: {}",
+            row_id, field_id, err
+          );
+        }
+      }
+    }
+
+    if let Some(dependencies) = dependencies.upgrade() {
+      dependencies.lock().await.remove_database(&database_id);
+    }
+  });
+}
+
+async fn did_update_rollup_source_cell(
+  database_id: &str,
+  field_id: &str,
+  row_id: &RowId,
+  editors: &Mutex<DatabaseEditorMap>,
+  dependencies: &Mutex<RollupDependencies>,
+) -> FlowyResult<()> {
+  let dependents = dependencies
+    .lock()
+    .await
+    .get_dependents(database_id, field_id);
+  for dependent in dependents {
+    let (editor, related_editors) = match get_rollup_editors(editors, &dependent).await {
+      None => {
+        trace!(
+          "[Rollup]: databases of field:{} are not opened",
+          dependent.field_id
+        );
+        continue;
+      },
+      Some(rollup_editors) => rollup_editors,
+    };
+
+    let row_ids = match dependent.source {
+      RollupSource::Relation => vec![row_id.clone()],
+      RollupSource::Target => {
+        editor
+          .get_row_ids_related_to(&dependent.relation_field_id, row_id)
+          .await
+      },
+    };
+    if !row_ids.is_empty() {
+      update_rollup_cells(
+        &editor,
+        &related_editors,
+        Some(&dependent.field_id),
+        Some(row_ids),
+      )
+      .await?;
+    }
+  }
+  Ok(())
+}
@@ -1,6 +1,9 @@
+use collab_database::fields::Field;
 use collab_database::rows::{RowDetail, RowId};
 use collab_database::views::DatabaseLayout;
 
+use crate::services::field::RollupTypeOption;
+
 #[derive(Debug, Clone)]
 pub enum DatabaseRowEvent {
   InsertRow(InsertedRow),
@@ -64,3 +67,11 @@ pub struct CreateDatabaseViewParams {
   pub view_id: String,
   pub layout_type: DatabaseLayout,
 }
+
+/// A rollup field along with the id of the database that its relation field links to.
+#[derive(Debug, Clone)]
+pub struct RollupField {
+  pub field: Field,
+  pub type_option: RollupTypeOption,
+  pub related_database_id: String,
+}
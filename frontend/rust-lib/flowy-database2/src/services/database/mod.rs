@@ -1,8 +1,12 @@
 mod database_editor;
 mod database_observe;
+mod database_rollup;
 mod entities;
+mod undo;
 mod util;
 
 pub use database_editor::*;
+pub(crate) use database_rollup::*;
 pub use entities::*;
+pub(crate) use undo::*;
 pub(crate) use util::database_view_setting_pb_from_view;
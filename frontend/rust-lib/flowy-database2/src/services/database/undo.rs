@@ -0,0 +1,150 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use collab_database::fields::Field;
+use collab_database::rows::{Cell, Row, RowId};
+use collab_database::views::{FilterMap, OrderObjectPosition};
+
+use crate::services::field_settings::FieldSettings;
+use crate::services::group::GroupSetting;
+use crate::services::sort::Sort;
+
+/// The number of steps kept in the undo stack, the oldest steps are dropped first.
+const MAX_UNDO_STEPS: usize = 100;
+
+/// An edit made by the local user. Reverting an edit returns the edit that reverts it again, so
+/// the same edits are used to undo and to redo.
+#[derive(Debug, Clone)]
+pub enum DatabaseEdit {
+  /// The cells of the row before the edit, `None` if the cell was empty
+  UpdatedCells {
+    view_id: String,
+    row_id: RowId,
+    old_cells: Vec<(String, Option<Cell>)>,
+  },
+  /// The rows created in the view
+  CreatedRows {
+    view_id: String,
+    row_ids: Vec<RowId>,
+  },
+  /// The deleted rows and their position in the view they were deleted from, in the order of
+  /// the view
+  DeletedRows {
+    view_id: String,
+    rows: Vec<(Row, OrderObjectPosition)>,
+  },
+  MovedRow {
+    view_id: String,
+    row_id: RowId,
+    old_index: usize,
+  },
+  CreatedField {
+    field_id: String,
+  },
+  /// The deleted field, its position in the inline view and its settings in each view
+  DeletedField {
+    field: Field,
+    position: OrderObjectPosition,
+    view_settings: Vec<FieldViewSettings>,
+  },
+  /// The field before its type or its type option changed, e.g. when a select option is added
+  UpdatedField {
+    old_field: Field,
+  },
+}
+
+/// The settings of a field in a view, which are removed with the field when it is deleted
+#[derive(Debug, Clone)]
+pub struct FieldViewSettings {
+  pub view_id: String,
+  pub field_settings: Option<FieldSettings>,
+  /// The filters of the view containing the field and their index in the filters of the view
+  pub filters: Vec<(usize, FilterMap)>,
+  /// The sorts of the field and their index in the sorts of the view
+  pub sorts: Vec<(usize, Sort)>,
+  /// The group settings of the view, empty if the view is not grouped by the field
+  pub group_settings: Vec<GroupSetting>,
+}
+
+/// Keeps the edits made with the [DatabaseEditor](crate::services::database::DatabaseEditor) of a
+/// database. A step is the list of edits made by one user action, which are reverted together.
+///
+/// Only the edits made through the editor are recorded, so the changes received from the other
+/// devices and collaborators are never undone. The edits made to revert a step are applied
+/// without being recorded, so the user can keep editing while a step is undone or redone.
+#[derive(Default)]
+pub struct DatabaseUndoManager {
+  undo_stack: Mutex<VecDeque<Vec<DatabaseEdit>>>,
+  redo_stack: Mutex<VecDeque<Vec<DatabaseEdit>>>,
+}
+
+impl DatabaseUndoManager {
+  /// Records a new step. The redo stack is cleared as the undone steps can't be applied after
+  /// the new step.
+  pub fn record(&self, edits: Vec<DatabaseEdit>) {
+    if edits.is_empty() {
+      return;
+    }
+
+    push_step(&mut self.undo_stack.lock().unwrap(), edits);
+    self.redo_stack.lock().unwrap().clear();
+  }
+
+  pub fn can_undo(&self) -> bool {
+    !self.undo_stack.lock().unwrap().is_empty()
+  }
+
+  pub fn can_redo(&self) -> bool {
+    !self.redo_stack.lock().unwrap().is_empty()
+  }
+
+  /// Removes all the steps, e.g. when the database is restored from a snapshot
+  pub fn clear(&self) {
+    self.undo_stack.lock().unwrap().clear();
+    self.redo_stack.lock().unwrap().clear();
+  }
+
+  /// Takes the latest step to undo. [Self::finish_reverting] must be called once the step is
+  /// reverted.
+  pub fn start_undo(&self) -> Option<Vec<DatabaseEdit>> {
+    self.undo_stack.lock().unwrap().pop_back()
+  }
+
+  /// Takes the latest undone step. [Self::finish_reverting] must be called once the step is
+  /// reverted.
+  pub fn start_redo(&self) -> Option<Vec<DatabaseEdit>> {
+    self.redo_stack.lock().unwrap().pop_back()
+  }
+
+  /// Pushes the edits reverting an undone step to the redo stack, or the edits reverting a redone
+  /// step to the undo stack. The edits that failed to be reverted are pushed back to the stack
+  /// they were taken from, so they can be reverted again.
+  pub fn finish_reverting(
+    &self,
+    is_undo: bool,
+    reverted_edits: Vec<DatabaseEdit>,
+    unreverted_edits: Vec<DatabaseEdit>,
+  ) {
+    let (mut source_stack, mut target_stack) = (
+      self.undo_stack.lock().unwrap(),
+      self.redo_stack.lock().unwrap(),
+    );
+    if !is_undo {
+      std::mem::swap(&mut source_stack, &mut target_stack);
+    }
+    if !reverted_edits.is_empty() {
+      push_step(&mut target_stack, reverted_edits);
+    }
+    if !unreverted_edits.is_empty() {
+      push_step(&mut source_stack, unreverted_edits);
+    }
+  }
+}
+
+/// Pushes the step to the stack, dropping the oldest step if the stack is full
+fn push_step(stack: &mut VecDeque<Vec<DatabaseEdit>>, edits: Vec<DatabaseEdit>) {
+  stack.push_back(edits);
+  if stack.len() > MAX_UNDO_STEPS {
+    stack.pop_front();
+  }
+}
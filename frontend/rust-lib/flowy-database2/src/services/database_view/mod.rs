@@ -9,6 +9,7 @@ mod notifier;
 mod view_calculations;
 mod view_editor;
 mod view_filter;
+mod view_formatting;
 mod view_group;
 mod view_operation;
 mod view_sort;
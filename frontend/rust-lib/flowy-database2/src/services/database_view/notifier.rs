@@ -1,8 +1,10 @@
 #![allow(clippy::while_let_loop)]
 use crate::entities::{
   CalculationChangesetNotificationPB, DatabaseViewSettingPB, FilterChangesetNotificationPB,
-  GroupChangesPB, GroupRowsNotificationPB, ReorderAllRowsPB, ReorderSingleRowPB,
-  RowsVisibilityChangePB, SortChangesetNotificationPB,
+  GroupCalculationsPB, GroupChangesPB, GroupRowsNotificationPB, ReorderAllRowsPB,
+  ReorderSingleRowPB, RepeatedFormattingRulePB, RepeatedGroupPB, RepeatedRowTemplatePB,
+  RowsFormattingChangesetPB, RowsVisibilityChangePB, SortChangesetNotificationPB,
+  SubGroupRowsNotificationPB,
 };
 use crate::notification::{database_notification_builder, DatabaseNotification};
 use crate::services::filter::FilterResultNotification;
@@ -17,6 +19,7 @@ pub enum DatabaseViewChanged {
   ReorderAllRowsNotification(ReorderAllRowsResult),
   ReorderSingleRowNotification(ReorderSingleRowResult),
   CalculationValueNotification(CalculationChangesetNotificationPB),
+  FormattingNotification(RowsFormattingChangesetPB),
 }
 
 pub type DatabaseViewChangedNotifier = broadcast::Sender<DatabaseViewChanged>;
@@ -89,6 +92,14 @@ impl DatabaseViewChangedReceiverRunner {
             .payload(notification)
             .send()
           },
+          DatabaseViewChanged::FormattingNotification(notification) => {
+            database_notification_builder(
+              &notification.view_id,
+              DatabaseNotification::DidUpdateRowsFormatting,
+            )
+            .payload(notification)
+            .send()
+          },
         }
       })
       .await;
@@ -107,6 +118,27 @@ pub async fn notify_did_update_filter(notification: FilterChangesetNotificationP
     .send();
 }
 
+pub async fn notify_did_update_formatting_rules(view_id: &str, rules: RepeatedFormattingRulePB) {
+  database_notification_builder(view_id, DatabaseNotification::DidUpdateFormattingRules)
+    .payload(rules)
+    .send();
+}
+
+pub async fn notify_did_update_row_templates(view_id: &str, templates: RepeatedRowTemplatePB) {
+  database_notification_builder(view_id, DatabaseNotification::DidUpdateRowTemplates)
+    .payload(templates)
+    .send();
+}
+
+pub async fn notify_did_update_group_calculations(payload: GroupCalculationsPB) {
+  database_notification_builder(
+    &payload.group_id,
+    DatabaseNotification::DidUpdateGroupCalculations,
+  )
+  .payload(payload)
+  .send();
+}
+
 pub async fn notify_did_update_calculation(notification: CalculationChangesetNotificationPB) {
   database_notification_builder(
     &notification.view_id,
@@ -124,6 +156,21 @@ pub async fn notify_did_update_sort(notification: SortChangesetNotificationPB) {
   }
 }
 
+pub(crate) async fn notify_did_update_sub_groups(view_id: &str, groups: RepeatedGroupPB) {
+  database_notification_builder(view_id, DatabaseNotification::DidUpdateSubGroups)
+    .payload(groups)
+    .send();
+}
+
+pub(crate) async fn notify_did_update_sub_group_rows(payload: SubGroupRowsNotificationPB) {
+  database_notification_builder(
+    &payload.view_id,
+    DatabaseNotification::DidUpdateSubGroupRows,
+  )
+  .payload(payload)
+  .send();
+}
+
 pub(crate) async fn notify_did_update_num_of_groups(view_id: &str, changeset: GroupChangesPB) {
   database_notification_builder(view_id, DatabaseNotification::DidUpdateNumOfGroups)
     .payload(changeset)
@@ -4,18 +4,22 @@ use std::sync::Arc;
 
 use collab_database::rows::Cell;
 
+use crate::entities::FieldType;
 use crate::services::calculations::{
   Calculation, CalculationsController, CalculationsDelegate, CalculationsTaskHandler,
 };
 
+use crate::services::cell::CellCache;
 use crate::services::database_view::{
   gen_handler_id, DatabaseViewChangedNotifier, DatabaseViewOperation,
 };
+use crate::services::share::get_exported_cell;
 
 pub async fn make_calculations_controller(
   view_id: &str,
   delegate: Arc<dyn DatabaseViewOperation>,
   notifier: DatabaseViewChangedNotifier,
+  cell_cache: CellCache,
 ) -> Arc<CalculationsController> {
   let calculations = delegate.get_all_calculations(view_id).await;
   let task_scheduler = delegate.get_task_scheduler();
@@ -29,6 +33,7 @@ pub async fn make_calculations_controller(
     calculations,
     task_scheduler.clone(),
     notifier,
+    cell_cache,
   );
 
   let calculations_controller = Arc::new(calculations_controller);
@@ -47,6 +52,23 @@ struct DatabaseViewCalculationsDelegateImpl(Arc<dyn DatabaseViewOperation>);
 #[async_trait]
 impl CalculationsDelegate for DatabaseViewCalculationsDelegateImpl {
   async fn get_cells_for_field(&self, view_id: &str, field_id: &str) -> Vec<Arc<Cell>> {
+    // The cells of the timestamp fields are not stored in the rows
+    if let Some(field) = self.0.get_field(field_id).await {
+      if matches!(
+        FieldType::from(field.field_type),
+        FieldType::CreatedTime | FieldType::LastEditedTime
+      ) {
+        let row_orders = self.0.get_all_row_orders(view_id).await;
+        return self
+          .0
+          .get_all_rows(view_id, row_orders)
+          .await
+          .iter()
+          .filter_map(|row| get_exported_cell(row, &field).map(Arc::new))
+          .collect();
+      }
+    }
+
     self
       .0
       .get_cells_for_field(view_id, field_id)
@@ -1,14 +1,18 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use super::{notify_did_update_calculation, DatabaseViewChanged};
+use super::{
+  notify_did_update_calculation, notify_did_update_formatting_rules,
+  notify_did_update_group_calculations, DatabaseViewChanged,
+};
 use crate::entities::{
   CalculationChangesetNotificationPB, CalendarEventPB, CreateRowPayloadPB, DatabaseLayoutMetaPB,
   DatabaseLayoutSettingPB, DeleteSortPayloadPB, FieldSettingsChangesetPB, FieldType,
   GroupChangesPB, GroupPB, InsertedRowPB, LayoutSettingChangeset, LayoutSettingParams,
-  RemoveCalculationChangesetPB, ReorderSortPayloadPB, RowMetaPB, RowsChangePB,
-  SortChangesetNotificationPB, SortPB, UpdateCalculationChangesetPB, UpdateSortPayloadPB,
+  RemoveCalculationChangesetPB, ReorderSortPayloadPB, RepeatedFormattingRulePB, RepeatedGroupPB,
+  RowMetaPB, RowsChangePB, SortChangesetNotificationPB, SortPB, SubGroupRowsNotificationPB,
+  UpdateCalculationChangesetPB, UpdateSortPayloadPB,
 };
 use crate::notification::{database_notification_builder, DatabaseNotification};
 use crate::services::calculations::{Calculation, CalculationChangeset, CalculationsController};
@@ -16,20 +20,27 @@ use crate::services::cell::{CellBuilder, CellCache};
 use crate::services::database::{database_view_setting_pb_from_view, DatabaseRowEvent, UpdatedRow};
 use crate::services::database_view::view_calculations::make_calculations_controller;
 use crate::services::database_view::view_filter::make_filter_controller;
-use crate::services::database_view::view_group::{get_cell_for_row, new_group_controller};
+use crate::services::database_view::view_formatting::make_formatting_controller;
+use crate::services::database_view::view_group::{
+  get_cell_for_row, new_group_controller, new_sub_group_controller,
+};
 use crate::services::database_view::view_operation::DatabaseViewOperation;
 use crate::services::database_view::view_sort::make_sort_controller;
 use crate::services::database_view::{
   notify_did_update_filter, notify_did_update_group_rows, notify_did_update_num_of_groups,
-  notify_did_update_setting, notify_did_update_sort, DatabaseLayoutDepsResolver,
-  DatabaseViewChangedNotifier, DatabaseViewChangedReceiverRunner,
+  notify_did_update_setting, notify_did_update_sort, notify_did_update_sub_group_rows,
+  notify_did_update_sub_groups, DatabaseLayoutDepsResolver, DatabaseViewChangedNotifier,
+  DatabaseViewChangedReceiverRunner,
 };
 use crate::services::field_settings::FieldSettings;
 use crate::services::filter::{Filter, FilterChangeset, FilterController};
+use crate::services::formatting::{FormattingController, FormattingRuleChangeset};
 use crate::services::group::{
-  DidMoveGroupRowResult, GroupChangeset, GroupController, MoveGroupRowContext, UpdatedCells,
+  DidMoveGroupRowResult, GroupChangeset, GroupController, GroupData, MoveGroupRowContext,
+  UpdatedCells,
 };
 use crate::services::setting::CalendarLayoutSetting;
+use crate::services::share::get_exported_cell;
 use crate::services::sort::{Sort, SortChangeset, SortController};
 use collab_database::database::{gen_database_calculation_id, gen_database_sort_id, gen_row_id};
 use collab_database::entity::DatabaseView;
@@ -40,7 +51,9 @@ use dashmap::DashMap;
 use flowy_error::{FlowyError, FlowyResult};
 
 use lib_infra::util::timestamp;
-use tokio::sync::{broadcast, RwLock};
+use tokio::select;
+use tokio::sync::{broadcast, Mutex, RwLock};
+use tokio_util::sync::CancellationToken;
 use tracing::{error, instrument, trace, warn};
 
 pub struct DatabaseViewEditor {
@@ -48,9 +61,12 @@ pub struct DatabaseViewEditor {
   pub view_id: String,
   delegate: Arc<dyn DatabaseViewOperation>,
   group_controller: Arc<RwLock<Option<Box<dyn GroupController>>>>,
+  /// Groups the rows of each group by a second field. It's only set if the view is grouped.
+  sub_group_controller: Arc<RwLock<Option<Box<dyn GroupController>>>>,
   filter_controller: Arc<FilterController>,
   sort_controller: Arc<RwLock<SortController>>,
   calculations_controller: Arc<CalculationsController>,
+  formatting_controller: Arc<FormattingController>,
   /// Use lazy_rows as cache that represents the row's order for given view
   /// It can't get the row id when deleting a row. it only returns the deleted index.
   /// So using this cache to get the row id by index
@@ -59,6 +75,11 @@ pub struct DatabaseViewEditor {
   pub(crate) row_orders: RwLock<Vec<RowOrder>>,
   pub(crate) row_by_row_id: DashMap<String, Arc<Row>>,
   pub notifier: DatabaseViewChangedNotifier,
+  /// Cancels the task of [DatabaseViewEditor::observe_day_change]
+  day_change_cancellation: CancellationToken,
+  /// Runs the group calculation tasks one after another, so the last notified values are
+  /// calculated from the latest rows.
+  group_calculations_lock: Arc<Mutex<()>>,
 }
 
 impl Drop for DatabaseViewEditor {
@@ -100,7 +121,7 @@ impl DatabaseViewEditor {
       delegate.clone(),
       notifier.clone(),
       filter_controller.clone(),
-      cell_cache,
+      cell_cache.clone(),
     )
     .await;
 
@@ -114,22 +135,39 @@ impl DatabaseViewEditor {
       )
       .await?,
     ));
+    let sub_group_controller = Arc::new(RwLock::new(
+      new_sub_group_controller(view_id.clone(), delegate.clone(), filter_controller.clone())
+        .await?,
+    ));
 
     // Calculations
-    let calculations_controller =
-      make_calculations_controller(&view_id, delegate.clone(), notifier.clone()).await;
+    let calculations_controller = make_calculations_controller(
+      &view_id,
+      delegate.clone(),
+      notifier.clone(),
+      cell_cache.clone(),
+    )
+    .await;
+
+    // Formatting
+    let formatting_controller =
+      make_formatting_controller(&view_id, delegate.clone(), notifier.clone(), cell_cache).await;
 
     Ok(Self {
       database_id,
       view_id,
       delegate,
       group_controller,
+      sub_group_controller,
       filter_controller,
       sort_controller,
       calculations_controller,
+      formatting_controller,
       row_orders: Default::default(),
       row_by_row_id: Default::default(),
       notifier,
+      day_change_cancellation: CancellationToken::new(),
+      group_calculations_lock: Default::default(),
     })
   }
 
@@ -139,6 +177,9 @@ impl DatabaseViewEditor {
     if let Some(group) = self.group_controller.write().await.as_mut() {
       group.load_group_data().await?;
     }
+    if let Some(sub_group) = self.sub_group_controller.write().await.as_mut() {
+      sub_group.load_group_data().await?;
+    }
 
     Ok(())
   }
@@ -168,10 +209,40 @@ impl DatabaseViewEditor {
     Ok(row_orders)
   }
 
+  /// Filters the rows again at the start of each day in the timezone of the user, as the relative
+  /// date filters select other dates. It stops when the editor is closed or dropped.
+  pub fn observe_day_change(self: &Arc<Self>) {
+    let weak_editor = Arc::downgrade(self);
+    let cancellation = self.day_change_cancellation.clone();
+    tokio::spawn(async move {
+      loop {
+        let delay = match weak_editor.upgrade() {
+          Some(editor) => editor.filter_controller.duration_until_next_day(),
+          None => break,
+        };
+        select! {
+          _ = tokio::time::sleep(delay) => {},
+          _ = cancellation.cancelled() => break,
+        }
+
+        match weak_editor.upgrade() {
+          Some(editor) => {
+            if let Err(err) = editor.v_did_change_day().await {
+              error!("Failed to filter the rows of the new day: {}", err);
+            }
+          },
+          None => break,
+        }
+      }
+    });
+  }
+
   pub async fn close(&self) {
+    self.day_change_cancellation.cancel();
     self.sort_controller.write().await.close().await;
     self.filter_controller.close().await;
     self.calculations_controller.close().await;
+    self.formatting_controller.close().await;
   }
 
   pub async fn has_filters(&self) -> bool {
@@ -219,6 +290,18 @@ impl DatabaseViewEditor {
       }
     }
 
+    // fill in cells according to sub_group_id if supplied
+    if let Some(sub_group_id) = params.sub_group_id {
+      if let Some(controller) = self.sub_group_controller.read().await.as_ref() {
+        let field = self
+          .delegate
+          .get_field(controller.get_grouping_field_id())
+          .await
+          .ok_or_else(|| FlowyError::internal().with_context("Failed to get sub grouping field"))?;
+        controller.will_create_row(&mut cells, &field, &sub_group_id);
+      }
+    }
+
     // fill in cells according to active filters
     let filter_controller = self.filter_controller.clone();
     filter_controller.fill_cells(&mut cells).await;
@@ -239,50 +322,94 @@ impl DatabaseViewEditor {
     }
   }
 
-  pub async fn v_did_create_row(
+  /// Notify the view that the rows have been inserted. Each row comes with its index in the
+  /// view and whether it was moved. The rows inserted by one change are handled together, so the
+  /// group calculations and the view tasks run once for all of them.
+  pub async fn v_did_create_rows(
     &self,
-    row_detail: &RowDetail,
-    index: u32,
-    is_move_row: bool,
+    rows: &[(RowDetail, u32, bool)],
     is_local_change: bool,
     row_changes: &DashMap<String, RowsChangePB>,
   ) {
+    if rows.is_empty() {
+      return;
+    }
+
     // Send the group notification if the current view has groups
+    let visible_rows = self
+      .v_filter_rows(
+        rows
+          .iter()
+          .map(|(row_detail, _, _)| Arc::new(row_detail.row.clone()))
+          .collect(),
+      )
+      .await
+      .into_iter()
+      .map(|row| row.id.clone())
+      .collect::<HashSet<_>>();
     if let Some(controller) = self.group_controller.write().await.as_mut() {
-      let rows = vec![Arc::new(row_detail.row.clone())];
-      let mut rows = self.v_filter_rows(rows).await;
-      if let Some(row) = rows.pop() {
-        let changesets = controller.did_create_row(&row, index as usize);
-        for changeset in changesets {
-          notify_did_update_group_rows(changeset).await;
+      for (row_detail, index, _) in rows {
+        if visible_rows.contains(&row_detail.row.id) {
+          let changesets = controller.did_create_row(&row_detail.row, *index as usize);
+          for changeset in changesets {
+            notify_did_update_group_rows(changeset).await;
+          }
         }
       }
     }
+    if self.sub_group_controller.read().await.is_some() && !visible_rows.is_empty() {
+      let mut created_rows = vec![];
+      for (row_detail, index, _) in rows {
+        if visible_rows.contains(&row_detail.row.id) {
+          self
+            .mut_sub_group_controller(|sub_group_controller, _| {
+              Ok(sub_group_controller.did_create_row(&row_detail.row, *index as usize))
+            })
+            .await;
+          created_rows.push(row_detail.row.clone());
+        }
+      }
+      self
+        .v_notify_did_update_sub_group_rows(&created_rows, HashMap::new(), false)
+        .await;
+    }
+    let mut group_ids = HashSet::new();
+    for (row_detail, _, _) in rows {
+      group_ids.extend(self.v_get_group_ids_of_row(&row_detail.row.id).await);
+    }
+    self.v_update_group_calculations(Some(group_ids)).await;
 
-    let index = self
-      .sort_controller
-      .write()
-      .await
-      .did_create_row(&row_detail.row)
-      .await;
+    for (row_detail, _, is_move_row) in rows {
+      let index = self
+        .sort_controller
+        .write()
+        .await
+        .did_create_row(&row_detail.row)
+        .await;
 
-    row_changes
-      .entry(self.view_id.clone())
-      .or_insert_with(|| {
-        let mut change = RowsChangePB::new();
-        change.is_move_row = is_move_row;
-        change
-      })
-      .inserted_rows
-      .push(InsertedRowPB {
-        row_meta: RowMetaPB::from(row_detail),
-        index: index.map(|index| index as i32),
-        is_new: true,
-        is_hidden_in_view: is_local_change && index.is_none(),
-      });
+      row_changes
+        .entry(self.view_id.clone())
+        .or_insert_with(|| {
+          let mut change = RowsChangePB::new();
+          change.is_move_row = *is_move_row;
+          change
+        })
+        .inserted_rows
+        .push(InsertedRowPB {
+          row_meta: RowMetaPB::from(row_detail),
+          index: index.map(|index| index as i32),
+          is_new: true,
+          is_hidden_in_view: is_local_change && index.is_none(),
+        });
+    }
 
     self
-      .gen_did_create_row_view_tasks(row_detail.row.clone())
+      .gen_did_create_rows_view_tasks(
+        rows
+          .iter()
+          .map(|(row_detail, _, _)| row_detail.row.clone())
+          .collect(),
+      )
       .await;
   }
 
@@ -295,11 +422,34 @@ impl DatabaseViewEditor {
     // notification. Because it's handled by the move_group_row function
     // 2. when the row is deleted remotely
     if !is_move_row || !is_local_change {
+      let group_ids = self.v_get_group_ids_of_row(&row.id).await;
+      let old_sub_group_pairs = self
+        .v_get_sub_group_pairs_of_rows(std::slice::from_ref(&row.id))
+        .await;
       // Send the group notification if the current view has groups;
       let result = self
         .mut_group_controller(|group_controller, _| group_controller.did_delete_row(row))
         .await;
-      handle_mut_group_result(&self.view_id, result).await;
+      let is_group_deleted = handle_mut_group_result(&self.view_id, result).await;
+      let result = self
+        .mut_sub_group_controller(|sub_group_controller, _| {
+          sub_group_controller.did_delete_row(row)
+        })
+        .await;
+      if let Some(result) = result {
+        if is_group_deleted || result.deleted_group.is_some() {
+          self.v_notify_did_update_sub_groups().await;
+        } else {
+          self
+            .v_notify_did_update_sub_group_rows(
+              std::slice::from_ref(row),
+              old_sub_group_pairs,
+              false,
+            )
+            .await;
+        }
+      }
+      self.v_update_group_calculations(Some(group_ids)).await;
     }
 
     // Updating calculations for each of the Rows cells is a tedious task
@@ -319,6 +469,42 @@ impl DatabaseViewEditor {
   /// send the view notification with [RowsChangePB]
   #[instrument(level = "trace", skip_all)]
   pub async fn v_did_update_row(&self, old_row: &Option<Row>, row: &Row, field_id: Option<String>) {
+    let group_ids = self.update_groups_of_row(old_row, row).await;
+
+    // The updated cell may be calculated in the groups even if the row stays in the same groups
+    self.v_update_group_calculations(Some(group_ids)).await;
+
+    // Each row update will trigger a calculations, filter and sort operation. We don't want
+    // to block the main thread, so we spawn a new task to do the work.
+    self
+      .gen_did_update_row_view_tasks(row.id.clone(), field_id)
+      .await;
+  }
+
+  /// Notify the view that the rows have been updated by a batch. Unlike [Self::v_did_update_row],
+  /// the filters, sorts and formatting rules are applied to the updated rows at once, so one
+  /// notification is sent for each of them instead of one per row.
+  #[instrument(level = "trace", skip_all)]
+  pub async fn v_did_update_rows(&self, rows: &[(Option<Row>, Row)], field_ids: Vec<String>) {
+    let mut group_ids = HashSet::new();
+    for (old_row, row) in rows {
+      group_ids.extend(self.update_groups_of_row(old_row, row).await);
+    }
+    self.v_update_group_calculations(Some(group_ids)).await;
+    let row_ids = rows.iter().map(|(_, row)| row.id.clone()).collect();
+    self
+      .gen_did_update_rows_view_tasks(row_ids, field_ids)
+      .await;
+  }
+
+  /// Moves the row to the groups and the sub groups matching its new cells. Returns the ids of
+  /// the groups that the row was in or is now in.
+  async fn update_groups_of_row(&self, old_row: &Option<Row>, row: &Row) -> HashSet<String> {
+    let mut group_ids = self.v_get_group_ids_of_row(&row.id).await;
+    let old_sub_group_pairs = self
+      .v_get_sub_group_pairs_of_rows(std::slice::from_ref(&row.id))
+      .await;
+    let mut is_num_of_groups_changed = false;
     if let Some(controller) = self.group_controller.write().await.as_mut() {
       let field = self
         .delegate
@@ -360,6 +546,7 @@ impl DatabaseViewEditor {
         }
 
         if !group_changes.is_empty() {
+          is_num_of_groups_changed = true;
           notify_did_update_num_of_groups(&self.view_id, group_changes).await;
         }
 
@@ -372,11 +559,33 @@ impl DatabaseViewEditor {
       }
     }
 
-    // Each row update will trigger a calculations, filter and sort operation. We don't want
-    // to block the main thread, so we spawn a new task to do the work.
-    self
-      .gen_did_update_row_view_tasks(row.id.clone(), field_id)
-      .await;
+    // The nested groups are sent again if a group or a sub group was inserted or deleted.
+    // Otherwise, only the sub groups that the row left, entered or stayed in are sent.
+    if self.sub_group_controller.read().await.is_some() {
+      let rows = vec![Arc::new(row.clone())];
+      let filtered_row = self.v_filter_rows(rows).await.pop();
+      let is_num_of_sub_groups_changed = self
+        .mut_sub_group_controller(|sub_group_controller, field| match filtered_row {
+          Some(filtered_row) => sub_group_controller
+            .did_update_group_row(old_row, &filtered_row, &field)
+            .map(|result| result.inserted_group.is_some() || result.deleted_group.is_some()),
+          None => sub_group_controller
+            .did_delete_row(row)
+            .map(|result| result.deleted_group.is_some()),
+        })
+        .await
+        .unwrap_or(false);
+      if is_num_of_groups_changed || is_num_of_sub_groups_changed {
+        self.v_notify_did_update_sub_groups().await;
+      } else {
+        self
+          .v_notify_did_update_sub_group_rows(std::slice::from_ref(row), old_sub_group_pairs, false)
+          .await;
+      }
+    }
+
+    group_ids.extend(self.v_get_group_ids_of_row(&row.id).await);
+    group_ids
   }
 
   pub async fn v_filter_rows(&self, rows: Vec<Arc<Row>>) -> Vec<Arc<Row>> {
@@ -434,12 +643,19 @@ impl DatabaseViewEditor {
     self.delegate.get_row_detail(&self.view_id, row_id).await
   }
 
+  /// Moves the row to the group and to the sub group if it's set. The returned cells contain the
+  /// cells of both the grouping field and the sub grouping field.
   pub async fn v_move_group_row(
     &self,
     row: &Row,
     to_group_id: &str,
+    to_sub_group_id: Option<&str>,
     to_row_id: Option<RowId>,
   ) -> UpdatedCells {
+    let mut group_ids = self.v_get_group_ids_of_row(&row.id).await;
+    let old_sub_group_pairs = self
+      .v_get_sub_group_pairs_of_rows(std::slice::from_ref(&row.id))
+      .await;
     let mut updated_cells = UpdatedCells::new();
     let result = self
       .mut_group_controller(|group_controller, field| {
@@ -448,20 +664,46 @@ impl DatabaseViewEditor {
           updated_cells: &mut updated_cells,
           field: &field,
           to_group_id,
-          to_row_id,
+          to_row_id: to_row_id.clone(),
         };
         group_controller.move_group_row(move_row_context)
       })
       .await;
+    let mut is_group_deleted = handle_mut_group_result(&self.view_id, result).await;
 
-    handle_mut_group_result(&self.view_id, result).await;
+    if let Some(to_sub_group_id) = to_sub_group_id {
+      let result = self
+        .mut_sub_group_controller(|sub_group_controller, field| {
+          let move_row_context = MoveGroupRowContext {
+            row,
+            updated_cells: &mut updated_cells,
+            field: &field,
+            to_group_id: to_sub_group_id,
+            to_row_id,
+          };
+          sub_group_controller.move_group_row(move_row_context)
+        })
+        .await;
+      is_group_deleted |= result.is_some_and(|result| result.deleted_group.is_some());
+    }
+    if is_group_deleted {
+      self.v_notify_did_update_sub_groups().await;
+    } else {
+      self
+        .v_notify_did_update_sub_group_rows(std::slice::from_ref(row), old_sub_group_pairs, true)
+        .await;
+    }
+
+    group_ids.extend(self.v_get_group_ids_of_row(&row.id).await);
+    self.v_update_group_calculations(Some(group_ids)).await;
     updated_cells
   }
 
-  /// Only call once after database view editor initialized
+  /// Returns the groups of the view with their sub groups. The rows of the groups are only read
+  /// when the view has calculations.
   #[tracing::instrument(level = "trace", skip(self))]
   pub async fn v_load_groups(&self) -> Option<Vec<GroupPB>> {
-    let groups = self
+    let mut groups = self
       .group_controller
       .read()
       .await
@@ -471,6 +713,16 @@ impl DatabaseViewEditor {
       .map(|group_data| GroupPB::from(group_data.clone()))
       .collect::<Vec<_>>();
     tracing::trace!("Number of groups: {}", groups.len());
+
+    if self.calculations_controller.has_calculations().await {
+      for (group, (_, rows)) in groups.iter_mut().zip(self.v_get_group_rows(None).await) {
+        group.calculations = self
+          .calculations_controller
+          .calculate_group(&group.group_id, &rows)
+          .await;
+      }
+    }
+    self.v_fill_sub_groups(&mut groups).await;
     Some(groups)
   }
 
@@ -484,10 +736,190 @@ impl DatabaseViewEditor {
       .and_then(|group| group.get_group(group_id))
     {
       None => Err(FlowyError::record_not_found().with_context("Can't find the group")),
-      Some((_, group)) => Ok(GroupPB::from(group)),
+      Some((_, group)) => {
+        let mut group = GroupPB::from(group);
+        if self.calculations_controller.has_calculations().await {
+          let group_ids = HashSet::from([group.group_id.clone()]);
+          if let Some((_, rows)) = self
+            .v_get_group_rows(Some(&group_ids))
+            .await
+            .into_iter()
+            .find(|(group_id, _)| group_id == &group.group_id)
+          {
+            group.calculations = self
+              .calculations_controller
+              .calculate_group(&group.group_id, &rows)
+              .await;
+          }
+        }
+        self
+          .v_fill_sub_groups(std::slice::from_mut(&mut group))
+          .await;
+        Ok(group)
+      },
+    }
+  }
+
+  /// Sets the sub groups of each group. A sub group only contains the rows of its group, the
+  /// calculations are only computed for the groups.
+  async fn v_fill_sub_groups(&self, groups: &mut [GroupPB]) {
+    let sub_group_controller = self.sub_group_controller.read().await;
+    let sub_groups = match sub_group_controller.as_ref() {
+      None => return,
+      Some(controller) => controller.get_all_groups(),
+    };
+
+    for group in groups.iter_mut() {
+      group.sub_groups = sub_groups
+        .iter()
+        .map(|sub_group| {
+          let rows = group
+            .rows
+            .iter()
+            .filter(|row| sub_group.contains_row(&RowId::from(row.id.clone())))
+            .cloned()
+            .collect();
+          GroupPB {
+            rows,
+            ..GroupPB::from(GroupData::new(
+              sub_group.id.clone(),
+              sub_group.field_id.clone(),
+              sub_group.is_visible,
+            ))
+          }
+        })
+        .collect();
+    }
+  }
+
+  /// Sends the nested groups if the view is sub grouped
+  async fn v_notify_did_update_sub_groups(&self) {
+    if self.sub_group_controller.read().await.is_none() {
+      return;
+    }
+    if let Some(groups) = self.v_load_groups().await {
+      notify_did_update_sub_groups(&self.view_id, RepeatedGroupPB { items: groups }).await;
+    }
+  }
+
+  /// Returns the pairs of group and sub group that contain each row. It's empty if the view is
+  /// not sub grouped.
+  async fn v_get_sub_group_pairs_of_rows(
+    &self,
+    row_ids: &[RowId],
+  ) -> HashMap<RowId, HashSet<(String, String)>> {
+    let group_controller = self.group_controller.read().await;
+    let sub_group_controller = self.sub_group_controller.read().await;
+    match (group_controller.as_ref(), sub_group_controller.as_ref()) {
+      (Some(controller), Some(sub_group_controller)) => row_ids
+        .iter()
+        .map(|row_id| {
+          let pairs =
+            get_sub_group_pairs_of_row(controller.as_ref(), sub_group_controller.as_ref(), row_id);
+          (row_id.clone(), pairs)
+        })
+        .collect(),
+      _ => HashMap::new(),
     }
   }
 
+  /// Sends the changes of the rows to each pair of group and sub group that they left, entered
+  /// or stayed in. `old_sub_group_pairs` are the pairs that contained the rows before the change.
+  /// The rows that stayed in a pair are sent as updated rows, or as moved rows if `is_move_row`.
+  async fn v_notify_did_update_sub_group_rows(
+    &self,
+    rows: &[Row],
+    old_sub_group_pairs: HashMap<RowId, HashSet<(String, String)>>,
+    is_move_row: bool,
+  ) {
+    let changesets = {
+      let group_controller = self.group_controller.read().await;
+      let sub_group_controller = self.sub_group_controller.read().await;
+      match (group_controller.as_ref(), sub_group_controller.as_ref()) {
+        (Some(controller), Some(sub_group_controller)) => sub_group_row_changesets(
+          &self.view_id,
+          controller.as_ref(),
+          sub_group_controller.as_ref(),
+          rows,
+          &old_sub_group_pairs,
+          is_move_row,
+        ),
+        _ => return,
+      }
+    };
+    for changeset in changesets {
+      trace!(
+        "[SubGroup]: group:{}, sub group:{} changed",
+        changeset.group_id,
+        changeset.sub_group_id
+      );
+      notify_did_update_sub_group_rows(changeset).await;
+    }
+  }
+
+  /// Returns the rows of the groups, all the groups if `group_ids` is None
+  async fn v_get_group_rows(
+    &self,
+    group_ids: Option<&HashSet<String>>,
+  ) -> Vec<(String, Vec<Arc<Row>>)> {
+    let group_row_ids = match self.group_controller.read().await.as_ref() {
+      None => return vec![],
+      Some(controller) => get_group_row_ids(controller.as_ref(), group_ids),
+    };
+    get_group_rows(&self.delegate, &self.view_id, group_row_ids).await
+  }
+
+  /// Returns the ids of the groups that contain the row
+  async fn v_get_group_ids_of_row(&self, row_id: &RowId) -> HashSet<String> {
+    match self.group_controller.read().await.as_ref() {
+      None => HashSet::new(),
+      Some(controller) => get_group_ids_of_row(controller.as_ref(), row_id),
+    }
+  }
+
+  /// Recalculates the calculations of the groups in a separate task and notifies the groups
+  /// whose values changed. When `group_ids` is provided, only the rows of those groups are read
+  /// and recalculated, otherwise all the groups are.
+  async fn v_update_group_calculations(&self, group_ids: Option<HashSet<String>>) {
+    if self.group_controller.read().await.is_none()
+      || group_ids
+        .as_ref()
+        .is_some_and(|group_ids| group_ids.is_empty())
+    {
+      return;
+    }
+
+    let view_id = self.view_id.clone();
+    let delegate = self.delegate.clone();
+    let weak_group_controller = Arc::downgrade(&self.group_controller);
+    let weak_calculations_controller = Arc::downgrade(&self.calculations_controller);
+    let group_calculations_lock = self.group_calculations_lock.clone();
+    tokio::spawn(async move {
+      let _guard = group_calculations_lock.lock().await;
+      if delegate.get_all_calculations(&view_id).await.is_empty() {
+        return;
+      }
+
+      let group_row_ids = match weak_group_controller.upgrade() {
+        None => return,
+        Some(group_controller) => match group_controller.read().await.as_ref() {
+          None => return,
+          Some(controller) => get_group_row_ids(controller.as_ref(), group_ids.as_ref()),
+        },
+      };
+
+      let group_rows = get_group_rows(&delegate, &view_id, group_row_ids).await;
+      if let Some(calculations_controller) = weak_calculations_controller.upgrade() {
+        for payload in calculations_controller
+          .did_receive_groups_changed(group_rows)
+          .await
+        {
+          notify_did_update_group_calculations(payload).await;
+        }
+      }
+    });
+  }
+
   #[tracing::instrument(level = "trace", skip(self), err)]
   pub async fn v_move_group(&self, from_group: &str, to_group: &str) -> FlowyResult<()> {
     self
@@ -503,6 +935,13 @@ impl DatabaseViewEditor {
     }
   }
 
+  pub async fn is_sub_grouping_field(&self, field_id: &str) -> bool {
+    match self.sub_group_controller.read().await.as_ref() {
+      Some(sub_group_controller) => sub_group_controller.get_grouping_field_id() == field_id,
+      None => false,
+    }
+  }
+
   /// Called when the user changes the grouping field
   pub async fn v_initialize_new_group(&self, field_id: &str) -> FlowyResult<()> {
     if let Some(view) = self.delegate.get_view(&self.view_id).await {
@@ -750,7 +1189,7 @@ impl DatabaseViewEditor {
       .map(|(field, _)| {
         let cells = rows
           .iter()
-          .filter_map(|row| row.cells.get(&field.id).cloned().map(Arc::new))
+          .filter_map(|row| get_exported_cell(row, field).map(Arc::new))
           .collect::<Vec<Arc<Cell>>>();
         (field.id.clone(), cells)
       })
@@ -829,6 +1268,7 @@ impl DatabaseViewEditor {
 
       notify_did_update_calculation(changeset).await;
     }
+    self.v_update_group_calculations(None).await;
 
     Ok(())
   }
@@ -852,6 +1292,7 @@ impl DatabaseViewEditor {
     if let Some(changeset) = changeset {
       notify_did_update_calculation(changeset).await;
     }
+    self.v_update_group_calculations(None).await;
 
     Ok(())
   }
@@ -868,7 +1309,54 @@ impl DatabaseViewEditor {
   pub async fn v_modify_filters(&self, changeset: FilterChangeset) -> FlowyResult<()> {
     let notification = self.filter_controller.apply_changeset(changeset).await;
     notify_did_update_filter(notification).await;
+    self.v_did_filter_rows_change().await
+  }
 
+  pub async fn v_get_all_formatting_rules(&self) -> RepeatedFormattingRulePB {
+    self.formatting_controller.get_all_rules().await
+  }
+
+  #[tracing::instrument(level = "trace", skip(self))]
+  pub async fn v_modify_formatting_rules(&self, changeset: FormattingRuleChangeset) {
+    let rules = self.formatting_controller.apply_changeset(changeset).await;
+    notify_did_update_formatting_rules(&self.view_id, rules).await;
+  }
+
+  /// Returns the ids of the formatting rules matched by each row
+  pub async fn v_get_rows_formatting(&self, rows: &[Arc<Row>]) -> HashMap<RowId, Vec<String>> {
+    if !self.formatting_controller.has_rules().await {
+      return HashMap::new();
+    }
+    self.formatting_controller.format_rows(rows).await
+  }
+
+  pub async fn v_format_rows_and_notify(&self, rows: &[Arc<Row>]) {
+    if self.formatting_controller.has_rules().await {
+      self
+        .formatting_controller
+        .format_rows_and_notify(rows)
+        .await;
+    }
+  }
+
+  async fn v_did_change_day(&self) -> FlowyResult<()> {
+    if self.formatting_controller.has_relative_date_rules().await {
+      self.formatting_controller.refresh().await;
+    }
+    if !self.filter_controller.has_relative_date_filters().await {
+      return Ok(());
+    }
+
+    trace!(
+      "[Database]: filter the rows of view:{} for the new day",
+      self.view_id
+    );
+    self.filter_controller.refresh().await;
+    self.v_did_filter_rows_change().await
+  }
+
+  /// Groups and calculates the rows again after the filtered rows changed
+  async fn v_did_filter_rows_change(&self) -> FlowyResult<()> {
     let group_controller_read_guard = self.group_controller.read().await;
     let grouping_field_id = group_controller_read_guard
       .as_ref()
@@ -878,6 +1366,9 @@ impl DatabaseViewEditor {
     if let Some(field_id) = grouping_field_id {
       self.v_group_by_field(&field_id).await?;
     }
+    if self.sub_group_controller.read().await.is_some() {
+      self.v_sub_group_by_field().await?;
+    }
 
     let row_orders = self.delegate.get_all_row_orders(&self.view_id).await;
     let rows = self.delegate.get_all_rows(&self.view_id, row_orders).await;
@@ -990,6 +1481,14 @@ impl DatabaseViewEditor {
     let notification = self.filter_controller.apply_changeset(changeset).await;
     notify_did_update_filter(notification).await;
 
+    if self.formatting_controller.has_rules().await {
+      self
+        .v_modify_formatting_rules(FormattingRuleChangeset::DeleteAllWithFieldId {
+          field_id: deleted_field_id.to_string(),
+        })
+        .await;
+    }
+
     let sorts = self.delegate.get_all_sorts(&self.view_id).await;
 
     if let Some(sort) = sorts.iter().find(|sort| sort.field_id == deleted_field_id) {
@@ -1009,6 +1508,57 @@ impl DatabaseViewEditor {
       .calculations_controller
       .did_receive_field_deleted(deleted_field_id.to_string())
       .await;
+
+    if self.is_sub_grouping_field(deleted_field_id).await {
+      let _ = self.v_sub_group_by_field().await;
+    }
+  }
+
+  /// Puts back the filters and the sorts removed with a field when its deletion is undone. The
+  /// filters and the sorts are restored at their index in the view.
+  pub async fn v_restore_filters_and_sorts(
+    &self,
+    filters: Vec<(usize, Filter)>,
+    sorts: Vec<(usize, Sort)>,
+  ) {
+    if !filters.is_empty() {
+      let notification = self
+        .filter_controller
+        .apply_changeset(FilterChangeset::Restore { filters })
+        .await;
+      notify_did_update_filter(notification).await;
+    }
+
+    let current_sorts = self.delegate.get_all_sorts(&self.view_id).await;
+    for (index, sort) in sorts {
+      if current_sorts.iter().any(|other| other.id == sort.id) {
+        continue;
+      }
+
+      self.delegate.insert_sort(&self.view_id, sort.clone()).await;
+      let mut sort_controller = self.sort_controller.write().await;
+      let notification = sort_controller
+        .apply_changeset(SortChangeset::from_insert(sort.clone()))
+        .await;
+      notify_did_update_sort(notification).await;
+
+      let to_sort = self
+        .delegate
+        .get_all_sorts(&self.view_id)
+        .await
+        .get(index)
+        .cloned();
+      if let Some(to_sort) = to_sort.filter(|to_sort| to_sort.id != sort.id) {
+        self
+          .delegate
+          .move_sort(&self.view_id, &sort.id, &to_sort.id)
+          .await;
+        let notification = sort_controller
+          .apply_changeset(SortChangeset::from_reorder(sort.id, to_sort.id))
+          .await;
+        notify_did_update_sort(notification).await;
+      }
+    }
   }
 
   pub async fn v_did_update_field_type(&self, field_id: &str, new_field_type: FieldType) {
@@ -1029,9 +1579,19 @@ impl DatabaseViewEditor {
       let notification = self.filter_controller.apply_changeset(changeset).await;
       notify_did_update_filter(notification).await;
     }
+    if self.formatting_controller.has_rules().await {
+      self
+        .v_modify_formatting_rules(FormattingRuleChangeset::DeleteAllWithFieldId {
+          field_id: field_id.to_string(),
+        })
+        .await;
+    }
     if self.is_grouping_field(field_id).await {
       let _ = self.v_group_by_field(field_id).await;
     }
+    if self.is_sub_grouping_field(field_id).await {
+      let _ = self.v_sub_group_by_field().await;
+    }
   }
 
   /// Notifies the view's field type-option data is changed
@@ -1051,14 +1611,16 @@ impl DatabaseViewEditor {
 
       // If the id of the grouping field is equal to the updated field's id
       // and something critical changed, then we need to update the group setting
-      if self.is_grouping_field(field_id).await
-        && matches!(
-          FieldType::from(field.field_type),
-          FieldType::SingleSelect | FieldType::MultiSelect
-        )
-      {
+      let is_select_field = matches!(
+        FieldType::from(field.field_type),
+        FieldType::SingleSelect | FieldType::MultiSelect
+      );
+      if self.is_grouping_field(field_id).await && is_select_field {
         self.v_group_by_field(field_id).await?;
       }
+      if self.is_sub_grouping_field(field_id).await && is_select_field {
+        self.v_sub_group_by_field().await?;
+      }
     }
 
     Ok(())
@@ -1109,6 +1671,26 @@ impl DatabaseViewEditor {
     Ok(())
   }
 
+  /// Called when the sub grouping field is set, updated or removed. The sub grouping field is
+  /// read from the group settings of the view.
+  #[tracing::instrument(level = "debug", skip_all, err)]
+  pub async fn v_sub_group_by_field(&self) -> FlowyResult<()> {
+    let mut new_sub_group_controller = new_sub_group_controller(
+      self.view_id.clone(),
+      self.delegate.clone(),
+      self.filter_controller.clone(),
+    )
+    .await?;
+    if let Some(controller) = &mut new_sub_group_controller {
+      controller.load_group_data().await?;
+    }
+    *self.sub_group_controller.write().await = new_sub_group_controller;
+
+    let groups = self.v_load_groups().await.unwrap_or_default();
+    notify_did_update_sub_groups(&self.view_id, RepeatedGroupPB { items: groups }).await;
+    Ok(())
+  }
+
   pub async fn v_get_calendar_event(&self, row_id: RowId) -> Option<CalendarEventPB> {
     let layout_ty = DatabaseLayout::Calendar;
     let calendar_setting = self.v_get_layout_settings(&layout_ty).await.calendar?;
@@ -1227,6 +1809,21 @@ impl DatabaseViewEditor {
 
     *self.group_controller.write().await = new_group_controller;
 
+    let new_sub_group_controller = match new_sub_group_controller(
+      self.view_id.clone(),
+      self.delegate.clone(),
+      self.filter_controller.clone(),
+    )
+    .await?
+    {
+      Some(mut controller) => {
+        controller.load_group_data().await?;
+        Some(controller)
+      },
+      None => None,
+    };
+    *self.sub_group_controller.write().await = new_sub_group_controller;
+
     let payload = DatabaseLayoutMetaPB {
       view_id: self.view_id.clone(),
       layout: new_layout_type.into(),
@@ -1270,14 +1867,31 @@ impl DatabaseViewEditor {
   where
     F: FnOnce(&mut Box<dyn GroupController>, Field) -> FlowyResult<T>,
   {
-    let group_field_id = self
-      .group_controller
+    self.mut_controller(&self.group_controller, f).await
+  }
+
+  async fn mut_sub_group_controller<F, T>(&self, f: F) -> Option<T>
+  where
+    F: FnOnce(&mut Box<dyn GroupController>, Field) -> FlowyResult<T>,
+  {
+    self.mut_controller(&self.sub_group_controller, f).await
+  }
+
+  async fn mut_controller<F, T>(
+    &self,
+    controller: &RwLock<Option<Box<dyn GroupController>>>,
+    f: F,
+  ) -> Option<T>
+  where
+    F: FnOnce(&mut Box<dyn GroupController>, Field) -> FlowyResult<T>,
+  {
+    let group_field_id = controller
       .read()
       .await
       .as_ref()
       .map(|controller| controller.get_grouping_field_id().to_owned())?;
     let field = self.delegate.get_field(&group_field_id).await?;
-    let mut write_guard = self.group_controller.write().await;
+    let mut write_guard = controller.write().await;
     if let Some(group_controller) = &mut *write_guard {
       f(group_controller, field).ok()
     } else {
@@ -1289,6 +1903,7 @@ impl DatabaseViewEditor {
     let weak_filter_controller = Arc::downgrade(&self.filter_controller);
     let weak_sort_controller = Arc::downgrade(&self.sort_controller);
     let weak_calculations_controller = Arc::downgrade(&self.calculations_controller);
+    let weak_formatting_controller = Arc::downgrade(&self.formatting_controller);
     tokio::spawn(async move {
       if let Some(filter_controller) = weak_filter_controller.upgrade() {
         filter_controller
@@ -1303,6 +1918,12 @@ impl DatabaseViewEditor {
           .await;
       }
 
+      if let Some(formatting_controller) = weak_formatting_controller.upgrade() {
+        formatting_controller
+          .did_receive_row_changed(row_id.clone())
+          .await;
+      }
+
       if let Some(calculations_controller) = weak_calculations_controller.upgrade() {
         if let Some(field_id) = field_id {
           calculations_controller
@@ -1313,21 +1934,204 @@ impl DatabaseViewEditor {
     });
   }
 
-  async fn gen_did_create_row_view_tasks(&self, row: Row) {
+  async fn gen_did_update_rows_view_tasks(&self, row_ids: Vec<RowId>, field_ids: Vec<String>) {
+    let weak_filter_controller = Arc::downgrade(&self.filter_controller);
+    let weak_sort_controller = Arc::downgrade(&self.sort_controller);
     let weak_calculations_controller = Arc::downgrade(&self.calculations_controller);
+    let weak_formatting_controller = Arc::downgrade(&self.formatting_controller);
     tokio::spawn(async move {
+      if let Some(filter_controller) = weak_filter_controller.upgrade() {
+        filter_controller
+          .did_receive_rows_changed(row_ids.clone())
+          .await;
+      }
+      if let Some(sort_controller) = weak_sort_controller.upgrade() {
+        sort_controller
+          .read()
+          .await
+          .did_receive_rows_changed()
+          .await;
+      }
+
+      if let Some(formatting_controller) = weak_formatting_controller.upgrade() {
+        formatting_controller
+          .did_receive_rows_changed(row_ids)
+          .await;
+      }
+
       if let Some(calculations_controller) = weak_calculations_controller.upgrade() {
-        calculations_controller
-          .did_receive_row_changed(row.clone())
+        for field_id in field_ids {
+          calculations_controller
+            .did_receive_cell_changed(field_id)
+            .await;
+        }
+      }
+    });
+  }
+
+  async fn gen_did_create_rows_view_tasks(&self, rows: Vec<Row>) {
+    let weak_calculations_controller = Arc::downgrade(&self.calculations_controller);
+    let weak_formatting_controller = Arc::downgrade(&self.formatting_controller);
+    tokio::spawn(async move {
+      if let Some(formatting_controller) = weak_formatting_controller.upgrade() {
+        let row_ids = rows.iter().map(|row| row.id.clone()).collect();
+        formatting_controller
+          .did_receive_rows_changed(row_ids)
           .await;
       }
+      if let Some(calculations_controller) = weak_calculations_controller.upgrade() {
+        for row in rows {
+          calculations_controller.did_receive_row_changed(row).await;
+        }
+      }
     });
   }
 }
 
-async fn handle_mut_group_result(view_id: &str, result: Option<DidMoveGroupRowResult>) {
+fn get_group_ids_of_row(controller: &dyn GroupController, row_id: &RowId) -> HashSet<String> {
+  controller
+    .get_all_groups()
+    .into_iter()
+    .filter(|group| group.contains_row(row_id))
+    .map(|group| group.id.clone())
+    .collect()
+}
+
+/// Returns the pairs of group and sub group that contain the row
+fn get_sub_group_pairs_of_row(
+  controller: &dyn GroupController,
+  sub_group_controller: &dyn GroupController,
+  row_id: &RowId,
+) -> HashSet<(String, String)> {
+  let sub_group_ids = get_group_ids_of_row(sub_group_controller, row_id);
+  get_group_ids_of_row(controller, row_id)
+    .into_iter()
+    .flat_map(|group_id| {
+      sub_group_ids
+        .iter()
+        .map(move |sub_group_id| (group_id.clone(), sub_group_id.clone()))
+    })
+    .collect()
+}
+
+/// Returns the changes of each pair of group and sub group after the rows changed. The index of
+/// an inserted row is its index among the rows of the group that are in the sub group.
+fn sub_group_row_changesets(
+  view_id: &str,
+  controller: &dyn GroupController,
+  sub_group_controller: &dyn GroupController,
+  rows: &[Row],
+  old_sub_group_pairs: &HashMap<RowId, HashSet<(String, String)>>,
+  is_move_row: bool,
+) -> Vec<SubGroupRowsNotificationPB> {
+  let groups = controller.get_all_groups();
+  let sub_groups = sub_group_controller.get_all_groups();
+  let index_of_row = |(group_id, sub_group_id): &(String, String), row_id: &RowId| {
+    let group = groups.iter().find(|group| &group.id == group_id)?;
+    let sub_group = sub_groups
+      .iter()
+      .find(|sub_group| &sub_group.id == sub_group_id)?;
+    group
+      .rows
+      .iter()
+      .filter(|row| sub_group.contains_row(&row.id))
+      .position(|row| &row.id == row_id)
+  };
+
+  let inserted_row = |pair: &(String, String), row: &Row| {
+    let inserted_row = InsertedRowPB::new(RowMetaPB::from(row));
+    match index_of_row(pair, &row.id) {
+      Some(index) => inserted_row.with_index(index as i32),
+      None => inserted_row,
+    }
+  };
+
+  let mut changesets: HashMap<(String, String), SubGroupRowsNotificationPB> = HashMap::new();
+  let empty_pairs = HashSet::new();
+  for row in rows {
+    let old_pairs = old_sub_group_pairs.get(&row.id).unwrap_or(&empty_pairs);
+    let new_pairs = get_sub_group_pairs_of_row(controller, sub_group_controller, &row.id);
+    for pair in old_pairs.union(&new_pairs) {
+      let changeset = changesets.entry(pair.clone()).or_insert_with(|| {
+        SubGroupRowsNotificationPB::new(view_id.to_string(), pair.0.clone(), pair.1.clone())
+      });
+      match (old_pairs.contains(pair), new_pairs.contains(pair)) {
+        (true, false) => changeset.deleted_rows.push(row.id.to_string()),
+        (false, true) => changeset.inserted_rows.push(inserted_row(pair, row)),
+        _ if is_move_row => {
+          changeset.deleted_rows.push(row.id.to_string());
+          changeset.inserted_rows.push(inserted_row(pair, row));
+        },
+        _ => changeset.updated_rows.push(RowMetaPB::from(row)),
+      }
+    }
+  }
+  changesets.into_values().collect()
+}
+
+/// Returns the row ids of the groups, all the groups if `group_ids` is None
+fn get_group_row_ids(
+  controller: &dyn GroupController,
+  group_ids: Option<&HashSet<String>>,
+) -> Vec<(String, Vec<RowId>)> {
+  controller
+    .get_all_groups()
+    .into_iter()
+    .filter(|group| group_ids.map_or(true, |group_ids| group_ids.contains(&group.id)))
+    .map(|group| {
+      let row_ids = group
+        .rows
+        .iter()
+        .map(|row| row.id.clone())
+        .collect::<Vec<_>>();
+      (group.id.clone(), row_ids)
+    })
+    .collect()
+}
+
+/// Returns the rows of each group. The rows are read from the database instead of the group
+/// controller, which only tracks which rows belong to which group, so the calculations use the
+/// latest content of the cells. Only the rows of the given groups are read.
+async fn get_group_rows(
+  delegate: &Arc<dyn DatabaseViewOperation>,
+  view_id: &str,
+  group_row_ids: Vec<(String, Vec<RowId>)>,
+) -> Vec<(String, Vec<Arc<Row>>)> {
+  let row_ids = group_row_ids
+    .iter()
+    .flat_map(|(_, row_ids)| row_ids.iter())
+    .collect::<HashSet<_>>();
+  let row_orders = delegate
+    .get_all_row_orders(view_id)
+    .await
+    .into_iter()
+    .filter(|row_order| row_ids.contains(&row_order.id))
+    .collect::<Vec<RowOrder>>();
+  let rows_by_id = delegate
+    .get_all_rows(view_id, row_orders)
+    .await
+    .into_iter()
+    .map(|row| (row.id.clone(), row))
+    .collect::<HashMap<_, _>>();
+
+  group_row_ids
+    .into_iter()
+    .map(|(group_id, row_ids)| {
+      let rows = row_ids
+        .iter()
+        .filter_map(|row_id| rows_by_id.get(row_id).cloned())
+        .collect();
+      (group_id, rows)
+    })
+    .collect()
+}
+
+/// Sends the changes of the groups. Returns true if a group was deleted.
+async fn handle_mut_group_result(view_id: &str, result: Option<DidMoveGroupRowResult>) -> bool {
+  let mut is_group_deleted = false;
   if let Some(result) = result {
     if let Some(deleted_group) = result.deleted_group {
+      is_group_deleted = true;
       trace!("Delete group after moving the row: {:?}", deleted_group);
       let payload = GroupChangesPB {
         view_id: view_id.to_string(),
@@ -1341,4 +2145,5 @@ async fn handle_mut_group_result(view_id: &str, result: Option<DidMoveGroupRowRe
       notify_did_update_group_rows(changeset).await;
     }
   }
+  is_group_deleted
 }
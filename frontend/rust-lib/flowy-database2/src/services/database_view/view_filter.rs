@@ -67,4 +67,12 @@ impl FilterDelegate for DatabaseViewFilterDelegateImpl {
   async fn save_filters(&self, view_id: &str, filters: &[Filter]) {
     self.0.save_filters(view_id, filters).await
   }
+
+  fn get_user_email(&self) -> Option<String> {
+    self.0.get_user_email()
+  }
+
+  fn get_timezone_id(&self) -> Option<String> {
+    self.0.get_timezone_id()
+  }
 }
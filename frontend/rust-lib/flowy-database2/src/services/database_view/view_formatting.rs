@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::services::cell::CellCache;
+use crate::services::database_view::{
+  gen_handler_id, DatabaseViewChangedNotifier, DatabaseViewOperation,
+};
+use crate::services::formatting::{
+  FormattingController, FormattingDelegate, FormattingRule, FormattingTaskHandler,
+};
+use collab_database::fields::Field;
+use collab_database::rows::{Row, RowDetail, RowId};
+
+pub async fn make_formatting_controller(
+  view_id: &str,
+  delegate: Arc<dyn DatabaseViewOperation>,
+  notifier: DatabaseViewChangedNotifier,
+  cell_cache: CellCache,
+) -> Arc<FormattingController> {
+  let task_scheduler = delegate.get_task_scheduler();
+  let formatting_delegate = DatabaseViewFormattingDelegateImpl(delegate.clone());
+
+  let handler_id = gen_handler_id();
+  let formatting_controller = FormattingController::new(
+    view_id,
+    &handler_id,
+    formatting_delegate,
+    task_scheduler.clone(),
+    cell_cache,
+    notifier,
+  )
+  .await;
+  let formatting_controller = Arc::new(formatting_controller);
+  task_scheduler
+    .write()
+    .await
+    .register_handler(FormattingTaskHandler::new(
+      handler_id,
+      formatting_controller.clone(),
+    ));
+  formatting_controller
+}
+
+struct DatabaseViewFormattingDelegateImpl(Arc<dyn DatabaseViewOperation>);
+
+#[async_trait]
+impl FormattingDelegate for DatabaseViewFormattingDelegateImpl {
+  async fn get_fields(&self, view_id: &str, field_ids: Option<Vec<String>>) -> Vec<Field> {
+    self.0.get_fields(view_id, field_ids).await
+  }
+
+  async fn get_rows(&self, view_id: &str) -> Vec<Arc<Row>> {
+    let row_orders = self.0.get_all_row_orders(view_id).await;
+    self.0.get_all_rows(view_id, row_orders).await
+  }
+
+  async fn get_row(&self, view_id: &str, rows_id: &RowId) -> Option<(usize, Arc<RowDetail>)> {
+    self.0.get_row_detail(view_id, rows_id).await
+  }
+
+  async fn get_all_formatting_rules(&self, view_id: &str) -> Vec<FormattingRule> {
+    self.0.get_all_formatting_rules(view_id).await
+  }
+
+  async fn save_formatting_rules(&self, view_id: &str, rules: &[FormattingRule]) {
+    self.0.save_formatting_rules(view_id, rules).await
+  }
+
+  fn get_user_email(&self) -> Option<String> {
+    self.0.get_user_email()
+  }
+
+  fn get_timezone_id(&self) -> Option<String> {
+    self.0.get_timezone_id()
+  }
+}
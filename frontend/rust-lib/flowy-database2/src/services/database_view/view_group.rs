@@ -2,7 +2,8 @@ use async_trait::async_trait;
 use std::sync::Arc;
 
 use collab_database::fields::Field;
-use collab_database::rows::{Row, RowId};
+use collab_database::rows::{Row, RowCell, RowId};
+use collab_database::views::DatabaseLayout;
 
 use flowy_error::FlowyResult;
 
@@ -12,8 +13,9 @@ use crate::services::field::RowSingleCellData;
 use crate::services::filter::FilterController;
 use crate::services::group::{
   make_group_controller, GroupContextDelegate, GroupController, GroupControllerDelegate,
-  GroupSetting,
+  GroupSetting, GROUP_SETTING_INDEX, SUB_GROUP_SETTING_INDEX,
 };
+use crate::services::share::get_exported_cell;
 
 pub async fn new_group_controller(
   view_id: String,
@@ -21,13 +23,16 @@ pub async fn new_group_controller(
   filter_controller: Arc<FilterController>,
   grouping_field: Option<Field>,
 ) -> FlowyResult<Option<Box<dyn GroupController>>> {
-  if !delegate.get_layout_for_view(&view_id).await.is_board() {
+  // The board is always grouped while the grid is only grouped if a grouping field is set
+  let layout = delegate.get_layout_for_view(&view_id).await;
+  if !layout.is_board() && !matches!(layout, DatabaseLayout::Grid) {
     return Ok(None);
   }
 
   let controller_delegate = GroupControllerDelegateImpl {
     delegate: delegate.clone(),
     filter_controller,
+    setting_index: GROUP_SETTING_INDEX,
   };
 
   let grouping_field = match grouping_field {
@@ -43,7 +48,13 @@ pub async fn new_group_controller(
             .find(|field| field.id == setting.field_id)
             .cloned()
         })
-        .or_else(|| find_suitable_grouping_field(&fields))
+        .or_else(|| {
+          if layout.is_board() {
+            find_suitable_grouping_field(&fields)
+          } else {
+            None
+          }
+        })
     },
   };
 
@@ -55,19 +66,62 @@ pub async fn new_group_controller(
   Ok(controller)
 }
 
+/// Returns the controller of the sub groups, which groups the rows of each group by a second
+/// field. The sub grouping field is read from the group settings of the view, it must be
+/// different from the grouping field.
+pub async fn new_sub_group_controller(
+  view_id: String,
+  delegate: Arc<dyn DatabaseViewOperation>,
+  filter_controller: Arc<FilterController>,
+) -> FlowyResult<Option<Box<dyn GroupController>>> {
+  let layout = delegate.get_layout_for_view(&view_id).await;
+  if !layout.is_board() && !matches!(layout, DatabaseLayout::Grid) {
+    return Ok(None);
+  }
+
+  let settings = delegate.get_group_setting(&view_id).await;
+  let grouping_field_id = match settings.get(GROUP_SETTING_INDEX) {
+    None => return Ok(None),
+    Some(setting) => setting.field_id.clone(),
+  };
+
+  let controller_delegate = GroupControllerDelegateImpl {
+    delegate: delegate.clone(),
+    filter_controller,
+    setting_index: SUB_GROUP_SETTING_INDEX,
+  };
+
+  let sub_grouping_field = match settings.get(SUB_GROUP_SETTING_INDEX) {
+    None => None,
+    Some(setting) => delegate.get_field(&setting.field_id).await,
+  };
+
+  let controller = match sub_grouping_field {
+    Some(field) if field.id != grouping_field_id => {
+      Some(make_group_controller(&view_id, field, controller_delegate).await?)
+    },
+    _ => None,
+  };
+
+  Ok(controller)
+}
+
 pub(crate) struct GroupControllerDelegateImpl {
   delegate: Arc<dyn DatabaseViewOperation>,
   filter_controller: Arc<FilterController>,
+  /// The index of the [GroupSetting] read by the controller. The first setting of the view is
+  /// the one of the grouping field and the second one is the one of the sub grouping field.
+  setting_index: usize,
 }
 
 #[async_trait]
 impl GroupContextDelegate for GroupControllerDelegateImpl {
   async fn get_group_setting(&self, view_id: &str) -> Option<Arc<GroupSetting>> {
     let mut settings = self.delegate.get_group_setting(view_id).await;
-    if settings.is_empty() {
+    if settings.len() <= self.setting_index {
       None
     } else {
-      Some(Arc::new(settings.remove(0)))
+      Some(Arc::new(settings.remove(self.setting_index)))
     }
   }
 
@@ -134,7 +188,18 @@ pub(crate) async fn get_cells_for_field(
   if let Some(field) = delegate.get_field(field_id).await {
     let field_type = FieldType::from(field.field_type);
     if let Some(handler) = delegate.get_type_option_cell_handler(&field) {
-      let cells = delegate.get_cells_for_field(view_id, field_id).await;
+      let cells = if field_type.is_created_time() || field_type.is_last_edited_time() {
+        // The cells of the timestamp fields are not stored in the rows
+        let row_orders = delegate.get_all_row_orders(view_id).await;
+        delegate
+          .get_all_rows(view_id, row_orders)
+          .await
+          .iter()
+          .map(|row| RowCell::new(row.id.clone(), get_exported_cell(row, &field)))
+          .collect()
+      } else {
+        delegate.get_cells_for_field(view_id, field_id).await
+      };
       return cells
         .iter()
         .map(|row_cell| {
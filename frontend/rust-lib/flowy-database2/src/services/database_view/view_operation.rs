@@ -17,6 +17,7 @@ use crate::services::calculations::Calculation;
 use crate::services::field::TypeOptionCellDataHandler;
 use crate::services::field_settings::FieldSettings;
 use crate::services::filter::Filter;
+use crate::services::formatting::FormattingRule;
 use crate::services::group::GroupSetting;
 use crate::services::sort::Sort;
 
@@ -104,6 +105,11 @@ pub trait DatabaseViewOperation: Send + Sync + 'static {
 
   async fn save_filters(&self, view_id: &str, filters: &[Filter]);
 
+  /// Returns the formatting rules of the view, in the order of the fields of their conditions
+  async fn get_all_formatting_rules(&self, view_id: &str) -> Vec<FormattingRule>;
+
+  async fn save_formatting_rules(&self, view_id: &str, rules: &[FormattingRule]);
+
   async fn get_layout_setting(
     &self,
     view_id: &str,
@@ -122,6 +128,12 @@ pub trait DatabaseViewOperation: Send + Sync + 'static {
   /// Returns a `TaskDispatcher` used to poll a `Task`
   fn get_task_scheduler(&self) -> Arc<TokioRwLock<TaskDispatcher>>;
 
+  /// Returns the email of the current user
+  fn get_user_email(&self) -> Option<String>;
+
+  /// Returns the timezone of the current user
+  fn get_timezone_id(&self) -> Option<String>;
+
   fn get_type_option_cell_handler(
     &self,
     field: &Field,
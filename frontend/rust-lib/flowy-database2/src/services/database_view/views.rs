@@ -78,6 +78,7 @@ impl DatabaseViews {
     drop(editor_map);
 
     editor.initialize().await?;
+    editor.observe_day_change();
     Ok(editor)
   }
 
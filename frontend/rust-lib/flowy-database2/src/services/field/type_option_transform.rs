@@ -1,5 +1,8 @@
 use crate::entities::FieldType;
-use crate::services::field::TypeOptionTransform;
+use crate::services::field::{
+  AutoNumberTypeOption, EmailTypeOption, FormulaTypeOption, PersonTypeOption, PhoneTypeOption,
+  RatingTypeOption, RollupTypeOption, TypeOptionTransform,
+};
 use async_trait::async_trait;
 use collab_database::database::Database;
 use collab_database::fields::checkbox_type_option::CheckboxTypeOption;
@@ -136,5 +139,26 @@ fn get_type_option_transform_handler(
     FieldType::Media => {
       Box::new(MediaTypeOption::from(type_option_data)) as Box<dyn TypeOptionTransformHandler>
     },
+    FieldType::Formula => {
+      Box::new(FormulaTypeOption::from(type_option_data)) as Box<dyn TypeOptionTransformHandler>
+    },
+    FieldType::Rollup => {
+      Box::new(RollupTypeOption::from(type_option_data)) as Box<dyn TypeOptionTransformHandler>
+    },
+    FieldType::Rating => {
+      Box::new(RatingTypeOption::from(type_option_data)) as Box<dyn TypeOptionTransformHandler>
+    },
+    FieldType::Email => {
+      Box::new(EmailTypeOption::from(type_option_data)) as Box<dyn TypeOptionTransformHandler>
+    },
+    FieldType::Phone => {
+      Box::new(PhoneTypeOption::from(type_option_data)) as Box<dyn TypeOptionTransformHandler>
+    },
+    FieldType::AutoNumber => {
+      Box::new(AutoNumberTypeOption::from(type_option_data)) as Box<dyn TypeOptionTransformHandler>
+    },
+    FieldType::Person => {
+      Box::new(PersonTypeOption::from(type_option_data)) as Box<dyn TypeOptionTransformHandler>
+    },
   }
 }
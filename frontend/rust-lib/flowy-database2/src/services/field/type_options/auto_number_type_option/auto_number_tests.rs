@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod tests {
+  use collab_database::fields::TypeOptionData;
+  use collab_database::rows::{Cell, Cells, Row, RowId};
+
+  use crate::entities::{TextFilterConditionPB, TextFilterPB};
+  use crate::services::cell::CellDataChangeset;
+  use crate::services::field::{
+    AutoNumberCellData, AutoNumberTypeOption, TypeOptionCellDataFilter,
+  };
+
+  const FIELD_ID: &str = "auto_number";
+
+  fn row(id: &str, created_at: i64, number: Option<i64>) -> Row {
+    let mut cells = Cells::new();
+    if let Some(number) = number {
+      cells.insert(FIELD_ID.to_string(), Cell::from(AutoNumberCellData(number)));
+    }
+    Row {
+      id: RowId::from(id.to_string()),
+      database_id: "database".to_string(),
+      cells,
+      height: 60,
+      visibility: true,
+      modified_at: created_at,
+      created_at,
+    }
+  }
+
+  #[test]
+  fn auto_number_counter_test() {
+    let mut type_option = AutoNumberTypeOption::new("TASK-");
+    assert_eq!(type_option.take_next_number(), 1);
+    assert_eq!(type_option.take_next_number(), 2);
+    assert_eq!(type_option.format_number(142), "TASK-142");
+
+    // The counter never goes back
+    type_option.reserve_number(10);
+    type_option.reserve_number(5);
+    assert_eq!(type_option.take_next_number(), 11);
+
+    let data: TypeOptionData = type_option.into();
+    let type_option = AutoNumberTypeOption::from(data);
+    assert_eq!(type_option.prefix, "TASK-");
+    assert_eq!(type_option.next_number, 12);
+  }
+
+  #[test]
+  fn auto_number_rows_in_creation_order_test() {
+    let mut type_option = AutoNumberTypeOption::default();
+    let rows = vec![row("c", 30, None), row("a", 10, None), row("b", 20, None)];
+    let numbered_rows = type_option.number_rows(FIELD_ID, rows.iter());
+    assert_eq!(
+      numbered_rows,
+      vec![
+        (RowId::from("a".to_string()), AutoNumberCellData(1)),
+        (RowId::from("b".to_string()), AutoNumberCellData(2)),
+        (RowId::from("c".to_string()), AutoNumberCellData(3)),
+      ]
+    );
+    assert_eq!(type_option.next_number, 4);
+  }
+
+  #[test]
+  fn auto_number_merged_duplicates_test() {
+    // Two devices created a row with the number 5 while offline. The counter of the merged
+    // type option is the counter of one of the devices.
+    let mut type_option = AutoNumberTypeOption {
+      prefix: "".to_string(),
+      next_number: 5,
+    };
+    let rows = vec![
+      row("a", 10, Some(4)),
+      row("x", 40, Some(5)),
+      row("y", 30, Some(5)),
+    ];
+    // The earliest row keeps its number
+    let numbered_rows = type_option.number_rows(FIELD_ID, rows.iter());
+    assert_eq!(
+      numbered_rows,
+      vec![(RowId::from("x".to_string()), AutoNumberCellData(6))]
+    );
+    assert_eq!(type_option.next_number, 7);
+
+    // The numbers are unique after the merge
+    let mut numbers = rows
+      .iter()
+      .map(|row| {
+        numbered_rows
+          .iter()
+          .find(|(row_id, _)| row_id == &row.id)
+          .map(|(_, number)| number.0)
+          .unwrap_or_else(|| AutoNumberCellData::from(row.cells.get(FIELD_ID).unwrap()).0)
+      })
+      .collect::<Vec<_>>();
+    numbers.sort();
+    assert_eq!(numbers, vec![4, 5, 6]);
+
+    // Merging the same rows on the other device assigns the same numbers
+    let mut other_type_option = AutoNumberTypeOption {
+      prefix: "".to_string(),
+      next_number: 6,
+    };
+    let reversed_rows = rows.iter().rev().collect::<Vec<_>>();
+    assert_eq!(
+      other_type_option.number_rows(FIELD_ID, reversed_rows),
+      numbered_rows
+    );
+  }
+
+  #[test]
+  fn auto_number_rows_above_counter_test() {
+    // The numbers of the rows are always above the counter
+    let mut type_option = AutoNumberTypeOption::default();
+    let rows = vec![row("a", 10, Some(8)), row("b", 20, None)];
+    let numbered_rows = type_option.number_rows(FIELD_ID, rows.iter());
+    assert_eq!(
+      numbered_rows,
+      vec![(RowId::from("b".to_string()), AutoNumberCellData(9))]
+    );
+  }
+
+  #[test]
+  fn auto_number_cell_is_read_only_test() {
+    let type_option = AutoNumberTypeOption::default();
+    assert!(type_option.apply_changeset("3".to_string(), None).is_err());
+  }
+
+  #[test]
+  fn auto_number_filter_test() {
+    let type_option = AutoNumberTypeOption::new("TASK-");
+    let filter = TextFilterPB {
+      condition: TextFilterConditionPB::TextContains,
+      content: "task-14".to_string(),
+      ..Default::default()
+    };
+    assert!(type_option.apply_filter(&filter, &AutoNumberCellData(142)));
+    assert!(!type_option.apply_filter(&filter, &AutoNumberCellData(152)));
+
+    let filter = TextFilterPB {
+      condition: TextFilterConditionPB::TextIsEmpty,
+      content: "".to_string(),
+      ..Default::default()
+    };
+    assert!(type_option.apply_filter(&filter, &AutoNumberCellData(0)));
+  }
+}
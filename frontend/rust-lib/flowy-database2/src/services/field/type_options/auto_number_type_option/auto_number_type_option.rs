@@ -0,0 +1,307 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use collab::preclude::Any;
+use collab::util::AnyMapExt;
+use collab_database::database::Database;
+use collab_database::fields::{TypeOptionCellReader, TypeOptionData, TypeOptionDataBuilder};
+use collab_database::rows::{get_field_type_from_cell, new_cell_builder, Cell, Row, RowId};
+use collab_database::template::util::ToCellString;
+use flowy_error::{FlowyError, FlowyResult};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::info;
+
+use crate::entities::{FieldType, TextFilterPB};
+use crate::services::cell::{CellDataChangeset, CellDataDecoder};
+use crate::services::field::type_options::util::ProtobufStr;
+use crate::services::field::{
+  CellDataProtobufEncoder, TypeOption, TypeOptionCellData, TypeOptionCellDataCompare,
+  TypeOptionCellDataFilter, TypeOptionTransform, CELL_DATA,
+};
+use crate::services::sort::SortCondition;
+
+const AUTO_NUMBER_PREFIX: &str = "prefix";
+const AUTO_NUMBER_NEXT_NUMBER: &str = "next_number";
+
+/// The type option of the auto number field. Every row gets a unique number when it's created,
+/// for example `TASK-142` with the prefix `TASK-`.
+///
+/// The counter is stored in the type option, which is part of the database collab, so all the
+/// devices editing the database share it. The counter only grows, so the number of a deleted
+/// row is never reused.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoNumberTypeOption {
+  pub prefix: String,
+  pub next_number: i64,
+}
+
+impl Default for AutoNumberTypeOption {
+  fn default() -> Self {
+    Self {
+      prefix: "".to_string(),
+      next_number: 1,
+    }
+  }
+}
+
+impl AutoNumberTypeOption {
+  pub fn new(prefix: &str) -> Self {
+    Self {
+      prefix: prefix.to_string(),
+      ..Default::default()
+    }
+  }
+
+  /// Returns the number with the prefix of the type option.
+  pub fn format_number(&self, number: i64) -> String {
+    format!("{}{}", self.prefix, number)
+  }
+
+  /// Returns the next number and advances the counter.
+  pub fn take_next_number(&mut self) -> i64 {
+    let number = self.next_number.max(1);
+    self.next_number = number + 1;
+    number
+  }
+
+  /// Advances the counter past the `number`, so it's never given to another row.
+  pub fn reserve_number(&mut self, number: i64) {
+    self.next_number = self.next_number.max(number + 1);
+  }
+
+  /// Gives a number to the rows that don't have one or whose number is also used by another row,
+  /// which happens when rows created offline on different devices are merged. The counter is
+  /// advanced past the numbers of all the rows, so the numbers are never reused.
+  ///
+  /// The rows are visited in the order of creation and the earliest row keeps a duplicated
+  /// number. The order doesn't depend on the device, so all the devices that merge the same
+  /// rows assign the same numbers and the numbers stay unique after merging. Returns the
+  /// numbers that need to be written to the rows.
+  pub fn number_rows<'a>(
+    &mut self,
+    field_id: &str,
+    rows: impl IntoIterator<Item = &'a Row>,
+  ) -> Vec<(RowId, AutoNumberCellData)> {
+    let mut rows = rows
+      .into_iter()
+      .map(|row| {
+        let number = row
+          .cells
+          .get(field_id)
+          .filter(|cell| get_field_type_from_cell::<FieldType>(cell) == Some(FieldType::AutoNumber))
+          .map(AutoNumberCellData::from)
+          .unwrap_or_default();
+        (row, number)
+      })
+      .collect::<Vec<_>>();
+    rows.sort_by(|(left, _), (right, _)| {
+      left
+        .created_at
+        .cmp(&right.created_at)
+        .then_with(|| left.id.as_str().cmp(right.id.as_str()))
+    });
+
+    for (_, number) in rows.iter() {
+      self.reserve_number(number.0);
+    }
+
+    let mut used_numbers = HashSet::new();
+    let mut numbered_rows = vec![];
+    for (row, number) in rows {
+      if number.is_cell_empty() || !used_numbers.insert(number.0) {
+        numbered_rows.push((row.id.clone(), AutoNumberCellData(self.take_next_number())));
+      }
+    }
+    numbered_rows
+  }
+}
+
+impl From<TypeOptionData> for AutoNumberTypeOption {
+  fn from(data: TypeOptionData) -> Self {
+    Self {
+      prefix: data
+        .get_as::<String>(AUTO_NUMBER_PREFIX)
+        .unwrap_or_default(),
+      next_number: data
+        .get_as::<i64>(AUTO_NUMBER_NEXT_NUMBER)
+        .unwrap_or(1)
+        .max(1),
+    }
+  }
+}
+
+impl From<AutoNumberTypeOption> for TypeOptionData {
+  fn from(data: AutoNumberTypeOption) -> Self {
+    TypeOptionDataBuilder::from([
+      (AUTO_NUMBER_PREFIX.into(), Any::from(data.prefix)),
+      (
+        AUTO_NUMBER_NEXT_NUMBER.into(),
+        Any::BigInt(data.next_number),
+      ),
+    ])
+  }
+}
+
+impl TypeOptionCellReader for AutoNumberTypeOption {
+  fn json_cell(&self, cell: &Cell) -> Value {
+    let cell_data = AutoNumberCellData::from(cell);
+    if cell_data.is_cell_empty() {
+      Value::Null
+    } else {
+      json!(self.format_number(cell_data.0))
+    }
+  }
+
+  fn numeric_cell(&self, cell: &Cell) -> Option<f64> {
+    let cell_data = AutoNumberCellData::from(cell);
+    if cell_data.is_cell_empty() {
+      None
+    } else {
+      Some(cell_data.0 as f64)
+    }
+  }
+
+  fn convert_raw_cell_data(&self, cell_data: &str) -> String {
+    cell_data.to_string()
+  }
+}
+
+impl TypeOption for AutoNumberTypeOption {
+  type CellData = AutoNumberCellData;
+  type CellChangeset = String;
+  type CellProtobufType = ProtobufStr;
+  type CellFilter = TextFilterPB;
+}
+
+#[async_trait]
+impl TypeOptionTransform for AutoNumberTypeOption {
+  async fn transform_type_option(
+    &mut self,
+    view_id: &str,
+    field_id: &str,
+    old_type_option_field_type: FieldType,
+    _old_type_option_data: TypeOptionData,
+    _new_type_option_field_type: FieldType,
+    database: &mut Database,
+  ) {
+    // The cells of the other field types are replaced by new numbers. If the field was an auto
+    // number field before, its counter is kept, so the previous numbers are not reused.
+    let rows = database
+      .get_rows_for_view(view_id, 20, None)
+      .await
+      .filter_map(|result| async { result.ok() })
+      .collect::<Vec<Row>>()
+      .await;
+    let numbered_rows = self.number_rows(field_id, rows.iter());
+    info!(
+      "Transforming {:?} to AutoNumberTypeOption, numbering {} rows",
+      old_type_option_field_type,
+      numbered_rows.len()
+    );
+    for (row_id, cell_data) in numbered_rows {
+      database
+        .update_row(row_id, |row| {
+          row.update_cells(|cell| {
+            cell.insert(field_id, Cell::from(cell_data));
+          });
+        })
+        .await;
+    }
+  }
+}
+
+impl CellDataProtobufEncoder for AutoNumberTypeOption {
+  fn protobuf_encode(
+    &self,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    ProtobufStr::from(self.stringify_cell_data(cell_data))
+  }
+}
+
+impl CellDataDecoder for AutoNumberTypeOption {
+  fn stringify_cell_data(&self, cell_data: <Self as TypeOption>::CellData) -> String {
+    if cell_data.is_cell_empty() {
+      "".to_string()
+    } else {
+      self.format_number(cell_data.0)
+    }
+  }
+}
+
+impl CellDataChangeset for AutoNumberTypeOption {
+  fn apply_changeset(
+    &self,
+    _changeset: <Self as TypeOption>::CellChangeset,
+    _cell: Option<Cell>,
+  ) -> FlowyResult<(Cell, <Self as TypeOption>::CellData)> {
+    Err(FlowyError::invalid_data().with_context("The cell of an auto number field is read-only"))
+  }
+}
+
+impl TypeOptionCellDataFilter for AutoNumberTypeOption {
+  fn apply_filter(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    filter.is_visible(self.stringify_cell_data(*cell_data))
+  }
+}
+
+impl TypeOptionCellDataCompare for AutoNumberTypeOption {
+  fn apply_cmp(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+    sort_condition: SortCondition,
+  ) -> Ordering {
+    match (cell_data.is_cell_empty(), other_cell_data.is_cell_empty()) {
+      (true, true) => Ordering::Equal,
+      (true, false) => Ordering::Greater,
+      (false, true) => Ordering::Less,
+      (false, false) => sort_condition.evaluate_order(cell_data.0.cmp(&other_cell_data.0)),
+    }
+  }
+}
+
+/// The number of the row, without the prefix. A cell without number is empty.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AutoNumberCellData(pub i64);
+
+impl TypeOptionCellData for AutoNumberCellData {
+  fn is_cell_empty(&self) -> bool {
+    self.0 <= 0
+  }
+}
+
+impl From<&Cell> for AutoNumberCellData {
+  fn from(cell: &Cell) -> Self {
+    let number = cell
+      .get_as::<String>(CELL_DATA)
+      .and_then(|s| s.parse::<i64>().ok())
+      .unwrap_or_default();
+    Self(number.max(0))
+  }
+}
+
+impl From<AutoNumberCellData> for Cell {
+  fn from(data: AutoNumberCellData) -> Self {
+    let mut cell = new_cell_builder(FieldType::AutoNumber);
+    cell.insert(CELL_DATA.into(), data.to_cell_string().into());
+    cell
+  }
+}
+
+impl ToCellString for AutoNumberCellData {
+  fn to_cell_string(&self) -> String {
+    if self.is_cell_empty() {
+      "".to_string()
+    } else {
+      self.0.to_string()
+    }
+  }
+}
@@ -1,17 +1,18 @@
 use crate::entities::{ChecklistCellDataPB, ChecklistFilterPB, SelectOptionPB};
-use crate::services::cell::{CellDataChangeset, CellDataDecoder};
+use crate::services::cell::{CellDataChangeset, CellDataDecoder, CellProtobufBlobParser};
 use crate::services::field::checklist_filter::{checklist_from_options, ChecklistCellChangeset};
 use crate::services::field::{
   CellDataProtobufEncoder, TypeOption, TypeOptionCellDataCompare, TypeOptionCellDataFilter,
   TypeOptionTransform,
 };
 use crate::services::sort::SortCondition;
+use bytes::Bytes;
 use collab_database::fields::checklist_type_option::ChecklistTypeOption;
 use collab_database::fields::select_type_option::{SelectOption, SELECTION_IDS_SEPARATOR};
 use collab_database::rows::Cell;
 use collab_database::template::check_list_parse::ChecklistCellData;
 use collab_database::template::util::TypeOptionCellData;
-use flowy_error::FlowyResult;
+use flowy_error::{internal_error, FlowyResult};
 use std::cmp::Ordering;
 
 impl TypeOption for ChecklistTypeOption {
@@ -184,3 +185,12 @@ impl TypeOptionCellDataCompare for ChecklistTypeOption {
 }
 
 impl TypeOptionTransform for ChecklistTypeOption {}
+
+pub struct ChecklistCellDataParser();
+impl CellProtobufBlobParser for ChecklistCellDataParser {
+  type Object = ChecklistCellDataPB;
+
+  fn parser(bytes: &Bytes) -> FlowyResult<Self::Object> {
+    ChecklistCellDataPB::try_from(bytes.as_ref()).map_err(internal_error)
+  }
+}
@@ -1,9 +1,10 @@
-use crate::entities::{DateFilterConditionPB, DateFilterPB};
+use crate::entities::{DateFilterConditionPB, DateFilterPB, RelativeDateRangePB};
 use crate::services::cell::insert_date_cell;
 use crate::services::filter::PreFillCellsWithFilter;
 
 use bytes::Bytes;
-use chrono::{Duration, Local, NaiveDate, TimeZone};
+use chrono::{DateTime, Datelike, Duration, Local, Months, NaiveDate, TimeZone};
+use chrono_tz::Tz;
 use collab_database::fields::date_type_option::DateCellData;
 use collab_database::fields::Field;
 use collab_database::rows::Cell;
@@ -64,10 +65,110 @@ impl DateFilterPB {
       DateFilterConditionPB::DateStartIsNotEmpty | DateFilterConditionPB::DateEndIsNotEmpty => {
         DateFilterStrategy::NotEmpty
       },
+      DateFilterConditionPB::DateStartsInRelativeRange
+      | DateFilterConditionPB::DateEndsInRelativeRange => DateFilterStrategy::InRange {
+        start: self.start?,
+        end: self.end?,
+      },
     };
 
     Some(strategy)
   }
+
+  /// Returns the filter with the bounds of its relative range of dates if it's a relative filter,
+  /// which depends on the current date. The days of the range are the ones of the timezone of
+  /// the user, or of the device if the timezone is empty or unknown. The `start` of the returned
+  /// filter is the first second of the range and its `end` is the first second after the range.
+  pub fn with_relative_range(&self, timezone_id: &str, now: i64) -> Option<Self> {
+    if !self.condition.is_relative() {
+      return None;
+    }
+
+    let bounds = match timezone_id.parse::<Tz>() {
+      Ok(timezone) => timezone
+        .timestamp_opt(now, 0)
+        .single()
+        .and_then(|now| self.relative_range_bounds(now)),
+      Err(_) => Local
+        .timestamp_opt(now, 0)
+        .single()
+        .and_then(|now| self.relative_range_bounds(now)),
+    };
+
+    Some(Self {
+      start: bounds.map(|(start, _)| start),
+      end: bounds.map(|(_, end)| end),
+      ..self.clone()
+    })
+  }
+
+  fn relative_range_bounds<T: TimeZone>(&self, now: DateTime<T>) -> Option<(i64, i64)> {
+    let today = now.date_naive();
+    let this_monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    let this_month = today.with_day(1)?;
+    let this_year = NaiveDate::from_ymd_opt(today.year(), 1, 1)?;
+
+    // The first day of the range and the first day after the range
+    let (first_day, day_after) = match self.relative_range? {
+      RelativeDateRangePB::Today => (today, today + Duration::days(1)),
+      RelativeDateRangePB::Yesterday => (today - Duration::days(1), today),
+      RelativeDateRangePB::Tomorrow => (today + Duration::days(1), today + Duration::days(2)),
+      RelativeDateRangePB::ThisWeek => (this_monday, this_monday + Duration::weeks(1)),
+      RelativeDateRangePB::LastWeek => (this_monday - Duration::weeks(1), this_monday),
+      RelativeDateRangePB::NextWeek => (
+        this_monday + Duration::weeks(1),
+        this_monday + Duration::weeks(2),
+      ),
+      RelativeDateRangePB::ThisMonth => (this_month, this_month + Months::new(1)),
+      RelativeDateRangePB::LastMonth => (this_month - Months::new(1), this_month),
+      RelativeDateRangePB::NextMonth => (this_month + Months::new(1), this_month + Months::new(2)),
+      RelativeDateRangePB::ThisYear => (this_year, this_year + Months::new(12)),
+      RelativeDateRangePB::LastYear => (this_year - Months::new(12), this_year),
+      RelativeDateRangePB::NextYear => (this_year + Months::new(12), this_year + Months::new(24)),
+      RelativeDateRangePB::LastNDays => {
+        let num_of_days = self.num_of_days.filter(|num_of_days| *num_of_days > 0)?;
+        (
+          today - Duration::days(num_of_days - 1),
+          today + Duration::days(1),
+        )
+      },
+      RelativeDateRangePB::NextNDays => {
+        let num_of_days = self.num_of_days.filter(|num_of_days| *num_of_days > 0)?;
+        (today, today + Duration::days(num_of_days))
+      },
+    };
+
+    let timezone = now.timezone();
+    Some((
+      start_of_day(&timezone, first_day)?,
+      start_of_day(&timezone, day_after)?,
+    ))
+  }
+}
+
+/// Returns the timestamp of the start of the day after the current one in the timezone of the
+/// user, which is when the relative date filters must be evaluated again.
+pub fn next_day_timestamp(timezone_id: &str, now: i64) -> Option<i64> {
+  match timezone_id.parse::<Tz>() {
+    Ok(timezone) => {
+      let tomorrow = timezone.timestamp_opt(now, 0).single()?.date_naive() + Duration::days(1);
+      start_of_day(&timezone, tomorrow)
+    },
+    Err(_) => {
+      let tomorrow = Local.timestamp_opt(now, 0).single()?.date_naive() + Duration::days(1);
+      start_of_day(&Local, tomorrow)
+    },
+  }
+}
+
+/// Returns the timestamp of the first second of the day. Some timezones skip midnight when
+/// switching to the daylight saving time, in which case the day starts at 1am.
+fn start_of_day<T: TimeZone>(timezone: &T, date: NaiveDate) -> Option<i64> {
+  [0, 1]
+    .into_iter()
+    .filter_map(|hour| date.and_hms_opt(hour, 0, 0))
+    .find_map(|date_time| timezone.from_local_datetime(&date_time).earliest())
+    .map(|date_time| date_time.timestamp())
 }
 
 #[inline]
@@ -84,7 +185,15 @@ enum DateFilterStrategy {
   After(i64),
   OnOrBefore(i64),
   OnOrAfter(i64),
-  DateBetween { start: i64, end: i64 },
+  DateBetween {
+    start: i64,
+    end: i64,
+  },
+  /// The timestamps from `start` (included) to `end` (excluded)
+  InRange {
+    start: i64,
+    end: i64,
+  },
   Empty,
   NotEmpty,
 }
@@ -123,6 +232,9 @@ impl DateFilterStrategy {
         let expected_end_date = naive_date_from_timestamp(end);
         cell_date >= expected_start_date && cell_date <= expected_end_date
       }),
+      DateFilterStrategy::InRange { start, end } => {
+        cell_data.is_some_and(|timestamp| timestamp >= start && timestamp < end)
+      },
       DateFilterStrategy::Empty => match cell_data {
         None => true,
         Some(timestamp) if naive_date_from_timestamp(timestamp).is_none() => true,
@@ -174,9 +286,10 @@ impl PreFillCellsWithFilter for DateFilterPB {
             .single()
             .map(|date_time| date_time.timestamp())
         }),
-      DateFilterConditionPB::DateStartsBetween | DateFilterConditionPB::DateEndsBetween => {
-        self.start
-      },
+      DateFilterConditionPB::DateStartsBetween
+      | DateFilterConditionPB::DateEndsBetween
+      | DateFilterConditionPB::DateStartsInRelativeRange
+      | DateFilterConditionPB::DateEndsInRelativeRange => self.start,
       _ => None,
     };
 
@@ -205,7 +318,8 @@ impl CellProtobufBlobParser for DateCellDataParser {
 
 #[cfg(test)]
 mod tests {
-  use crate::entities::{DateFilterConditionPB, DateFilterPB};
+  use crate::entities::{DateFilterConditionPB, DateFilterPB, RelativeDateRangePB};
+  use crate::services::field::date_type_option::date_filter::next_day_timestamp;
   use collab_database::fields::date_type_option::DateCellData;
 
   fn to_cell_data(timestamp: Option<i64>, end_timestamp: Option<i64>) -> DateCellData {
@@ -223,6 +337,8 @@ mod tests {
       timestamp: Some(1668387885),
       end: None,
       start: None,
+      relative_range: None,
+      num_of_days: None,
     };
 
     for (start, end, is_visible) in [
@@ -241,6 +357,8 @@ mod tests {
       timestamp: None,
       end: None,
       start: None,
+      relative_range: None,
+      num_of_days: None,
     };
 
     for (start, end, is_visible) in [
@@ -262,6 +380,8 @@ mod tests {
       timestamp: Some(1668387885),
       start: None,
       end: None,
+      relative_range: None,
+      num_of_days: None,
     };
 
     for (start, end, is_visible) in [
@@ -282,6 +402,8 @@ mod tests {
       timestamp: Some(1668387885),
       start: None,
       end: None,
+      relative_range: None,
+      num_of_days: None,
     };
 
     for (start, end, is_visible) in [
@@ -301,6 +423,8 @@ mod tests {
       timestamp: Some(1668387885),
       start: None,
       end: None,
+      relative_range: None,
+      num_of_days: None,
     };
 
     for (start, end, is_visible) in [
@@ -322,6 +446,8 @@ mod tests {
       start: Some(1668272685), // 11/13
       end: Some(1668618285),   // 11/17
       timestamp: None,
+      relative_range: None,
+      num_of_days: None,
     };
 
     for (start, end, is_visible, msg) in [
@@ -342,6 +468,8 @@ mod tests {
       start: None,
       end: Some(1668618285), // 11/17
       timestamp: None,
+      relative_range: None,
+      num_of_days: None,
     };
 
     for (start, end, is_visible, msg) in [
@@ -365,6 +493,8 @@ mod tests {
       start: None,
       end: None,
       timestamp: None,
+      relative_range: None,
+      num_of_days: None,
     };
 
     for (start, end, is_visible) in [(None, None, true), (Some(123), None, false)] {
@@ -382,6 +512,8 @@ mod tests {
       timestamp: Some(1668359085), // 11/14
       end: None,
       start: None,
+      relative_range: None,
+      num_of_days: None,
     };
 
     for (start, end, is_visible, msg) in [
@@ -405,6 +537,8 @@ mod tests {
       timestamp: None,
       start: None,
       end: None,
+      relative_range: None,
+      num_of_days: None,
     };
 
     for (start, end, is_visible, msg) in [
@@ -421,6 +555,107 @@ mod tests {
     }
   }
 
+  #[test]
+  fn date_filter_relative_range_test() {
+    // Oct 15, 2024 09:00 PDT, a Tuesday
+    let now = 1729008000;
+    let timezone_id = "America/Los_Angeles";
+
+    for (relative_range, num_of_days, start, end, msg) in [
+      (
+        RelativeDateRangePB::Today,
+        None,
+        1728975600,
+        1729062000,
+        "10/15 - 10/16",
+      ),
+      (
+        RelativeDateRangePB::ThisWeek,
+        None,
+        1728889200,
+        1729494000,
+        "10/14 - 10/21",
+      ),
+      (
+        RelativeDateRangePB::ThisMonth,
+        None,
+        1727766000,
+        1730444400,
+        "10/01 - 11/01",
+      ),
+      (
+        RelativeDateRangePB::ThisYear,
+        None,
+        1704096000,
+        1735718400,
+        "01/01 - 01/01",
+      ),
+      (
+        RelativeDateRangePB::LastNDays,
+        Some(3),
+        1728802800,
+        1729062000,
+        "10/13 - 10/16",
+      ),
+    ] {
+      let filter = DateFilterPB {
+        condition: DateFilterConditionPB::DateStartsInRelativeRange,
+        relative_range: Some(relative_range),
+        num_of_days,
+        ..Default::default()
+      }
+      .with_relative_range(timezone_id, now)
+      .unwrap();
+      assert_eq!(filter.start, Some(start), "{msg}");
+      assert_eq!(filter.end, Some(end), "{msg}");
+    }
+  }
+
+  #[test]
+  fn date_filter_today_test() {
+    let filter = DateFilterPB {
+      condition: DateFilterConditionPB::DateStartsInRelativeRange,
+      relative_range: Some(RelativeDateRangePB::Today),
+      ..Default::default()
+    }
+    .with_relative_range("America/Los_Angeles", 1729008000)
+    .unwrap();
+
+    for (start, end, is_visible, msg) in [
+      (Some(1728975540), None, false, "10/14/2024 23:59 PDT"),
+      (Some(1728975600), None, true, "10/15/2024 00:00 PDT"),
+      (Some(1729061940), None, true, "10/15/2024 23:59 PDT"),
+      (Some(1729062000), None, false, "10/16/2024 00:00 PDT"),
+      (None, None, false, "empty"),
+    ] {
+      assert_eq!(
+        filter.is_visible(&to_cell_data(start, end)).unwrap_or(true),
+        is_visible,
+        "{msg}"
+      );
+    }
+
+    // The filter is ignored without the number of days
+    let filter = DateFilterPB {
+      condition: DateFilterConditionPB::DateStartsInRelativeRange,
+      relative_range: Some(RelativeDateRangePB::NextNDays),
+      ..Default::default()
+    }
+    .with_relative_range("America/Los_Angeles", 1729008000)
+    .unwrap();
+    assert!(filter
+      .is_visible(&to_cell_data(Some(1729008000), None))
+      .is_none());
+  }
+
+  #[test]
+  fn next_day_timestamp_test() {
+    assert_eq!(
+      next_day_timestamp("America/Los_Angeles", 1729008000),
+      Some(1729062000)
+    );
+  }
+
   // #[test]
   // fn timezoned_filter_test() {
   //   let filter = DateFilterPB {
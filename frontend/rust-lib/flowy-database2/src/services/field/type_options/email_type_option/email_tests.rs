@@ -0,0 +1,36 @@
+#[cfg(test)]
+mod tests {
+  use crate::services::cell::CellDataChangeset;
+  use crate::services::field::{normalize_email, EmailTypeOption};
+
+  #[test]
+  fn normalize_email_test() {
+    assert_eq!(
+      normalize_email(" Lucas@AppFlowy.IO "),
+      Some("Lucas@appflowy.io".to_string())
+    );
+    assert_eq!(
+      normalize_email("mailto:hello@appflowy.io"),
+      Some("hello@appflowy.io".to_string())
+    );
+    assert_eq!(normalize_email("hello"), None);
+    assert_eq!(normalize_email("hello@appflowy"), None);
+    assert_eq!(normalize_email("hello world@appflowy.io"), None);
+  }
+
+  #[test]
+  fn email_changeset_test() {
+    let type_option = EmailTypeOption;
+    let (_, cell_data) = type_option
+      .apply_changeset("Hello@AppFlowy.io".to_string(), None)
+      .unwrap();
+    assert_eq!(cell_data.0, "Hello@appflowy.io");
+
+    let (_, cell_data) = type_option.apply_changeset("".to_string(), None).unwrap();
+    assert!(cell_data.0.is_empty());
+
+    assert!(type_option
+      .apply_changeset("not an email".to_string(), None)
+      .is_err());
+  }
+}
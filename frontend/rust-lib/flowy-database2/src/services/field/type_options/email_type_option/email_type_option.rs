@@ -0,0 +1,252 @@
+use std::cmp::Ordering;
+
+use async_trait::async_trait;
+use collab::util::AnyMapExt;
+use collab_database::database::Database;
+use collab_database::fields::{Field, TypeOptionCellReader, TypeOptionData, TypeOptionDataBuilder};
+use collab_database::rows::{new_cell_builder, Cell};
+use collab_database::template::util::ToCellString;
+use fancy_regex::Regex;
+use flowy_error::{FlowyError, FlowyResult};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::info;
+
+use crate::entities::{FieldType, TextFilterPB};
+use crate::services::cell::{CellDataChangeset, CellDataDecoder};
+use crate::services::field::type_options::util::ProtobufStr;
+use crate::services::field::{
+  CellDataProtobufEncoder, TypeOption, TypeOptionCellData, TypeOptionCellDataCompare,
+  TypeOptionCellDataFilter, TypeOptionTransform, CELL_DATA,
+};
+use crate::services::sort::SortCondition;
+
+lazy_static! {
+  static ref EMAIL_REGEX: Regex = Regex::new(
+    r"^[A-Za-z0-9.!#$%&'*+/=?^_`{|}~-]+@[A-Za-z0-9](?:[A-Za-z0-9-]*[A-Za-z0-9])?(?:\.[A-Za-z0-9](?:[A-Za-z0-9-]*[A-Za-z0-9])?)+$"
+  )
+  .unwrap();
+}
+
+/// Normalizes the email address: the whitespaces and the `mailto:` prefix are removed and the
+/// domain is lowercased. Returns None if the content is not a valid email address.
+pub fn normalize_email(content: &str) -> Option<String> {
+  let content = content.trim();
+  let content = match content.get(..7) {
+    Some(prefix) if prefix.eq_ignore_ascii_case("mailto:") => content[7..].trim(),
+    _ => content,
+  };
+  if !EMAIL_REGEX.is_match(content).unwrap_or(false) {
+    return None;
+  }
+
+  let (local, domain) = content.rsplit_once('@')?;
+  Some(format!("{}@{}", local, domain.to_lowercase()))
+}
+
+/// The type option of the email field. The cells only contain valid email addresses, see
+/// [normalize_email].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EmailTypeOption;
+
+impl From<TypeOptionData> for EmailTypeOption {
+  fn from(_data: TypeOptionData) -> Self {
+    Self
+  }
+}
+
+impl From<EmailTypeOption> for TypeOptionData {
+  fn from(_data: EmailTypeOption) -> Self {
+    TypeOptionDataBuilder::new()
+  }
+}
+
+impl TypeOptionCellReader for EmailTypeOption {
+  fn json_cell(&self, cell: &Cell) -> Value {
+    let cell_data = EmailCellData::from(cell);
+    if cell_data.is_cell_empty() {
+      Value::Null
+    } else {
+      json!(cell_data.0)
+    }
+  }
+
+  fn numeric_cell(&self, _cell: &Cell) -> Option<f64> {
+    None
+  }
+
+  fn convert_raw_cell_data(&self, cell_data: &str) -> String {
+    normalize_email(cell_data).unwrap_or_default()
+  }
+}
+
+impl TypeOption for EmailTypeOption {
+  type CellData = EmailCellData;
+  type CellChangeset = EmailCellChangeset;
+  type CellProtobufType = ProtobufStr;
+  type CellFilter = TextFilterPB;
+}
+
+#[async_trait]
+impl TypeOptionTransform for EmailTypeOption {
+  async fn transform_type_option(
+    &mut self,
+    view_id: &str,
+    field_id: &str,
+    old_type_option_field_type: FieldType,
+    _old_type_option_data: TypeOptionData,
+    _new_type_option_field_type: FieldType,
+    database: &mut Database,
+  ) {
+    match old_type_option_field_type {
+      FieldType::RichText | FieldType::URL => {
+        let rows = database
+          .get_cells_for_field(view_id, field_id)
+          .await
+          .into_iter()
+          .filter_map(|row| row.cell.map(|cell| (row.row_id, cell)))
+          .collect::<Vec<_>>();
+
+        info!(
+          "Transforming {:?} to EmailTypeOption, updating {} row's cell content",
+          old_type_option_field_type,
+          rows.len()
+        );
+        for (row_id, cell_data) in rows {
+          let content = cell_data.get_as::<String>(CELL_DATA).unwrap_or_default();
+          if let Some(email) = normalize_email(&content) {
+            database
+              .update_row(row_id, |row| {
+                row.update_cells(|cell| {
+                  cell.insert(field_id, Cell::from(EmailCellData(email)));
+                });
+              })
+              .await;
+          }
+        }
+      },
+      _ => {
+        // do nothing
+      },
+    }
+  }
+}
+
+impl CellDataProtobufEncoder for EmailTypeOption {
+  fn protobuf_encode(
+    &self,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    ProtobufStr::from(cell_data.0)
+  }
+}
+
+impl CellDataDecoder for EmailTypeOption {
+  fn decode_cell_with_transform(
+    &self,
+    cell: &Cell,
+    from_field_type: FieldType,
+    _field: &Field,
+  ) -> Option<<Self as TypeOption>::CellData> {
+    match from_field_type {
+      FieldType::RichText | FieldType::URL => {
+        let content = cell.get_as::<String>(CELL_DATA)?;
+        normalize_email(&content).map(EmailCellData)
+      },
+      _ => None,
+    }
+  }
+
+  fn stringify_cell_data(&self, cell_data: <Self as TypeOption>::CellData) -> String {
+    cell_data.0
+  }
+}
+
+/// The email address of the cell. An empty changeset clears the cell.
+pub type EmailCellChangeset = String;
+
+impl CellDataChangeset for EmailTypeOption {
+  fn apply_changeset(
+    &self,
+    changeset: <Self as TypeOption>::CellChangeset,
+    _cell: Option<Cell>,
+  ) -> FlowyResult<(Cell, <Self as TypeOption>::CellData)> {
+    let cell_data = if changeset.trim().is_empty() {
+      EmailCellData::default()
+    } else {
+      let email = normalize_email(&changeset).ok_or_else(|| {
+        FlowyError::email_format().with_context(format!("{} is not a valid email", changeset))
+      })?;
+      EmailCellData(email)
+    };
+    Ok((Cell::from(cell_data.clone()), cell_data))
+  }
+}
+
+impl TypeOptionCellDataFilter for EmailTypeOption {
+  fn apply_filter(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    filter.is_visible(cell_data)
+  }
+}
+
+impl TypeOptionCellDataCompare for EmailTypeOption {
+  fn apply_cmp(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+    sort_condition: SortCondition,
+  ) -> Ordering {
+    match (cell_data.is_cell_empty(), other_cell_data.is_cell_empty()) {
+      (true, true) => Ordering::Equal,
+      (true, false) => Ordering::Greater,
+      (false, true) => Ordering::Less,
+      (false, false) => {
+        let order = cell_data
+          .0
+          .to_lowercase()
+          .cmp(&other_cell_data.0.to_lowercase());
+        sort_condition.evaluate_order(order)
+      },
+    }
+  }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct EmailCellData(pub String);
+
+impl TypeOptionCellData for EmailCellData {
+  fn is_cell_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+}
+
+impl From<&Cell> for EmailCellData {
+  fn from(cell: &Cell) -> Self {
+    Self(cell.get_as(CELL_DATA).unwrap_or_default())
+  }
+}
+
+impl From<EmailCellData> for Cell {
+  fn from(data: EmailCellData) -> Self {
+    let mut cell = new_cell_builder(FieldType::Email);
+    cell.insert(CELL_DATA.into(), data.0.into());
+    cell
+  }
+}
+
+impl ToCellString for EmailCellData {
+  fn to_cell_string(&self) -> String {
+    self.0.clone()
+  }
+}
+
+impl AsRef<str> for EmailCellData {
+  fn as_ref(&self) -> &str {
+    self.0.as_str()
+  }
+}
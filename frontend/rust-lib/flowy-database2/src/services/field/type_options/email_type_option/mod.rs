@@ -0,0 +1,5 @@
+#![allow(clippy::module_inception)]
+mod email_tests;
+mod email_type_option;
+
+pub use email_type_option::*;
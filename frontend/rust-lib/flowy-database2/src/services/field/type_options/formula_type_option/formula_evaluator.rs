@@ -0,0 +1,170 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+use collab_database::fields::date_type_option::DateCellData;
+use collab_database::fields::Field;
+use collab_database::rows::{Cell, Row};
+use tracing::warn;
+
+use crate::entities::{CheckboxCellDataPB, FieldType};
+use crate::services::cell::stringify_cell;
+use crate::services::field::{
+  FormulaCellData, FormulaContext, FormulaError, FormulaTypeOption, FormulaValue, TypeOptionCellExt,
+};
+
+/// Returns true if the field is referenced by any formula field. Formula fields that reference
+/// other formula fields are recomputed along with them, so direct references are enough.
+pub fn is_referenced_by_formula(fields: &[Field], field_id: &str) -> bool {
+  let field_name = match fields.iter().find(|field| field.id == field_id) {
+    Some(field) => &field.name,
+    None => return false,
+  };
+  formula_fields(fields).any(|(_, type_option)| {
+    type_option
+      .referenced_field_names()
+      .iter()
+      .any(|name| name == field_name)
+  })
+}
+
+pub fn has_formula_field(fields: &[Field]) -> bool {
+  formula_fields(fields).next().is_some()
+}
+
+fn formula_fields(fields: &[Field]) -> impl Iterator<Item = (&Field, FormulaTypeOption)> {
+  fields.iter().filter_map(|field| {
+    if FieldType::from(field.field_type).is_formula() {
+      let type_option = field
+        .get_type_option::<FormulaTypeOption>(FieldType::Formula)
+        .unwrap_or_default();
+      Some((field, type_option))
+    } else {
+      None
+    }
+  })
+}
+
+/// Computes all the formula cells of the row. Only the cells whose value differs from the one
+/// stored in the row are returned.
+pub fn compute_formula_cells(row: &Row, fields: &[Field]) -> HashMap<String, Cell> {
+  let evaluator = FormulaRowEvaluator::new(row, fields);
+  formula_fields(fields)
+    .filter_map(|(field, _)| {
+      let value = evaluator.evaluate_formula(field).unwrap_or_else(|err| {
+        warn!(
+          "[Formula]: failed to evaluate field:{} in row:{}, error: {}",
+          field.id, row.id, err
+        );
+        FormulaValue::Empty
+      });
+      let cell_data = FormulaCellData(value.to_string());
+      let old_cell_data = row
+        .cells
+        .get(&field.id)
+        .map(FormulaCellData::from)
+        .unwrap_or_default();
+      if old_cell_data == cell_data && row.cells.contains_key(&field.id) {
+        None
+      } else {
+        Some((field.id.clone(), Cell::from(cell_data)))
+      }
+    })
+    .collect()
+}
+
+struct FormulaRowEvaluator<'a> {
+  row: &'a Row,
+  field_by_name: HashMap<&'a str, &'a Field>,
+  computed: RefCell<HashMap<String, FormulaValue>>,
+  evaluating: RefCell<HashSet<String>>,
+}
+
+impl<'a> FormulaRowEvaluator<'a> {
+  fn new(row: &'a Row, fields: &'a [Field]) -> Self {
+    let mut field_by_name = HashMap::new();
+    for field in fields {
+      field_by_name.entry(field.name.as_str()).or_insert(field);
+    }
+    Self {
+      row,
+      field_by_name,
+      computed: RefCell::new(HashMap::new()),
+      evaluating: RefCell::new(HashSet::new()),
+    }
+  }
+
+  fn evaluate_formula(&self, field: &Field) -> Result<FormulaValue, FormulaError> {
+    if let Some(value) = self.computed.borrow().get(&field.id) {
+      return Ok(value.clone());
+    }
+
+    if !self.evaluating.borrow_mut().insert(field.id.clone()) {
+      return Err(FormulaError::new(format!(
+        "Circular reference to '{}'",
+        field.name
+      )));
+    }
+    let result = field
+      .get_type_option::<FormulaTypeOption>(FieldType::Formula)
+      .unwrap_or_default()
+      .parse_expression()
+      .and_then(|expr| expr.evaluate(self));
+    self.evaluating.borrow_mut().remove(&field.id);
+
+    let value = result?;
+    self
+      .computed
+      .borrow_mut()
+      .insert(field.id.clone(), value.clone());
+    Ok(value)
+  }
+}
+
+impl FormulaContext for FormulaRowEvaluator<'_> {
+  fn field_value(&self, field_name: &str) -> Result<FormulaValue, FormulaError> {
+    let field = self
+      .field_by_name
+      .get(field_name)
+      .ok_or_else(|| FormulaError::new(format!("Field '{}' does not exist", field_name)))?;
+
+    let field_type = FieldType::from(field.field_type);
+    if field_type.is_formula() {
+      return self.evaluate_formula(field);
+    }
+
+    let value = match field_type {
+      FieldType::CreatedTime => FormulaValue::Date(self.row.created_at),
+      FieldType::LastEditedTime => FormulaValue::Date(self.row.modified_at),
+      _ => match self.row.cells.get(&field.id) {
+        None => FormulaValue::Empty,
+        Some(cell) => cell_to_formula_value(cell, field, field_type),
+      },
+    };
+    Ok(value)
+  }
+}
+
+fn cell_to_formula_value(cell: &Cell, field: &Field, field_type: FieldType) -> FormulaValue {
+  match field_type {
+    FieldType::Number | FieldType::Time | FieldType::Rating | FieldType::AutoNumber => {
+      TypeOptionCellExt::new(field, None)
+        .get_type_option_cell_data_handler()
+        .and_then(|handler| handler.handle_numeric_cell(cell))
+        .map(FormulaValue::Number)
+        .unwrap_or(FormulaValue::Empty)
+    },
+    FieldType::DateTime => DateCellData::from(cell)
+      .timestamp
+      .map(FormulaValue::Date)
+      .unwrap_or(FormulaValue::Empty),
+    FieldType::Checkbox => FormulaValue::Bool(CheckboxCellDataPB::from(cell).is_checked),
+    _ => {
+      let s = stringify_cell(cell, field);
+      if s.is_empty() {
+        FormulaValue::Empty
+      } else {
+        FormulaValue::Text(s)
+      }
+    },
+  }
+}
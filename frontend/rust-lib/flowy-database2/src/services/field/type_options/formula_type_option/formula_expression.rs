@@ -0,0 +1,816 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+use chrono::{DateTime, Duration, Months, Utc};
+
+/// The error produced when a formula expression can't be parsed or evaluated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormulaError(pub String);
+
+impl FormulaError {
+  pub fn new<T: ToString>(msg: T) -> Self {
+    Self(msg.to_string())
+  }
+}
+
+impl Display for FormulaError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.write_str(&self.0)
+  }
+}
+
+impl std::error::Error for FormulaError {}
+
+/// The value produced by evaluating a [FormulaExpr].
+///
+/// Dates are represented as unix timestamps in seconds.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormulaValue {
+  Empty,
+  Number(f64),
+  Text(String),
+  Bool(bool),
+  Date(i64),
+}
+
+impl FormulaValue {
+  pub fn is_empty(&self) -> bool {
+    match self {
+      FormulaValue::Empty => true,
+      FormulaValue::Text(s) => s.is_empty(),
+      _ => false,
+    }
+  }
+
+  pub fn as_number(&self) -> Option<f64> {
+    match self {
+      FormulaValue::Number(n) => Some(*n),
+      FormulaValue::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+      FormulaValue::Text(s) => s.trim().parse::<f64>().ok(),
+      FormulaValue::Empty | FormulaValue::Date(_) => None,
+    }
+  }
+
+  pub fn is_truthy(&self) -> bool {
+    match self {
+      FormulaValue::Empty => false,
+      FormulaValue::Number(n) => *n != 0.0,
+      FormulaValue::Text(s) => !s.is_empty(),
+      FormulaValue::Bool(b) => *b,
+      FormulaValue::Date(_) => true,
+    }
+  }
+}
+
+impl Display for FormulaValue {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      FormulaValue::Empty => Ok(()),
+      FormulaValue::Number(n) => f.write_str(&format_number(*n)),
+      FormulaValue::Text(s) => f.write_str(s),
+      FormulaValue::Bool(b) => f.write_str(if *b { "true" } else { "false" }),
+      FormulaValue::Date(timestamp) => match DateTime::<Utc>::from_timestamp(*timestamp, 0) {
+        Some(date) if timestamp % SECONDS_PER_DAY == 0 => {
+          f.write_str(&date.format("%Y-%m-%d").to_string())
+        },
+        Some(date) => f.write_str(&date.format("%Y-%m-%d %H:%M").to_string()),
+        None => Ok(()),
+      },
+    }
+  }
+}
+
+pub(crate) fn format_number(n: f64) -> String {
+  if n.fract() == 0.0 && n.abs() < 1e15 {
+    format!("{}", n as i64)
+  } else {
+    let s = format!("{:.10}", n);
+    s.trim_end_matches('0').trim_end_matches('.').to_string()
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+  Neg,
+  Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+  Add,
+  Sub,
+  Mul,
+  Div,
+  Rem,
+  Concat,
+  Eq,
+  NotEq,
+  Lt,
+  LtEq,
+  Gt,
+  GtEq,
+  And,
+  Or,
+}
+
+/// The parsed form of a formula expression.
+///
+/// Fields are referenced by name using braces, e.g. `{Price} * {Quantity}`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormulaExpr {
+  Number(f64),
+  Text(String),
+  Bool(bool),
+  Field(String),
+  Unary {
+    op: UnaryOp,
+    expr: Box<FormulaExpr>,
+  },
+  Binary {
+    op: BinaryOp,
+    left: Box<FormulaExpr>,
+    right: Box<FormulaExpr>,
+  },
+  Call {
+    name: String,
+    args: Vec<FormulaExpr>,
+  },
+}
+
+/// Provides the values of the fields referenced by a formula.
+pub trait FormulaContext {
+  fn field_value(&self, field_name: &str) -> Result<FormulaValue, FormulaError>;
+}
+
+impl FormulaExpr {
+  /// Returns the names of all the fields referenced by the expression, without duplicates.
+  pub fn referenced_fields(&self) -> Vec<String> {
+    let mut names = vec![];
+    self.collect_fields(&mut names);
+    names
+  }
+
+  fn collect_fields(&self, names: &mut Vec<String>) {
+    match self {
+      FormulaExpr::Field(name) => {
+        if !names.contains(name) {
+          names.push(name.clone());
+        }
+      },
+      FormulaExpr::Unary { expr, .. } => expr.collect_fields(names),
+      FormulaExpr::Binary { left, right, .. } => {
+        left.collect_fields(names);
+        right.collect_fields(names);
+      },
+      FormulaExpr::Call { args, .. } => args.iter().for_each(|arg| arg.collect_fields(names)),
+      FormulaExpr::Number(_) | FormulaExpr::Text(_) | FormulaExpr::Bool(_) => {},
+    }
+  }
+
+  pub fn evaluate(&self, ctx: &dyn FormulaContext) -> Result<FormulaValue, FormulaError> {
+    match self {
+      FormulaExpr::Number(n) => Ok(FormulaValue::Number(*n)),
+      FormulaExpr::Text(s) => Ok(FormulaValue::Text(s.clone())),
+      FormulaExpr::Bool(b) => Ok(FormulaValue::Bool(*b)),
+      FormulaExpr::Field(name) => ctx.field_value(name),
+      FormulaExpr::Unary { op, expr } => {
+        let value = expr.evaluate(ctx)?;
+        match op {
+          UnaryOp::Neg => match value {
+            FormulaValue::Empty => Ok(FormulaValue::Empty),
+            value => value
+              .as_number()
+              .map(|n| FormulaValue::Number(-n))
+              .ok_or_else(|| FormulaError::new("Can't negate a non-numeric value")),
+          },
+          UnaryOp::Not => Ok(FormulaValue::Bool(!value.is_truthy())),
+        }
+      },
+      FormulaExpr::Binary { op, left, right } => match op {
+        // Short-circuit the logical operators
+        BinaryOp::And => {
+          let left = left.evaluate(ctx)?;
+          if !left.is_truthy() {
+            return Ok(FormulaValue::Bool(false));
+          }
+          Ok(FormulaValue::Bool(right.evaluate(ctx)?.is_truthy()))
+        },
+        BinaryOp::Or => {
+          let left = left.evaluate(ctx)?;
+          if left.is_truthy() {
+            return Ok(FormulaValue::Bool(true));
+          }
+          Ok(FormulaValue::Bool(right.evaluate(ctx)?.is_truthy()))
+        },
+        _ => eval_binary(*op, left.evaluate(ctx)?, right.evaluate(ctx)?),
+      },
+      FormulaExpr::Call { name, args } => eval_call(name, args, ctx),
+    }
+  }
+}
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Converts a number of days, which may be fractional, to seconds
+fn days_to_seconds(days: f64) -> Result<i64, FormulaError> {
+  to_i64(days * SECONDS_PER_DAY as f64).ok_or_else(|| FormulaError::new("Date out of range"))
+}
+
+/// Truncates the number to an integer. Returns None if the number is not finite or doesn't fit
+/// in an i64.
+fn to_i64(n: f64) -> Option<i64> {
+  let n = n.trunc();
+  if n.is_finite() && n >= i64::MIN as f64 && n < i64::MAX as f64 {
+    Some(n as i64)
+  } else {
+    None
+  }
+}
+
+fn eval_binary(
+  op: BinaryOp,
+  left: FormulaValue,
+  right: FormulaValue,
+) -> Result<FormulaValue, FormulaError> {
+  use FormulaValue::*;
+  match op {
+    BinaryOp::Add => match (left, right) {
+      (Empty, Empty) => Ok(Empty),
+      (Date(date), Number(days)) | (Number(days), Date(date)) => {
+        let seconds = days_to_seconds(days)?;
+        date
+          .checked_add(seconds)
+          .map(Date)
+          .ok_or_else(|| FormulaError::new("Date out of range"))
+      },
+      (left @ Text(_), right) | (left, right @ Text(_)) => Ok(Text(format!("{}{}", left, right))),
+      (left, right) => numeric_op(&left, &right, |a, b| Ok(a + b)),
+    },
+    BinaryOp::Sub => match (left, right) {
+      (Empty, Empty) => Ok(Empty),
+      (Date(date), Number(days)) => {
+        let seconds = days_to_seconds(days)?;
+        date
+          .checked_sub(seconds)
+          .map(Date)
+          .ok_or_else(|| FormulaError::new("Date out of range"))
+      },
+      (Date(a), Date(b)) => Ok(Number((a as f64 - b as f64) / SECONDS_PER_DAY as f64)),
+      (left, right) => numeric_op(&left, &right, |a, b| Ok(a - b)),
+    },
+    BinaryOp::Mul => numeric_op(&left, &right, |a, b| Ok(a * b)),
+    BinaryOp::Div => numeric_op(&left, &right, |a, b| {
+      if b == 0.0 {
+        Err(FormulaError::new("Division by zero"))
+      } else {
+        Ok(a / b)
+      }
+    }),
+    BinaryOp::Rem => numeric_op(&left, &right, |a, b| {
+      if b == 0.0 {
+        Err(FormulaError::new("Division by zero"))
+      } else {
+        Ok(a % b)
+      }
+    }),
+    BinaryOp::Concat => Ok(Text(format!("{}{}", left, right))),
+    BinaryOp::Eq => Ok(Bool(compare_values(&left, &right) == Some(Ordering::Equal))),
+    BinaryOp::NotEq => Ok(Bool(compare_values(&left, &right) != Some(Ordering::Equal))),
+    BinaryOp::Lt => Ok(Bool(compare_values(&left, &right) == Some(Ordering::Less))),
+    BinaryOp::LtEq => Ok(Bool(matches!(
+      compare_values(&left, &right),
+      Some(Ordering::Less | Ordering::Equal)
+    ))),
+    BinaryOp::Gt => Ok(Bool(
+      compare_values(&left, &right) == Some(Ordering::Greater),
+    )),
+    BinaryOp::GtEq => Ok(Bool(matches!(
+      compare_values(&left, &right),
+      Some(Ordering::Greater | Ordering::Equal)
+    ))),
+    BinaryOp::And | BinaryOp::Or => unreachable!("logical operators are short-circuited"),
+  }
+}
+
+/// Applies a numeric operator. Empty operands are treated as zero.
+fn numeric_op<F>(
+  left: &FormulaValue,
+  right: &FormulaValue,
+  f: F,
+) -> Result<FormulaValue, FormulaError>
+where
+  F: Fn(f64, f64) -> Result<f64, FormulaError>,
+{
+  let to_number = |value: &FormulaValue| match value {
+    FormulaValue::Empty => Ok(0.0),
+    value => value
+      .as_number()
+      .ok_or_else(|| FormulaError::new(format!("Expected a number, found '{}'", value))),
+  };
+  f(to_number(left)?, to_number(right)?).map(FormulaValue::Number)
+}
+
+fn compare_values(left: &FormulaValue, right: &FormulaValue) -> Option<Ordering> {
+  use FormulaValue::*;
+  match (left, right) {
+    (Empty, Empty) => Some(Ordering::Equal),
+    (Date(a), Date(b)) => Some(a.cmp(b)),
+    (Bool(a), Bool(b)) => Some(a.cmp(b)),
+    (Number(a), Number(b)) => a.partial_cmp(b),
+    // Compare numerically when a number is compared with a numeric text
+    (Number(_), _) | (_, Number(_)) => match (left.as_number(), right.as_number()) {
+      (Some(a), Some(b)) => a.partial_cmp(&b),
+      _ => Some(left.to_string().cmp(&right.to_string())),
+    },
+    (left, right) => Some(left.to_string().cmp(&right.to_string())),
+  }
+}
+
+fn eval_call(
+  name: &str,
+  args: &[FormulaExpr],
+  ctx: &dyn FormulaContext,
+) -> Result<FormulaValue, FormulaError> {
+  let expect_args = |min: usize, max: usize| -> Result<(), FormulaError> {
+    if args.len() < min || args.len() > max {
+      Err(FormulaError::new(format!(
+        "Function '{}' received {} argument(s)",
+        name,
+        args.len()
+      )))
+    } else {
+      Ok(())
+    }
+  };
+
+  match name {
+    "if" => {
+      expect_args(2, 3)?;
+      if args[0].evaluate(ctx)?.is_truthy() {
+        args[1].evaluate(ctx)
+      } else if let Some(otherwise) = args.get(2) {
+        otherwise.evaluate(ctx)
+      } else {
+        Ok(FormulaValue::Empty)
+      }
+    },
+    "len" => {
+      expect_args(1, 1)?;
+      let value = args[0].evaluate(ctx)?;
+      Ok(FormulaValue::Number(
+        value.to_string().chars().count() as f64
+      ))
+    },
+    "round" => {
+      expect_args(1, 2)?;
+      let value = args[0].evaluate(ctx)?;
+      if value.is_empty() {
+        return Ok(FormulaValue::Empty);
+      }
+      let n = value
+        .as_number()
+        .ok_or_else(|| FormulaError::new("round expects a number"))?;
+      let digits = match args.get(1) {
+        Some(arg) => arg.evaluate(ctx)?.as_number().unwrap_or(0.0) as i32,
+        None => 0,
+      };
+      let factor = 10f64.powi(digits);
+      Ok(FormulaValue::Number((n * factor).round() / factor))
+    },
+    "concat" => {
+      let mut s = String::new();
+      for arg in args {
+        s.push_str(&arg.evaluate(ctx)?.to_string());
+      }
+      Ok(FormulaValue::Text(s))
+    },
+    "date_add" => {
+      expect_args(3, 3)?;
+      let date = match args[0].evaluate(ctx)? {
+        FormulaValue::Date(date) => date,
+        FormulaValue::Empty => return Ok(FormulaValue::Empty),
+        _ => return Err(FormulaError::new("date_add expects a date")),
+      };
+      let amount = args[1]
+        .evaluate(ctx)?
+        .as_number()
+        .ok_or_else(|| FormulaError::new("date_add expects a numeric amount"))?;
+      let amount = to_i64(amount).ok_or_else(|| FormulaError::new("Date out of range"))?;
+      let unit = args[2].evaluate(ctx)?.to_string();
+      date_add(date, amount, &unit).map(FormulaValue::Date)
+    },
+    "date_diff" => {
+      expect_args(2, 3)?;
+      let (end, start) = match (args[0].evaluate(ctx)?, args[1].evaluate(ctx)?) {
+        (FormulaValue::Date(end), FormulaValue::Date(start)) => (end, start),
+        (FormulaValue::Empty, _) | (_, FormulaValue::Empty) => return Ok(FormulaValue::Empty),
+        _ => return Err(FormulaError::new("date_diff expects two dates")),
+      };
+      let unit = match args.get(2) {
+        Some(arg) => arg.evaluate(ctx)?.to_string(),
+        None => "days".to_string(),
+      };
+      date_diff(end, start, &unit).map(|n| FormulaValue::Number(n as f64))
+    },
+    _ => Err(FormulaError::new(format!("Unknown function '{}'", name))),
+  }
+}
+
+fn date_add(timestamp: i64, amount: i64, unit: &str) -> Result<i64, FormulaError> {
+  let date = DateTime::<Utc>::from_timestamp(timestamp, 0)
+    .ok_or_else(|| FormulaError::new("Invalid date"))?;
+  let months = |n: i64| -> Option<DateTime<Utc>> {
+    let months = Months::new(u32::try_from(n.unsigned_abs()).ok()?);
+    if n >= 0 {
+      date.checked_add_months(months)
+    } else {
+      date.checked_sub_months(months)
+    }
+  };
+  // The durations are None when the amount is out of range, instead of panicking
+  let add_duration =
+    |duration: Option<Duration>| duration.and_then(|duration| date.checked_add_signed(duration));
+  let result = match unit.to_lowercase().as_str() {
+    "minutes" | "minute" => add_duration(Duration::try_minutes(amount)),
+    "hours" | "hour" => add_duration(Duration::try_hours(amount)),
+    "days" | "day" => add_duration(Duration::try_days(amount)),
+    "weeks" | "week" => add_duration(Duration::try_weeks(amount)),
+    "months" | "month" => months(amount),
+    "years" | "year" => amount.checked_mul(12).and_then(months),
+    _ => return Err(FormulaError::new(format!("Unknown date unit '{}'", unit))),
+  };
+  result
+    .map(|date| date.timestamp())
+    .ok_or_else(|| FormulaError::new("Date out of range"))
+}
+
+fn date_diff(end: i64, start: i64, unit: &str) -> Result<i64, FormulaError> {
+  let seconds = end
+    .checked_sub(start)
+    .ok_or_else(|| FormulaError::new("Date out of range"))?;
+  let n = match unit.to_lowercase().as_str() {
+    "minutes" | "minute" => seconds / 60,
+    "hours" | "hour" => seconds / 3600,
+    "days" | "day" => seconds / SECONDS_PER_DAY,
+    "weeks" | "week" => seconds / (SECONDS_PER_DAY * 7),
+    "months" | "month" => month_diff(end, start)?,
+    "years" | "year" => month_diff(end, start)? / 12,
+    _ => return Err(FormulaError::new(format!("Unknown date unit '{}'", unit))),
+  };
+  Ok(n)
+}
+
+fn month_diff(end: i64, start: i64) -> Result<i64, FormulaError> {
+  use chrono::Datelike;
+  let to_date = |timestamp: i64| {
+    DateTime::<Utc>::from_timestamp(timestamp, 0).ok_or_else(|| FormulaError::new("Invalid date"))
+  };
+  let (end, start) = (to_date(end)?, to_date(start)?);
+  let mut months =
+    (end.year() as i64 - start.year() as i64) * 12 + end.month() as i64 - start.month() as i64;
+  // Only count the months that are fully elapsed
+  if months > 0 && end.day() < start.day() {
+    months -= 1;
+  } else if months < 0 && end.day() > start.day() {
+    months += 1;
+  }
+  Ok(months)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+  Number(f64),
+  Text(String),
+  Ident(String),
+  Field(String),
+  LParen,
+  RParen,
+  Comma,
+  Plus,
+  Minus,
+  Star,
+  Slash,
+  Percent,
+  Amp,
+  EqEq,
+  NotEq,
+  Lt,
+  LtEq,
+  Gt,
+  GtEq,
+  AndAnd,
+  OrOr,
+  Bang,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, FormulaError> {
+  let chars = input.chars().collect::<Vec<char>>();
+  let tokens = tokenize_chars(&chars)?;
+  Ok(tokens.into_iter().map(|(token, _)| token).collect())
+}
+
+/// Returns the tokens and the range of characters of each token in the input
+fn tokenize_chars(chars: &[char]) -> Result<Vec<(Token, Range<usize>)>, FormulaError> {
+  let mut tokens = vec![];
+  let mut i = 0;
+  while i < chars.len() {
+    let c = chars[i];
+    let token_start = i;
+    match c {
+      c if c.is_whitespace() => i += 1,
+      '0'..='9' | '.' => {
+        let start = i;
+        while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+          i += 1;
+        }
+        let s = chars[start..i].iter().collect::<String>();
+        let n = s
+          .parse::<f64>()
+          .map_err(|_| FormulaError::new(format!("Invalid number '{}'", s)))?;
+        tokens.push((Token::Number(n), token_start..i));
+      },
+      '"' | '\'' => {
+        let quote = c;
+        i += 1;
+        let mut s = String::new();
+        loop {
+          match chars.get(i) {
+            None => return Err(FormulaError::new("Unterminated string")),
+            Some('\\') if chars.get(i + 1).is_some() => {
+              s.push(chars[i + 1]);
+              i += 2;
+            },
+            Some(ch) if *ch == quote => {
+              i += 1;
+              break;
+            },
+            Some(ch) => {
+              s.push(*ch);
+              i += 1;
+            },
+          }
+        }
+        tokens.push((Token::Text(s), token_start..i));
+      },
+      '{' => {
+        i += 1;
+        let start = i;
+        while i < chars.len() && chars[i] != '}' {
+          i += 1;
+        }
+        if i >= chars.len() {
+          return Err(FormulaError::new("Unterminated field reference"));
+        }
+        let name = chars[start..i]
+          .iter()
+          .collect::<String>()
+          .trim()
+          .to_string();
+        if name.is_empty() {
+          return Err(FormulaError::new("Empty field reference"));
+        }
+        i += 1;
+        tokens.push((Token::Field(name), token_start..i));
+      },
+      c if c.is_alphabetic() || c == '_' => {
+        let start = i;
+        while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+          i += 1;
+        }
+        let ident = chars[start..i].iter().collect();
+        tokens.push((Token::Ident(ident), token_start..i));
+      },
+      _ => {
+        let next = chars.get(i + 1).copied();
+        let (token, len) = match (c, next) {
+          ('=', Some('=')) => (Token::EqEq, 2),
+          ('!', Some('=')) => (Token::NotEq, 2),
+          ('<', Some('=')) => (Token::LtEq, 2),
+          ('>', Some('=')) => (Token::GtEq, 2),
+          ('&', Some('&')) => (Token::AndAnd, 2),
+          ('|', Some('|')) => (Token::OrOr, 2),
+          ('=', _) => (Token::EqEq, 1),
+          ('<', _) => (Token::Lt, 1),
+          ('>', _) => (Token::Gt, 1),
+          ('!', _) => (Token::Bang, 1),
+          ('&', _) => (Token::Amp, 1),
+          ('(', _) => (Token::LParen, 1),
+          (')', _) => (Token::RParen, 1),
+          (',', _) => (Token::Comma, 1),
+          ('+', _) => (Token::Plus, 1),
+          ('-', _) => (Token::Minus, 1),
+          ('*', _) => (Token::Star, 1),
+          ('/', _) => (Token::Slash, 1),
+          ('%', _) => (Token::Percent, 1),
+          _ => return Err(FormulaError::new(format!("Unexpected character '{}'", c))),
+        };
+        i += len;
+        tokens.push((token, token_start..i));
+      },
+    }
+  }
+  Ok(tokens)
+}
+
+/// Replaces the references to the field `old_name` with references to `new_name`. Only the field
+/// references are renamed, the strings and the other fields containing the name are kept.
+pub fn rename_field_references(
+  expression: &str,
+  old_name: &str,
+  new_name: &str,
+) -> Result<String, FormulaError> {
+  let chars = expression.chars().collect::<Vec<char>>();
+  let mut result = String::with_capacity(expression.len());
+  let mut last_end = 0;
+  for (token, range) in tokenize_chars(&chars)? {
+    if let Token::Field(name) = token {
+      if name == old_name {
+        result.extend(&chars[last_end..range.start]);
+        result.push('{');
+        result.push_str(new_name);
+        result.push('}');
+        last_end = range.end;
+      }
+    }
+  }
+  result.extend(&chars[last_end..]);
+  Ok(result)
+}
+
+/// Parses the formula expression into a [FormulaExpr].
+///
+/// Grammar, from the lowest to the highest precedence:
+/// `||`, `&&`, comparison (`== != < <= > >=`), additive (`+ - &`), multiplicative (`* / %`),
+/// unary (`- !`) and primary (literals, `{Field}`, `fn(args)` and parentheses).
+pub fn parse_formula(expression: &str) -> Result<FormulaExpr, FormulaError> {
+  let tokens = tokenize(expression)?;
+  if tokens.is_empty() {
+    return Err(FormulaError::new("The formula is empty"));
+  }
+  let mut parser = Parser { tokens, pos: 0 };
+  let expr = parser.parse_or()?;
+  if parser.pos < parser.tokens.len() {
+    return Err(FormulaError::new(format!(
+      "Unexpected token {:?}",
+      parser.tokens[parser.pos]
+    )));
+  }
+  Ok(expr)
+}
+
+struct Parser {
+  tokens: Vec<Token>,
+  pos: usize,
+}
+
+impl Parser {
+  fn peek(&self) -> Option<&Token> {
+    self.tokens.get(self.pos)
+  }
+
+  fn next(&mut self) -> Option<Token> {
+    let token = self.tokens.get(self.pos).cloned();
+    self.pos += 1;
+    token
+  }
+
+  fn expect(&mut self, expected: Token) -> Result<(), FormulaError> {
+    match self.next() {
+      Some(token) if token == expected => Ok(()),
+      other => Err(FormulaError::new(format!(
+        "Expected {:?}, found {:?}",
+        expected, other
+      ))),
+    }
+  }
+
+  fn binary(op: BinaryOp, left: FormulaExpr, right: FormulaExpr) -> FormulaExpr {
+    FormulaExpr::Binary {
+      op,
+      left: Box::new(left),
+      right: Box::new(right),
+    }
+  }
+
+  fn parse_or(&mut self) -> Result<FormulaExpr, FormulaError> {
+    let mut left = self.parse_and()?;
+    while self.peek() == Some(&Token::OrOr) {
+      self.pos += 1;
+      let right = self.parse_and()?;
+      left = Self::binary(BinaryOp::Or, left, right);
+    }
+    Ok(left)
+  }
+
+  fn parse_and(&mut self) -> Result<FormulaExpr, FormulaError> {
+    let mut left = self.parse_comparison()?;
+    while self.peek() == Some(&Token::AndAnd) {
+      self.pos += 1;
+      let right = self.parse_comparison()?;
+      left = Self::binary(BinaryOp::And, left, right);
+    }
+    Ok(left)
+  }
+
+  fn parse_comparison(&mut self) -> Result<FormulaExpr, FormulaError> {
+    let left = self.parse_additive()?;
+    let op = match self.peek() {
+      Some(Token::EqEq) => BinaryOp::Eq,
+      Some(Token::NotEq) => BinaryOp::NotEq,
+      Some(Token::Lt) => BinaryOp::Lt,
+      Some(Token::LtEq) => BinaryOp::LtEq,
+      Some(Token::Gt) => BinaryOp::Gt,
+      Some(Token::GtEq) => BinaryOp::GtEq,
+      _ => return Ok(left),
+    };
+    self.pos += 1;
+    let right = self.parse_additive()?;
+    Ok(Self::binary(op, left, right))
+  }
+
+  fn parse_additive(&mut self) -> Result<FormulaExpr, FormulaError> {
+    let mut left = self.parse_multiplicative()?;
+    loop {
+      let op = match self.peek() {
+        Some(Token::Plus) => BinaryOp::Add,
+        Some(Token::Minus) => BinaryOp::Sub,
+        Some(Token::Amp) => BinaryOp::Concat,
+        _ => return Ok(left),
+      };
+      self.pos += 1;
+      let right = self.parse_multiplicative()?;
+      left = Self::binary(op, left, right);
+    }
+  }
+
+  fn parse_multiplicative(&mut self) -> Result<FormulaExpr, FormulaError> {
+    let mut left = self.parse_unary()?;
+    loop {
+      let op = match self.peek() {
+        Some(Token::Star) => BinaryOp::Mul,
+        Some(Token::Slash) => BinaryOp::Div,
+        Some(Token::Percent) => BinaryOp::Rem,
+        _ => return Ok(left),
+      };
+      self.pos += 1;
+      let right = self.parse_unary()?;
+      left = Self::binary(op, left, right);
+    }
+  }
+
+  fn parse_unary(&mut self) -> Result<FormulaExpr, FormulaError> {
+    let op = match self.peek() {
+      Some(Token::Minus) => UnaryOp::Neg,
+      Some(Token::Bang) => UnaryOp::Not,
+      _ => return self.parse_primary(),
+    };
+    self.pos += 1;
+    let expr = self.parse_unary()?;
+    Ok(FormulaExpr::Unary {
+      op,
+      expr: Box::new(expr),
+    })
+  }
+
+  fn parse_primary(&mut self) -> Result<FormulaExpr, FormulaError> {
+    match self.next() {
+      Some(Token::Number(n)) => Ok(FormulaExpr::Number(n)),
+      Some(Token::Text(s)) => Ok(FormulaExpr::Text(s)),
+      Some(Token::Field(name)) => Ok(FormulaExpr::Field(name)),
+      Some(Token::LParen) => {
+        let expr = self.parse_or()?;
+        self.expect(Token::RParen)?;
+        Ok(expr)
+      },
+      Some(Token::Ident(ident)) => match ident.to_lowercase().as_str() {
+        "true" => Ok(FormulaExpr::Bool(true)),
+        "false" => Ok(FormulaExpr::Bool(false)),
+        name => {
+          let name = name.to_string();
+          self.expect(Token::LParen)?;
+          let mut args = vec![];
+          if self.peek() == Some(&Token::RParen) {
+            self.pos += 1;
+          } else {
+            loop {
+              args.push(self.parse_or()?);
+              match self.next() {
+                Some(Token::Comma) => continue,
+                Some(Token::RParen) => break,
+                other => {
+                  return Err(FormulaError::new(format!(
+                    "Expected ',' or ')', found {:?}",
+                    other
+                  )))
+                },
+              }
+            }
+          }
+          Ok(FormulaExpr::Call { name, args })
+        },
+      },
+      other => Err(FormulaError::new(format!("Unexpected token {:?}", other))),
+    }
+  }
+}
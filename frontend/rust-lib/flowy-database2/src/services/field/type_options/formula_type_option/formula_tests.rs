@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+
+  use crate::services::field::{
+    parse_formula, rename_field_references, FormulaContext, FormulaError, FormulaValue,
+  };
+
+  struct MockContext(HashMap<&'static str, FormulaValue>);
+
+  impl FormulaContext for MockContext {
+    fn field_value(&self, field_name: &str) -> Result<FormulaValue, FormulaError> {
+      self
+        .0
+        .get(field_name)
+        .cloned()
+        .ok_or_else(|| FormulaError::new(field_name))
+    }
+  }
+
+  fn evaluate(expression: &str) -> Result<FormulaValue, FormulaError> {
+    let ctx = MockContext(HashMap::from([
+      ("Price", FormulaValue::Number(12.5)),
+      ("Quantity", FormulaValue::Number(4.0)),
+      ("Name", FormulaValue::Text("AppFlowy".to_string())),
+      ("Done", FormulaValue::Bool(true)),
+      // 2024-03-01 00:00:00 UTC
+      ("Start", FormulaValue::Date(1709251200)),
+      ("Empty", FormulaValue::Empty),
+    ]));
+    parse_formula(expression)?.evaluate(&ctx)
+  }
+
+  fn assert_formula(expression: &str, expected: &str) {
+    assert_eq!(evaluate(expression).unwrap().to_string(), expected);
+  }
+
+  #[test]
+  fn formula_arithmetic_test() {
+    assert_formula("1 + 2 * 3", "7");
+    assert_formula("(1 + 2) * 3", "9");
+    assert_formula("{Price} * {Quantity}", "50");
+    assert_formula("-{Price} + 2.5", "-10");
+    assert_formula("7 % 4", "3");
+    assert_formula("10 / 4", "2.5");
+    assert_formula("{Empty} + 1", "1");
+    assert!(evaluate("1 / 0").is_err());
+  }
+
+  #[test]
+  fn formula_string_test() {
+    assert_formula("\"Hello \" + {Name}", "Hello AppFlowy");
+    assert_formula("{Name} & \" x\" & {Quantity}", "AppFlowy x4");
+    assert_formula("concat({Name}, '-', 1)", "AppFlowy-1");
+    assert_formula("len({Name})", "8");
+  }
+
+  #[test]
+  fn formula_if_test() {
+    assert_formula("if({Price} > 10, \"expensive\", \"cheap\")", "expensive");
+    assert_formula("if({Done}, 1, 0)", "1");
+    assert_formula("if(!{Done}, 1)", "");
+    assert_formula(
+      "if({Price} >= 12.5 && {Quantity} == 4, \"yes\", \"no\")",
+      "yes",
+    );
+    assert_formula("if({Empty} || false, \"yes\", \"no\")", "no");
+  }
+
+  #[test]
+  fn formula_round_test() {
+    assert_formula("round(2.5)", "3");
+    assert_formula("round(3.14159, 2)", "3.14");
+    assert_formula("round({Price} / 3, 1)", "4.2");
+  }
+
+  #[test]
+  fn formula_date_test() {
+    assert_formula("{Start}", "2024-03-01");
+    assert_formula("{Start} + 1", "2024-03-02");
+    assert_formula("date_add({Start}, 1, \"months\")", "2024-04-01");
+    assert_formula("date_add({Start}, -1, \"years\")", "2023-03-01");
+    assert_formula(
+      "date_diff(date_add({Start}, 2, \"weeks\"), {Start}, \"days\")",
+      "14",
+    );
+    assert_formula("date_add({Start}, 10, \"days\") - {Start}", "10");
+  }
+
+  #[test]
+  fn formula_date_out_of_range_test() {
+    assert!(evaluate("date_add({Start}, 1000000000000000, \"weeks\")").is_err());
+    assert!(evaluate("date_add({Start}, 1000000000000000000, \"years\")").is_err());
+    assert!(evaluate("date_add({Start}, 5000000000, \"months\")").is_err());
+    assert!(evaluate("{Start} + 100000000000000000000000").is_err());
+    assert!(evaluate("{Start} - 100000000000000000").is_err());
+  }
+
+  #[test]
+  fn formula_referenced_fields_test() {
+    let expr = parse_formula("if({Done}, {Price} * {Quantity}, {Price})").unwrap();
+    assert_eq!(expr.referenced_fields(), vec!["Done", "Price", "Quantity"]);
+  }
+
+  #[test]
+  fn formula_rename_field_references_test() {
+    let expression = "{Price} * {Price count} & \"{Price}\" & 'Price'";
+    assert_eq!(
+      rename_field_references(expression, "Price", "Cost").unwrap(),
+      "{Cost} * {Price count} & \"{Price}\" & 'Price'"
+    );
+  }
+
+  #[test]
+  fn formula_parse_error_test() {
+    assert!(parse_formula("").is_err());
+    assert!(parse_formula("1 +").is_err());
+    assert!(parse_formula("{Price").is_err());
+    assert!(parse_formula("\"abc").is_err());
+    assert!(parse_formula("round(1, 2").is_err());
+    assert!(evaluate("unknown(1)").is_err());
+    assert!(evaluate("{Missing} + 1").is_err());
+  }
+}
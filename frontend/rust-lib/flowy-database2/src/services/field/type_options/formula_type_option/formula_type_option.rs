@@ -0,0 +1,187 @@
+use std::cmp::Ordering;
+
+use collab::preclude::Any;
+use collab::util::AnyMapExt;
+use collab_database::fields::{TypeOptionCellReader, TypeOptionData, TypeOptionDataBuilder};
+use collab_database::rows::{new_cell_builder, Cell};
+use collab_database::template::util::ToCellString;
+use flowy_error::{FlowyError, FlowyResult};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::entities::{FieldType, TextFilterPB};
+use crate::services::cell::{CellDataChangeset, CellDataDecoder};
+use crate::services::field::type_options::util::ProtobufStr;
+use crate::services::field::{
+  parse_formula, CellDataProtobufEncoder, FormulaError, FormulaExpr, TypeOption,
+  TypeOptionCellData, TypeOptionCellDataCompare, TypeOptionCellDataFilter, TypeOptionTransform,
+  CELL_DATA,
+};
+use crate::services::sort::SortCondition;
+
+const FORMULA_EXPRESSION: &str = "expression";
+
+/// The type option of the formula field. The cells of a formula field are computed from the
+/// [FormulaTypeOption::expression] and stored in the row, so they can be filtered, sorted and
+/// calculated like any other cell.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FormulaTypeOption {
+  pub expression: String,
+}
+
+impl FormulaTypeOption {
+  pub fn new(expression: &str) -> Self {
+    Self {
+      expression: expression.to_string(),
+    }
+  }
+
+  pub fn parse_expression(&self) -> Result<FormulaExpr, FormulaError> {
+    parse_formula(&self.expression)
+  }
+
+  /// Returns the names of the fields referenced by the expression. Returns an empty vec if the
+  /// expression is invalid.
+  pub fn referenced_field_names(&self) -> Vec<String> {
+    self
+      .parse_expression()
+      .map(|expr| expr.referenced_fields())
+      .unwrap_or_default()
+  }
+}
+
+impl From<TypeOptionData> for FormulaTypeOption {
+  fn from(data: TypeOptionData) -> Self {
+    Self {
+      expression: data
+        .get_as::<String>(FORMULA_EXPRESSION)
+        .unwrap_or_default(),
+    }
+  }
+}
+
+impl From<FormulaTypeOption> for TypeOptionData {
+  fn from(data: FormulaTypeOption) -> Self {
+    TypeOptionDataBuilder::from([(FORMULA_EXPRESSION.into(), Any::from(data.expression))])
+  }
+}
+
+impl TypeOptionCellReader for FormulaTypeOption {
+  fn json_cell(&self, cell: &Cell) -> Value {
+    let cell_data = FormulaCellData::from(cell);
+    match cell_data.0.parse::<f64>() {
+      Ok(number) => json!(number),
+      Err(_) => json!(cell_data.0),
+    }
+  }
+
+  fn numeric_cell(&self, cell: &Cell) -> Option<f64> {
+    FormulaCellData::from(cell).0.parse::<f64>().ok()
+  }
+
+  fn convert_raw_cell_data(&self, cell_data: &str) -> String {
+    cell_data.to_string()
+  }
+}
+
+impl TypeOption for FormulaTypeOption {
+  type CellData = FormulaCellData;
+  type CellChangeset = String;
+  type CellProtobufType = ProtobufStr;
+  type CellFilter = TextFilterPB;
+}
+
+impl TypeOptionTransform for FormulaTypeOption {}
+
+impl CellDataProtobufEncoder for FormulaTypeOption {
+  fn protobuf_encode(
+    &self,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    ProtobufStr::from(cell_data.0)
+  }
+}
+
+impl CellDataDecoder for FormulaTypeOption {
+  fn stringify_cell_data(&self, cell_data: <Self as TypeOption>::CellData) -> String {
+    cell_data.0
+  }
+}
+
+impl CellDataChangeset for FormulaTypeOption {
+  fn apply_changeset(
+    &self,
+    _changeset: <Self as TypeOption>::CellChangeset,
+    _cell: Option<Cell>,
+  ) -> FlowyResult<(Cell, <Self as TypeOption>::CellData)> {
+    Err(FlowyError::invalid_data().with_context("The cell of a formula field is read-only"))
+  }
+}
+
+impl TypeOptionCellDataFilter for FormulaTypeOption {
+  fn apply_filter(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    filter.is_visible(cell_data)
+  }
+}
+
+impl TypeOptionCellDataCompare for FormulaTypeOption {
+  fn apply_cmp(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+    sort_condition: SortCondition,
+  ) -> Ordering {
+    match (cell_data.is_cell_empty(), other_cell_data.is_cell_empty()) {
+      (true, true) => Ordering::Equal,
+      (true, false) => Ordering::Greater,
+      (false, true) => Ordering::Less,
+      (false, false) => {
+        let order = match (cell_data.0.parse::<f64>(), other_cell_data.0.parse::<f64>()) {
+          (Ok(left), Ok(right)) => left.total_cmp(&right),
+          _ => cell_data.0.cmp(&other_cell_data.0),
+        };
+        sort_condition.evaluate_order(order)
+      },
+    }
+  }
+}
+
+/// The computed value of a formula cell.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct FormulaCellData(pub String);
+
+impl TypeOptionCellData for FormulaCellData {
+  fn is_cell_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+}
+
+impl From<&Cell> for FormulaCellData {
+  fn from(cell: &Cell) -> Self {
+    Self(cell.get_as(CELL_DATA).unwrap_or_default())
+  }
+}
+
+impl From<FormulaCellData> for Cell {
+  fn from(data: FormulaCellData) -> Self {
+    let mut cell = new_cell_builder(FieldType::Formula);
+    cell.insert(CELL_DATA.into(), data.0.into());
+    cell
+  }
+}
+
+impl ToCellString for FormulaCellData {
+  fn to_cell_string(&self) -> String {
+    self.0.clone()
+  }
+}
+
+impl AsRef<str> for FormulaCellData {
+  fn as_ref(&self) -> &str {
+    self.0.as_str()
+  }
+}
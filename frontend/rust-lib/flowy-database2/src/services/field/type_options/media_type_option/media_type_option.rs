@@ -55,7 +55,14 @@ impl CellDataDecoder for MediaTypeOption {
       | FieldType::LastEditedTime
       | FieldType::CreatedTime
       | FieldType::Relation
-      | FieldType::Media => None,
+      | FieldType::Media
+      | FieldType::Formula
+      | FieldType::Rollup
+      | FieldType::Rating
+      | FieldType::Email
+      | FieldType::Phone
+      | FieldType::AutoNumber
+      | FieldType::Person => None,
     }
   }
 
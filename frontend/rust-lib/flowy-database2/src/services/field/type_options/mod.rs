@@ -1,9 +1,16 @@
+pub mod auto_number_type_option;
 pub mod checkbox_type_option;
 pub mod checklist_type_option;
 pub mod date_type_option;
+pub mod email_type_option;
+pub mod formula_type_option;
 pub mod media_type_option;
 pub mod number_type_option;
+pub mod person_type_option;
+pub mod phone_type_option;
+pub mod rating_type_option;
 pub mod relation_type_option;
+pub mod rollup_type_option;
 pub mod selection_type_option;
 pub mod summary_type_option;
 pub mod text_type_option;
@@ -15,12 +22,19 @@ mod type_option_cell;
 mod url_type_option;
 mod util;
 
+pub use auto_number_type_option::*;
 pub use checkbox_type_option::*;
 pub use checklist_type_option::*;
 pub use date_type_option::*;
+pub use email_type_option::*;
+pub use formula_type_option::*;
 
 pub use number_type_option::*;
+pub use person_type_option::*;
+pub use phone_type_option::*;
+pub use rating_type_option::*;
 pub use relation_type_option::*;
+pub use rollup_type_option::*;
 pub use selection_type_option::*;
 pub use text_type_option::*;
 pub use time_type_option::*;
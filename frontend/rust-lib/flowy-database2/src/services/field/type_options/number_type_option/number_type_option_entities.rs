@@ -1,4 +1,5 @@
 use crate::services::cell::{CellBytesCustomParser, CellProtobufBlobParser};
+use crate::services::field::type_options::util::ProtobufStr;
 use bytes::Bytes;
 use collab_database::fields::number_type_option::{NumberCellFormat, NumberFormat};
 use flowy_error::FlowyResult;
@@ -14,6 +15,18 @@ impl CellProtobufBlobParser for NumberCellDataParser {
   }
 }
 
+/// Parses the cell as it's sent to the client, the number is formatted with the format of the
+/// type option.
+pub struct NumberCellStrParser();
+impl CellProtobufBlobParser for NumberCellStrParser {
+  type Object = ProtobufStr;
+  fn parser(bytes: &Bytes) -> FlowyResult<Self::Object> {
+    Ok(ProtobufStr::from(
+      String::from_utf8(bytes.to_vec()).unwrap_or_default(),
+    ))
+  }
+}
+
 pub struct NumberCellCustomDataParser(pub NumberFormat);
 impl CellBytesCustomParser for NumberCellCustomDataParser {
   type Object = NumberCellFormat;
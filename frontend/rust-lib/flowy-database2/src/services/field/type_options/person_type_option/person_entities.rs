@@ -0,0 +1,132 @@
+use std::str::FromStr;
+
+use bytes::Bytes;
+use collab::util::AnyMapExt;
+use collab_database::rows::{new_cell_builder, Cell};
+use collab_database::template::util::ToCellString;
+use flowy_error::{internal_error, FlowyResult};
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{FieldType, PersonCellDataPB};
+use crate::services::cell::CellProtobufBlobParser;
+use crate::services::field::{normalize_email, TypeOptionCellData, CELL_DATA};
+
+/// A workspace member referenced by a person cell. The email identifies the member, the name and
+/// the avatar are kept so the cell can be displayed without loading the workspace members.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Person {
+  pub email: String,
+  pub name: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub avatar_url: Option<String>,
+}
+
+impl Person {
+  /// Returns a person that is only known by its email, the email is used as its name.
+  pub fn from_email(email: &str) -> Self {
+    Self {
+      email: email.to_string(),
+      name: email.to_string(),
+      avatar_url: None,
+    }
+  }
+
+  pub fn has_email(&self, email: &str) -> bool {
+    self.email.eq_ignore_ascii_case(email)
+  }
+
+  pub fn display_name(&self) -> &str {
+    if self.name.is_empty() {
+      &self.email
+    } else {
+      &self.name
+    }
+  }
+}
+
+/// The persons of the cell, stored as a json array in the cell.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PersonCellData {
+  pub persons: Vec<Person>,
+}
+
+impl PersonCellData {
+  pub fn contains(&self, email: &str) -> bool {
+    self.persons.iter().any(|person| person.has_email(email))
+  }
+
+  pub fn emails(&self) -> impl Iterator<Item = &str> {
+    self.persons.iter().map(|person| person.email.as_str())
+  }
+}
+
+impl TypeOptionCellData for PersonCellData {
+  fn is_cell_empty(&self) -> bool {
+    self.persons.is_empty()
+  }
+}
+
+impl From<&Cell> for PersonCellData {
+  fn from(cell: &Cell) -> Self {
+    cell
+      .get_as::<String>(CELL_DATA)
+      .and_then(|s| PersonCellData::from_str(&s).ok())
+      .unwrap_or_default()
+  }
+}
+
+impl From<PersonCellData> for Cell {
+  fn from(data: PersonCellData) -> Self {
+    let mut cell = new_cell_builder(FieldType::Person);
+    cell.insert(CELL_DATA.into(), data.to_cell_string().into());
+    cell
+  }
+}
+
+impl ToCellString for PersonCellData {
+  fn to_cell_string(&self) -> String {
+    if self.persons.is_empty() {
+      "".to_string()
+    } else {
+      serde_json::to_string(&self.persons).unwrap_or_default()
+    }
+  }
+}
+
+impl FromStr for PersonCellData {
+  type Err = serde_json::Error;
+
+  /// Parses the json array of persons. A comma-separated list of emails, which is how the persons
+  /// are written in csv files, is accepted too and the invalid emails are skipped.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let s = s.trim();
+    if s.starts_with('[') {
+      let persons = serde_json::from_str::<Vec<Person>>(s)?;
+      return Ok(Self { persons });
+    }
+
+    let mut persons: Vec<Person> = vec![];
+    for email in s.split(',').filter_map(normalize_email) {
+      if !persons.iter().any(|person| person.has_email(&email)) {
+        persons.push(Person::from_email(&email));
+      }
+    }
+    Ok(Self { persons })
+  }
+}
+
+/// Inserts and removes persons of the cell. The persons are identified by their email.
+#[derive(Debug, Clone, Default)]
+pub struct PersonCellChangeset {
+  pub inserted_persons: Vec<Person>,
+  pub removed_emails: Vec<String>,
+}
+
+pub struct PersonCellDataParser();
+impl CellProtobufBlobParser for PersonCellDataParser {
+  type Object = PersonCellDataPB;
+
+  fn parser(bytes: &Bytes) -> FlowyResult<Self::Object> {
+    PersonCellDataPB::try_from(bytes.as_ref()).map_err(internal_error)
+  }
+}
@@ -0,0 +1,60 @@
+use collab_database::fields::Field;
+use collab_database::rows::Cell;
+
+use crate::entities::{PersonFilterConditionPB, PersonFilterPB};
+use crate::services::field::{Person, PersonCellData};
+use crate::services::filter::PreFillCellsWithFilter;
+
+impl PersonFilterPB {
+  /// Returns None if the filter requires emails but none were provided, in which case the filter
+  /// is ignored. The [PersonFilterConditionPB::PersonIsMe] filter must be bound to the current
+  /// user with [PersonFilterPB::with_current_user] first.
+  pub fn is_visible(&self, cell_data: &PersonCellData) -> Option<bool> {
+    let is_visible = match self.condition {
+      PersonFilterConditionPB::PersonIsEmpty => cell_data.persons.is_empty(),
+      PersonFilterConditionPB::PersonIsNotEmpty => !cell_data.persons.is_empty(),
+      PersonFilterConditionPB::PersonIsMe => cell_data.contains(self.emails.first()?),
+      PersonFilterConditionPB::PersonContains => {
+        if self.emails.is_empty() {
+          return None;
+        }
+        self.emails.iter().any(|email| cell_data.contains(email))
+      },
+      PersonFilterConditionPB::PersonDoesNotContain => {
+        if self.emails.is_empty() {
+          return None;
+        }
+        !self.emails.iter().any(|email| cell_data.contains(email))
+      },
+    };
+    Some(is_visible)
+  }
+
+  /// Returns the filter with the email of the current user if it's a
+  /// [PersonFilterConditionPB::PersonIsMe] filter, which depends on who is looking at the view.
+  pub fn with_current_user(&self, email: &str) -> Option<Self> {
+    if self.condition != PersonFilterConditionPB::PersonIsMe {
+      return None;
+    }
+    Some(Self {
+      condition: PersonFilterConditionPB::PersonIsMe,
+      emails: vec![email.to_string()],
+    })
+  }
+}
+
+impl PreFillCellsWithFilter for PersonFilterPB {
+  fn get_compliant_cell(&self, _field: &Field) -> Option<Cell> {
+    let email = match self.condition {
+      PersonFilterConditionPB::PersonIsMe | PersonFilterConditionPB::PersonContains => {
+        self.emails.first()
+      },
+      _ => None,
+    }?;
+
+    let cell_data = PersonCellData {
+      persons: vec![Person::from_email(email)],
+    };
+    Some(Cell::from(cell_data))
+  }
+}
@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod tests {
+  use std::str::FromStr;
+
+  use collab_database::fields::TypeOptionData;
+  use collab_database::template::util::ToCellString;
+
+  use crate::entities::{PersonFilterConditionPB, PersonFilterPB};
+  use crate::services::cell::{CellDataChangeset, CellDataDecoder};
+  use crate::services::field::{
+    Person, PersonCellChangeset, PersonCellData, PersonTypeOption, TypeOptionCellDataFilter,
+  };
+
+  fn person(email: &str, name: &str) -> Person {
+    Person {
+      email: email.to_string(),
+      name: name.to_string(),
+      avatar_url: None,
+    }
+  }
+
+  fn insert(persons: Vec<Person>) -> PersonCellChangeset {
+    PersonCellChangeset {
+      inserted_persons: persons,
+      removed_emails: vec![],
+    }
+  }
+
+  #[test]
+  fn person_type_option_serde_test() {
+    let type_option = PersonTypeOption { is_single: true };
+    let data: TypeOptionData = type_option.into();
+    assert!(PersonTypeOption::from(data).is_single);
+  }
+
+  #[test]
+  fn person_multiple_assignees_changeset_test() {
+    let type_option = PersonTypeOption::default();
+    let (cell, cell_data) = type_option
+      .apply_changeset(
+        insert(vec![
+          person("lucas@appflowy.io", "Lucas"),
+          person("nathan@appflowy.io", "Nathan"),
+        ]),
+        None,
+      )
+      .unwrap();
+    assert_eq!(cell_data.persons.len(), 2);
+    assert_eq!(type_option.stringify_cell_data(cell_data), "Lucas, Nathan");
+
+    // Inserting the same member again refreshes its name
+    let (cell, cell_data) = type_option
+      .apply_changeset(
+        insert(vec![person("Lucas@AppFlowy.io", "Lucas Xu")]),
+        Some(cell),
+      )
+      .unwrap();
+    assert_eq!(
+      type_option.stringify_cell_data(cell_data),
+      "Lucas Xu, Nathan"
+    );
+
+    let changeset = PersonCellChangeset {
+      inserted_persons: vec![],
+      removed_emails: vec!["nathan@appflowy.io".to_string()],
+    };
+    let (_, cell_data) = type_option.apply_changeset(changeset, Some(cell)).unwrap();
+    assert_eq!(
+      cell_data.emails().collect::<Vec<_>>(),
+      vec!["Lucas@AppFlowy.io"]
+    );
+  }
+
+  #[test]
+  fn person_single_assignee_changeset_test() {
+    let type_option = PersonTypeOption { is_single: true };
+    let (cell, _) = type_option
+      .apply_changeset(insert(vec![person("lucas@appflowy.io", "Lucas")]), None)
+      .unwrap();
+    let (_, cell_data) = type_option
+      .apply_changeset(
+        insert(vec![person("nathan@appflowy.io", "Nathan")]),
+        Some(cell),
+      )
+      .unwrap();
+    assert_eq!(
+      cell_data.persons,
+      vec![person("nathan@appflowy.io", "Nathan")]
+    );
+  }
+
+  #[test]
+  fn person_cell_data_from_str_test() {
+    let cell_data = PersonCellData {
+      persons: vec![person("lucas@appflowy.io", "Lucas")],
+    };
+    let s = cell_data.to_cell_string();
+    assert_eq!(PersonCellData::from_str(&s).unwrap(), cell_data);
+
+    // The emails of a csv file, the invalid ones are skipped
+    let cell_data =
+      PersonCellData::from_str("lucas@appflowy.io, not an email, nathan@appflowy.io").unwrap();
+    assert_eq!(
+      cell_data.emails().collect::<Vec<_>>(),
+      vec!["lucas@appflowy.io", "nathan@appflowy.io"]
+    );
+  }
+
+  #[test]
+  fn person_filter_test() {
+    let type_option = PersonTypeOption::default();
+    let cell_data = PersonCellData {
+      persons: vec![person("lucas@appflowy.io", "Lucas")],
+    };
+    let filter = |condition: PersonFilterConditionPB, emails: &[&str]| PersonFilterPB {
+      condition,
+      emails: emails.iter().map(|email| email.to_string()).collect(),
+    };
+
+    let is_me = filter(PersonFilterConditionPB::PersonIsMe, &[]);
+    let lucas = is_me.with_current_user("lucas@appflowy.io").unwrap();
+    let nathan = is_me.with_current_user("nathan@appflowy.io").unwrap();
+    assert!(type_option.apply_filter(&lucas, &cell_data));
+    assert!(!type_option.apply_filter(&nathan, &cell_data));
+
+    let contains = filter(
+      PersonFilterConditionPB::PersonContains,
+      &["nathan@appflowy.io", "LUCAS@appflowy.io"],
+    );
+    assert!(type_option.apply_filter(&contains, &cell_data));
+    assert!(contains.with_current_user("lucas@appflowy.io").is_none());
+
+    let does_not_contain = filter(
+      PersonFilterConditionPB::PersonDoesNotContain,
+      &["nathan@appflowy.io"],
+    );
+    assert!(type_option.apply_filter(&does_not_contain, &cell_data));
+
+    let is_empty = filter(PersonFilterConditionPB::PersonIsEmpty, &[]);
+    assert!(!type_option.apply_filter(&is_empty, &cell_data));
+    assert!(type_option.apply_filter(&is_empty, &PersonCellData::default()));
+  }
+}
@@ -0,0 +1,242 @@
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use collab::preclude::Any;
+use collab::util::AnyMapExt;
+use collab_database::database::Database;
+use collab_database::fields::{Field, TypeOptionCellReader, TypeOptionData, TypeOptionDataBuilder};
+use collab_database::rows::Cell;
+use collab_database::template::util::ToCellString;
+use flowy_error::FlowyResult;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::info;
+
+use crate::entities::{FieldType, PersonCellDataPB, PersonFilterPB};
+use crate::services::cell::{CellDataChangeset, CellDataDecoder};
+use crate::services::field::{
+  CellDataProtobufEncoder, PersonCellChangeset, PersonCellData, TypeOption, TypeOptionCellData,
+  TypeOptionCellDataCompare, TypeOptionCellDataFilter, TypeOptionTransform, CELL_DATA,
+};
+use crate::services::sort::SortCondition;
+
+const PERSON_IS_SINGLE: &str = "is_single";
+
+/// The type option of the person field. The cells reference the members of the workspace by
+/// their email.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PersonTypeOption {
+  /// If true, the cell contains at most one person.
+  pub is_single: bool,
+}
+
+impl From<TypeOptionData> for PersonTypeOption {
+  fn from(data: TypeOptionData) -> Self {
+    Self {
+      is_single: data.get_as::<bool>(PERSON_IS_SINGLE).unwrap_or_default(),
+    }
+  }
+}
+
+impl From<PersonTypeOption> for TypeOptionData {
+  fn from(data: PersonTypeOption) -> Self {
+    TypeOptionDataBuilder::from([(PERSON_IS_SINGLE.into(), Any::Bool(data.is_single))])
+  }
+}
+
+impl TypeOptionCellReader for PersonTypeOption {
+  fn json_cell(&self, cell: &Cell) -> Value {
+    let cell_data = PersonCellData::from(cell);
+    if cell_data.is_cell_empty() {
+      Value::Null
+    } else {
+      json!(cell_data.persons)
+    }
+  }
+
+  fn numeric_cell(&self, _cell: &Cell) -> Option<f64> {
+    None
+  }
+
+  fn convert_raw_cell_data(&self, cell_data: &str) -> String {
+    PersonCellData::from_str(cell_data)
+      .map(|cell_data| cell_data.to_cell_string())
+      .unwrap_or_default()
+  }
+}
+
+impl TypeOption for PersonTypeOption {
+  type CellData = PersonCellData;
+  type CellChangeset = PersonCellChangeset;
+  type CellProtobufType = PersonCellDataPB;
+  type CellFilter = PersonFilterPB;
+}
+
+#[async_trait]
+impl TypeOptionTransform for PersonTypeOption {
+  async fn transform_type_option(
+    &mut self,
+    view_id: &str,
+    field_id: &str,
+    old_type_option_field_type: FieldType,
+    _old_type_option_data: TypeOptionData,
+    _new_type_option_field_type: FieldType,
+    database: &mut Database,
+  ) {
+    match old_type_option_field_type {
+      FieldType::RichText | FieldType::Email => {
+        let rows = database
+          .get_cells_for_field(view_id, field_id)
+          .await
+          .into_iter()
+          .filter_map(|row| row.cell.map(|cell| (row.row_id, cell)))
+          .collect::<Vec<_>>();
+
+        info!(
+          "Transforming {:?} to PersonTypeOption, updating {} row's cell content",
+          old_type_option_field_type,
+          rows.len()
+        );
+        for (row_id, cell_data) in rows {
+          let content = cell_data.get_as::<String>(CELL_DATA).unwrap_or_default();
+          if let Some(cell_data) = self.parse_emails(&content) {
+            database
+              .update_row(row_id, |row| {
+                row.update_cells(|cell| {
+                  cell.insert(field_id, Cell::from(cell_data));
+                });
+              })
+              .await;
+          }
+        }
+      },
+      _ => {
+        // do nothing
+      },
+    }
+  }
+}
+
+impl PersonTypeOption {
+  /// Returns the persons of the comma-separated emails, or None if there's no valid email.
+  fn parse_emails(&self, content: &str) -> Option<PersonCellData> {
+    let mut cell_data = PersonCellData::from_str(content).ok()?;
+    if self.is_single {
+      cell_data.persons.truncate(1);
+    }
+    (!cell_data.is_cell_empty()).then_some(cell_data)
+  }
+}
+
+impl CellDataProtobufEncoder for PersonTypeOption {
+  fn protobuf_encode(
+    &self,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    PersonCellDataPB::from(cell_data)
+  }
+}
+
+impl CellDataDecoder for PersonTypeOption {
+  fn decode_cell_with_transform(
+    &self,
+    cell: &Cell,
+    from_field_type: FieldType,
+    _field: &Field,
+  ) -> Option<<Self as TypeOption>::CellData> {
+    match from_field_type {
+      FieldType::RichText | FieldType::Email => {
+        let content = cell.get_as::<String>(CELL_DATA)?;
+        self.parse_emails(&content)
+      },
+      _ => None,
+    }
+  }
+
+  fn stringify_cell_data(&self, cell_data: <Self as TypeOption>::CellData) -> String {
+    cell_data
+      .persons
+      .iter()
+      .map(|person| person.display_name())
+      .collect::<Vec<_>>()
+      .join(", ")
+  }
+}
+
+impl CellDataChangeset for PersonTypeOption {
+  fn apply_changeset(
+    &self,
+    changeset: <Self as TypeOption>::CellChangeset,
+    cell: Option<Cell>,
+  ) -> FlowyResult<(Cell, <Self as TypeOption>::CellData)> {
+    let mut persons = cell
+      .as_ref()
+      .map(PersonCellData::from)
+      .unwrap_or_default()
+      .persons;
+    persons.retain(|person| {
+      !changeset
+        .removed_emails
+        .iter()
+        .any(|email| person.has_email(email))
+    });
+
+    if self.is_single {
+      // The last inserted person replaces the person of the cell
+      if let Some(person) = changeset.inserted_persons.into_iter().last() {
+        persons = vec![person];
+      }
+    } else {
+      for inserted in changeset.inserted_persons {
+        match persons
+          .iter_mut()
+          .find(|person| person.has_email(&inserted.email))
+        {
+          // Refresh the name and the avatar of the member
+          Some(person) => *person = inserted,
+          None => persons.push(inserted),
+        }
+      }
+    }
+
+    let cell_data = PersonCellData { persons };
+    Ok((Cell::from(cell_data.clone()), cell_data))
+  }
+}
+
+impl TypeOptionCellDataFilter for PersonTypeOption {
+  fn apply_filter(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    filter.is_visible(cell_data).unwrap_or(true)
+  }
+}
+
+impl TypeOptionCellDataCompare for PersonTypeOption {
+  fn apply_cmp(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+    sort_condition: SortCondition,
+  ) -> Ordering {
+    match (cell_data.is_cell_empty(), other_cell_data.is_cell_empty()) {
+      (true, true) => Ordering::Equal,
+      (true, false) => Ordering::Greater,
+      (false, true) => Ordering::Less,
+      (false, false) => {
+        let order = self
+          .stringify_cell_data(cell_data.clone())
+          .to_lowercase()
+          .cmp(
+            &self
+              .stringify_cell_data(other_cell_data.clone())
+              .to_lowercase(),
+          );
+        sort_condition.evaluate_order(order)
+      },
+    }
+  }
+}
@@ -0,0 +1,5 @@
+#![allow(clippy::module_inception)]
+mod phone_tests;
+mod phone_type_option;
+
+pub use phone_type_option::*;
@@ -0,0 +1,37 @@
+#[cfg(test)]
+mod tests {
+  use crate::services::cell::CellDataChangeset;
+  use crate::services::field::{normalize_phone, PhoneTypeOption};
+
+  #[test]
+  fn normalize_phone_test() {
+    assert_eq!(
+      normalize_phone("+1 (555) 123-4567"),
+      Some("+15551234567".to_string())
+    );
+    assert_eq!(
+      normalize_phone("tel:030.1234.5678"),
+      Some("03012345678".to_string())
+    );
+    assert_eq!(normalize_phone("12"), None);
+    assert_eq!(normalize_phone("1234567890123456"), None);
+    assert_eq!(normalize_phone("555-CALL-NOW"), None);
+    assert_eq!(normalize_phone("1+234567"), None);
+  }
+
+  #[test]
+  fn phone_changeset_test() {
+    let type_option = PhoneTypeOption;
+    let (_, cell_data) = type_option
+      .apply_changeset("+49 30 1234567".to_string(), None)
+      .unwrap();
+    assert_eq!(cell_data.0, "+49301234567");
+
+    let (_, cell_data) = type_option.apply_changeset(" ".to_string(), None).unwrap();
+    assert!(cell_data.0.is_empty());
+
+    assert!(type_option
+      .apply_changeset("phone".to_string(), None)
+      .is_err());
+  }
+}
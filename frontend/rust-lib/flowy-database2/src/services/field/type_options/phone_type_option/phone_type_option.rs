@@ -0,0 +1,255 @@
+use std::cmp::Ordering;
+
+use async_trait::async_trait;
+use collab::util::AnyMapExt;
+use collab_database::database::Database;
+use collab_database::fields::{Field, TypeOptionCellReader, TypeOptionData, TypeOptionDataBuilder};
+use collab_database::rows::{new_cell_builder, Cell};
+use collab_database::template::util::ToCellString;
+use flowy_error::{FlowyError, FlowyResult};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::info;
+
+use crate::entities::{FieldType, TextFilterPB};
+use crate::services::cell::{CellDataChangeset, CellDataDecoder};
+use crate::services::field::type_options::util::ProtobufStr;
+use crate::services::field::{
+  CellDataProtobufEncoder, TypeOption, TypeOptionCellData, TypeOptionCellDataCompare,
+  TypeOptionCellDataFilter, TypeOptionTransform, CELL_DATA,
+};
+use crate::services::sort::SortCondition;
+
+/// The minimum and maximum number of digits of a phone number. The maximum is the length of
+/// an E.164 number.
+const MIN_PHONE_DIGITS: usize = 3;
+const MAX_PHONE_DIGITS: usize = 15;
+
+/// Normalizes the phone number by removing the `tel:` prefix and the separators, which are
+/// whitespaces, `-`, `.`, `(` and `)`. A leading `+` is kept. Returns None if the content
+/// contains any other character or has too few or too many digits.
+pub fn normalize_phone(content: &str) -> Option<String> {
+  let content = content.trim();
+  let content = match content.get(..4) {
+    Some(prefix) if prefix.eq_ignore_ascii_case("tel:") => content[4..].trim(),
+    _ => content,
+  };
+  let (prefix, number) = match content.strip_prefix('+') {
+    Some(number) => ("+", number),
+    None => ("", content),
+  };
+
+  let mut digits = String::with_capacity(number.len());
+  for c in number.chars() {
+    match c {
+      '0'..='9' => digits.push(c),
+      ' ' | '-' | '.' | '(' | ')' => {},
+      _ => return None,
+    }
+  }
+  if digits.len() < MIN_PHONE_DIGITS || digits.len() > MAX_PHONE_DIGITS {
+    return None;
+  }
+  Some(format!("{}{}", prefix, digits))
+}
+
+/// The type option of the phone field. The cells only contain normalized phone numbers, see
+/// [normalize_phone].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PhoneTypeOption;
+
+impl From<TypeOptionData> for PhoneTypeOption {
+  fn from(_data: TypeOptionData) -> Self {
+    Self
+  }
+}
+
+impl From<PhoneTypeOption> for TypeOptionData {
+  fn from(_data: PhoneTypeOption) -> Self {
+    TypeOptionDataBuilder::new()
+  }
+}
+
+impl TypeOptionCellReader for PhoneTypeOption {
+  fn json_cell(&self, cell: &Cell) -> Value {
+    let cell_data = PhoneCellData::from(cell);
+    if cell_data.is_cell_empty() {
+      Value::Null
+    } else {
+      json!(cell_data.0)
+    }
+  }
+
+  fn numeric_cell(&self, _cell: &Cell) -> Option<f64> {
+    None
+  }
+
+  fn convert_raw_cell_data(&self, cell_data: &str) -> String {
+    normalize_phone(cell_data).unwrap_or_default()
+  }
+}
+
+impl TypeOption for PhoneTypeOption {
+  type CellData = PhoneCellData;
+  type CellChangeset = PhoneCellChangeset;
+  type CellProtobufType = ProtobufStr;
+  type CellFilter = TextFilterPB;
+}
+
+#[async_trait]
+impl TypeOptionTransform for PhoneTypeOption {
+  async fn transform_type_option(
+    &mut self,
+    view_id: &str,
+    field_id: &str,
+    old_type_option_field_type: FieldType,
+    _old_type_option_data: TypeOptionData,
+    _new_type_option_field_type: FieldType,
+    database: &mut Database,
+  ) {
+    match old_type_option_field_type {
+      FieldType::RichText | FieldType::Number => {
+        let rows = database
+          .get_cells_for_field(view_id, field_id)
+          .await
+          .into_iter()
+          .filter_map(|row| row.cell.map(|cell| (row.row_id, cell)))
+          .collect::<Vec<_>>();
+
+        info!(
+          "Transforming {:?} to PhoneTypeOption, updating {} row's cell content",
+          old_type_option_field_type,
+          rows.len()
+        );
+        for (row_id, cell_data) in rows {
+          let content = cell_data.get_as::<String>(CELL_DATA).unwrap_or_default();
+          if let Some(phone) = normalize_phone(&content) {
+            database
+              .update_row(row_id, |row| {
+                row.update_cells(|cell| {
+                  cell.insert(field_id, Cell::from(PhoneCellData(phone)));
+                });
+              })
+              .await;
+          }
+        }
+      },
+      _ => {
+        // do nothing
+      },
+    }
+  }
+}
+
+impl CellDataProtobufEncoder for PhoneTypeOption {
+  fn protobuf_encode(
+    &self,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    ProtobufStr::from(cell_data.0)
+  }
+}
+
+impl CellDataDecoder for PhoneTypeOption {
+  fn decode_cell_with_transform(
+    &self,
+    cell: &Cell,
+    from_field_type: FieldType,
+    _field: &Field,
+  ) -> Option<<Self as TypeOption>::CellData> {
+    match from_field_type {
+      FieldType::RichText | FieldType::Number => {
+        let content = cell.get_as::<String>(CELL_DATA)?;
+        normalize_phone(&content).map(PhoneCellData)
+      },
+      _ => None,
+    }
+  }
+
+  fn stringify_cell_data(&self, cell_data: <Self as TypeOption>::CellData) -> String {
+    cell_data.0
+  }
+}
+
+/// The phone number of the cell. An empty changeset clears the cell.
+pub type PhoneCellChangeset = String;
+
+impl CellDataChangeset for PhoneTypeOption {
+  fn apply_changeset(
+    &self,
+    changeset: <Self as TypeOption>::CellChangeset,
+    _cell: Option<Cell>,
+  ) -> FlowyResult<(Cell, <Self as TypeOption>::CellData)> {
+    let cell_data = if changeset.trim().is_empty() {
+      PhoneCellData::default()
+    } else {
+      let phone = normalize_phone(&changeset).ok_or_else(|| {
+        FlowyError::invalid_data()
+          .with_context(format!("{} is not a valid phone number", changeset))
+      })?;
+      PhoneCellData(phone)
+    };
+    Ok((Cell::from(cell_data.clone()), cell_data))
+  }
+}
+
+impl TypeOptionCellDataFilter for PhoneTypeOption {
+  fn apply_filter(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    filter.is_visible(cell_data)
+  }
+}
+
+impl TypeOptionCellDataCompare for PhoneTypeOption {
+  fn apply_cmp(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+    sort_condition: SortCondition,
+  ) -> Ordering {
+    match (cell_data.is_cell_empty(), other_cell_data.is_cell_empty()) {
+      (true, true) => Ordering::Equal,
+      (true, false) => Ordering::Greater,
+      (false, true) => Ordering::Less,
+      (false, false) => sort_condition.evaluate_order(cell_data.0.cmp(&other_cell_data.0)),
+    }
+  }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct PhoneCellData(pub String);
+
+impl TypeOptionCellData for PhoneCellData {
+  fn is_cell_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+}
+
+impl From<&Cell> for PhoneCellData {
+  fn from(cell: &Cell) -> Self {
+    Self(cell.get_as(CELL_DATA).unwrap_or_default())
+  }
+}
+
+impl From<PhoneCellData> for Cell {
+  fn from(data: PhoneCellData) -> Self {
+    let mut cell = new_cell_builder(FieldType::Phone);
+    cell.insert(CELL_DATA.into(), data.0.into());
+    cell
+  }
+}
+
+impl ToCellString for PhoneCellData {
+  fn to_cell_string(&self) -> String {
+    self.0.clone()
+  }
+}
+
+impl AsRef<str> for PhoneCellData {
+  fn as_ref(&self) -> &str {
+    self.0.as_str()
+  }
+}
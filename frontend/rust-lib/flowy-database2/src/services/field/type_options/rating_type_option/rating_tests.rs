@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod tests {
+  use collab_database::fields::TypeOptionData;
+
+  use crate::entities::{NumberFilterConditionPB, NumberFilterPB};
+  use crate::services::cell::CellDataChangeset;
+  use crate::services::field::{RatingCellData, RatingTypeOption, TypeOptionCellDataFilter};
+
+  #[test]
+  fn rating_changeset_test() {
+    let type_option = RatingTypeOption::new(5);
+    for (input, expected) in [
+      ("3", 3),
+      ("2.6", 3),
+      ("7", 5),
+      ("-1", 0),
+      ("", 0),
+      ("abc", 0),
+    ] {
+      let (_, cell_data) = type_option
+        .apply_changeset(input.to_string(), None)
+        .unwrap();
+      assert_eq!(cell_data, RatingCellData(expected), "input: {}", input);
+    }
+  }
+
+  #[test]
+  fn rating_type_option_serde_test() {
+    let data: TypeOptionData = RatingTypeOption::new(8).into();
+    assert_eq!(RatingTypeOption::from(data).max, 8);
+
+    // The maximum number of stars is clamped.
+    assert_eq!(RatingTypeOption::new(100).max, 10);
+    assert_eq!(RatingTypeOption::new(0).max, 1);
+  }
+
+  #[test]
+  fn rating_filter_test() {
+    let type_option = RatingTypeOption::default();
+    let filter = NumberFilterPB {
+      condition: NumberFilterConditionPB::GreaterThanOrEqualTo,
+      content: "3".to_string(),
+    };
+    assert!(type_option.apply_filter(&filter, &RatingCellData(4)));
+    assert!(type_option.apply_filter(&filter, &RatingCellData(3)));
+    assert!(!type_option.apply_filter(&filter, &RatingCellData(2)));
+
+    let filter = NumberFilterPB {
+      condition: NumberFilterConditionPB::NumberIsEmpty,
+      content: "".to_string(),
+    };
+    assert!(type_option.apply_filter(&filter, &RatingCellData(0)));
+    assert!(!type_option.apply_filter(&filter, &RatingCellData(1)));
+  }
+}
@@ -0,0 +1,266 @@
+use std::cmp::Ordering;
+
+use async_trait::async_trait;
+use collab::preclude::Any;
+use collab::util::AnyMapExt;
+use collab_database::database::Database;
+use collab_database::fields::number_type_option::{NumberCellFormat, NumberFormat};
+use collab_database::fields::{Field, TypeOptionCellReader, TypeOptionData, TypeOptionDataBuilder};
+use collab_database::rows::{new_cell_builder, Cell};
+use collab_database::template::util::ToCellString;
+use flowy_error::FlowyResult;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::info;
+
+use crate::entities::{FieldType, NumberFilterPB};
+use crate::services::cell::{CellDataChangeset, CellDataDecoder};
+use crate::services::field::type_options::util::ProtobufStr;
+use crate::services::field::{
+  CellDataProtobufEncoder, TypeOption, TypeOptionCellData, TypeOptionCellDataCompare,
+  TypeOptionCellDataFilter, TypeOptionTransform, CELL_DATA,
+};
+use crate::services::sort::SortCondition;
+
+const RATING_MAX: &str = "max";
+
+pub const DEFAULT_RATING_MAX: i64 = 5;
+pub const MAX_RATING_MAX: i64 = 10;
+
+/// The type option of the rating field. The value of a rating cell is the number of stars,
+/// between 1 and [RatingTypeOption::max]. A cell without stars is empty.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RatingTypeOption {
+  pub max: i64,
+}
+
+impl Default for RatingTypeOption {
+  fn default() -> Self {
+    Self {
+      max: DEFAULT_RATING_MAX,
+    }
+  }
+}
+
+impl RatingTypeOption {
+  pub fn new(max: i64) -> Self {
+    Self {
+      max: max.clamp(1, MAX_RATING_MAX),
+    }
+  }
+
+  /// Parses the content into a rating. Numbers are rounded to the nearest star and clamped to
+  /// the range of the type option. Returns an empty rating if the content is not a number.
+  pub fn parse_rating(&self, content: &str) -> RatingCellData {
+    let stars = content
+      .trim()
+      .parse::<f64>()
+      .ok()
+      .filter(|number| number.is_finite())
+      .map(|number| (number.round() as i64).clamp(0, self.max))
+      .unwrap_or_default();
+    RatingCellData(stars)
+  }
+}
+
+impl From<TypeOptionData> for RatingTypeOption {
+  fn from(data: TypeOptionData) -> Self {
+    Self::new(data.get_as::<i64>(RATING_MAX).unwrap_or(DEFAULT_RATING_MAX))
+  }
+}
+
+impl From<RatingTypeOption> for TypeOptionData {
+  fn from(data: RatingTypeOption) -> Self {
+    TypeOptionDataBuilder::from([(RATING_MAX.into(), Any::BigInt(data.max))])
+  }
+}
+
+impl TypeOptionCellReader for RatingTypeOption {
+  fn json_cell(&self, cell: &Cell) -> Value {
+    match RatingCellData::from(cell).0 {
+      0 => Value::Null,
+      stars => json!(stars),
+    }
+  }
+
+  fn numeric_cell(&self, cell: &Cell) -> Option<f64> {
+    let cell_data = RatingCellData::from(cell);
+    if cell_data.is_cell_empty() {
+      None
+    } else {
+      Some(cell_data.0 as f64)
+    }
+  }
+
+  fn convert_raw_cell_data(&self, cell_data: &str) -> String {
+    self.parse_rating(cell_data).to_cell_string()
+  }
+}
+
+impl TypeOption for RatingTypeOption {
+  type CellData = RatingCellData;
+  type CellChangeset = RatingCellChangeset;
+  type CellProtobufType = ProtobufStr;
+  type CellFilter = NumberFilterPB;
+}
+
+#[async_trait]
+impl TypeOptionTransform for RatingTypeOption {
+  async fn transform_type_option(
+    &mut self,
+    view_id: &str,
+    field_id: &str,
+    old_type_option_field_type: FieldType,
+    _old_type_option_data: TypeOptionData,
+    _new_type_option_field_type: FieldType,
+    database: &mut Database,
+  ) {
+    match old_type_option_field_type {
+      FieldType::RichText | FieldType::Number => {
+        let rows = database
+          .get_cells_for_field(view_id, field_id)
+          .await
+          .into_iter()
+          .filter_map(|row| row.cell.map(|cell| (row.row_id, cell)))
+          .collect::<Vec<_>>();
+
+        info!(
+          "Transforming {:?} to RatingTypeOption, updating {} row's cell content",
+          old_type_option_field_type,
+          rows.len()
+        );
+        for (row_id, cell_data) in rows {
+          let content = cell_data.get_as::<String>(CELL_DATA).unwrap_or_default();
+          let rating = self.parse_rating(&content);
+          database
+            .update_row(row_id, |row| {
+              row.update_cells(|cell| {
+                cell.insert(field_id, Cell::from(rating));
+              });
+            })
+            .await;
+        }
+      },
+      _ => {
+        // do nothing
+      },
+    }
+  }
+}
+
+impl CellDataProtobufEncoder for RatingTypeOption {
+  fn protobuf_encode(
+    &self,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    ProtobufStr::from(cell_data.to_cell_string())
+  }
+}
+
+impl CellDataDecoder for RatingTypeOption {
+  fn decode_cell(&self, cell: &Cell) -> FlowyResult<<Self as TypeOption>::CellData> {
+    let cell_data = RatingCellData::from(cell);
+    Ok(RatingCellData(cell_data.0.clamp(0, self.max)))
+  }
+
+  fn decode_cell_with_transform(
+    &self,
+    cell: &Cell,
+    from_field_type: FieldType,
+    _field: &Field,
+  ) -> Option<<Self as TypeOption>::CellData> {
+    match from_field_type {
+      FieldType::RichText | FieldType::Number => {
+        let content = cell.get_as::<String>(CELL_DATA)?;
+        Some(self.parse_rating(&content))
+      },
+      _ => None,
+    }
+  }
+
+  fn stringify_cell_data(&self, cell_data: <Self as TypeOption>::CellData) -> String {
+    cell_data.to_cell_string()
+  }
+}
+
+/// The number of stars. Non-numeric changesets clear the rating.
+pub type RatingCellChangeset = String;
+
+impl CellDataChangeset for RatingTypeOption {
+  fn apply_changeset(
+    &self,
+    changeset: <Self as TypeOption>::CellChangeset,
+    _cell: Option<Cell>,
+  ) -> FlowyResult<(Cell, <Self as TypeOption>::CellData)> {
+    let cell_data = self.parse_rating(&changeset);
+    Ok((Cell::from(cell_data), cell_data))
+  }
+}
+
+impl TypeOptionCellDataFilter for RatingTypeOption {
+  fn apply_filter(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    match NumberCellFormat::from_format_str(&cell_data.to_cell_string(), &NumberFormat::Num) {
+      Ok(cell_data) => filter.is_visible(&cell_data).unwrap_or(true),
+      Err(_) => true,
+    }
+  }
+}
+
+impl TypeOptionCellDataCompare for RatingTypeOption {
+  fn apply_cmp(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+    sort_condition: SortCondition,
+  ) -> Ordering {
+    match (cell_data.is_cell_empty(), other_cell_data.is_cell_empty()) {
+      (true, true) => Ordering::Equal,
+      (true, false) => Ordering::Greater,
+      (false, true) => Ordering::Less,
+      (false, false) => sort_condition.evaluate_order(cell_data.0.cmp(&other_cell_data.0)),
+    }
+  }
+}
+
+/// The number of stars of a rating cell. The stars are stored as a string, so the cell can be
+/// read by the number and text fields after the field type is changed.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RatingCellData(pub i64);
+
+impl TypeOptionCellData for RatingCellData {
+  fn is_cell_empty(&self) -> bool {
+    self.0 <= 0
+  }
+}
+
+impl From<&Cell> for RatingCellData {
+  fn from(cell: &Cell) -> Self {
+    let stars = cell
+      .get_as::<String>(CELL_DATA)
+      .and_then(|s| s.parse::<i64>().ok())
+      .unwrap_or_default();
+    Self(stars.max(0))
+  }
+}
+
+impl From<RatingCellData> for Cell {
+  fn from(data: RatingCellData) -> Self {
+    let mut cell = new_cell_builder(FieldType::Rating);
+    cell.insert(CELL_DATA.into(), data.to_cell_string().into());
+    cell
+  }
+}
+
+impl ToCellString for RatingCellData {
+  fn to_cell_string(&self) -> String {
+    if self.is_cell_empty() {
+      "".to_string()
+    } else {
+      self.0.to_string()
+    }
+  }
+}
@@ -1,4 +1,5 @@
 mod relation;
 mod relation_entities;
+mod relation_filter;
 
 pub use relation_entities::*;
@@ -74,8 +74,8 @@ impl TypeOptionCellDataCompare for RelationTypeOption {
 }
 
 impl TypeOptionCellDataFilter for RelationTypeOption {
-  fn apply_filter(&self, _filter: &RelationFilterPB, _cell_data: &RelationCellData) -> bool {
-    true
+  fn apply_filter(&self, filter: &RelationFilterPB, cell_data: &RelationCellData) -> bool {
+    filter.is_visible(cell_data).unwrap_or(true)
   }
 }
 
@@ -1,7 +1,21 @@
+use bytes::Bytes;
 use collab_database::rows::RowId;
+use flowy_error::{internal_error, FlowyResult};
+
+use crate::entities::RelationCellDataPB;
+use crate::services::cell::CellProtobufBlobParser;
 
 #[derive(Debug, Clone, Default)]
 pub struct RelationCellChangeset {
   pub inserted_row_ids: Vec<RowId>,
   pub removed_row_ids: Vec<RowId>,
 }
+
+pub struct RelationCellDataParser();
+impl CellProtobufBlobParser for RelationCellDataParser {
+  type Object = RelationCellDataPB;
+
+  fn parser(bytes: &Bytes) -> FlowyResult<Self::Object> {
+    RelationCellDataPB::try_from(bytes.as_ref()).map_err(internal_error)
+  }
+}
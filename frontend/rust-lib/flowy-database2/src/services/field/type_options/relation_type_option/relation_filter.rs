@@ -0,0 +1,128 @@
+use collab_database::fields::Field;
+use collab_database::rows::{Cell, RowId};
+use collab_database::template::relation_parse::RelationCellData;
+
+use crate::entities::{RelationFilterConditionPB, RelationFilterPB};
+use crate::services::filter::PreFillCellsWithFilter;
+
+impl RelationFilterPB {
+  /// Returns None if the filter requires row ids but none were provided, in which case the filter
+  /// is ignored.
+  pub fn is_visible(&self, cell_data: &RelationCellData) -> Option<bool> {
+    let related_row_ids = &cell_data.row_ids;
+    let contains = |row_id: &String| related_row_ids.iter().any(|id| id.as_str() == row_id);
+
+    let is_visible = match self.condition {
+      RelationFilterConditionPB::RelationIsEmpty => related_row_ids.is_empty(),
+      RelationFilterConditionPB::RelationIsNotEmpty => !related_row_ids.is_empty(),
+      RelationFilterConditionPB::RelationContains => contains(self.row_ids.first()?),
+      RelationFilterConditionPB::RelationDoesNotContain => !contains(self.row_ids.first()?),
+      RelationFilterConditionPB::RelationContainsAny => {
+        if self.row_ids.is_empty() {
+          return None;
+        }
+        self.row_ids.iter().any(contains)
+      },
+      RelationFilterConditionPB::RelationContainsAll => {
+        if self.row_ids.is_empty() {
+          return None;
+        }
+        self.row_ids.iter().all(contains)
+      },
+    };
+    Some(is_visible)
+  }
+}
+
+impl PreFillCellsWithFilter for RelationFilterPB {
+  fn get_compliant_cell(&self, _field: &Field) -> Option<Cell> {
+    let row_ids = match self.condition {
+      RelationFilterConditionPB::RelationContains
+      | RelationFilterConditionPB::RelationContainsAny => {
+        self.row_ids.first().map(|row_id| vec![row_id.clone()])
+      },
+      RelationFilterConditionPB::RelationContainsAll => {
+        (!self.row_ids.is_empty()).then(|| self.row_ids.clone())
+      },
+      _ => None,
+    }?;
+
+    let cell_data = RelationCellData {
+      row_ids: row_ids.into_iter().map(RowId::from).collect(),
+    };
+    Some(Cell::from(cell_data))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use collab_database::rows::RowId;
+  use collab_database::template::relation_parse::RelationCellData;
+
+  use crate::entities::{RelationFilterConditionPB, RelationFilterPB};
+
+  fn cell_data(row_ids: &[&str]) -> RelationCellData {
+    RelationCellData {
+      row_ids: row_ids
+        .iter()
+        .map(|id| RowId::from(id.to_string()))
+        .collect(),
+    }
+  }
+
+  fn filter(condition: RelationFilterConditionPB, row_ids: &[&str]) -> RelationFilterPB {
+    RelationFilterPB {
+      condition,
+      row_ids: row_ids.iter().map(|id| id.to_string()).collect(),
+    }
+  }
+
+  #[test]
+  fn relation_filter_is_empty_test() {
+    let is_empty = filter(RelationFilterConditionPB::RelationIsEmpty, &[]);
+    assert_eq!(is_empty.is_visible(&cell_data(&[])), Some(true));
+    assert_eq!(is_empty.is_visible(&cell_data(&["a"])), Some(false));
+
+    let is_not_empty = filter(RelationFilterConditionPB::RelationIsNotEmpty, &[]);
+    assert_eq!(is_not_empty.is_visible(&cell_data(&[])), Some(false));
+    assert_eq!(is_not_empty.is_visible(&cell_data(&["a"])), Some(true));
+  }
+
+  #[test]
+  fn relation_filter_contains_test() {
+    let contains = filter(RelationFilterConditionPB::RelationContains, &["a"]);
+    let does_not_contain = filter(RelationFilterConditionPB::RelationDoesNotContain, &["a"]);
+    for (row_ids, is_visible) in [
+      (vec![], false),
+      (vec!["a"], true),
+      (vec!["b"], false),
+      (vec!["b", "a"], true),
+    ] {
+      let cell_data = cell_data(&row_ids);
+      assert_eq!(contains.is_visible(&cell_data), Some(is_visible));
+      assert_eq!(does_not_contain.is_visible(&cell_data), Some(!is_visible));
+    }
+
+    let contains = filter(RelationFilterConditionPB::RelationContains, &[]);
+    assert_eq!(contains.is_visible(&cell_data(&["a"])), None);
+  }
+
+  #[test]
+  fn relation_filter_contains_any_and_all_test() {
+    let contains_any = filter(RelationFilterConditionPB::RelationContainsAny, &["a", "b"]);
+    let contains_all = filter(RelationFilterConditionPB::RelationContainsAll, &["a", "b"]);
+    for (row_ids, any, all) in [
+      (vec![], false, false),
+      (vec!["a"], true, false),
+      (vec!["c"], false, false),
+      (vec!["b", "c", "a"], true, true),
+    ] {
+      let cell_data = cell_data(&row_ids);
+      assert_eq!(contains_any.is_visible(&cell_data), Some(any));
+      assert_eq!(contains_all.is_visible(&cell_data), Some(all));
+    }
+
+    let contains_any = filter(RelationFilterConditionPB::RelationContainsAny, &[]);
+    assert_eq!(contains_any.is_visible(&cell_data(&["a"])), None);
+  }
+}
@@ -0,0 +1,7 @@
+#![allow(clippy::module_inception)]
+mod rollup_calculation;
+mod rollup_tests;
+mod rollup_type_option;
+
+pub use rollup_calculation::*;
+pub use rollup_type_option::*;
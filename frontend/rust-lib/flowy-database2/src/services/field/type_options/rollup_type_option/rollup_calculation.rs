@@ -0,0 +1,101 @@
+use std::collections::HashSet;
+
+use crate::services::field::format_number;
+
+/// The way the values of the target field in the related rows are aggregated into the rollup
+/// cell.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RollupCalculation {
+  #[default]
+  ShowOriginal = 0,
+  Sum = 1,
+  Average = 2,
+  Min = 3,
+  Max = 4,
+  Count = 5,
+  CountUnique = 6,
+  PercentChecked = 7,
+}
+
+impl From<i64> for RollupCalculation {
+  fn from(value: i64) -> Self {
+    match value {
+      1 => RollupCalculation::Sum,
+      2 => RollupCalculation::Average,
+      3 => RollupCalculation::Min,
+      4 => RollupCalculation::Max,
+      5 => RollupCalculation::Count,
+      6 => RollupCalculation::CountUnique,
+      7 => RollupCalculation::PercentChecked,
+      _ => RollupCalculation::ShowOriginal,
+    }
+  }
+}
+
+impl From<RollupCalculation> for i64 {
+  fn from(value: RollupCalculation) -> Self {
+    value as i64
+  }
+}
+
+/// The value of the target field in one of the related rows.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RollupSourceValue {
+  pub content: String,
+  pub number: Option<f64>,
+  pub is_checked: bool,
+}
+
+impl RollupCalculation {
+  /// Aggregates the values of the related rows. Returns an empty string if there is nothing to
+  /// aggregate, so the rollup cell is treated as empty by the filters and sorts.
+  pub fn aggregate(&self, values: &[RollupSourceValue]) -> String {
+    let numbers = || values.iter().filter_map(|value| value.number);
+    match self {
+      RollupCalculation::ShowOriginal => values
+        .iter()
+        .filter(|value| !value.content.is_empty())
+        .map(|value| value.content.as_str())
+        .collect::<Vec<&str>>()
+        .join(", "),
+      RollupCalculation::Sum => {
+        if numbers().next().is_none() {
+          return String::new();
+        }
+        format_number(numbers().sum())
+      },
+      RollupCalculation::Average => {
+        let count = numbers().count();
+        if count == 0 {
+          return String::new();
+        }
+        format_number(numbers().sum::<f64>() / count as f64)
+      },
+      RollupCalculation::Min => numbers()
+        .reduce(f64::min)
+        .map(format_number)
+        .unwrap_or_default(),
+      RollupCalculation::Max => numbers()
+        .reduce(f64::max)
+        .map(format_number)
+        .unwrap_or_default(),
+      RollupCalculation::Count => values.len().to_string(),
+      RollupCalculation::CountUnique => values
+        .iter()
+        .filter(|value| !value.content.is_empty())
+        .map(|value| value.content.as_str())
+        .collect::<HashSet<&str>>()
+        .len()
+        .to_string(),
+      RollupCalculation::PercentChecked => {
+        if values.is_empty() {
+          return String::new();
+        }
+        let checked = values.iter().filter(|value| value.is_checked).count();
+        let percent = checked as f64 / values.len() as f64 * 100.0;
+        format!("{}%", format_number((percent * 100.0).round() / 100.0))
+      },
+    }
+  }
+}
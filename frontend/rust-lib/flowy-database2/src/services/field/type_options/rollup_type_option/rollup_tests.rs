@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+  use collab_database::fields::TypeOptionData;
+
+  use crate::services::field::{RollupCalculation, RollupSourceValue, RollupTypeOption};
+
+  fn number(n: f64) -> RollupSourceValue {
+    RollupSourceValue {
+      content: n.to_string(),
+      number: Some(n),
+      is_checked: false,
+    }
+  }
+
+  fn text(s: &str) -> RollupSourceValue {
+    RollupSourceValue {
+      content: s.to_string(),
+      number: None,
+      is_checked: false,
+    }
+  }
+
+  fn checkbox(is_checked: bool) -> RollupSourceValue {
+    RollupSourceValue {
+      content: if is_checked { "Yes" } else { "No" }.to_string(),
+      number: None,
+      is_checked,
+    }
+  }
+
+  #[test]
+  fn rollup_numeric_calculation_test() {
+    let values = vec![number(1.5), number(3.0), text(""), number(5.5)];
+    assert_eq!(RollupCalculation::Sum.aggregate(&values), "10");
+    assert_eq!(
+      RollupCalculation::Average.aggregate(&values),
+      "3.3333333333"
+    );
+    assert_eq!(RollupCalculation::Min.aggregate(&values), "1.5");
+    assert_eq!(RollupCalculation::Max.aggregate(&values), "5.5");
+  }
+
+  #[test]
+  fn rollup_numeric_calculation_without_values_test() {
+    let values = vec![text("a"), text("")];
+    assert_eq!(RollupCalculation::Sum.aggregate(&values), "");
+    assert_eq!(RollupCalculation::Average.aggregate(&values), "");
+    assert_eq!(RollupCalculation::Min.aggregate(&values), "");
+    assert_eq!(RollupCalculation::Max.aggregate(&values), "");
+    assert_eq!(RollupCalculation::Sum.aggregate(&[]), "");
+  }
+
+  #[test]
+  fn rollup_count_calculation_test() {
+    let values = vec![text("a"), text("b"), text("a"), text("")];
+    assert_eq!(RollupCalculation::Count.aggregate(&values), "4");
+    assert_eq!(RollupCalculation::CountUnique.aggregate(&values), "2");
+    assert_eq!(RollupCalculation::Count.aggregate(&[]), "0");
+  }
+
+  #[test]
+  fn rollup_show_original_test() {
+    let values = vec![text("Design"), text(""), text("Build")];
+    assert_eq!(
+      RollupCalculation::ShowOriginal.aggregate(&values),
+      "Design, Build"
+    );
+  }
+
+  #[test]
+  fn rollup_percent_checked_test() {
+    let values = vec![checkbox(true), checkbox(false), checkbox(true)];
+    assert_eq!(
+      RollupCalculation::PercentChecked.aggregate(&values),
+      "66.67%"
+    );
+    assert_eq!(RollupCalculation::PercentChecked.aggregate(&[]), "");
+  }
+
+  #[test]
+  fn rollup_type_option_serde_test() {
+    let type_option = RollupTypeOption::new("relation", "hours", RollupCalculation::Average);
+    let data: TypeOptionData = type_option.into();
+    let type_option = RollupTypeOption::from(data);
+    assert_eq!(type_option.relation_field_id, "relation");
+    assert_eq!(type_option.target_field_id, "hours");
+    assert_eq!(type_option.calculation, RollupCalculation::Average);
+  }
+}
@@ -0,0 +1,213 @@
+use std::cmp::Ordering;
+
+use collab::preclude::Any;
+use collab::util::AnyMapExt;
+use collab_database::fields::{TypeOptionCellReader, TypeOptionData, TypeOptionDataBuilder};
+use collab_database::rows::{new_cell_builder, Cell};
+use collab_database::template::util::ToCellString;
+use flowy_error::{FlowyError, FlowyResult};
+use serde_json::{json, Value};
+
+use crate::entities::{FieldType, TextFilterPB};
+use crate::services::cell::{CellDataChangeset, CellDataDecoder};
+use crate::services::field::type_options::util::ProtobufStr;
+use crate::services::field::{
+  CellDataProtobufEncoder, RollupCalculation, TypeOption, TypeOptionCellData,
+  TypeOptionCellDataCompare, TypeOptionCellDataFilter, TypeOptionTransform, CELL_DATA,
+};
+use crate::services::sort::SortCondition;
+
+const ROLLUP_RELATION_FIELD_ID: &str = "relation_field_id";
+const ROLLUP_TARGET_FIELD_ID: &str = "target_field_id";
+const ROLLUP_CALCULATION: &str = "calculation";
+
+/// The type option of the rollup field. A rollup field aggregates the values of the
+/// [RollupTypeOption::target_field_id] field of the rows linked by the
+/// [RollupTypeOption::relation_field_id] relation field. The computed value is stored in the row.
+#[derive(Debug, Clone, Default)]
+pub struct RollupTypeOption {
+  pub relation_field_id: String,
+  pub target_field_id: String,
+  pub calculation: RollupCalculation,
+}
+
+impl RollupTypeOption {
+  pub fn new(
+    relation_field_id: &str,
+    target_field_id: &str,
+    calculation: RollupCalculation,
+  ) -> Self {
+    Self {
+      relation_field_id: relation_field_id.to_string(),
+      target_field_id: target_field_id.to_string(),
+      calculation,
+    }
+  }
+
+  pub fn is_configured(&self) -> bool {
+    !self.relation_field_id.is_empty() && !self.target_field_id.is_empty()
+  }
+}
+
+impl From<TypeOptionData> for RollupTypeOption {
+  fn from(data: TypeOptionData) -> Self {
+    Self {
+      relation_field_id: data
+        .get_as::<String>(ROLLUP_RELATION_FIELD_ID)
+        .unwrap_or_default(),
+      target_field_id: data
+        .get_as::<String>(ROLLUP_TARGET_FIELD_ID)
+        .unwrap_or_default(),
+      calculation: data
+        .get_as::<i64>(ROLLUP_CALCULATION)
+        .map(RollupCalculation::from)
+        .unwrap_or_default(),
+    }
+  }
+}
+
+impl From<RollupTypeOption> for TypeOptionData {
+  fn from(data: RollupTypeOption) -> Self {
+    TypeOptionDataBuilder::from([
+      (
+        ROLLUP_RELATION_FIELD_ID.into(),
+        Any::from(data.relation_field_id),
+      ),
+      (
+        ROLLUP_TARGET_FIELD_ID.into(),
+        Any::from(data.target_field_id),
+      ),
+      (
+        ROLLUP_CALCULATION.into(),
+        Any::BigInt(i64::from(data.calculation)),
+      ),
+    ])
+  }
+}
+
+impl TypeOptionCellReader for RollupTypeOption {
+  fn json_cell(&self, cell: &Cell) -> Value {
+    let cell_data = RollupCellData::from(cell);
+    match cell_data.to_number() {
+      Some(number) => json!(number),
+      None => json!(cell_data.0),
+    }
+  }
+
+  fn numeric_cell(&self, cell: &Cell) -> Option<f64> {
+    RollupCellData::from(cell).to_number()
+  }
+
+  fn convert_raw_cell_data(&self, cell_data: &str) -> String {
+    cell_data.to_string()
+  }
+}
+
+impl TypeOption for RollupTypeOption {
+  type CellData = RollupCellData;
+  type CellChangeset = String;
+  type CellProtobufType = ProtobufStr;
+  type CellFilter = TextFilterPB;
+}
+
+impl TypeOptionTransform for RollupTypeOption {}
+
+impl CellDataProtobufEncoder for RollupTypeOption {
+  fn protobuf_encode(
+    &self,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    ProtobufStr::from(cell_data.0)
+  }
+}
+
+impl CellDataDecoder for RollupTypeOption {
+  fn stringify_cell_data(&self, cell_data: <Self as TypeOption>::CellData) -> String {
+    cell_data.0
+  }
+}
+
+impl CellDataChangeset for RollupTypeOption {
+  fn apply_changeset(
+    &self,
+    _changeset: <Self as TypeOption>::CellChangeset,
+    _cell: Option<Cell>,
+  ) -> FlowyResult<(Cell, <Self as TypeOption>::CellData)> {
+    Err(FlowyError::invalid_data().with_context("The cell of a rollup field is read-only"))
+  }
+}
+
+impl TypeOptionCellDataFilter for RollupTypeOption {
+  fn apply_filter(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    filter.is_visible(cell_data)
+  }
+}
+
+impl TypeOptionCellDataCompare for RollupTypeOption {
+  fn apply_cmp(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+    sort_condition: SortCondition,
+  ) -> Ordering {
+    match (cell_data.is_cell_empty(), other_cell_data.is_cell_empty()) {
+      (true, true) => Ordering::Equal,
+      (true, false) => Ordering::Greater,
+      (false, true) => Ordering::Less,
+      (false, false) => {
+        let order = match (cell_data.to_number(), other_cell_data.to_number()) {
+          (Some(left), Some(right)) => left.total_cmp(&right),
+          _ => cell_data.0.cmp(&other_cell_data.0),
+        };
+        sort_condition.evaluate_order(order)
+      },
+    }
+  }
+}
+
+/// The aggregated value of a rollup cell.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct RollupCellData(pub String);
+
+impl RollupCellData {
+  /// Returns the numeric value of the cell. Percentages are returned without the percent sign.
+  pub fn to_number(&self) -> Option<f64> {
+    self.0.trim_end_matches('%').parse::<f64>().ok()
+  }
+}
+
+impl TypeOptionCellData for RollupCellData {
+  fn is_cell_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+}
+
+impl From<&Cell> for RollupCellData {
+  fn from(cell: &Cell) -> Self {
+    Self(cell.get_as(CELL_DATA).unwrap_or_default())
+  }
+}
+
+impl From<RollupCellData> for Cell {
+  fn from(data: RollupCellData) -> Self {
+    let mut cell = new_cell_builder(FieldType::Rollup);
+    cell.insert(CELL_DATA.into(), data.0.into());
+    cell
+  }
+}
+
+impl ToCellString for RollupCellData {
+  fn to_cell_string(&self) -> String {
+    self.0.clone()
+  }
+}
+
+impl AsRef<str> for RollupCellData {
+  fn as_ref(&self) -> &str {
+    self.0.as_str()
+  }
+}
@@ -1,13 +1,25 @@
 use collab_database::{fields::Field, rows::Cell};
+use dashmap::DashMap;
+use fancy_regex::Regex;
+use lazy_static::lazy_static;
 
 use crate::entities::{TextFilterConditionPB, TextFilterPB};
 use crate::services::cell::insert_text_cell;
 use crate::services::filter::PreFillCellsWithFilter;
 
+/// The number of compiled patterns that are kept, the cache is cleared when it's full.
+const MAX_CACHED_REGEXES: usize = 64;
+
+lazy_static! {
+  /// The compiled patterns of the regex filters, which are applied to every row. The pattern is
+  /// None if it's not a valid regular expression.
+  static ref REGEX_BY_PATTERN: DashMap<String, Option<Regex>> = DashMap::new();
+}
+
 impl TextFilterPB {
   pub fn is_visible<T: AsRef<str>>(&self, cell_data: T) -> bool {
-    let cell_data = cell_data.as_ref().to_lowercase();
-    let content = &self.content.to_lowercase();
+    let cell_data = self.normalize(cell_data.as_ref());
+    let content = &self.normalize(&self.content);
 
     match self.condition {
       TextFilterConditionPB::TextIs
@@ -16,6 +28,7 @@ impl TextFilterPB {
       | TextFilterConditionPB::TextDoesNotContain
       | TextFilterConditionPB::TextStartsWith
       | TextFilterConditionPB::TextEndsWith
+      | TextFilterConditionPB::TextMatchesRegex
         if content.is_empty() =>
       {
         true
@@ -28,12 +41,64 @@ impl TextFilterPB {
       TextFilterConditionPB::TextEndsWith => cell_data.ends_with(content),
       TextFilterConditionPB::TextIsEmpty => cell_data.is_empty(),
       TextFilterConditionPB::TextIsNotEmpty => !cell_data.is_empty(),
+      TextFilterConditionPB::TextMatchesRegex => match self.get_regex(content) {
+        // The text is matched as it is, the case is handled by the pattern
+        Some(regex) => regex.is_match(&cell_data).unwrap_or(false),
+        // The filter is ignored until the pattern is valid
+        None => true,
+      },
+      TextFilterConditionPB::TextMatchesAnyOf => {
+        self.values.is_empty()
+          || self
+            .values
+            .iter()
+            .any(|value| self.normalize(value) == cell_data)
+      },
+    }
+  }
+
+  /// Returns the text as it is compared according to the options of the filter. The regex
+  /// patterns are case-insensitive with the `i` flag instead.
+  fn normalize(&self, text: &str) -> String {
+    let text = if self.trim_whitespace {
+      text.trim()
+    } else {
+      text
+    };
+
+    if self.case_sensitive || self.condition == TextFilterConditionPB::TextMatchesRegex {
+      text.to_string()
+    } else {
+      text.to_lowercase()
     }
   }
+
+  fn get_regex(&self, content: &str) -> Option<Regex> {
+    let pattern = if self.case_sensitive {
+      content.to_string()
+    } else {
+      format!("(?i){}", content)
+    };
+
+    if let Some(regex) = REGEX_BY_PATTERN.get(&pattern) {
+      return regex.clone();
+    }
+
+    let regex = Regex::new(&pattern).ok();
+    if regex.is_none() {
+      tracing::warn!("Invalid regex of the text filter: {}", content);
+    }
+    if REGEX_BY_PATTERN.len() >= MAX_CACHED_REGEXES {
+      REGEX_BY_PATTERN.clear();
+    }
+    REGEX_BY_PATTERN.insert(pattern, regex.clone());
+    regex
+  }
 }
 
 impl PreFillCellsWithFilter for TextFilterPB {
   fn get_compliant_cell(&self, field: &Field) -> Option<Cell> {
+    // A text that matches a regex can't be generated from the pattern
     let text = match self.condition {
       TextFilterConditionPB::TextIs
       | TextFilterConditionPB::TextContains
@@ -43,6 +108,7 @@ impl PreFillCellsWithFilter for TextFilterPB {
       {
         Some(self.content.clone())
       },
+      TextFilterConditionPB::TextMatchesAnyOf => self.values.first().cloned(),
       _ => None,
     };
 
@@ -54,12 +120,14 @@ impl PreFillCellsWithFilter for TextFilterPB {
 mod tests {
   #![allow(clippy::all)]
   use crate::entities::{TextFilterConditionPB, TextFilterPB};
+  use crate::services::filter::ParseFilterData;
 
   #[test]
   fn text_filter_equal_test() {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::TextIs,
       content: "appflowy".to_owned(),
+      ..Default::default()
     };
 
     assert_eq!(text_filter.is_visible("AppFlowy"), true);
@@ -71,6 +139,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::TextIs,
       content: "".to_owned(),
+      ..Default::default()
     };
 
     assert_eq!(text_filter.is_visible("AppFlowy"), true);
@@ -84,6 +153,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::TextStartsWith,
       content: "appflowy".to_owned(),
+      ..Default::default()
     };
 
     assert_eq!(text_filter.is_visible("AppFlowy.io"), true);
@@ -94,6 +164,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::TextStartsWith,
       content: "".to_owned(),
+      ..Default::default()
     };
 
     assert_eq!(text_filter.is_visible("AppFlowy.io"), true);
@@ -107,6 +178,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::TextEndsWith,
       content: "appflowy".to_owned(),
+      ..Default::default()
     };
 
     assert_eq!(text_filter.is_visible("https://github.com/appflowy"), true);
@@ -117,6 +189,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::TextEndsWith,
       content: "".to_owned(),
+      ..Default::default()
     };
 
     assert_eq!(text_filter.is_visible("https://github.com/appflowy"), true);
@@ -129,6 +202,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::TextIsEmpty,
       content: "appflowy".to_owned(),
+      ..Default::default()
     };
 
     assert_eq!(text_filter.is_visible(""), true);
@@ -137,6 +211,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::TextIsEmpty,
       content: "".to_owned(),
+      ..Default::default()
     };
 
     assert_eq!(text_filter.is_visible(""), true);
@@ -147,6 +222,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::TextContains,
       content: "appflowy".to_owned(),
+      ..Default::default()
     };
 
     assert_eq!(text_filter.is_visible("https://github.com/appflowy"), true);
@@ -158,6 +234,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::TextContains,
       content: "".to_owned(),
+      ..Default::default()
     };
 
     assert_eq!(text_filter.is_visible("https://github.com/appflowy"), true);
@@ -166,4 +243,89 @@ mod tests {
     assert_eq!(text_filter.is_visible(""), true);
     assert_eq!(text_filter.is_visible("github"), true);
   }
+
+  #[test]
+  fn text_filter_regex_test() {
+    let text_filter = TextFilterPB {
+      condition: TextFilterConditionPB::TextMatchesRegex,
+      content: r"^error: \d+".to_owned(),
+      ..Default::default()
+    };
+
+    assert_eq!(text_filter.is_visible("ERROR: 404 not found"), true);
+    assert_eq!(text_filter.is_visible("warning: 404"), false);
+    assert_eq!(text_filter.is_visible(""), false);
+
+    let text_filter = TextFilterPB {
+      condition: TextFilterConditionPB::TextMatchesRegex,
+      content: r"^error: \d+".to_owned(),
+      case_sensitive: true,
+      ..Default::default()
+    };
+
+    assert_eq!(text_filter.is_visible("ERROR: 404 not found"), false);
+    assert_eq!(text_filter.is_visible("error: 404 not found"), true);
+
+    // An invalid pattern is ignored
+    let text_filter = TextFilterPB {
+      condition: TextFilterConditionPB::TextMatchesRegex,
+      content: "(error".to_owned(),
+      ..Default::default()
+    };
+
+    assert_eq!(text_filter.is_visible("warning"), true);
+  }
+
+  #[test]
+  fn text_filter_case_sensitive_test() {
+    let text_filter = TextFilterPB {
+      condition: TextFilterConditionPB::TextIs,
+      content: "AppFlowy".to_owned(),
+      case_sensitive: true,
+      ..Default::default()
+    };
+
+    assert_eq!(text_filter.is_visible("AppFlowy"), true);
+    assert_eq!(text_filter.is_visible("appflowy"), false);
+  }
+
+  #[test]
+  fn text_filter_trim_whitespace_test() {
+    let text_filter = TextFilterPB {
+      condition: TextFilterConditionPB::TextEndsWith,
+      content: "flowy ".to_owned(),
+      trim_whitespace: true,
+      ..Default::default()
+    };
+
+    assert_eq!(text_filter.is_visible(" AppFlowy  "), true);
+    assert_eq!(text_filter.is_visible("AppFlowy.io"), false);
+
+    let text_filter = TextFilterPB {
+      condition: TextFilterConditionPB::TextIsEmpty,
+      trim_whitespace: true,
+      ..Default::default()
+    };
+
+    assert_eq!(text_filter.is_visible("  "), true);
+  }
+
+  #[test]
+  fn text_filter_matches_any_of_test() {
+    let text_filter = TextFilterPB {
+      condition: TextFilterConditionPB::TextMatchesAnyOf,
+      values: vec!["Done".to_owned(), "In progress".to_owned()],
+      ..Default::default()
+    };
+
+    assert_eq!(text_filter.is_visible("done"), true);
+    assert_eq!(text_filter.is_visible("In Progress"), true);
+    assert_eq!(text_filter.is_visible("Not started"), false);
+    assert_eq!(text_filter.is_visible(""), false);
+
+    // The values are stored in the content of the filter
+    let content = text_filter.to_content();
+    let parsed_filter = TextFilterPB::parse(TextFilterConditionPB::TextMatchesAnyOf as u8, content);
+    assert_eq!(parsed_filter.values, text_filter.values);
+  }
 }
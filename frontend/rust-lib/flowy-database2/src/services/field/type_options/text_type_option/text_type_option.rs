@@ -52,7 +52,14 @@ impl CellDataDecoder for RichTextTypeOption {
       | FieldType::Summary
       | FieldType::Translate
       | FieldType::Media
-      | FieldType::Time => Some(StringCellData::from(stringify_cell(cell, field))),
+      | FieldType::Time
+      | FieldType::Formula
+      | FieldType::Rollup
+      | FieldType::Rating
+      | FieldType::Email
+      | FieldType::Phone
+      | FieldType::AutoNumber
+      | FieldType::Person => Some(StringCellData::from(stringify_cell(cell, field))),
       FieldType::Checklist
       | FieldType::LastEditedTime
       | FieldType::CreatedTime
@@ -1,2 +1,4 @@
 #![allow(clippy::module_inception)]
 mod timestamp_type_option;
+
+pub use timestamp_type_option::*;
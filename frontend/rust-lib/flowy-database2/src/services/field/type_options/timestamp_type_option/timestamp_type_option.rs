@@ -1,14 +1,15 @@
 use crate::entities::{DateFilterPB, TimestampCellDataPB};
-use crate::services::cell::{CellDataChangeset, CellDataDecoder};
+use crate::services::cell::{CellDataChangeset, CellDataDecoder, CellProtobufBlobParser};
 use crate::services::field::{
   default_order, CellDataProtobufEncoder, TypeOption, TypeOptionCellDataCompare,
   TypeOptionCellDataFilter, TypeOptionTransform,
 };
 use crate::services::sort::SortCondition;
+use bytes::Bytes;
 use collab_database::fields::timestamp_type_option::TimestampTypeOption;
 use collab_database::rows::Cell;
 use collab_database::template::timestamp_parse::TimestampCellData;
-use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+use flowy_error::{internal_error, ErrorCode, FlowyError, FlowyResult};
 use std::cmp::Ordering;
 
 impl TypeOption for TimestampTypeOption {
@@ -90,3 +91,12 @@ impl TypeOptionCellDataCompare for TimestampTypeOption {
     }
   }
 }
+
+pub struct TimestampCellDataParser();
+impl CellProtobufBlobParser for TimestampCellDataParser {
+  type Object = TimestampCellDataPB;
+
+  fn parser(bytes: &Bytes) -> FlowyResult<Self::Object> {
+    TimestampCellDataPB::try_from(bytes.as_ref()).map_err(internal_error)
+  }
+}
@@ -1,10 +1,16 @@
 use crate::entities::{
-  CheckboxTypeOptionPB, ChecklistTypeOptionPB, DateTypeOptionPB, FieldType, MediaTypeOptionPB,
-  MultiSelectTypeOptionPB, NumberTypeOptionPB, RelationTypeOptionPB, RichTextTypeOptionPB,
-  SingleSelectTypeOptionPB, SummarizationTypeOptionPB, TimeTypeOptionPB, TimestampTypeOptionPB,
-  TranslateTypeOptionPB, URLTypeOptionPB,
+  AutoNumberTypeOptionPB, CheckboxTypeOptionPB, ChecklistTypeOptionPB, DateTypeOptionPB,
+  EmailTypeOptionPB, FieldType, FormulaTypeOptionPB, MediaTypeOptionPB, MultiSelectTypeOptionPB,
+  NumberTypeOptionPB, PersonTypeOptionPB, PhoneTypeOptionPB, RatingTypeOptionPB,
+  RelationTypeOptionPB, RichTextTypeOptionPB, RollupTypeOptionPB, SingleSelectTypeOptionPB,
+  SummarizationTypeOptionPB, TimeTypeOptionPB, TimestampTypeOptionPB, TranslateTypeOptionPB,
+  URLTypeOptionPB,
 };
 use crate::services::cell::CellDataDecoder;
+use crate::services::field::{
+  AutoNumberTypeOption, EmailTypeOption, FormulaTypeOption, PersonTypeOption, PhoneTypeOption,
+  RatingTypeOption, RollupTypeOption,
+};
 use crate::services::filter::{ParseFilterData, PreFillCellsWithFilter};
 use crate::services::sort::SortCondition;
 use async_trait::async_trait;
@@ -190,6 +196,27 @@ pub fn type_option_data_from_pb<T: Into<Bytes>>(
     FieldType::Media => {
       MediaTypeOptionPB::try_from(bytes).map(|pb| MediaTypeOption::from(pb).into())
     },
+    FieldType::Formula => {
+      FormulaTypeOptionPB::try_from(bytes).map(|pb| FormulaTypeOption::from(pb).into())
+    },
+    FieldType::Rollup => {
+      RollupTypeOptionPB::try_from(bytes).map(|pb| RollupTypeOption::from(pb).into())
+    },
+    FieldType::Rating => {
+      RatingTypeOptionPB::try_from(bytes).map(|pb| RatingTypeOption::from(pb).into())
+    },
+    FieldType::Email => {
+      EmailTypeOptionPB::try_from(bytes).map(|pb| EmailTypeOption::from(pb).into())
+    },
+    FieldType::Phone => {
+      PhoneTypeOptionPB::try_from(bytes).map(|pb| PhoneTypeOption::from(pb).into())
+    },
+    FieldType::AutoNumber => {
+      AutoNumberTypeOptionPB::try_from(bytes).map(|pb| AutoNumberTypeOption::from(pb).into())
+    },
+    FieldType::Person => {
+      PersonTypeOptionPB::try_from(bytes).map(|pb| PersonTypeOption::from(pb).into())
+    },
   }
 }
 
@@ -273,6 +300,48 @@ pub fn type_option_to_pb(type_option: TypeOptionData, field_type: &FieldType) ->
         .try_into()
         .unwrap()
     },
+    FieldType::Formula => {
+      let formula_type_option: FormulaTypeOption = type_option.into();
+      FormulaTypeOptionPB::from(formula_type_option)
+        .try_into()
+        .unwrap()
+    },
+    FieldType::Rollup => {
+      let rollup_type_option: RollupTypeOption = type_option.into();
+      RollupTypeOptionPB::from(rollup_type_option)
+        .try_into()
+        .unwrap()
+    },
+    FieldType::Rating => {
+      let rating_type_option: RatingTypeOption = type_option.into();
+      RatingTypeOptionPB::from(rating_type_option)
+        .try_into()
+        .unwrap()
+    },
+    FieldType::Email => {
+      let email_type_option: EmailTypeOption = type_option.into();
+      EmailTypeOptionPB::from(email_type_option)
+        .try_into()
+        .unwrap()
+    },
+    FieldType::Phone => {
+      let phone_type_option: PhoneTypeOption = type_option.into();
+      PhoneTypeOptionPB::from(phone_type_option)
+        .try_into()
+        .unwrap()
+    },
+    FieldType::AutoNumber => {
+      let auto_number_type_option: AutoNumberTypeOption = type_option.into();
+      AutoNumberTypeOptionPB::from(auto_number_type_option)
+        .try_into()
+        .unwrap()
+    },
+    FieldType::Person => {
+      let person_type_option: PersonTypeOption = type_option.into();
+      PersonTypeOptionPB::from(person_type_option)
+        .try_into()
+        .unwrap()
+    },
   }
 }
 
@@ -296,5 +365,12 @@ pub fn default_type_option_data_from_type(field_type: FieldType) -> TypeOptionDa
     FieldType::Translate => TranslateTypeOption::default().into(),
     FieldType::Time => TimeTypeOption.into(),
     FieldType::Media => MediaTypeOption::default().into(),
+    FieldType::Formula => FormulaTypeOption::default().into(),
+    FieldType::Rollup => RollupTypeOption::default().into(),
+    FieldType::Rating => RatingTypeOption::default().into(),
+    FieldType::Email => EmailTypeOption.into(),
+    FieldType::Phone => PhoneTypeOption.into(),
+    FieldType::AutoNumber => AutoNumberTypeOption::default().into(),
+    FieldType::Person => PersonTypeOption::default().into(),
   }
 }
@@ -1,8 +1,9 @@
 use crate::entities::FieldType;
 use crate::services::cell::{CellCache, CellDataChangeset, CellDataDecoder, CellProtobufBlob};
 use crate::services::field::{
-  CellDataProtobufEncoder, TypeOption, TypeOptionCellData, TypeOptionCellDataCompare,
-  TypeOptionCellDataFilter, TypeOptionTransform,
+  AutoNumberTypeOption, CellDataProtobufEncoder, EmailTypeOption, FormulaTypeOption,
+  PersonTypeOption, PhoneTypeOption, RatingTypeOption, RollupTypeOption, TypeOption,
+  TypeOptionCellData, TypeOptionCellDataCompare, TypeOptionCellDataFilter, TypeOptionTransform,
 };
 use crate::services::sort::SortCondition;
 use collab::preclude::Any;
@@ -95,6 +96,10 @@ pub trait TypeOptionCellDataHandler: Send + Sync + 'static {
 
   fn handle_numeric_cell(&self, cell: &Cell) -> Option<f64>;
 
+  /// Returns the typed json value of the cell, for example a number for [FieldType::Number] or
+  /// a boolean for [FieldType::Checkbox].
+  fn handle_json_cell(&self, cell: &Cell) -> serde_json::Value;
+
   fn handle_is_empty(&self, cell: &Cell, field: &Field) -> bool;
 }
 
@@ -344,6 +349,10 @@ where
     self.numeric_cell(cell)
   }
 
+  fn handle_json_cell(&self, cell: &Cell) -> serde_json::Value {
+    self.json_cell(cell)
+  }
+
   fn handle_is_empty(&self, cell: &Cell, field: &Field) -> bool {
     let cell_data = self.get_cell_data(cell, field).unwrap_or_default();
 
@@ -511,6 +520,76 @@ impl<'a> TypeOptionCellExt<'a> {
             self.cell_data_cache.clone(),
           )
         }),
+      FieldType::Formula => self
+        .field
+        .get_type_option::<FormulaTypeOption>(field_type)
+        .map(|type_option| {
+          TypeOptionCellDataHandlerImpl::new_with_boxed(
+            type_option,
+            field_type,
+            self.cell_data_cache.clone(),
+          )
+        }),
+      FieldType::Rollup => self
+        .field
+        .get_type_option::<RollupTypeOption>(field_type)
+        .map(|type_option| {
+          TypeOptionCellDataHandlerImpl::new_with_boxed(
+            type_option,
+            field_type,
+            self.cell_data_cache.clone(),
+          )
+        }),
+      FieldType::Rating => self
+        .field
+        .get_type_option::<RatingTypeOption>(field_type)
+        .map(|type_option| {
+          TypeOptionCellDataHandlerImpl::new_with_boxed(
+            type_option,
+            field_type,
+            self.cell_data_cache.clone(),
+          )
+        }),
+      FieldType::Email => self
+        .field
+        .get_type_option::<EmailTypeOption>(field_type)
+        .map(|type_option| {
+          TypeOptionCellDataHandlerImpl::new_with_boxed(
+            type_option,
+            field_type,
+            self.cell_data_cache.clone(),
+          )
+        }),
+      FieldType::Phone => self
+        .field
+        .get_type_option::<PhoneTypeOption>(field_type)
+        .map(|type_option| {
+          TypeOptionCellDataHandlerImpl::new_with_boxed(
+            type_option,
+            field_type,
+            self.cell_data_cache.clone(),
+          )
+        }),
+      FieldType::AutoNumber => self
+        .field
+        .get_type_option::<AutoNumberTypeOption>(field_type)
+        .map(|type_option| {
+          TypeOptionCellDataHandlerImpl::new_with_boxed(
+            type_option,
+            field_type,
+            self.cell_data_cache.clone(),
+          )
+        }),
+      FieldType::Person => self
+        .field
+        .get_type_option::<PersonTypeOption>(field_type)
+        .map(|type_option| {
+          TypeOptionCellDataHandlerImpl::new_with_boxed(
+            type_option,
+            field_type,
+            self.cell_data_cache.clone(),
+          )
+        }),
     }
   }
 
@@ -539,6 +618,19 @@ pub fn is_type_option_cell_transformable(
       | (FieldType::RichText, FieldType::URL)
       | (FieldType::RichText, FieldType::Number)
       | (FieldType::RichText, FieldType::DateTime)
+      | (FieldType::RichText, FieldType::Rating)
+      | (FieldType::RichText, FieldType::Email)
+      | (FieldType::RichText, FieldType::Phone)
+      | (FieldType::RichText, FieldType::Person)
+    // Number
+      | (FieldType::Number, FieldType::Rating)
+      | (FieldType::Number, FieldType::Phone)
+      | (FieldType::Rating, FieldType::Number)
+      | (FieldType::Phone, FieldType::Number)
+    // URL
+      | (FieldType::URL, FieldType::Email)
+    // Email
+      | (FieldType::Email, FieldType::Person)
       | (_, FieldType::RichText)
   )
 }
@@ -594,4 +686,12 @@ impl RowSingleCellData {
     into_check_list_field_cell_data,
     <CheckboxTypeOption as TypeOption>::CellData
   );
+  into_cell_data!(
+    into_person_field_cell_data,
+    <PersonTypeOption as TypeOption>::CellData
+  );
+  into_cell_data!(
+    into_relation_field_cell_data,
+    <RelationTypeOption as TypeOption>::CellData
+  );
 }
@@ -1,15 +1,20 @@
 use async_trait::async_trait;
+use chrono::Utc;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use collab::lock::RwLock;
 use collab_database::database::gen_database_filter_id;
+use collab_database::fields::date_type_option::DateCellData;
 use collab_database::fields::Field;
 use collab_database::rows::{Cell, Cells, Row, RowDetail, RowId};
 use collab_database::template::timestamp_parse::TimestampCellData;
 use dashmap::DashMap;
 use flowy_error::FlowyResult;
+use lib_infra::box_any::BoxAny;
 use lib_infra::priority_task::{QualityOfService, Task, TaskContent, TaskDispatcher};
 use rayon::prelude::*;
 
@@ -21,9 +26,12 @@ use crate::entities::filter_entities::*;
 use crate::entities::{FieldType, InsertedRowPB, RowMetaPB};
 use crate::services::cell::CellCache;
 use crate::services::database_view::{DatabaseViewChanged, DatabaseViewChangedNotifier};
+use crate::services::field::date_type_option::date_filter::next_day_timestamp;
 use crate::services::field::TypeOptionCellExt;
 use crate::services::filter::{Filter, FilterChangeset, FilterInner, FilterResultNotification};
 
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
 #[async_trait]
 pub trait FilterDelegate: Send + Sync + 'static {
   async fn get_field(&self, field_id: &str) -> Option<Field>;
@@ -32,6 +40,30 @@ pub trait FilterDelegate: Send + Sync + 'static {
   async fn get_row(&self, view_id: &str, rows_id: &RowId) -> Option<(usize, Arc<RowDetail>)>;
   async fn get_all_filters(&self, view_id: &str) -> Vec<Filter>;
   async fn save_filters(&self, view_id: &str, filters: &[Filter]);
+  /// Returns the email of the current user, used by the "is me" filter of the person field.
+  fn get_user_email(&self) -> Option<String>;
+  /// Returns the timezone of the current user, used by the relative date filters.
+  fn get_timezone_id(&self) -> Option<String>;
+}
+
+/// The data the filters depend on besides the cells, which can change between two filterings of
+/// the same rows.
+#[derive(Debug)]
+pub(crate) struct FilterContext {
+  user_email: Option<String>,
+  timezone_id: String,
+  /// The timestamp of the filtering, which the relative date filters depend on
+  now: i64,
+}
+
+impl FilterContext {
+  pub(crate) fn new(user_email: Option<String>, timezone_id: Option<String>) -> Self {
+    Self {
+      user_email,
+      timezone_id: timezone_id.unwrap_or_default(),
+      now: Utc::now().timestamp(),
+    }
+  }
 }
 
 pub trait PreFillCellsWithFilter {
@@ -45,6 +77,7 @@ pub struct FilterController {
   result_by_row_id: DashMap<RowId, bool>,
   cell_cache: CellCache,
   filters: RwLock<Vec<Filter>>,
+  user_email: Option<String>,
   task_scheduler: Arc<TokioRwLock<TaskDispatcher>>,
   notifier: DatabaseViewChangedNotifier,
 }
@@ -100,6 +133,7 @@ impl FilterController {
       delegate.save_filters(view_id, &filters).await;
     }
 
+    let user_email = delegate.get_user_email();
     Self {
       view_id: view_id.to_string(),
       handler_id: handler_id.to_string(),
@@ -107,6 +141,7 @@ impl FilterController {
       result_by_row_id: DashMap::default(),
       cell_cache,
       filters: RwLock::new(filters),
+      user_email,
       task_scheduler,
       notifier,
     }
@@ -116,6 +151,38 @@ impl FilterController {
     !self.filters.read().await.is_empty()
   }
 
+  pub async fn has_relative_date_filters(&self) -> bool {
+    self
+      .filters
+      .read()
+      .await
+      .iter()
+      .any(|filter| filter.has_relative_date_filter())
+  }
+
+  /// Filters all the rows again, e.g. when the day changes and the relative date filters select
+  /// other dates.
+  pub async fn refresh(&self) {
+    self
+      .gen_task(FilterEvent::FilterDidChanged, QualityOfService::Background)
+      .await;
+  }
+
+  /// Returns the time left before the start of the next day in the timezone of the user.
+  pub fn duration_until_next_day(&self) -> Duration {
+    let now = Utc::now().timestamp();
+    let timezone_id = self.delegate.get_timezone_id().unwrap_or_default();
+    let seconds = next_day_timestamp(&timezone_id, now)
+      .map(|next_day| next_day - now)
+      .filter(|seconds| *seconds > 0)
+      .unwrap_or(SECONDS_PER_DAY);
+    Duration::from_secs(seconds as u64)
+  }
+
+  fn get_filter_context(&self) -> FilterContext {
+    FilterContext::new(self.user_email.clone(), self.delegate.get_timezone_id())
+  }
+
   pub async fn close(&self) {
     self
       .task_scheduler
@@ -148,6 +215,19 @@ impl FilterController {
     }
   }
 
+  /// Filters the rows again after their cells were written in a batch. Unlike [Self::refresh],
+  /// only the given rows are filtered and one notification is sent for all of them.
+  pub async fn did_receive_rows_changed(&self, row_ids: Vec<RowId>) {
+    if !self.filters.read().await.is_empty() && !row_ids.is_empty() {
+      self
+        .gen_task(
+          FilterEvent::RowsDidChanged(row_ids),
+          QualityOfService::UserInteractive,
+        )
+        .await
+    }
+  }
+
   #[tracing::instrument(level = "trace", skip(self))]
   pub async fn apply_changeset(&self, changeset: FilterChangeset) -> FilterChangesetNotificationPB {
     let mut filters = self.filters.write().await;
@@ -212,6 +292,19 @@ impl FilterController {
           Self::delete_filter(&mut filters, &filter_id)
         }
       },
+      FilterChangeset::Restore {
+        filters: restored_filters,
+      } => {
+        for (index, filter) in restored_filters {
+          match filters.iter().position(|other| other.id == filter.id) {
+            Some(position) => filters[position] = filter,
+            None => {
+              let index = index.min(filters.len());
+              filters.insert(index, filter);
+            },
+          }
+        }
+      },
     }
 
     self.delegate.save_filters(&self.view_id, &filters).await;
@@ -232,6 +325,7 @@ impl FilterController {
     }
 
     let field_map = self.get_field_map().await;
+    let context = self.get_filter_context();
 
     while let Some(current_inner) = min_required_filters.pop() {
       if let FilterInner::Data {
@@ -255,12 +349,15 @@ impl FilterController {
               let filter = condition_and_content.cloned::<TextFilterPB>().unwrap();
               filter.get_compliant_cell(field)
             },
-            FieldType::Number => {
+            FieldType::Number | FieldType::Rating => {
               let filter = condition_and_content.cloned::<NumberFilterPB>().unwrap();
               filter.get_compliant_cell(field)
             },
             FieldType::DateTime => {
               let filter = condition_and_content.cloned::<DateFilterPB>().unwrap();
+              let filter = filter
+                .with_relative_range(&context.timezone_id, context.now)
+                .unwrap_or(filter);
               filter.get_compliant_cell(field)
             },
             FieldType::SingleSelect => {
@@ -287,6 +384,19 @@ impl FilterController {
               let filter = condition_and_content.cloned::<TimeFilterPB>().unwrap();
               filter.get_compliant_cell(field)
             },
+            FieldType::Relation => {
+              let filter = condition_and_content.cloned::<RelationFilterPB>().unwrap();
+              filter.get_compliant_cell(field)
+            },
+            FieldType::Person => {
+              let filter = condition_and_content.cloned::<PersonFilterPB>().unwrap();
+              let filter = context
+                .user_email
+                .as_deref()
+                .and_then(|email| filter.with_current_user(email))
+                .unwrap_or(filter);
+              filter.get_compliant_cell(field)
+            },
             _ => None,
           };
 
@@ -312,34 +422,38 @@ impl FilterController {
         let mut rows = self.delegate.get_rows(&self.view_id).await;
         self.filter_rows_and_notify(&mut rows).await?
       },
-      FilterEvent::RowDidChanged(row_id) => self.filter_single_row_handler(row_id).await?,
+      FilterEvent::RowDidChanged(row_id) => self.filter_rows_handler(vec![row_id]).await?,
+      FilterEvent::RowsDidChanged(row_ids) => self.filter_rows_handler(row_ids).await?,
     }
     Ok(())
   }
 
-  async fn filter_single_row_handler(&self, row_id: RowId) -> FlowyResult<()> {
+  async fn filter_rows_handler(&self, row_ids: Vec<RowId>) -> FlowyResult<()> {
     let filters = self.filters.read().await;
-
-    if let Some((_, row_detail)) = self.delegate.get_row(&self.view_id, &row_id).await {
-      let field_by_field_id = self.get_field_map().await;
-      let mut notification = FilterResultNotification::new(self.view_id.clone());
-      if filter_row(
-        &row_detail.row,
-        &self.result_by_row_id,
-        &field_by_field_id,
-        &self.cell_cache,
-        &filters,
-      ) {
-        if let Some((index, _row)) = self.delegate.get_row(&self.view_id, &row_id).await {
+    let field_by_field_id = self.get_field_map().await;
+    let context = self.get_filter_context();
+    let mut notification = FilterResultNotification::new(self.view_id.clone());
+    for row_id in row_ids {
+      if let Some((index, row_detail)) = self.delegate.get_row(&self.view_id, &row_id).await {
+        if filter_row(
+          &row_detail.row,
+          &self.result_by_row_id,
+          &field_by_field_id,
+          &self.cell_cache,
+          &filters,
+          &context,
+        ) {
           notification.visible_rows.push(
             InsertedRowPB::new(RowMetaPB::from(row_detail.as_ref().clone()))
               .with_index(index as i32),
           )
+        } else {
+          notification.invisible_rows.push(row_id);
         }
-      } else {
-        notification.invisible_rows.push(row_id);
       }
+    }
 
+    if !notification.visible_rows.is_empty() || !notification.invisible_rows.is_empty() {
       let _ = self
         .notifier
         .send(DatabaseViewChanged::FilterNotification(notification));
@@ -350,6 +464,7 @@ impl FilterController {
   pub async fn filter_rows_and_notify(&self, rows: &mut Vec<Arc<Row>>) -> FlowyResult<()> {
     let filters = self.filters.read().await;
     let field_by_field_id = self.get_field_map().await;
+    let context = self.get_filter_context();
     let (visible_rows, invisible_rows): (Vec<_>, Vec<_>) =
       rows.par_iter().enumerate().partition_map(|(index, row)| {
         if filter_row(
@@ -358,6 +473,7 @@ impl FilterController {
           &field_by_field_id,
           &self.cell_cache,
           &filters,
+          &context,
         ) {
           let row_meta = RowMetaPB::from(row.as_ref());
           // Visible rows go into the left partition
@@ -386,6 +502,7 @@ impl FilterController {
   pub async fn filter_rows(&self, mut rows: Vec<Arc<Row>>) -> Vec<Arc<Row>> {
     let filters = self.filters.read().await;
     let field_by_field_id = self.get_field_map().await;
+    let context = self.get_filter_context();
     rows.par_iter().for_each(|row| {
       let _ = filter_row(
         row,
@@ -393,6 +510,7 @@ impl FilterController {
         &field_by_field_id,
         &self.cell_cache,
         &filters,
+        &context,
       );
     });
 
@@ -452,13 +570,15 @@ fn filter_row(
   field_by_field_id: &HashMap<String, Field>,
   cell_data_cache: &CellCache,
   filters: &Vec<Filter>,
+  context: &FilterContext,
 ) -> bool {
   // Create a filter result cache if it doesn't exist
   let mut filter_result = result_by_row_id.entry(row.id.clone()).or_insert(true);
   let mut new_is_visible = true;
 
   for filter in filters {
-    if let Some(is_visible) = apply_filter(row, field_by_field_id, cell_data_cache, filter) {
+    if let Some(is_visible) = apply_filter(row, field_by_field_id, cell_data_cache, filter, context)
+    {
       new_is_visible = new_is_visible && is_visible;
       // short-circuit as soon as one filter tree returns false
       if !new_is_visible {
@@ -471,12 +591,14 @@ fn filter_row(
   new_is_visible
 }
 
-/// Recursively applies a `Filter` to a `Row`'s cells.
-fn apply_filter(
+/// Recursively applies a `Filter` to a `Row`'s cells. It's also used to match the conditions of
+/// the formatting rules.
+pub(crate) fn apply_filter(
   row: &Row,
   field_by_field_id: &HashMap<String, Field>,
   cell_data_cache: &CellCache,
   filter: &Filter,
+  context: &FilterContext,
 ) -> Option<bool> {
   match &filter.inner {
     FilterInner::And { children } => {
@@ -484,7 +606,13 @@ fn apply_filter(
         return None;
       }
       for child_filter in children.iter() {
-        if let Some(false) = apply_filter(row, field_by_field_id, cell_data_cache, child_filter) {
+        if let Some(false) = apply_filter(
+          row,
+          field_by_field_id,
+          cell_data_cache,
+          child_filter,
+          context,
+        ) {
           return Some(false);
         }
       }
@@ -495,12 +623,62 @@ fn apply_filter(
         return None;
       }
       for child_filter in children.iter() {
-        if let Some(true) = apply_filter(row, field_by_field_id, cell_data_cache, child_filter) {
+        if let Some(true) = apply_filter(
+          row,
+          field_by_field_id,
+          cell_data_cache,
+          child_filter,
+          context,
+        ) {
           return Some(true);
         }
       }
       Some(false)
     },
+    FilterInner::Not { children } => {
+      // The row is visible unless it matches all the children
+      let mut is_matched = None;
+      for child_filter in children.iter() {
+        match apply_filter(
+          row,
+          field_by_field_id,
+          cell_data_cache,
+          child_filter,
+          context,
+        ) {
+          Some(false) => return Some(true),
+          Some(true) => is_matched = Some(true),
+          None => {},
+        }
+      }
+      is_matched.map(|is_matched| !is_matched)
+    },
+    FilterInner::FieldComparison {
+      field_id,
+      field_type,
+      other_field_id,
+      condition,
+    } => {
+      let (field, other_field) = match (
+        field_by_field_id.get(field_id),
+        field_by_field_id.get(other_field_id),
+      ) {
+        (Some(field), Some(other_field)) => (field, other_field),
+        _ => {
+          tracing::error!("cannot find field");
+          return Some(false);
+        },
+      };
+      if *field_type != FieldType::from(field.field_type) {
+        error!("field type of filter doesn't match field type of field");
+        return Some(false);
+      }
+      // The row is hidden if one of the cells is empty or the values can't be compared
+      let ordering = get_comparable_cell_value(row, field, cell_data_cache)
+        .zip(get_comparable_cell_value(row, other_field, cell_data_cache))
+        .and_then(|(value, other_value)| value.compare(&other_value));
+      Some(ordering.is_some_and(|ordering| condition.is_satisfied(ordering)))
+    },
     FilterInner::Data {
       field_id,
       field_type,
@@ -530,6 +708,28 @@ fn apply_filter(
         _ => None,
       };
       let cell = timestamp_cell.or_else(|| row.cells.get(field_id).cloned());
+      // The "is me" filter of the person field depends on the user who is looking at the view
+      let person_filter = match (field_type, context.user_email.as_deref()) {
+        (FieldType::Person, Some(email)) => condition_and_content
+          .cloned::<PersonFilterPB>()
+          .and_then(|filter| filter.with_current_user(email))
+          .map(BoxAny::new),
+        _ => None,
+      };
+      // The relative date filters depend on the current date in the timezone of the user
+      let date_filter = match field_type {
+        FieldType::DateTime | FieldType::CreatedTime | FieldType::LastEditedTime => {
+          condition_and_content
+            .cloned::<DateFilterPB>()
+            .and_then(|filter| filter.with_relative_range(&context.timezone_id, context.now))
+            .map(BoxAny::new)
+        },
+        _ => None,
+      };
+      let condition_and_content = person_filter
+        .as_ref()
+        .or(date_filter.as_ref())
+        .unwrap_or(condition_and_content);
       if let Some(handler) = TypeOptionCellExt::new(field, Some(cell_data_cache.clone()))
         .get_type_option_cell_data_handler()
       {
@@ -541,10 +741,67 @@ fn apply_filter(
   }
 }
 
+/// The value of a cell when it's compared with the cell of another field of the same row.
+#[derive(Debug, PartialEq)]
+enum ComparableCellValue {
+  Number(f64),
+  Timestamp(i64),
+  Text(String),
+}
+
+impl ComparableCellValue {
+  /// Returns None if the values are of different kinds, e.g. a number and a date.
+  fn compare(&self, other: &Self) -> Option<Ordering> {
+    match (self, other) {
+      (Self::Number(left), Self::Number(right)) => left.partial_cmp(right),
+      (Self::Timestamp(left), Self::Timestamp(right)) => Some(left.cmp(right)),
+      (Self::Text(left), Self::Text(right)) => Some(left.cmp(right)),
+      _ => None,
+    }
+  }
+}
+
+/// Returns None if the cell is empty. The texts are compared case-insensitively.
+fn get_comparable_cell_value(
+  row: &Row,
+  field: &Field,
+  cell_data_cache: &CellCache,
+) -> Option<ComparableCellValue> {
+  let field_type = FieldType::from(field.field_type);
+  match field_type {
+    FieldType::CreatedTime => return Some(ComparableCellValue::Timestamp(row.created_at)),
+    FieldType::LastEditedTime => return Some(ComparableCellValue::Timestamp(row.modified_at)),
+    _ => {},
+  }
+
+  let cell = row.cells.get(&field.id)?;
+  let handler = TypeOptionCellExt::new(field, Some(cell_data_cache.clone()))
+    .get_type_option_cell_data_handler()?;
+  match field_type {
+    FieldType::Number | FieldType::Rating | FieldType::AutoNumber => handler
+      .handle_numeric_cell(cell)
+      .map(ComparableCellValue::Number),
+    FieldType::DateTime => handler
+      .handle_get_boxed_cell_data(cell, field)?
+      .unbox_or_none::<DateCellData>()?
+      .timestamp
+      .map(ComparableCellValue::Timestamp),
+    _ => {
+      let text = handler.handle_stringify_cell(cell, field);
+      if text.is_empty() {
+        None
+      } else {
+        Some(ComparableCellValue::Text(text.to_lowercase()))
+      }
+    },
+  }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 enum FilterEvent {
   FilterDidChanged,
   RowDidChanged(RowId),
+  RowsDidChanged(Vec<RowId>),
 }
 
 impl FilterEvent {
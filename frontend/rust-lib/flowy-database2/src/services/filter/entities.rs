@@ -15,9 +15,9 @@ use lib_infra::box_any::BoxAny;
 use tracing::error;
 
 use crate::entities::{
-  CheckboxFilterPB, ChecklistFilterPB, DateFilterContent, DateFilterPB, FieldType, FilterType,
-  InsertedRowPB, MediaFilterPB, NumberFilterPB, RelationFilterPB, SelectOptionFilterPB,
-  TextFilterPB, TimeFilterPB,
+  CheckboxFilterPB, ChecklistFilterPB, DateFilterContent, DateFilterPB, FieldComparisonConditionPB,
+  FieldType, FilterType, InsertedRowPB, MediaFilterPB, NumberFilterPB, PersonFilterPB,
+  RelationFilterPB, SelectOptionFilterPB, TextFilterPB, TimeFilterPB,
 };
 
 pub trait ParseFilterData {
@@ -35,11 +35,13 @@ impl Filter {
   /// multiple AND/OR filters but no Data filters is considered "empty".
   pub fn is_empty(&self) -> bool {
     match &self.inner {
-      FilterInner::And { children } | FilterInner::Or { children } => children
+      FilterInner::And { children }
+      | FilterInner::Or { children }
+      | FilterInner::Not { children } => children
         .iter()
         .map(|filter| filter.is_empty())
         .all(|is_empty| is_empty),
-      FilterInner::Data { .. } => false,
+      FilterInner::Data { .. } | FilterInner::FieldComparison { .. } => false,
     }
   }
 
@@ -49,7 +51,9 @@ impl Filter {
       return Some(self);
     }
     match &mut self.inner {
-      FilterInner::And { children } | FilterInner::Or { children } => {
+      FilterInner::And { children }
+      | FilterInner::Or { children }
+      | FilterInner::Not { children } => {
         for child_filter in children.iter_mut() {
           let result = child_filter.find_filter(filter_id);
           if result.is_some() {
@@ -58,7 +62,7 @@ impl Filter {
         }
         None
       },
-      FilterInner::Data { .. } => None,
+      FilterInner::Data { .. } | FilterInner::FieldComparison { .. } => None,
     }
   }
 
@@ -69,7 +73,9 @@ impl Filter {
       return None;
     }
     match &mut self.inner {
-      FilterInner::And { children } | FilterInner::Or { children } => {
+      FilterInner::And { children }
+      | FilterInner::Or { children }
+      | FilterInner::Not { children } => {
         for child_filter in children.iter_mut() {
           if child_filter.id == filter_id {
             return Some(child_filter);
@@ -81,63 +87,57 @@ impl Filter {
         }
         None
       },
-      FilterInner::Data { .. } => None,
+      FilterInner::Data { .. } | FilterInner::FieldComparison { .. } => None,
     }
   }
 
-  /// Converts a filter from And/Or/Data to And/Or. If the current type of the filter is Data,
-  /// return the FilterInner after the conversion.
+  /// Converts a filter from And/Or/Not/Data to And/Or/Not. If the current type of the filter is
+  /// Data, return the FilterInner after the conversion.
   pub fn convert_to_and_or_filter_type(
     &mut self,
     filter_type: FilterType,
   ) -> FlowyResult<Option<FilterInner>> {
     match (&mut self.inner, filter_type) {
-      (FilterInner::And { children }, FilterType::Or) => {
-        self.inner = FilterInner::Or {
-          children: mem::take(children),
-        };
-        Ok(None)
-      },
-      (FilterInner::Or { children }, FilterType::And) => {
-        self.inner = FilterInner::And {
-          children: mem::take(children),
-        };
-        Ok(None)
-      },
-      (FilterInner::Data { .. }, FilterType::And) => {
-        let mut inner = FilterInner::And { children: vec![] };
-        mem::swap(&mut self.inner, &mut inner);
-        Ok(Some(inner))
-      },
-      (FilterInner::Data { .. }, FilterType::Or) => {
-        let mut inner = FilterInner::Or { children: vec![] };
-        mem::swap(&mut self.inner, &mut inner);
-        Ok(Some(inner))
-      },
       (_, FilterType::Data) => {
-        // from And/Or to Data
+        // from And/Or/Not to Data
         Err(FlowyError::internal().with_context(format!(
           "conversion from {:?} to FilterType::Data not supported",
           FilterType::from(&self.inner)
         )))
       },
-      _ => {
+      (inner, filter_type) if FilterType::from(&*inner) == filter_type => {
         tracing::warn!("conversion to the same filter type");
         Ok(None)
       },
+      (
+        FilterInner::And { children }
+        | FilterInner::Or { children }
+        | FilterInner::Not { children },
+        filter_type,
+      ) => {
+        self.inner = FilterInner::new_group(filter_type, mem::take(children));
+        Ok(None)
+      },
+      (FilterInner::Data { .. } | FilterInner::FieldComparison { .. }, filter_type) => {
+        let mut inner = FilterInner::new_group(filter_type, vec![]);
+        mem::swap(&mut self.inner, &mut inner);
+        Ok(Some(inner))
+      },
     }
   }
 
   /// Insert a filter into the current filter in the filter tree. If the current filter
-  /// is an AND/OR filter, then the filter is appended to its children. Otherwise, the current
-  /// filter is converted to an AND filter, after which the current data filter and the new filter
-  /// are added to the AND filter's children.
+  /// is an AND/OR/NOT filter, then the filter is appended to its children. Otherwise, the
+  /// current filter is converted to an AND filter, after which the current data filter and the
+  /// new filter are added to the AND filter's children.
   pub fn insert_filter(&mut self, filter: Filter) -> FlowyResult<()> {
     match &mut self.inner {
-      FilterInner::And { children } | FilterInner::Or { children } => {
+      FilterInner::And { children }
+      | FilterInner::Or { children }
+      | FilterInner::Not { children } => {
         children.push(filter);
       },
-      FilterInner::Data { .. } => {
+      FilterInner::Data { .. } | FilterInner::FieldComparison { .. } => {
         // convert to FilterInner::And by default
         let old_filter = self
           .convert_to_and_or_filter_type(FilterType::And)
@@ -155,13 +155,16 @@ impl Filter {
     Ok(())
   }
 
-  /// Update the criteria of a data filter. Return an error if the current filter is an AND/OR
-  /// filter.
+  /// Update the criteria of a data filter. Return an error if the current filter is an
+  /// AND/OR/NOT filter.
   pub fn update_filter_data(&mut self, filter_data: FilterInner) -> FlowyResult<()> {
     match &self.inner {
-      FilterInner::And { .. } | FilterInner::Or { .. } => Err(FlowyError::internal().with_context(
-        format!("unexpected filter type {:?}", FilterType::from(&self.inner)),
-      )),
+      FilterInner::And { .. } | FilterInner::Or { .. } | FilterInner::Not { .. } => {
+        Err(FlowyError::internal().with_context(format!(
+          "unexpected filter type {:?}",
+          FilterType::from(&self.inner)
+        )))
+      },
       _ => {
         self.inner = filter_data;
         Ok(())
@@ -174,7 +177,9 @@ impl Filter {
   /// cannot have children), or the filter to be deleted cannot be found.
   pub fn delete_filter(&mut self, filter_id: &str) -> FlowyResult<()> {
     match &mut self.inner {
-      FilterInner::And { children } | FilterInner::Or { children } => children
+      FilterInner::And { children }
+      | FilterInner::Or { children }
+      | FilterInner::Not { children } => children
         .iter()
         .position(|filter| filter.id == filter_id)
         .map(|position| {
@@ -184,17 +189,19 @@ impl Filter {
           FlowyError::internal()
             .with_context(format!("filter with filter_id {:?} not found", filter_id))
         }),
-      FilterInner::Data { .. } => Err(
+      FilterInner::Data { .. } | FilterInner::FieldComparison { .. } => Err(
         FlowyError::internal().with_context("unexpected parent filter type of FilterInner::Data"),
       ),
     }
   }
 
-  /// Recursively finds any Data filter whose `field_id` is equal to `matching_field_id`. Any found
-  /// filters' id is appended to the `ids` vector.
+  /// Recursively finds any Data filter whose `field_id` is equal to `matching_field_id`, including
+  /// the comparisons with that field. Any found filters' id is appended to the `ids` vector.
   pub fn find_all_filters_with_field_id(&self, matching_field_id: &str, ids: &mut Vec<String>) {
     match &self.inner {
-      FilterInner::And { children } | FilterInner::Or { children } => {
+      FilterInner::And { children }
+      | FilterInner::Or { children }
+      | FilterInner::Not { children } => {
         for child_filter in children.iter() {
           child_filter.find_all_filters_with_field_id(matching_field_id, ids);
         }
@@ -204,6 +211,15 @@ impl Filter {
           ids.push(self.id.clone());
         }
       },
+      FilterInner::FieldComparison {
+        field_id,
+        other_field_id,
+        ..
+      } => {
+        if field_id == matching_field_id || other_field_id == matching_field_id {
+          ids.push(self.id.clone());
+        }
+      },
     }
   }
 
@@ -215,6 +231,8 @@ impl Filter {
   /// 2. an AND filter, then all of its effective children should be
   ///    included.
   /// 3. an OR filter, then only the first child should be included.
+  /// 4. a NOT filter or a comparison of two fields, then it should be skipped, since the cells
+  ///    that satisfy it can't be deduced from the filter alone.
   pub fn get_min_effective_filters<'a>(&'a self, min_effective_filters: &mut Vec<&'a FilterInner>) {
     match &self.inner {
       FilterInner::And { children } => {
@@ -228,13 +246,16 @@ impl Filter {
         }
       },
       FilterInner::Data { .. } => min_effective_filters.push(&self.inner),
+      FilterInner::Not { .. } | FilterInner::FieldComparison { .. } => {},
     }
   }
 
   /// Recursively get all of the filtering field ids and the associated filter_ids
   pub fn get_all_filtering_field_ids(&self, field_ids: &mut HashMap<String, Vec<String>>) {
     match &self.inner {
-      FilterInner::And { children } | FilterInner::Or { children } => {
+      FilterInner::And { children }
+      | FilterInner::Or { children }
+      | FilterInner::Not { children } => {
         for child in children.iter() {
           child.get_all_filtering_field_ids(field_ids);
         }
@@ -245,6 +266,43 @@ impl Filter {
           .and_modify(|filter_ids| filter_ids.push(self.id.clone()))
           .or_insert_with(|| vec![self.id.clone()]);
       },
+      FilterInner::FieldComparison {
+        field_id,
+        other_field_id,
+        ..
+      } => {
+        for field_id in [field_id, other_field_id] {
+          let filter_ids = field_ids.entry(field_id.clone()).or_default();
+          if !filter_ids.contains(&self.id) {
+            filter_ids.push(self.id.clone());
+          }
+        }
+      },
+    }
+  }
+
+  /// Recursively determine if any Data filter is a relative date filter, which must be applied
+  /// again when the day changes.
+  pub fn has_relative_date_filter(&self) -> bool {
+    match &self.inner {
+      FilterInner::And { children }
+      | FilterInner::Or { children }
+      | FilterInner::Not { children } => children
+        .iter()
+        .any(|filter| filter.has_relative_date_filter()),
+      FilterInner::Data {
+        field_type,
+        condition_and_content,
+        ..
+      } => {
+        matches!(
+          field_type,
+          FieldType::DateTime | FieldType::CreatedTime | FieldType::LastEditedTime
+        ) && condition_and_content
+          .cloned::<DateFilterPB>()
+          .is_some_and(|filter| filter.condition.is_relative())
+      },
+      FilterInner::FieldComparison { .. } => false,
     }
   }
 }
@@ -257,14 +315,35 @@ pub enum FilterInner {
   Or {
     children: Vec<Filter>,
   },
+  /// Negates the AND of its children
+  Not {
+    children: Vec<Filter>,
+  },
   Data {
     field_id: String,
     field_type: FieldType,
     condition_and_content: BoxAny,
   },
+  /// Compares the cells of two fields of the same row
+  FieldComparison {
+    field_id: String,
+    field_type: FieldType,
+    other_field_id: String,
+    condition: FieldComparisonConditionPB,
+  },
 }
 
 impl FilterInner {
+  /// Returns an empty AND/OR/NOT filter. A Data filter can't have children, so it's converted to
+  /// an AND filter like in [Filter::insert_filter].
+  fn new_group(filter_type: FilterType, children: Vec<Filter>) -> Self {
+    match filter_type {
+      FilterType::Or => FilterInner::Or { children },
+      FilterType::Not => FilterInner::Not { children },
+      FilterType::And | FilterType::Data => FilterInner::And { children },
+    }
+  }
+
   pub fn new_data(
     field_id: String,
     field_type: FieldType,
@@ -275,7 +354,9 @@ impl FilterInner {
       FieldType::RichText | FieldType::URL => {
         BoxAny::new(TextFilterPB::parse(condition as u8, content))
       },
-      FieldType::Number => BoxAny::new(NumberFilterPB::parse(condition as u8, content)),
+      FieldType::Number | FieldType::Rating => {
+        BoxAny::new(NumberFilterPB::parse(condition as u8, content))
+      },
       FieldType::DateTime => BoxAny::new(DateFilterPB::parse(condition as u8, content)),
       FieldType::CreatedTime | FieldType::LastEditedTime => {
         let filter = DateFilterPB::parse(condition as u8, content).remove_end_date_conditions();
@@ -297,6 +378,12 @@ impl FilterInner {
       FieldType::Translate => BoxAny::new(TextFilterPB::parse(condition as u8, content)),
       FieldType::Time => BoxAny::new(TimeFilterPB::parse(condition as u8, content)),
       FieldType::Media => BoxAny::new(MediaFilterPB::parse(condition as u8, content)),
+      FieldType::Formula
+      | FieldType::Rollup
+      | FieldType::Email
+      | FieldType::Phone
+      | FieldType::AutoNumber => BoxAny::new(TextFilterPB::parse(condition as u8, content)),
+      FieldType::Person => BoxAny::new(PersonFilterPB::parse(condition as u8, content)),
     };
 
     FilterInner::Data {
@@ -311,6 +398,8 @@ impl FilterInner {
       FilterInner::And { .. } => FILTER_AND_INDEX,
       FilterInner::Or { .. } => FILTER_OR_INDEX,
       FilterInner::Data { .. } => FILTER_DATA_INDEX,
+      FilterInner::Not { .. } => FILTER_NOT_INDEX,
+      FilterInner::FieldComparison { .. } => FILTER_FIELD_COMPARISON_INDEX,
     }
   }
 }
@@ -322,10 +411,15 @@ const FIELD_TYPE: &str = "ty";
 const FILTER_CONDITION: &str = "condition";
 const FILTER_CONTENT: &str = "content";
 const FILTER_CHILDREN: &str = "children";
+const FILTER_CASE_SENSITIVE: &str = "case_sensitive";
+const FILTER_TRIM_WHITESPACE: &str = "trim_whitespace";
+const FILTER_OTHER_FIELD_ID: &str = "other_field_id";
 
 const FILTER_AND_INDEX: i64 = 0;
 const FILTER_OR_INDEX: i64 = 1;
 const FILTER_DATA_INDEX: i64 = 2;
+const FILTER_NOT_INDEX: i64 = 3;
+const FILTER_FIELD_COMPARISON_INDEX: i64 = 4;
 
 impl<'a> From<&'a Filter> for FilterMap {
   fn from(filter: &'a Filter) -> Self {
@@ -335,7 +429,9 @@ impl<'a> From<&'a Filter> for FilterMap {
     ]);
 
     builder = match &filter.inner {
-      FilterInner::And { children } | FilterInner::Or { children } => {
+      FilterInner::And { children }
+      | FilterInner::Or { children }
+      | FilterInner::Not { children } => {
         let mut vec = Vec::with_capacity(children.len());
         for child in children.iter() {
           let any: Any = FilterMap::from(child).into();
@@ -344,6 +440,18 @@ impl<'a> From<&'a Filter> for FilterMap {
         builder.insert(FILTER_CHILDREN.into(), Any::from(vec));
         builder
       },
+      FilterInner::FieldComparison {
+        field_id,
+        field_type,
+        other_field_id,
+        condition,
+      } => {
+        builder.insert(FIELD_ID.into(), field_id.as_str().into());
+        builder.insert(FIELD_TYPE.into(), Any::BigInt(i64::from(field_type)));
+        builder.insert(FILTER_OTHER_FIELD_ID.into(), other_field_id.as_str().into());
+        builder.insert(FILTER_CONDITION.into(), Any::BigInt(*condition as i64));
+        builder
+      },
       FilterInner::Data {
         field_id,
         field_type,
@@ -353,9 +461,9 @@ impl<'a> From<&'a Filter> for FilterMap {
           let (condition, content) = match field_type {
             FieldType::RichText | FieldType::URL => {
               let filter = condition_and_content.cloned::<TextFilterPB>()?;
-              (filter.condition as u8, filter.content)
+              (filter.condition as u8, filter.to_content())
             },
-            FieldType::Number => {
+            FieldType::Number | FieldType::Rating => {
               let filter = condition_and_content.cloned::<NumberFilterPB>()?;
               (filter.condition as u8, filter.content)
             },
@@ -365,6 +473,8 @@ impl<'a> From<&'a Filter> for FilterMap {
                 start: filter.start,
                 end: filter.end,
                 timestamp: filter.timestamp,
+                relative_range: filter.relative_range.map(|range| range as u8),
+                num_of_days: filter.num_of_days,
               }
               .to_json_string();
               (filter.condition as u8, content)
@@ -384,11 +494,12 @@ impl<'a> From<&'a Filter> for FilterMap {
             },
             FieldType::Relation => {
               let filter = condition_and_content.cloned::<RelationFilterPB>()?;
-              (filter.condition as u8, "".to_string())
+              let content = filter.content();
+              (filter.condition as u8, content)
             },
             FieldType::Summary => {
               let filter = condition_and_content.cloned::<TextFilterPB>()?;
-              (filter.condition as u8, filter.content)
+              (filter.condition as u8, filter.to_content())
             },
             FieldType::Time => {
               let filter = condition_and_content.cloned::<TimeFilterPB>()?;
@@ -396,12 +507,25 @@ impl<'a> From<&'a Filter> for FilterMap {
             },
             FieldType::Translate => {
               let filter = condition_and_content.cloned::<TextFilterPB>()?;
-              (filter.condition as u8, filter.content)
+              (filter.condition as u8, filter.to_content())
             },
             FieldType::Media => {
               let filter = condition_and_content.cloned::<MediaFilterPB>()?;
               (filter.condition as u8, filter.content)
             },
+            FieldType::Formula
+            | FieldType::Rollup
+            | FieldType::Email
+            | FieldType::Phone
+            | FieldType::AutoNumber => {
+              let filter = condition_and_content.cloned::<TextFilterPB>()?;
+              (filter.condition as u8, filter.to_content())
+            },
+            FieldType::Person => {
+              let filter = condition_and_content.cloned::<PersonFilterPB>()?;
+              let content = filter.content();
+              (filter.condition as u8, content)
+            },
           };
           Some((condition, content))
         };
@@ -415,6 +539,19 @@ impl<'a> From<&'a Filter> for FilterMap {
         builder.insert(FIELD_TYPE.into(), Any::BigInt(i64::from(field_type)));
         builder.insert(FILTER_CONDITION.into(), Any::BigInt(condition as i64));
         builder.insert(FILTER_CONTENT.into(), content.into());
+
+        // The content of the text filters created before the options were added is the raw
+        // text, so the options are stored next to it
+        if let Some(filter) = condition_and_content.cloned::<TextFilterPB>() {
+          builder.insert(
+            FILTER_CASE_SENSITIVE.into(),
+            Any::Bool(filter.case_sensitive),
+          );
+          builder.insert(
+            FILTER_TRIM_WHITESPACE.into(),
+            Any::Bool(filter.trim_whitespace),
+          );
+        }
         builder
       },
     };
@@ -441,6 +578,9 @@ impl TryFrom<FilterMap> for Filter {
         FILTER_OR_INDEX => FilterInner::Or {
           children: get_children(filter_map),
         },
+        FILTER_NOT_INDEX => FilterInner::Not {
+          children: get_children(filter_map),
+        },
         FILTER_DATA_INDEX => {
           let field_id: String = filter_map
             .get_as(FIELD_ID)
@@ -452,7 +592,31 @@ impl TryFrom<FilterMap> for Filter {
           let condition: i64 = filter_map.get_as(FILTER_CONDITION).unwrap_or_default();
           let content: String = filter_map.get_as(FILTER_CONTENT).unwrap_or_default();
 
-          FilterInner::new_data(field_id, field_type, condition, content)
+          let inner = FilterInner::new_data(field_id, field_type, condition, content);
+          with_text_filter_options(inner, &filter_map)
+        },
+        FILTER_FIELD_COMPARISON_INDEX => {
+          let field_id: String = filter_map
+            .get_as(FIELD_ID)
+            .ok_or_else(|| anyhow::anyhow!("invalid filter data"))?;
+          let other_field_id: String = filter_map
+            .get_as(FILTER_OTHER_FIELD_ID)
+            .ok_or_else(|| anyhow::anyhow!("invalid filter data"))?;
+          let field_type = filter_map
+            .get_as::<i64>(FIELD_TYPE)
+            .map(FieldType::from)
+            .unwrap_or_default();
+          let condition = filter_map
+            .get_as::<i64>(FILTER_CONDITION)
+            .and_then(|condition| FieldComparisonConditionPB::try_from(condition as u8).ok())
+            .unwrap_or_default();
+
+          FilterInner::FieldComparison {
+            field_id,
+            field_type,
+            other_field_id,
+            condition,
+          }
         },
         _ => bail!("Unsupported filter type"),
       },
@@ -462,6 +626,36 @@ impl TryFrom<FilterMap> for Filter {
   }
 }
 
+/// Sets the options of the text filter that are stored next to its content
+fn with_text_filter_options(inner: FilterInner, filter_map: &FilterMap) -> FilterInner {
+  match inner {
+    FilterInner::Data {
+      field_id,
+      field_type,
+      condition_and_content,
+    } => {
+      let condition_and_content = match condition_and_content.cloned::<TextFilterPB>() {
+        Some(mut filter) => {
+          filter.case_sensitive = filter_map
+            .get_as::<bool>(FILTER_CASE_SENSITIVE)
+            .unwrap_or_default();
+          filter.trim_whitespace = filter_map
+            .get_as::<bool>(FILTER_TRIM_WHITESPACE)
+            .unwrap_or_default();
+          BoxAny::new(filter)
+        },
+        None => condition_and_content,
+      };
+      FilterInner::Data {
+        field_id,
+        field_type,
+        condition_and_content,
+      }
+    },
+    inner => inner,
+  }
+}
+
 fn get_children(filter_map: FilterMap) -> Vec<Filter> {
   //TODO: this method wouldn't be necessary if we could make Filters serializable in backward
   // compatible way
@@ -503,6 +697,11 @@ pub enum FilterChangeset {
   DeleteAllWithFieldId {
     field_id: String,
   },
+  /// Puts back the top level filters at their index, replacing the filters with the same id.
+  /// Used to restore the filters removed with a field when its deletion is undone.
+  Restore {
+    filters: Vec<(usize, Filter)>,
+  },
 }
 
 #[derive(Clone, Debug)]
@@ -0,0 +1,141 @@
+use collab_database::fields::select_type_option::SelectOption;
+use fancy_regex::Regex;
+use flowy_error::{FlowyError, FlowyResult};
+
+use crate::entities::FieldType;
+
+/// Finds the occurrences of a query in the text of the cells, see
+/// [stringify_cell](crate::services::cell::stringify_cell). The query is either plain text or a
+/// regular expression.
+pub struct TextMatcher {
+  regex: Regex,
+}
+
+impl TextMatcher {
+  pub fn new(query: &str, is_regex: bool, is_case_sensitive: bool) -> FlowyResult<Self> {
+    let pattern = if is_regex {
+      query.to_string()
+    } else {
+      escape_regex(query)
+    };
+    let pattern = if is_case_sensitive {
+      pattern
+    } else {
+      format!("(?i){}", pattern)
+    };
+    let regex = Regex::new(&pattern).map_err(|err| {
+      FlowyError::invalid_data().with_context(format!("Invalid regex: {}, {}", query, err))
+    })?;
+    Ok(Self { regex })
+  }
+
+  /// Returns the start and the end of the occurrences in the text. The offsets are counted in
+  /// characters, not in bytes. The empty occurrences are ignored.
+  pub fn find(&self, text: &str) -> Vec<(usize, usize)> {
+    self
+      .regex
+      .find_iter(text)
+      .map_while(|result| result.ok())
+      .filter(|m| m.start() < m.end())
+      .map(|m| {
+        let start = text[..m.start()].chars().count();
+        let end = start + m.as_str().chars().count();
+        (start, end)
+      })
+      .collect()
+  }
+
+  pub fn is_match(&self, text: &str) -> bool {
+    !self.find(text).is_empty()
+  }
+
+  /// Replaces all the occurrences in the text. The replacement is inserted as it is, the groups
+  /// of a regular expression are not expanded.
+  pub fn replace(&self, text: &str, replacement: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut last_end = 0;
+    for m in self.regex.find_iter(text).map_while(|result| result.ok()) {
+      if m.start() == m.end() {
+        continue;
+      }
+      result.push_str(&text[last_end..m.start()]);
+      result.push_str(replacement);
+      last_end = m.end();
+    }
+    result.push_str(&text[last_end..]);
+    result
+  }
+}
+
+/// Returns true if the text of the cells of the field can be replaced. The select options are
+/// renamed instead, see [rename_select_options].
+pub fn is_replaceable_field_type(field_type: &FieldType) -> bool {
+  matches!(
+    field_type,
+    FieldType::RichText
+      | FieldType::URL
+      | FieldType::Email
+      | FieldType::Phone
+      | FieldType::Translate
+      | FieldType::Summary
+  )
+}
+
+/// Renames the options whose name matches. An option is not renamed if its new name is empty or
+/// already used by another option. Returns the number of renamed options.
+pub fn rename_select_options(
+  options: &mut [SelectOption],
+  matcher: &TextMatcher,
+  replacement: &str,
+) -> usize {
+  let mut names = options
+    .iter()
+    .map(|option| option.name.clone())
+    .collect::<Vec<String>>();
+  let mut num_of_renamed_options = 0;
+  for option in options.iter_mut() {
+    if !matcher.is_match(&option.name) {
+      continue;
+    }
+    let new_name = matcher.replace(&option.name, replacement);
+    let new_name = new_name.trim();
+    if new_name.is_empty() || names.iter().any(|name| name == new_name) {
+      continue;
+    }
+    names.push(new_name.to_string());
+    option.name = new_name.to_string();
+    num_of_renamed_options += 1;
+  }
+  num_of_renamed_options
+}
+
+fn escape_regex(text: &str) -> String {
+  let mut escaped = String::with_capacity(text.len());
+  for c in text.chars() {
+    if matches!(
+      c,
+      '\\'
+        | '.'
+        | '+'
+        | '*'
+        | '?'
+        | '('
+        | ')'
+        | '|'
+        | '['
+        | ']'
+        | '{'
+        | '}'
+        | '^'
+        | '$'
+        | '#'
+        | '&'
+        | '-'
+        | '~'
+    ) {
+      escaped.push('\\');
+    }
+    escaped.push(c);
+  }
+  escaped
+}
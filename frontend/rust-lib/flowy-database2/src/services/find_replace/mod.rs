@@ -0,0 +1,3 @@
+mod matcher;
+
+pub use matcher::*;
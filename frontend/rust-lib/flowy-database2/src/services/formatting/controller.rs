@@ -0,0 +1,357 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use collab_database::database::gen_database_filter_id;
+use collab_database::fields::Field;
+use collab_database::rows::{Row, RowDetail, RowId};
+use dashmap::DashMap;
+use flowy_error::FlowyResult;
+use lib_infra::priority_task::{QualityOfService, Task, TaskContent, TaskDispatcher};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock as TokioRwLock;
+use tracing::trace;
+
+use crate::entities::{RepeatedFormattingRulePB, RowFormattingPB, RowsFormattingChangesetPB};
+use crate::services::cell::CellCache;
+use crate::services::database_view::{DatabaseViewChanged, DatabaseViewChangedNotifier};
+use crate::services::filter::{apply_filter, Filter, FilterContext};
+use crate::services::formatting::{FormattingRule, FormattingRuleChangeset};
+
+#[async_trait]
+pub trait FormattingDelegate: Send + Sync + 'static {
+  async fn get_fields(&self, view_id: &str, field_ids: Option<Vec<String>>) -> Vec<Field>;
+  async fn get_rows(&self, view_id: &str) -> Vec<Arc<Row>>;
+  async fn get_row(&self, view_id: &str, rows_id: &RowId) -> Option<(usize, Arc<RowDetail>)>;
+  async fn get_all_formatting_rules(&self, view_id: &str) -> Vec<FormattingRule>;
+  async fn save_formatting_rules(&self, view_id: &str, rules: &[FormattingRule]);
+  fn get_user_email(&self) -> Option<String>;
+  fn get_timezone_id(&self) -> Option<String>;
+}
+
+/// Matches the rows of a view against its formatting rules. The conditions of the rules are
+/// evaluated like the filters, see [apply_filter].
+pub struct FormattingController {
+  view_id: String,
+  handler_id: String,
+  delegate: Box<dyn FormattingDelegate>,
+  /// The ids of the rules matched by each row, in the order of the rules
+  rule_ids_by_row_id: DashMap<RowId, Vec<String>>,
+  cell_cache: CellCache,
+  rules: TokioRwLock<Vec<FormattingRule>>,
+  task_scheduler: Arc<TokioRwLock<TaskDispatcher>>,
+  notifier: DatabaseViewChangedNotifier,
+}
+
+impl Drop for FormattingController {
+  fn drop(&mut self) {
+    tracing::trace!("Drop {}", std::any::type_name::<Self>());
+  }
+}
+
+impl FormattingController {
+  pub async fn new<T>(
+    view_id: &str,
+    handler_id: &str,
+    delegate: T,
+    task_scheduler: Arc<TokioRwLock<TaskDispatcher>>,
+    cell_cache: CellCache,
+    notifier: DatabaseViewChangedNotifier,
+  ) -> Self
+  where
+    T: FormattingDelegate + 'static,
+  {
+    // ensure the fields of every rule exist
+    let field_ids = delegate
+      .get_fields(view_id, None)
+      .await
+      .into_iter()
+      .map(|field| field.id)
+      .collect::<Vec<_>>();
+
+    let mut rules = delegate.get_all_formatting_rules(view_id).await;
+    trace!("[Database]: formatting rules: {:?}", rules);
+    let len = rules.len();
+    rules.retain(|rule| {
+      rule
+        .get_field_ids()
+        .iter()
+        .all(|field_id| field_ids.contains(field_id))
+    });
+    if rules.len() != len {
+      delegate.save_formatting_rules(view_id, &rules).await;
+    }
+
+    Self {
+      view_id: view_id.to_string(),
+      handler_id: handler_id.to_string(),
+      delegate: Box::new(delegate),
+      rule_ids_by_row_id: DashMap::default(),
+      cell_cache,
+      rules: TokioRwLock::new(rules),
+      task_scheduler,
+      notifier,
+    }
+  }
+
+  pub async fn has_rules(&self) -> bool {
+    !self.rules.read().await.is_empty()
+  }
+
+  pub async fn has_relative_date_rules(&self) -> bool {
+    self
+      .rules
+      .read()
+      .await
+      .iter()
+      .any(|rule| rule.condition.has_relative_date_filter())
+  }
+
+  pub async fn get_all_rules(&self) -> RepeatedFormattingRulePB {
+    RepeatedFormattingRulePB::from(&*self.rules.read().await)
+  }
+
+  /// Matches all the rows again, e.g. when the day changes and the relative date conditions
+  /// select other dates.
+  pub async fn refresh(&self) {
+    self
+      .gen_task(
+        FormattingEvent::RulesDidChanged,
+        QualityOfService::Background,
+      )
+      .await;
+  }
+
+  pub async fn close(&self) {
+    self
+      .task_scheduler
+      .write()
+      .await
+      .unregister_handler(&self.handler_id)
+      .await;
+  }
+
+  #[tracing::instrument(name = "schedule_formatting_task", level = "trace", skip(self))]
+  async fn gen_task(&self, task_type: FormattingEvent, qos: QualityOfService) {
+    let task_id = self.task_scheduler.read().await.next_task_id();
+    let task = Task::new(
+      &self.handler_id,
+      task_id,
+      TaskContent::Text(task_type.to_json_string()),
+      qos,
+    );
+    self.task_scheduler.write().await.add_task(task);
+  }
+
+  pub async fn did_receive_row_changed(&self, row_id: RowId) {
+    if self.has_rules().await {
+      self
+        .gen_task(
+          FormattingEvent::RowDidChanged(row_id),
+          QualityOfService::UserInteractive,
+        )
+        .await
+    }
+  }
+
+  /// Matches the rows again after their cells were written in a batch. Unlike [Self::refresh],
+  /// only the given rows are matched.
+  pub async fn did_receive_rows_changed(&self, row_ids: Vec<RowId>) {
+    if self.has_rules().await && !row_ids.is_empty() {
+      self
+        .gen_task(
+          FormattingEvent::RowsDidChanged(row_ids),
+          QualityOfService::UserInteractive,
+        )
+        .await
+    }
+  }
+
+  #[tracing::instrument(level = "trace", skip(self))]
+  pub async fn apply_changeset(
+    &self,
+    changeset: FormattingRuleChangeset,
+  ) -> RepeatedFormattingRulePB {
+    let mut rules = self.rules.write().await;
+
+    match changeset {
+      FormattingRuleChangeset::Upsert {
+        rule_id,
+        condition,
+        target,
+        color,
+      } => {
+        let rule_id = rule_id.unwrap_or_else(gen_database_filter_id);
+        let rule = FormattingRule {
+          id: rule_id.clone(),
+          condition: Filter {
+            id: rule_id.clone(),
+            inner: condition,
+          },
+          target,
+          color,
+        };
+        match rules.iter_mut().find(|rule| rule.id == rule_id) {
+          Some(old_rule) => *old_rule = rule,
+          None => rules.push(rule),
+        }
+      },
+      FormattingRuleChangeset::Delete { rule_id } => rules.retain(|rule| rule.id != rule_id),
+      FormattingRuleChangeset::DeleteAllWithFieldId { field_id } => {
+        rules.retain(|rule| !rule.get_field_ids().contains(&field_id))
+      },
+    }
+
+    self
+      .delegate
+      .save_formatting_rules(&self.view_id, &rules)
+      .await;
+
+    self
+      .gen_task(
+        FormattingEvent::RulesDidChanged,
+        QualityOfService::Background,
+      )
+      .await;
+
+    RepeatedFormattingRulePB::from(&*rules)
+  }
+
+  #[tracing::instrument(name = "process_formatting_task", level = "trace", skip_all, err)]
+  pub async fn process(&self, predicate: &str) -> FlowyResult<()> {
+    let event_type = FormattingEvent::from_str(predicate).unwrap();
+    match event_type {
+      FormattingEvent::RulesDidChanged => {
+        let rows = self.delegate.get_rows(&self.view_id).await;
+        self.format_rows_and_notify(&rows).await
+      },
+      FormattingEvent::RowDidChanged(row_id) => {
+        if let Some((_, row_detail)) = self.delegate.get_row(&self.view_id, &row_id).await {
+          let rows = vec![Arc::new(row_detail.row.clone())];
+          self.format_rows_and_notify(&rows).await
+        }
+      },
+      FormattingEvent::RowsDidChanged(row_ids) => {
+        let mut rows = Vec::with_capacity(row_ids.len());
+        for row_id in row_ids {
+          if let Some((_, row_detail)) = self.delegate.get_row(&self.view_id, &row_id).await {
+            rows.push(Arc::new(row_detail.row.clone()));
+          }
+        }
+        self.format_rows_and_notify(&rows).await
+      },
+    }
+    Ok(())
+  }
+
+  /// Returns the ids of the rules matched by each row
+  pub async fn format_rows(&self, rows: &[Arc<Row>]) -> HashMap<RowId, Vec<String>> {
+    let rules = self.rules.read().await;
+    let field_by_field_id = self.get_field_map().await;
+    let context = FilterContext::new(
+      self.delegate.get_user_email(),
+      self.delegate.get_timezone_id(),
+    );
+    rows
+      .iter()
+      .map(|row| {
+        let rule_ids = match_rules(row, &field_by_field_id, &self.cell_cache, &rules, &context);
+        self
+          .rule_ids_by_row_id
+          .insert(row.id.clone(), rule_ids.clone());
+        (row.id.clone(), rule_ids)
+      })
+      .collect()
+  }
+
+  /// Matches the rows and sends the rows whose matched rules changed
+  pub async fn format_rows_and_notify(&self, rows: &[Arc<Row>]) {
+    let old_rule_ids_by_row_id = rows
+      .iter()
+      .map(|row| {
+        let rule_ids = self
+          .rule_ids_by_row_id
+          .get(&row.id)
+          .map(|rule_ids| rule_ids.clone())
+          .unwrap_or_default();
+        (row.id.clone(), rule_ids)
+      })
+      .collect::<HashMap<_, _>>();
+
+    let changed_rows = self
+      .format_rows(rows)
+      .await
+      .into_iter()
+      .filter(|(row_id, rule_ids)| old_rule_ids_by_row_id.get(row_id) != Some(rule_ids))
+      .map(|(row_id, rule_ids)| RowFormattingPB {
+        row_id: row_id.into_inner(),
+        rule_ids,
+      })
+      .collect::<Vec<_>>();
+
+    if !changed_rows.is_empty() {
+      let notification = RowsFormattingChangesetPB {
+        view_id: self.view_id.clone(),
+        rows: changed_rows,
+      };
+      let _ = self
+        .notifier
+        .send(DatabaseViewChanged::FormattingNotification(notification));
+    }
+  }
+
+  async fn get_field_map(&self) -> HashMap<String, Field> {
+    self
+      .delegate
+      .get_fields(&self.view_id, None)
+      .await
+      .into_iter()
+      .map(|field| (field.id.clone(), field))
+      .collect::<HashMap<String, Field>>()
+  }
+}
+
+/// Returns the ids of the rules whose condition is matched by the row
+fn match_rules(
+  row: &Row,
+  field_by_field_id: &HashMap<String, Field>,
+  cell_data_cache: &CellCache,
+  rules: &[FormattingRule],
+  context: &FilterContext,
+) -> Vec<String> {
+  rules
+    .iter()
+    .filter(|rule| {
+      apply_filter(
+        row,
+        field_by_field_id,
+        cell_data_cache,
+        &rule.condition,
+        context,
+      ) == Some(true)
+    })
+    .map(|rule| rule.id.clone())
+    .collect()
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+enum FormattingEvent {
+  RulesDidChanged,
+  RowDidChanged(RowId),
+  RowsDidChanged(Vec<RowId>),
+}
+
+impl FormattingEvent {
+  fn to_json_string(&self) -> String {
+    serde_json::to_string(self).unwrap()
+  }
+}
+
+impl FromStr for FormattingEvent {
+  type Err = serde_json::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    serde_json::from_str(s)
+  }
+}
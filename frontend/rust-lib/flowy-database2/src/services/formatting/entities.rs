@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use std::ops::Deref;
+
+use anyhow::bail;
+use collab::preclude::{Any, Map, Out};
+use collab::util::AnyMapExt;
+use collab_database::database::Database;
+use collab_database::views::FilterMap;
+
+use crate::entities::{FormattingTargetPB, SelectOptionColorPB};
+use crate::services::filter::{Filter, FilterInner};
+
+/// The prefix of the keys of the formatting rules in the root map of the database. The rules are
+/// a setting of the view, like its filters and sorts, so they are stored by view id and removed
+/// with the view.
+pub const FORMATTING_RULES: &str = "formatting_rules";
+
+const RULE_ID: &str = "id";
+const RULE_CONDITION: &str = "condition";
+const RULE_TARGET: &str = "target";
+const RULE_COLOR: &str = "color";
+
+#[derive(Debug)]
+pub struct FormattingRule {
+  pub id: String,
+  /// A Data or FieldComparison filter whose id is the id of the rule
+  pub condition: Filter,
+  pub target: FormattingTargetPB,
+  pub color: SelectOptionColorPB,
+}
+
+impl FormattingRule {
+  /// Returns the id of the field of the condition, which is the field of the coloured cells
+  pub fn field_id(&self) -> &str {
+    match &self.condition.inner {
+      FilterInner::Data { field_id, .. } | FilterInner::FieldComparison { field_id, .. } => {
+        field_id
+      },
+      FilterInner::And { .. } | FilterInner::Or { .. } | FilterInner::Not { .. } => "",
+    }
+  }
+
+  /// Returns the ids of all the fields the condition depends on
+  pub fn get_field_ids(&self) -> Vec<String> {
+    let mut field_ids = HashMap::new();
+    self.condition.get_all_filtering_field_ids(&mut field_ids);
+    field_ids.into_keys().collect()
+  }
+}
+
+#[derive(Debug)]
+pub enum FormattingRuleChangeset {
+  /// Creates a new rule if `rule_id` is None, otherwise replaces the rule
+  Upsert {
+    rule_id: Option<String>,
+    condition: FilterInner,
+    target: FormattingTargetPB,
+    color: SelectOptionColorPB,
+  },
+  Delete {
+    rule_id: String,
+  },
+  DeleteAllWithFieldId {
+    field_id: String,
+  },
+}
+
+impl<'a> From<&'a FormattingRule> for Any {
+  fn from(rule: &'a FormattingRule) -> Self {
+    let condition: Any = FilterMap::from(&rule.condition).into();
+    let map = HashMap::from([
+      (RULE_ID.to_string(), rule.id.as_str().into()),
+      (RULE_CONDITION.to_string(), condition),
+      (RULE_TARGET.to_string(), Any::BigInt(rule.target as i64)),
+      (
+        RULE_COLOR.to_string(),
+        Any::BigInt(rule.color.clone() as i64),
+      ),
+    ]);
+    Any::from(map)
+  }
+}
+
+impl TryFrom<&Any> for FormattingRule {
+  type Error = anyhow::Error;
+
+  fn try_from(value: &Any) -> Result<Self, Self::Error> {
+    let rule_map = match value {
+      Any::Map(rule_map) => rule_map,
+      _ => bail!("invalid formatting rule"),
+    };
+    let id: String = rule_map
+      .get_as(RULE_ID)
+      .ok_or_else(|| anyhow::anyhow!("invalid formatting rule"))?;
+    let condition = match rule_map.get(RULE_CONDITION) {
+      Some(Any::Map(condition_map)) => Filter::try_from(condition_map.deref().clone())?,
+      _ => bail!("invalid formatting rule condition"),
+    };
+    let target = rule_map
+      .get_as::<i64>(RULE_TARGET)
+      .and_then(|target| FormattingTargetPB::try_from(target as u8).ok())
+      .unwrap_or_default();
+    let color = rule_map
+      .get_as::<i64>(RULE_COLOR)
+      .map(color_from_i64)
+      .unwrap_or_default();
+
+    Ok(Self {
+      id,
+      condition,
+      target,
+      color,
+    })
+  }
+}
+
+fn color_from_i64(value: i64) -> SelectOptionColorPB {
+  match value {
+    1 => SelectOptionColorPB::Pink,
+    2 => SelectOptionColorPB::LightPink,
+    3 => SelectOptionColorPB::Orange,
+    4 => SelectOptionColorPB::Yellow,
+    5 => SelectOptionColorPB::Lime,
+    6 => SelectOptionColorPB::Green,
+    7 => SelectOptionColorPB::Aqua,
+    8 => SelectOptionColorPB::Blue,
+    _ => SelectOptionColorPB::Purple,
+  }
+}
+
+fn formatting_rules_key(view_id: &str) -> String {
+  format!("{}:{}", FORMATTING_RULES, view_id)
+}
+
+/// Returns the formatting rules of the view
+pub fn get_formatting_rules(database: &Database, view_id: &str) -> Vec<FormattingRule> {
+  let txn = database.collab.transact();
+  let items = match database.body.root.get(&txn, &formatting_rules_key(view_id)) {
+    Some(Out::Any(Any::Array(items))) => items,
+    _ => return vec![],
+  };
+  let mut rules = vec![];
+  for item in items.iter() {
+    match FormattingRule::try_from(item) {
+      Ok(rule) => rules.push(rule),
+      Err(err) => tracing::error!("Failed to deserialize formatting rule: {:?}", err),
+    }
+  }
+  rules
+}
+
+/// Replaces the formatting rules of the view
+pub fn save_formatting_rules(database: &mut Database, view_id: &str, rules: &[FormattingRule]) {
+  if rules.is_empty() {
+    remove_formatting_rules(database, view_id);
+    return;
+  }
+  let items = rules.iter().map(Any::from).collect::<Vec<_>>();
+  let mut txn = database.collab.transact_mut();
+  database
+    .body
+    .root
+    .insert(&mut txn, formatting_rules_key(view_id), Any::from(items));
+}
+
+/// Removes the formatting rules of the view, called when the view is deleted
+pub fn remove_formatting_rules(database: &mut Database, view_id: &str) {
+  let mut txn = database.collab.transact_mut();
+  database
+    .body
+    .root
+    .remove(&mut txn, &formatting_rules_key(view_id));
+}
@@ -0,0 +1,41 @@
+use crate::services::formatting::FormattingController;
+use async_trait::async_trait;
+
+use lib_infra::priority_task::{TaskContent, TaskHandler};
+use std::sync::Arc;
+
+pub struct FormattingTaskHandler {
+  handler_id: String,
+  formatting_controller: Arc<FormattingController>,
+}
+
+impl FormattingTaskHandler {
+  pub fn new(handler_id: String, formatting_controller: Arc<FormattingController>) -> Self {
+    Self {
+      handler_id,
+      formatting_controller,
+    }
+  }
+}
+
+#[async_trait]
+impl TaskHandler for FormattingTaskHandler {
+  fn handler_id(&self) -> &str {
+    &self.handler_id
+  }
+
+  fn handler_name(&self) -> &str {
+    "FormattingTaskHandler"
+  }
+
+  async fn run(&self, content: TaskContent) -> Result<(), anyhow::Error> {
+    let formatting_controller = self.formatting_controller.clone();
+    if let TaskContent::Text(predicate) = content {
+      formatting_controller
+        .process(&predicate)
+        .await
+        .map_err(anyhow::Error::from)?;
+    }
+    Ok(())
+  }
+}
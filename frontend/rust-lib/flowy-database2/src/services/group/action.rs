@@ -24,6 +24,13 @@ pub trait GroupCustomize: Send + Sync {
     None
   }
 
+  /// Returns the cell of the grouping field in the row. The cells of some fields, like the
+  /// [FieldType::CreatedTime](crate::entities::FieldType::CreatedTime) field, are not stored in
+  /// the row and are built from the row itself.
+  fn get_grouping_cell(&self, row: &Row, field_id: &str) -> Option<Cell> {
+    row.cells.get(field_id).cloned()
+  }
+
   /// Returns a bool value to determine whether the group should contain this cell or not.
   fn can_group(
     &self,
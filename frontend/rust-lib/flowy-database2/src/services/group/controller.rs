@@ -3,7 +3,7 @@ use std::marker::PhantomData;
 use std::sync::Arc;
 
 use collab_database::fields::{Field, TypeOptionData};
-use collab_database::rows::{Cells, Row, RowId};
+use collab_database::rows::{Cell, Cells, Row, RowId};
 use flowy_error::{FlowyError, FlowyResult};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
@@ -39,6 +39,7 @@ pub trait GroupControllerDelegate: Send + Sync + 'static {
 /// `GroupController` that only has one group.
 pub struct BaseGroupController<C, G, P> {
   pub grouping_field_id: String,
+  pub grouping_field_type: FieldType,
   pub context: GroupControllerContext<C>,
   group_builder_phantom: PhantomData<G>,
   cell_parser_phantom: PhantomData<P>,
@@ -58,6 +59,7 @@ where
   ) -> FlowyResult<Self> {
     Ok(Self {
       grouping_field_id: grouping_field.id.clone(),
+      grouping_field_type: FieldType::from(grouping_field.field_type),
       context,
       group_builder_phantom: PhantomData,
       cell_parser_phantom: PhantomData,
@@ -194,9 +196,9 @@ where
   #[tracing::instrument(level = "trace", skip_all, fields(row_count=%rows.len(), group_result))]
   fn fill_groups(&mut self, rows: &[&Row], _field: &Field) -> FlowyResult<()> {
     for row in rows {
-      let cell = match row.cells.get(&self.grouping_field_id) {
+      let cell = match self.get_grouping_cell(row, &self.grouping_field_id) {
         None => self.placeholder_cell(),
-        Some(cell) => Some(cell.clone()),
+        Some(cell) => Some(cell),
       };
 
       if let Some(cell) = cell {
@@ -245,9 +247,9 @@ where
   fn did_create_row(&mut self, row: &Row, index: usize) -> Vec<GroupRowsNotificationPB> {
     let mut changesets: Vec<GroupRowsNotificationPB> = vec![];
 
-    let cell = match row.cells.get(&self.grouping_field_id) {
+    let cell = match self.get_grouping_cell(row, &self.grouping_field_id) {
       None => self.placeholder_cell(),
-      Some(cell) => Some(cell.clone()),
+      Some(cell) => Some(cell),
     };
 
     if let Some(cell) = cell {
@@ -303,8 +305,12 @@ where
       deleted_group: None,
       row_changesets: vec![],
     };
-    if let Some(cell_data) = get_cell_data_from_row::<P>(Some(new_row), field) {
-      let old_cell_data = get_cell_data_from_row::<P>(old_row.as_ref(), field);
+    let cell = self.get_grouping_cell(new_row, &field.id);
+    if let Some(cell_data) = get_cell_data_from_cell::<P>(cell.as_ref(), field) {
+      let old_cell = old_row
+        .as_ref()
+        .and_then(|old_row| self.get_grouping_cell(old_row, &field.id));
+      let old_cell_data = get_cell_data_from_cell::<P>(old_cell.as_ref(), field);
       if let Ok((insert, delete)) =
         self.create_or_delete_group_when_cell_changed(new_row, old_cell_data.as_ref(), &cell_data)
       {
@@ -332,8 +338,8 @@ where
     };
 
     // remove row from its group if it is in a group
-    if let Some(cell) = row.cells.get(&self.grouping_field_id) {
-      let cell_data = <T as TypeOption>::CellData::from(cell);
+    if let Some(cell) = self.get_grouping_cell(row, &self.grouping_field_id) {
+      let cell_data = <T as TypeOption>::CellData::from(&cell);
       if !cell_data.is_cell_empty() {
         (result.deleted_group, result.row_changesets) = self.delete_row(row, &cell_data);
         return Ok(result);
@@ -365,8 +371,8 @@ where
       deleted_group: None,
       row_changesets: vec![],
     };
-    let cell = match context.row.cells.get(&self.grouping_field_id) {
-      Some(cell) => Some(cell.clone()),
+    let cell = match self.get_grouping_cell(context.row, &self.grouping_field_id) {
+      Some(cell) => Some(cell),
       None => self.placeholder_cell(),
     };
 
@@ -451,11 +457,11 @@ struct GroupedRow {
   group_id: String,
 }
 
-fn get_cell_data_from_row<P: CellProtobufBlobParser>(
-  row: Option<&Row>,
+fn get_cell_data_from_cell<P: CellProtobufBlobParser>(
+  cell: Option<&Cell>,
   field: &Field,
 ) -> Option<P::Object> {
-  let cell = row.and_then(|row| row.cells.get(&field.id))?;
+  let cell = cell?;
   let cell_bytes = get_cell_protobuf(cell, field, None);
   cell_bytes.parser::<P>().ok()
 }
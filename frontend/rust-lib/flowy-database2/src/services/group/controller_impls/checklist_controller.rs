@@ -0,0 +1,221 @@
+use async_trait::async_trait;
+use collab_database::fields::checklist_type_option::ChecklistTypeOption;
+use collab_database::fields::{Field, TypeOptionData};
+use collab_database::rows::{Cell, Cells, Row};
+use collab_database::template::check_list_parse::ChecklistCellData;
+use flowy_error::FlowyResult;
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{GroupPB, GroupRowsNotificationPB, InsertedRowPB, RowMetaPB};
+use crate::services::field::checklist_type_option::checklist_type_option::ChecklistCellDataParser;
+use crate::services::field::TypeOption;
+use crate::services::group::action::GroupCustomize;
+use crate::services::group::configuration::GroupControllerContext;
+use crate::services::group::controller::BaseGroupController;
+use crate::services::group::{
+  make_no_status_group, move_group_row, GeneratedGroups, Group, GroupsBuilder, MoveGroupRowContext,
+};
+
+pub const CHECKLIST_NOT_STARTED: &str = "not_started";
+pub const CHECKLIST_IN_PROGRESS: &str = "in_progress";
+pub const CHECKLIST_COMPLETED: &str = "completed";
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct ChecklistGroupConfiguration {
+  pub hide_empty: bool,
+}
+
+/// Groups the rows by the completion of their checklist: none of the tasks, some of the tasks or
+/// all the tasks are completed. The rows without tasks are in the no status group.
+pub type ChecklistGroupController =
+  BaseGroupController<ChecklistGroupConfiguration, ChecklistGroupBuilder, ChecklistCellDataParser>;
+
+pub type ChecklistGroupControllerContext = GroupControllerContext<ChecklistGroupConfiguration>;
+
+/// Returns the id of the group of the checklist, or None if the checklist has no tasks.
+fn get_checklist_group_id(
+  num_of_tasks: usize,
+  num_of_completed_tasks: usize,
+) -> Option<&'static str> {
+  if num_of_tasks == 0 {
+    None
+  } else if num_of_completed_tasks == 0 {
+    Some(CHECKLIST_NOT_STARTED)
+  } else if num_of_completed_tasks < num_of_tasks {
+    Some(CHECKLIST_IN_PROGRESS)
+  } else {
+    Some(CHECKLIST_COMPLETED)
+  }
+}
+
+fn get_cell_data_group_id(cell_data: &ChecklistCellData) -> Option<&'static str> {
+  let num_of_completed_tasks = cell_data
+    .options
+    .iter()
+    .filter(|option| cell_data.selected_option_ids.contains(&option.id))
+    .count();
+  get_checklist_group_id(cell_data.options.len(), num_of_completed_tasks)
+}
+
+#[async_trait]
+impl GroupCustomize for ChecklistGroupController {
+  type GroupTypeOption = ChecklistTypeOption;
+
+  fn placeholder_cell(&self) -> Option<Cell> {
+    Some(Cell::from(ChecklistCellData::default()))
+  }
+
+  fn can_group(
+    &self,
+    content: &str,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> bool {
+    get_cell_data_group_id(cell_data) == Some(content)
+  }
+
+  fn add_or_remove_row_when_cell_changed(
+    &mut self,
+    row: &Row,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+  ) -> Vec<GroupRowsNotificationPB> {
+    let group_id =
+      get_checklist_group_id(cell_data.options.len(), cell_data.selected_options.len());
+    let mut changesets = vec![];
+    self.context.iter_mut_status_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if group_id == Some(group.id.as_str()) {
+        if !group.contains_row(&row.id) {
+          changeset
+            .inserted_rows
+            .push(InsertedRowPB::new(RowMetaPB::from(row)));
+          group.add_row(row.clone());
+        }
+      } else if group.contains_row(&row.id) {
+        group.remove_row(&row.id);
+        changeset.deleted_rows.push(row.id.clone().into_inner());
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+    changesets
+  }
+
+  fn delete_row(
+    &mut self,
+    row: &Row,
+    _cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> (Option<GroupPB>, Vec<GroupRowsNotificationPB>) {
+    let mut changesets = vec![];
+    self.context.iter_mut_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if group.contains_row(&row.id) {
+        changeset.deleted_rows.push(row.id.clone().into_inner());
+        group.remove_row(&row.id);
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+    (None, changesets)
+  }
+
+  fn move_row(&mut self, mut context: MoveGroupRowContext) -> Vec<GroupRowsNotificationPB> {
+    let is_moved_to_other_group = self
+      .context
+      .get_group(context.to_group_id)
+      .is_some_and(|(_, group)| !group.contains_row(&context.row.id));
+
+    let mut group_changeset = vec![];
+    self.context.iter_mut_groups(|group| {
+      if let Some(changeset) = move_group_row(group, &mut context) {
+        group_changeset.push(changeset);
+      }
+    });
+
+    // Completes or restarts all the tasks of the row. The tasks of a row moved to the in progress
+    // group are kept as they are.
+    if is_moved_to_other_group {
+      let mut cell_data = context
+        .row
+        .cells
+        .get(&context.field.id)
+        .map(ChecklistCellData::from)
+        .unwrap_or_default();
+      let is_updated = match context.to_group_id {
+        CHECKLIST_COMPLETED => {
+          cell_data.selected_option_ids = cell_data
+            .options
+            .iter()
+            .map(|option| option.id.clone())
+            .collect();
+          true
+        },
+        CHECKLIST_NOT_STARTED => {
+          cell_data.selected_option_ids.clear();
+          true
+        },
+        _ => false,
+      };
+      if is_updated {
+        context
+          .updated_cells
+          .insert(context.field.id.clone(), Cell::from(cell_data));
+      }
+    }
+    group_changeset
+  }
+
+  async fn delete_group(&mut self, _group_id: &str) -> FlowyResult<Option<TypeOptionData>> {
+    Ok(None)
+  }
+
+  fn will_create_row(&self, _cells: &mut Cells, _field: &Field, group_id: &str) {
+    // A new row has no tasks, it can't be completed or in progress
+    tracing::trace!("Create a row in the checklist group: {}", group_id);
+  }
+}
+
+pub struct ChecklistGroupBuilder();
+#[async_trait]
+impl GroupsBuilder for ChecklistGroupBuilder {
+  type Context = ChecklistGroupControllerContext;
+  type GroupTypeOption = ChecklistTypeOption;
+
+  async fn build(
+    field: &Field,
+    _context: &Self::Context,
+    _type_option: &Self::GroupTypeOption,
+  ) -> GeneratedGroups {
+    let groups = [
+      CHECKLIST_NOT_STARTED,
+      CHECKLIST_IN_PROGRESS,
+      CHECKLIST_COMPLETED,
+    ]
+    .into_iter()
+    .map(|group_id| Group::new(group_id.to_string()))
+    .collect();
+
+    GeneratedGroups {
+      no_status_group: Some(make_no_status_group(field)),
+      groups,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::services::group::controller_impls::checklist_controller::{
+    get_checklist_group_id, CHECKLIST_COMPLETED, CHECKLIST_IN_PROGRESS, CHECKLIST_NOT_STARTED,
+  };
+
+  #[test]
+  fn checklist_group_id_test() {
+    assert_eq!(get_checklist_group_id(0, 0), None);
+    assert_eq!(get_checklist_group_id(3, 0), Some(CHECKLIST_NOT_STARTED));
+    assert_eq!(get_checklist_group_id(3, 2), Some(CHECKLIST_IN_PROGRESS));
+    assert_eq!(get_checklist_group_id(3, 3), Some(CHECKLIST_COMPLETED));
+  }
+}
@@ -266,11 +266,17 @@ fn make_group_from_date_cell(cell_data: &DateCellData, setting_content: &str) ->
   Group::new(group_id)
 }
 
-const GROUP_ID_DATE_FORMAT: &str = "%Y/%m/%d";
+pub(crate) const GROUP_ID_DATE_FORMAT: &str = "%Y/%m/%d";
 
 fn get_date_group_id(cell_data: &DateCellData, setting_content: &str) -> String {
+  get_timestamp_group_id(cell_data.timestamp, setting_content)
+}
+
+/// Returns the id of the group of the timestamp according to the [DateCondition] of the group
+/// setting. It's shared by the groups of the date and the timestamp fields.
+pub(crate) fn get_timestamp_group_id(timestamp: Option<i64>, setting_content: &str) -> String {
   let config = DateGroupConfiguration::from_json(setting_content).unwrap_or_default();
-  let date_time = date_time_from_timestamp(cell_data.timestamp);
+  let date_time = date_time_from_timestamp(timestamp);
 
   let date_format = GROUP_ID_DATE_FORMAT;
   let month_format = &date_format.replace("%d", "01");
@@ -0,0 +1,317 @@
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use collab_database::fields::number_type_option::{NumberCellFormat, NumberTypeOption};
+use collab_database::fields::{Field, TypeOptionData};
+use collab_database::rows::{Cells, Row};
+use collab_database::template::number_parse::NumberCellData;
+use flowy_error::{internal_error, FlowyResult};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{
+  GroupPB, GroupRowsNotificationPB, InsertedGroupPB, InsertedRowPB, RowMetaPB,
+};
+use crate::services::cell::insert_text_cell;
+use crate::services::field::{NumberCellStrParser, TypeOption};
+use crate::services::group::action::GroupCustomize;
+use crate::services::group::configuration::GroupControllerContext;
+use crate::services::group::controller::BaseGroupController;
+use crate::services::group::{
+  make_no_status_group, move_group_row, GeneratedGroups, Group, GroupsBuilder, MoveGroupRowContext,
+};
+
+const DEFAULT_BUCKET_WIDTH: i64 = 10;
+
+#[derive(Serialize, Deserialize)]
+pub struct NumberGroupConfiguration {
+  #[serde(default)]
+  pub hide_empty: bool,
+  /// The width of the range of numbers of each group.
+  #[serde(default = "default_bucket_width")]
+  pub bucket_width: f64,
+}
+
+impl Default for NumberGroupConfiguration {
+  fn default() -> Self {
+    Self {
+      hide_empty: false,
+      bucket_width: default_bucket_width(),
+    }
+  }
+}
+
+fn default_bucket_width() -> f64 {
+  DEFAULT_BUCKET_WIDTH as f64
+}
+
+impl NumberGroupConfiguration {
+  pub fn from_json(s: &str) -> Result<Self, serde_json::Error> {
+    serde_json::from_str(s)
+  }
+
+  pub fn to_json(&self) -> FlowyResult<String> {
+    serde_json::to_string(self).map_err(internal_error)
+  }
+
+  /// Returns the default width if the configured one is not a positive number.
+  fn bucket_width(&self) -> Decimal {
+    Decimal::from_str(&self.bucket_width.to_string())
+      .ok()
+      .filter(|width| width.is_sign_positive() && !width.is_zero())
+      .unwrap_or_else(|| Decimal::from(DEFAULT_BUCKET_WIDTH))
+  }
+}
+
+/// Groups the rows by ranges of numbers. The id of a group is the lower bound of its range, e.g.
+/// with a bucket width of 10, the group `20` contains the numbers from 20 (included) to 30
+/// (excluded).
+pub type NumberGroupController =
+  BaseGroupController<NumberGroupConfiguration, NumberGroupBuilder, NumberCellStrParser>;
+
+pub type NumberGroupControllerContext = GroupControllerContext<NumberGroupConfiguration>;
+
+impl NumberGroupController {
+  /// Returns the id of the group of the row. The number is read from the cell instead of the
+  /// protobuf data, which is formatted with the currency symbol.
+  fn get_row_group_id(&self, row: &Row) -> Option<String> {
+    let cell = row.cells.get(&self.grouping_field_id)?;
+    let number = number_from_cell_data(&NumberCellData::from(cell))?;
+    Some(get_number_group_id(
+      number,
+      &self.context.get_setting_content(),
+    ))
+  }
+}
+
+#[async_trait]
+impl GroupCustomize for NumberGroupController {
+  type GroupTypeOption = NumberTypeOption;
+
+  fn can_group(
+    &self,
+    content: &str,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> bool {
+    number_from_cell_data(cell_data).is_some_and(|number| {
+      content == get_number_group_id(number, &self.context.get_setting_content())
+    })
+  }
+
+  fn create_or_delete_group_when_cell_changed(
+    &mut self,
+    row: &Row,
+    _old_cell_data: Option<&<Self::GroupTypeOption as TypeOption>::CellProtobufType>,
+    _cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+  ) -> FlowyResult<(Option<InsertedGroupPB>, Option<GroupPB>)> {
+    let group_id = self.get_row_group_id(row);
+
+    // Create the group of the range if it's the first number in it
+    let mut inserted_group = None;
+    if let Some(group_id) = group_id.as_ref() {
+      if self.context.get_group(group_id).is_none() {
+        let mut new_group = self.context.add_new_group(Group::new(group_id.clone()))?;
+        new_group.group.rows.push(RowMetaPB::from(row.clone()));
+        inserted_group = Some(new_group);
+      }
+    }
+
+    // Delete the old group of the row if there are no other rows in that group
+    let deleted_group = self
+      .context
+      .groups()
+      .into_iter()
+      .find(|group| {
+        group.id != self.grouping_field_id
+          && Some(&group.id) != group_id.as_ref()
+          && group.rows.len() == 1
+          && group.contains_row(&row.id)
+      })
+      .cloned();
+    let deleted_group = match deleted_group {
+      None => None,
+      Some(group) => {
+        self.context.delete_group(&group.id)?;
+        Some(GroupPB::from(group))
+      },
+    };
+
+    Ok((inserted_group, deleted_group))
+  }
+
+  fn add_or_remove_row_when_cell_changed(
+    &mut self,
+    row: &Row,
+    _cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+  ) -> Vec<GroupRowsNotificationPB> {
+    let group_id = self.get_row_group_id(row);
+    let mut changesets = vec![];
+    self.context.iter_mut_status_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if Some(&group.id) == group_id.as_ref() {
+        if !group.contains_row(&row.id) {
+          changeset
+            .inserted_rows
+            .push(InsertedRowPB::new(RowMetaPB::from(row.clone())));
+          group.add_row(row.clone());
+        }
+      } else if group.contains_row(&row.id) {
+        group.remove_row(&row.id);
+        changeset.deleted_rows.push(row.id.clone().into_inner());
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+    changesets
+  }
+
+  fn delete_row(
+    &mut self,
+    row: &Row,
+    _cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> (Option<GroupPB>, Vec<GroupRowsNotificationPB>) {
+    let deleted_group = self
+      .context
+      .groups()
+      .into_iter()
+      .find(|group| {
+        group.id != self.grouping_field_id && group.rows.len() == 1 && group.contains_row(&row.id)
+      })
+      .cloned();
+
+    let mut changesets = vec![];
+    self.context.iter_mut_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if group.contains_row(&row.id) {
+        group.remove_row(&row.id);
+        changeset.deleted_rows.push(row.id.clone().into_inner());
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+
+    let deleted_group = deleted_group.map(|group| {
+      let _ = self.context.delete_group(&group.id);
+      group.into()
+    });
+
+    (deleted_group, changesets)
+  }
+
+  fn move_row(&mut self, mut context: MoveGroupRowContext) -> Vec<GroupRowsNotificationPB> {
+    let mut group_changeset = vec![];
+    self.context.iter_mut_groups(|group| {
+      if let Some(changeset) = move_group_row(group, &mut context) {
+        group_changeset.push(changeset);
+      }
+    });
+    group_changeset
+  }
+
+  async fn delete_group(&mut self, group_id: &str) -> FlowyResult<Option<TypeOptionData>> {
+    self.context.delete_group(group_id)?;
+    Ok(None)
+  }
+
+  fn will_create_row(&self, cells: &mut Cells, field: &Field, group_id: &str) {
+    match self.context.get_group(group_id) {
+      None => tracing::warn!("Can not find the group: {}", group_id),
+      Some((_, group)) => {
+        let cell = insert_text_cell(group.id.clone(), field);
+        cells.insert(field.id.clone(), cell);
+      },
+    }
+  }
+}
+
+pub struct NumberGroupBuilder();
+#[async_trait]
+impl GroupsBuilder for NumberGroupBuilder {
+  type Context = NumberGroupControllerContext;
+  type GroupTypeOption = NumberTypeOption;
+
+  async fn build(
+    field: &Field,
+    context: &Self::Context,
+    type_option: &Self::GroupTypeOption,
+  ) -> GeneratedGroups {
+    // Read all the cells for the grouping field
+    let cells = context.get_all_cells().await;
+
+    // The cell data is formatted with the format of the type option
+    let mut lower_bounds = cells
+      .into_iter()
+      .flat_map(|value| value.into_number_field_cell_data())
+      .flat_map(|cell_data| {
+        NumberCellFormat::from_format_str(&cell_data.0, &type_option.format)
+          .ok()
+          .and_then(|format| format.decimal().as_ref().copied())
+      })
+      .map(|number| bucket_lower_bound(number, &context.get_setting_content()))
+      .collect::<Vec<_>>();
+    lower_bounds.sort();
+    lower_bounds.dedup();
+
+    let groups = lower_bounds
+      .into_iter()
+      .map(|lower_bound| Group::new(lower_bound.to_string()))
+      .collect();
+
+    GeneratedGroups {
+      no_status_group: Some(make_no_status_group(field)),
+      groups,
+    }
+  }
+}
+
+/// Returns the number stored in the cell, which is not formatted.
+fn number_from_cell_data(cell_data: &NumberCellData) -> Option<Decimal> {
+  Decimal::from_str(cell_data.0.trim()).ok()
+}
+
+fn bucket_lower_bound(number: Decimal, setting_content: &str) -> Decimal {
+  let config = NumberGroupConfiguration::from_json(setting_content).unwrap_or_default();
+  let width = config.bucket_width();
+  ((number / width).floor() * width).normalize()
+}
+
+fn get_number_group_id(number: Decimal, setting_content: &str) -> String {
+  bucket_lower_bound(number, setting_content).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::str::FromStr;
+
+  use rust_decimal::Decimal;
+
+  use crate::services::group::controller_impls::number_controller::get_number_group_id;
+
+  #[test]
+  fn number_group_id_test() {
+    let tests = [
+      ("25", "", "20"),
+      ("20", "", "20"),
+      ("-3", "", "-10"),
+      ("0.5", r#"{"bucket_width": 0.25}"#, "0.5"),
+      ("7.9", r#"{"hide_empty": false, "bucket_width": 2}"#, "6"),
+      // Falls back to the default width
+      ("15", r#"{"bucket_width": 0}"#, "10"),
+      ("15", r#"{"bucket_width": -5}"#, "10"),
+    ];
+
+    for (i, (number, setting_content, exp_group_id)) in tests.iter().enumerate() {
+      let number = Decimal::from_str(number).unwrap();
+      assert_eq!(
+        get_number_group_id(number, setting_content),
+        *exp_group_id,
+        "test {}",
+        i
+      );
+    }
+  }
+}
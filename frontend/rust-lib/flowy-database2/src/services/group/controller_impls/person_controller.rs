@@ -0,0 +1,244 @@
+use async_trait::async_trait;
+use collab_database::fields::{Field, TypeOptionData};
+use collab_database::rows::{new_cell_builder, Cell, Cells, Row};
+use serde::{Deserialize, Serialize};
+
+use flowy_error::FlowyResult;
+
+use crate::entities::{
+  FieldType, GroupPB, GroupRowsNotificationPB, InsertedGroupPB, InsertedRowPB, RowMetaPB,
+};
+use crate::services::cell::insert_person_cell;
+use crate::services::field::{
+  Person, PersonCellData, PersonCellDataParser, PersonTypeOption, TypeOption,
+};
+use crate::services::group::action::GroupCustomize;
+use crate::services::group::configuration::GroupControllerContext;
+use crate::services::group::controller::BaseGroupController;
+use crate::services::group::{
+  make_no_status_group, move_group_row, GeneratedGroups, Group, GroupsBuilder, MoveGroupRowContext,
+};
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct PersonGroupConfiguration {
+  pub hide_empty: bool,
+}
+
+/// Groups the rows by assignee. There is one group per workspace member found in the cells and
+/// the id of the group is the email of the member. A row with several persons is in several
+/// groups.
+pub type PersonGroupController =
+  BaseGroupController<PersonGroupConfiguration, PersonGroupGenerator, PersonCellDataParser>;
+
+pub type PersonGroupControllerContext = GroupControllerContext<PersonGroupConfiguration>;
+
+impl PersonGroupController {
+  /// Returns the person of the group, read from the cells of the rows in the groups so its name
+  /// and avatar are kept.
+  fn find_person(&self, email: &str) -> Person {
+    self
+      .context
+      .groups()
+      .into_iter()
+      .flat_map(|group| group.rows.iter())
+      .filter_map(|row| row.cells.get(&self.grouping_field_id))
+      .flat_map(|cell| PersonCellData::from(cell).persons)
+      .find(|person| person.has_email(email))
+      .unwrap_or_else(|| Person::from_email(email))
+  }
+}
+
+#[async_trait]
+impl GroupCustomize for PersonGroupController {
+  type GroupTypeOption = PersonTypeOption;
+
+  fn placeholder_cell(&self) -> Option<Cell> {
+    let mut cell = new_cell_builder(FieldType::Person);
+    cell.insert("data".into(), "".into());
+    Some(cell)
+  }
+
+  fn can_group(
+    &self,
+    content: &str,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> bool {
+    cell_data.contains(content)
+  }
+
+  fn create_or_delete_group_when_cell_changed(
+    &mut self,
+    row: &Row,
+    old_cell_data: Option<&<Self::GroupTypeOption as TypeOption>::CellProtobufType>,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+  ) -> FlowyResult<(Option<InsertedGroupPB>, Option<GroupPB>)> {
+    let cell_data = PersonCellData::from(cell_data.clone());
+
+    // Create the group of the person that was assigned for the first time
+    let mut inserted_group = None;
+    if let Some(email) = cell_data
+      .emails()
+      .find(|email| self.context.get_group(email).is_none())
+    {
+      let mut new_group = self.context.add_new_group(Group::new(email.to_string()))?;
+      new_group.group.rows.push(RowMetaPB::from(row.clone()));
+      inserted_group = Some(new_group);
+    }
+
+    // Delete the group of the person that is no longer assigned to any row
+    let deleted_group = old_cell_data
+      .map(|old_cell_data| PersonCellData::from(old_cell_data.clone()))
+      .and_then(|old_cell_data| {
+        old_cell_data
+          .emails()
+          .filter(|email| !cell_data.contains(email))
+          .find_map(|email| match self.context.get_group(email) {
+            Some((_, group)) if group.rows.len() == 1 && group.contains_row(&row.id) => {
+              Some(group.id.clone())
+            },
+            _ => None,
+          })
+      });
+    let deleted_group = match deleted_group {
+      None => None,
+      Some(group_id) => {
+        let group = self
+          .context
+          .get_group(&group_id)
+          .map(|(_, group)| group.clone());
+        self.context.delete_group(&group_id)?;
+        group.map(GroupPB::from)
+      },
+    };
+
+    Ok((inserted_group, deleted_group))
+  }
+
+  fn add_or_remove_row_when_cell_changed(
+    &mut self,
+    row: &Row,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+  ) -> Vec<GroupRowsNotificationPB> {
+    let cell_data = PersonCellData::from(cell_data.clone());
+    let mut changesets = vec![];
+    self.context.iter_mut_status_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if cell_data.contains(&group.id) {
+        if !group.contains_row(&row.id) {
+          changeset
+            .inserted_rows
+            .push(InsertedRowPB::new(RowMetaPB::from(row)));
+          group.add_row(row.clone());
+        }
+      } else if group.contains_row(&row.id) {
+        group.remove_row(&row.id);
+        changeset.deleted_rows.push(row.id.clone().into_inner());
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+    changesets
+  }
+
+  fn delete_row(
+    &mut self,
+    row: &Row,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> (Option<GroupPB>, Vec<GroupRowsNotificationPB>) {
+    let deleted_group = cell_data
+      .emails()
+      .find_map(|email| match self.context.get_group(email) {
+        Some((_, group)) if group.rows.len() == 1 && group.contains_row(&row.id) => {
+          Some(group.clone())
+        },
+        _ => None,
+      });
+
+    let mut changesets = vec![];
+    self.context.iter_mut_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if group.contains_row(&row.id) {
+        group.remove_row(&row.id);
+        changeset.deleted_rows.push(row.id.clone().into_inner());
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+
+    let deleted_group = deleted_group.map(|group| {
+      let _ = self.context.delete_group(&group.id);
+      group.into()
+    });
+
+    (deleted_group, changesets)
+  }
+
+  fn move_row(&mut self, mut context: MoveGroupRowContext) -> Vec<GroupRowsNotificationPB> {
+    let person = self.find_person(context.to_group_id);
+    let mut group_changeset = vec![];
+    self.context.iter_mut_groups(|group| {
+      if let Some(changeset) = move_group_row(group, &mut context) {
+        group_changeset.push(changeset);
+      }
+    });
+
+    // The moved row is assigned to the person of the group it was moved to
+    if context.updated_cells.contains_key(&context.field.id) {
+      let cell = insert_person_cell(vec![person], context.field);
+      context.updated_cells.insert(context.field.id.clone(), cell);
+    }
+    group_changeset
+  }
+
+  async fn delete_group(&mut self, group_id: &str) -> FlowyResult<Option<TypeOptionData>> {
+    self.context.delete_group(group_id)?;
+    Ok(None)
+  }
+
+  fn will_create_row(&self, cells: &mut Cells, field: &Field, group_id: &str) {
+    match self.context.get_group(group_id) {
+      None => tracing::warn!("Can not find the group: {}", group_id),
+      Some((_, group)) => {
+        let cell = insert_person_cell(vec![self.find_person(&group.id)], field);
+        cells.insert(field.id.clone(), cell);
+      },
+    }
+  }
+}
+
+pub struct PersonGroupGenerator();
+#[async_trait]
+impl GroupsBuilder for PersonGroupGenerator {
+  type Context = PersonGroupControllerContext;
+  type GroupTypeOption = PersonTypeOption;
+
+  async fn build(
+    field: &Field,
+    context: &Self::Context,
+    _type_option: &Self::GroupTypeOption,
+  ) -> GeneratedGroups {
+    // Read all the cells for the grouping field
+    let cells = context.get_all_cells().await;
+
+    // Generate one group for each person
+    let mut groups: Vec<Group> = vec![];
+    for person in cells
+      .into_iter()
+      .flat_map(|value| value.into_person_field_cell_data())
+      .flat_map(|cell_data| cell_data.persons)
+    {
+      if !groups.iter().any(|group| person.has_email(&group.id)) {
+        groups.push(Group::new(person.email));
+      }
+    }
+
+    GeneratedGroups {
+      no_status_group: Some(make_no_status_group(field)),
+      groups,
+    }
+  }
+}
@@ -0,0 +1,217 @@
+use async_trait::async_trait;
+use collab_database::fields::relation_type_option::RelationTypeOption;
+use collab_database::fields::{Field, TypeOptionData};
+use collab_database::rows::{Cell, Cells, Row, RowId};
+use collab_database::template::relation_parse::RelationCellData;
+use serde::{Deserialize, Serialize};
+
+use flowy_error::FlowyResult;
+
+use crate::entities::{
+  GroupPB, GroupRowsNotificationPB, InsertedGroupPB, InsertedRowPB, RelationCellDataPB, RowMetaPB,
+};
+use crate::services::cell::insert_relation_cell;
+use crate::services::field::{RelationCellDataParser, TypeOption};
+use crate::services::group::action::GroupCustomize;
+use crate::services::group::configuration::GroupControllerContext;
+use crate::services::group::controller::BaseGroupController;
+use crate::services::group::{
+  make_no_status_group, move_group_row, GeneratedGroups, Group, GroupsBuilder, MoveGroupRowContext,
+};
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct RelationGroupConfiguration {
+  pub hide_empty: bool,
+}
+
+/// Groups the rows by the related rows. There is one group per related row and the id of the
+/// group is the id of the related row. A row with several related rows is in several groups.
+pub type RelationGroupController =
+  BaseGroupController<RelationGroupConfiguration, RelationGroupGenerator, RelationCellDataParser>;
+
+pub type RelationGroupControllerContext = GroupControllerContext<RelationGroupConfiguration>;
+
+fn contains_row_id(cell_data: &RelationCellDataPB, row_id: &str) -> bool {
+  cell_data.row_ids.iter().any(|id| id == row_id)
+}
+
+#[async_trait]
+impl GroupCustomize for RelationGroupController {
+  type GroupTypeOption = RelationTypeOption;
+
+  fn placeholder_cell(&self) -> Option<Cell> {
+    Some(Cell::from(RelationCellData { row_ids: vec![] }))
+  }
+
+  fn can_group(
+    &self,
+    content: &str,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> bool {
+    cell_data.row_ids.iter().any(|id| id.as_str() == content)
+  }
+
+  fn create_or_delete_group_when_cell_changed(
+    &mut self,
+    row: &Row,
+    old_cell_data: Option<&<Self::GroupTypeOption as TypeOption>::CellProtobufType>,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+  ) -> FlowyResult<(Option<InsertedGroupPB>, Option<GroupPB>)> {
+    // Create the group of the row that was related for the first time
+    let mut inserted_group = None;
+    if let Some(row_id) = cell_data
+      .row_ids
+      .iter()
+      .find(|row_id| self.context.get_group(row_id).is_none())
+    {
+      let mut new_group = self.context.add_new_group(Group::new(row_id.clone()))?;
+      new_group.group.rows.push(RowMetaPB::from(row.clone()));
+      inserted_group = Some(new_group);
+    }
+
+    // Delete the group of the related row that is no longer related to any row
+    let deleted_group = old_cell_data.and_then(|old_cell_data| {
+      old_cell_data
+        .row_ids
+        .iter()
+        .filter(|row_id| !contains_row_id(cell_data, row_id))
+        .find_map(|row_id| match self.context.get_group(row_id) {
+          Some((_, group)) if group.rows.len() == 1 && group.contains_row(&row.id) => {
+            Some(group.clone())
+          },
+          _ => None,
+        })
+    });
+    let deleted_group = match deleted_group {
+      None => None,
+      Some(group) => {
+        self.context.delete_group(&group.id)?;
+        Some(GroupPB::from(group))
+      },
+    };
+
+    Ok((inserted_group, deleted_group))
+  }
+
+  fn add_or_remove_row_when_cell_changed(
+    &mut self,
+    row: &Row,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+  ) -> Vec<GroupRowsNotificationPB> {
+    let mut changesets = vec![];
+    self.context.iter_mut_status_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if contains_row_id(cell_data, &group.id) {
+        if !group.contains_row(&row.id) {
+          changeset
+            .inserted_rows
+            .push(InsertedRowPB::new(RowMetaPB::from(row)));
+          group.add_row(row.clone());
+        }
+      } else if group.contains_row(&row.id) {
+        group.remove_row(&row.id);
+        changeset.deleted_rows.push(row.id.clone().into_inner());
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+    changesets
+  }
+
+  fn delete_row(
+    &mut self,
+    row: &Row,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> (Option<GroupPB>, Vec<GroupRowsNotificationPB>) {
+    let deleted_group =
+      cell_data
+        .row_ids
+        .iter()
+        .find_map(|row_id| match self.context.get_group(row_id) {
+          Some((_, group)) if group.rows.len() == 1 && group.contains_row(&row.id) => {
+            Some(group.clone())
+          },
+          _ => None,
+        });
+
+    let mut changesets = vec![];
+    self.context.iter_mut_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if group.contains_row(&row.id) {
+        group.remove_row(&row.id);
+        changeset.deleted_rows.push(row.id.clone().into_inner());
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+
+    let deleted_group = deleted_group.map(|group| {
+      let _ = self.context.delete_group(&group.id);
+      group.into()
+    });
+
+    (deleted_group, changesets)
+  }
+
+  fn move_row(&mut self, mut context: MoveGroupRowContext) -> Vec<GroupRowsNotificationPB> {
+    let mut group_changeset = vec![];
+    self.context.iter_mut_groups(|group| {
+      if let Some(changeset) = move_group_row(group, &mut context) {
+        group_changeset.push(changeset);
+      }
+    });
+    group_changeset
+  }
+
+  async fn delete_group(&mut self, group_id: &str) -> FlowyResult<Option<TypeOptionData>> {
+    self.context.delete_group(group_id)?;
+    Ok(None)
+  }
+
+  fn will_create_row(&self, cells: &mut Cells, field: &Field, group_id: &str) {
+    match self.context.get_group(group_id) {
+      None => tracing::warn!("Can not find the group: {}", group_id),
+      Some((_, group)) => {
+        let cell = insert_relation_cell(vec![RowId::from(group.id.clone())], field);
+        cells.insert(field.id.clone(), cell);
+      },
+    }
+  }
+}
+
+pub struct RelationGroupGenerator();
+#[async_trait]
+impl GroupsBuilder for RelationGroupGenerator {
+  type Context = RelationGroupControllerContext;
+  type GroupTypeOption = RelationTypeOption;
+
+  async fn build(
+    field: &Field,
+    context: &Self::Context,
+    _type_option: &Self::GroupTypeOption,
+  ) -> GeneratedGroups {
+    // Read all the cells for the grouping field
+    let cells = context.get_all_cells().await;
+
+    // Generate one group for each related row
+    let mut groups: Vec<Group> = vec![];
+    for row_id in cells
+      .into_iter()
+      .flat_map(|value| value.into_relation_field_cell_data())
+      .flat_map(|cell_data| cell_data.row_ids)
+    {
+      if !groups.iter().any(|group| group.id == row_id.as_str()) {
+        groups.push(Group::new(row_id.into_inner()));
+      }
+    }
+
+    GeneratedGroups {
+      no_status_group: Some(make_no_status_group(field)),
+      groups,
+    }
+  }
+}
@@ -2,14 +2,15 @@ use crate::entities::{
   FieldType, GroupRowsNotificationPB, InsertedRowPB, RowMetaPB, SelectOptionCellDataPB,
 };
 use crate::services::cell::{
-  insert_checkbox_cell, insert_date_cell, insert_select_option_cell, insert_url_cell,
+  insert_checkbox_cell, insert_date_cell, insert_person_cell, insert_relation_cell,
+  insert_select_option_cell, insert_text_cell, insert_url_cell,
 };
-use crate::services::field::CHECK;
+use crate::services::field::{Person, CHECK};
 use crate::services::group::{Group, GroupData, MoveGroupRowContext};
 use chrono::NaiveDateTime;
 use collab_database::fields::select_type_option::{SelectOption, SelectOptionIds};
 use collab_database::fields::Field;
-use collab_database::rows::{Cell, Row};
+use collab_database::rows::{Cell, Row, RowId};
 use tracing::debug;
 
 pub fn add_or_remove_select_option_row(
@@ -153,6 +154,24 @@ pub fn make_inserted_cell(group_id: &str, field: &Field) -> Option<Cell> {
       let cell = insert_url_cell(group_id.to_owned(), field);
       Some(cell)
     },
+    FieldType::Person => {
+      let cell = insert_person_cell(vec![Person::from_email(group_id)], field);
+      Some(cell)
+    },
+    FieldType::Number => {
+      // The id of the group is the lower bound of its range
+      let cell = insert_text_cell(group_id.to_owned(), field);
+      Some(cell)
+    },
+    FieldType::Relation => {
+      let cell = insert_relation_cell(vec![RowId::from(group_id.to_owned())], field);
+      Some(cell)
+    },
+    FieldType::Checklist | FieldType::CreatedTime | FieldType::LastEditedTime => {
+      // The cell depends on the content of the row or can't be edited, the group controller
+      // updates it itself.
+      None
+    },
     FieldType::DateTime => {
       let date =
         NaiveDateTime::parse_from_str(&format!("{} 00:00:00", group_id), "%Y/%m/%d %H:%M:%S")
@@ -0,0 +1,213 @@
+use async_trait::async_trait;
+use collab_database::fields::timestamp_type_option::TimestampTypeOption;
+use collab_database::fields::{Field, TypeOptionData};
+use collab_database::rows::{Cell, Cells, Row};
+use collab_database::template::timestamp_parse::TimestampCellData;
+use flowy_error::FlowyResult;
+
+use crate::entities::{
+  GroupPB, GroupRowsNotificationPB, InsertedGroupPB, InsertedRowPB, RowMetaPB,
+};
+use crate::services::field::timestamp_type_option::TimestampCellDataParser;
+use crate::services::field::TypeOption;
+use crate::services::group::action::GroupCustomize;
+use crate::services::group::configuration::GroupControllerContext;
+use crate::services::group::controller::BaseGroupController;
+use crate::services::group::controller_impls::date_controller::get_timestamp_group_id;
+use crate::services::group::{
+  make_no_status_group, move_group_row, DateGroupConfiguration, GeneratedGroups, Group,
+  GroupsBuilder, MoveGroupRowContext,
+};
+
+/// Groups the rows by the time they were created or last edited. The groups are the same as the
+/// ones of the [DateGroupController](crate::services::group::DateGroupController). The cells of
+/// these fields can't be edited, so a row can't be moved to another group.
+pub type TimestampGroupController =
+  BaseGroupController<DateGroupConfiguration, TimestampGroupBuilder, TimestampCellDataParser>;
+
+pub type TimestampGroupControllerContext = GroupControllerContext<DateGroupConfiguration>;
+
+#[async_trait]
+impl GroupCustomize for TimestampGroupController {
+  type GroupTypeOption = TimestampTypeOption;
+
+  fn get_grouping_cell(&self, row: &Row, _field_id: &str) -> Option<Cell> {
+    let timestamp = if self.grouping_field_type.is_created_time() {
+      row.created_at
+    } else {
+      row.modified_at
+    };
+    Some(TimestampCellData::new(timestamp).to_cell(self.grouping_field_type))
+  }
+
+  fn can_group(
+    &self,
+    content: &str,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> bool {
+    content == get_timestamp_group_id(cell_data.timestamp, &self.context.get_setting_content())
+  }
+
+  fn create_or_delete_group_when_cell_changed(
+    &mut self,
+    row: &Row,
+    old_cell_data: Option<&<Self::GroupTypeOption as TypeOption>::CellProtobufType>,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+  ) -> FlowyResult<(Option<InsertedGroupPB>, Option<GroupPB>)> {
+    let setting_content = self.context.get_setting_content();
+    let group_id = get_timestamp_group_id(cell_data.timestamp, &setting_content);
+    let mut inserted_group = None;
+    if self.context.get_group(&group_id).is_none() {
+      let mut new_group = self.context.add_new_group(Group::new(group_id.clone()))?;
+      new_group.group.rows.push(RowMetaPB::from(row.clone()));
+      inserted_group = Some(new_group);
+    }
+
+    // Delete the old group if there are no rows in that group
+    let deleted_group = match old_cell_data
+      .map(|old_cell_data| get_timestamp_group_id(old_cell_data.timestamp, &setting_content))
+      .filter(|old_group_id| old_group_id != &group_id)
+      .and_then(|old_group_id| self.context.get_group(&old_group_id))
+    {
+      Some((_, group)) if group.rows.len() == 1 && group.contains_row(&row.id) => {
+        Some(group.clone())
+      },
+      _ => None,
+    };
+
+    let deleted_group = match deleted_group {
+      None => None,
+      Some(group) => {
+        self.context.delete_group(&group.id)?;
+        Some(GroupPB::from(group))
+      },
+    };
+
+    Ok((inserted_group, deleted_group))
+  }
+
+  fn add_or_remove_row_when_cell_changed(
+    &mut self,
+    row: &Row,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+  ) -> Vec<GroupRowsNotificationPB> {
+    let mut changesets = vec![];
+    let group_id = get_timestamp_group_id(cell_data.timestamp, &self.context.get_setting_content());
+    self.context.iter_mut_status_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if group.id == group_id {
+        if !group.contains_row(&row.id) {
+          changeset
+            .inserted_rows
+            .push(InsertedRowPB::new(RowMetaPB::from(row.clone())));
+          group.add_row(row.clone());
+        }
+      } else if group.contains_row(&row.id) {
+        group.remove_row(&row.id);
+        changeset.deleted_rows.push(row.id.clone().into_inner());
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+    changesets
+  }
+
+  fn delete_row(
+    &mut self,
+    row: &Row,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> (Option<GroupPB>, Vec<GroupRowsNotificationPB>) {
+    let mut changesets = vec![];
+    self.context.iter_mut_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if group.contains_row(&row.id) {
+        group.remove_row(&row.id);
+        changeset.deleted_rows.push(row.id.clone().into_inner());
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+
+    let group_id = get_timestamp_group_id(cell_data.timestamp, &self.context.get_setting_content());
+    let deleted_group = match self.context.get_group(&group_id) {
+      Some((_, group)) if group.rows.is_empty() => Some(group.clone()),
+      _ => None,
+    };
+
+    let deleted_group = deleted_group.map(|group| {
+      let _ = self.context.delete_group(&group.id);
+      group.into()
+    });
+
+    (deleted_group, changesets)
+  }
+
+  fn move_row(&mut self, mut context: MoveGroupRowContext) -> Vec<GroupRowsNotificationPB> {
+    // The row can only be reordered in its own group
+    let is_same_group = self
+      .context
+      .get_group(context.to_group_id)
+      .is_some_and(|(_, group)| group.contains_row(&context.row.id));
+    if !is_same_group {
+      tracing::warn!(
+        "Can not move the row to the group: {}, the timestamp can't be edited",
+        context.to_group_id
+      );
+      return vec![];
+    }
+
+    let mut group_changeset = vec![];
+    self.context.iter_mut_groups(|group| {
+      if let Some(changeset) = move_group_row(group, &mut context) {
+        group_changeset.push(changeset);
+      }
+    });
+    group_changeset
+  }
+
+  async fn delete_group(&mut self, group_id: &str) -> FlowyResult<Option<TypeOptionData>> {
+    self.context.delete_group(group_id)?;
+    Ok(None)
+  }
+
+  fn will_create_row(&self, _cells: &mut Cells, _field: &Field, group_id: &str) {
+    // The new row is created now, its timestamp can't be set to the date of the group
+    tracing::trace!("Create a row in the timestamp group: {}", group_id);
+  }
+}
+
+pub struct TimestampGroupBuilder();
+#[async_trait]
+impl GroupsBuilder for TimestampGroupBuilder {
+  type Context = TimestampGroupControllerContext;
+  type GroupTypeOption = TimestampTypeOption;
+
+  async fn build(
+    field: &Field,
+    context: &Self::Context,
+    _type_option: &Self::GroupTypeOption,
+  ) -> GeneratedGroups {
+    // Read all the cells for the grouping field
+    let cells = context.get_all_cells().await;
+
+    // Generate the groups
+    let setting_content = context.get_setting_content();
+    let mut groups: Vec<Group> = cells
+      .into_iter()
+      .flat_map(|value| value.into_timestamp_field_cell_data())
+      .filter(|cell| cell.timestamp.is_some())
+      .map(|cell| Group::new(get_timestamp_group_id(cell.timestamp, &setting_content)))
+      .collect();
+    groups.sort_by(|a, b| a.id.cmp(&b.id));
+    groups.dedup_by(|a, b| a.id == b.id);
+
+    GeneratedGroups {
+      no_status_group: Some(make_no_status_group(field)),
+      groups,
+    }
+  }
+}
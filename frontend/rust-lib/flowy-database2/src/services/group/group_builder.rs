@@ -9,11 +9,14 @@ use flowy_error::FlowyResult;
 use crate::entities::FieldType;
 use crate::services::field::TypeOption;
 use crate::services::group::{
-  CheckboxGroupController, CheckboxGroupControllerContext, DateGroupController,
-  DateGroupControllerContext, DefaultGroupController, Group, GroupContextDelegate, GroupController,
-  GroupControllerDelegate, GroupSetting, MultiSelectGroupController,
-  MultiSelectGroupControllerContext, SingleSelectGroupController,
-  SingleSelectGroupControllerContext, URLGroupController, URLGroupControllerContext,
+  CheckboxGroupController, CheckboxGroupControllerContext, ChecklistGroupController,
+  ChecklistGroupControllerContext, DateGroupController, DateGroupControllerContext,
+  DefaultGroupController, Group, GroupContextDelegate, GroupController, GroupControllerDelegate,
+  GroupSetting, MultiSelectGroupController, MultiSelectGroupControllerContext,
+  NumberGroupController, NumberGroupControllerContext, PersonGroupController,
+  PersonGroupControllerContext, RelationGroupController, RelationGroupControllerContext,
+  SingleSelectGroupController, SingleSelectGroupControllerContext, TimestampGroupController,
+  TimestampGroupControllerContext, URLGroupController, URLGroupControllerContext,
 };
 
 /// The [GroupsBuilder] trait is used to generate the groups for different [FieldType]
@@ -122,6 +125,17 @@ where
         URLGroupController::new(&grouping_field, configuration, delegate.clone()).await?;
       group_controller = Box::new(controller);
     },
+    FieldType::Person => {
+      let configuration = PersonGroupControllerContext::new(
+        view_id.to_string(),
+        grouping_field.clone(),
+        delegate.clone(),
+      )
+      .await?;
+      let controller =
+        PersonGroupController::new(&grouping_field, configuration, delegate.clone()).await?;
+      group_controller = Box::new(controller);
+    },
     FieldType::DateTime => {
       let configuration = DateGroupControllerContext::new(
         view_id.to_string(),
@@ -133,6 +147,50 @@ where
         DateGroupController::new(&grouping_field, configuration, delegate.clone()).await?;
       group_controller = Box::new(controller);
     },
+    FieldType::Number => {
+      let configuration = NumberGroupControllerContext::new(
+        view_id.to_string(),
+        grouping_field.clone(),
+        delegate.clone(),
+      )
+      .await?;
+      let controller =
+        NumberGroupController::new(&grouping_field, configuration, delegate.clone()).await?;
+      group_controller = Box::new(controller);
+    },
+    FieldType::Relation => {
+      let configuration = RelationGroupControllerContext::new(
+        view_id.to_string(),
+        grouping_field.clone(),
+        delegate.clone(),
+      )
+      .await?;
+      let controller =
+        RelationGroupController::new(&grouping_field, configuration, delegate.clone()).await?;
+      group_controller = Box::new(controller);
+    },
+    FieldType::CreatedTime | FieldType::LastEditedTime => {
+      let configuration = TimestampGroupControllerContext::new(
+        view_id.to_string(),
+        grouping_field.clone(),
+        delegate.clone(),
+      )
+      .await?;
+      let controller =
+        TimestampGroupController::new(&grouping_field, configuration, delegate.clone()).await?;
+      group_controller = Box::new(controller);
+    },
+    FieldType::Checklist => {
+      let configuration = ChecklistGroupControllerContext::new(
+        view_id.to_string(),
+        grouping_field.clone(),
+        delegate.clone(),
+      )
+      .await?;
+      let controller =
+        ChecklistGroupController::new(&grouping_field, configuration, delegate.clone()).await?;
+      group_controller = Box::new(controller);
+    },
     _ => {
       group_controller = Box::new(DefaultGroupController::new(
         view_id,
@@ -157,6 +215,11 @@ where
 ///
 /// * `field`: making the group configuration for the field
 ///
+/// The index of the setting of the grouping field in the group settings of a view
+pub const GROUP_SETTING_INDEX: usize = 0;
+/// The index of the setting of the sub grouping field in the group settings of a view
+pub const SUB_GROUP_SETTING_INDEX: usize = 1;
+
 pub fn default_group_setting(field: &Field) -> GroupSetting {
   let field_id = field.id.clone();
   GroupSetting::new(field_id, field.field_type, "".to_owned())
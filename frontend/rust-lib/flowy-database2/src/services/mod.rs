@@ -5,7 +5,10 @@ pub mod database_view;
 pub mod field;
 pub mod field_settings;
 pub mod filter;
+pub mod find_replace;
+pub mod formatting;
 pub mod group;
+pub mod row_template;
 pub mod setting;
 pub mod share;
 pub mod snapshot;
@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+
+use anyhow::bail;
+use collab::preclude::{Any, Map, Out};
+use collab::util::AnyMapExt;
+use collab_database::database::{Database, DatabaseData};
+use flowy_error::FlowyResult;
+use nanoid::nanoid;
+use serde::{Deserialize, Serialize};
+
+/// The prefix of the keys of the row templates in the root map of the database. Each template is
+/// stored under its own key, so the templates edited on different devices are merged instead of
+/// overwriting each other. The templates are shared by all the views of the database.
+pub const ROW_TEMPLATES: &str = "row_templates";
+
+const TEMPLATE_ID: &str = "id";
+const TEMPLATE_NAME: &str = "name";
+const TEMPLATE_CELLS: &str = "cells";
+const TEMPLATE_DOCUMENT: &str = "document";
+const TEMPLATE_CREATED_AT: &str = "created_at";
+
+pub fn gen_row_template_id() -> String {
+  format!("t:{}", nanoid!(6))
+}
+
+/// A named set of cells prefilling the rows created from the template
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RowTemplate {
+  pub id: String,
+  pub name: String,
+  /// The content of the cells by field id, written like the data of a new row
+  pub cells: HashMap<String, String>,
+  /// The data of the row document, serialized in json
+  pub document: Option<String>,
+  /// The creation time in milliseconds. The templates are listed in the order of creation.
+  #[serde(default)]
+  pub created_at: i64,
+}
+
+impl RowTemplate {
+  pub fn new(name: String, cells: HashMap<String, String>, document: Option<String>) -> Self {
+    Self {
+      id: gen_row_template_id(),
+      name,
+      cells,
+      document,
+      created_at: chrono::Utc::now().timestamp_millis(),
+    }
+  }
+}
+
+#[derive(Debug)]
+pub enum RowTemplateChangeset {
+  /// Creates a new template if `template_id` is None, otherwise replaces the template
+  Upsert {
+    template_id: Option<String>,
+    name: String,
+    cells: HashMap<String, String>,
+    document: Option<String>,
+  },
+  Delete {
+    template_id: String,
+  },
+}
+
+impl<'a> From<&'a RowTemplate> for Any {
+  fn from(template: &'a RowTemplate) -> Self {
+    let cells = template
+      .cells
+      .iter()
+      .map(|(field_id, content)| (field_id.clone(), Any::from(content.as_str())))
+      .collect::<HashMap<String, Any>>();
+    let mut map = HashMap::from([
+      (TEMPLATE_ID.to_string(), template.id.as_str().into()),
+      (TEMPLATE_NAME.to_string(), template.name.as_str().into()),
+      (TEMPLATE_CELLS.to_string(), Any::from(cells)),
+      (
+        TEMPLATE_CREATED_AT.to_string(),
+        Any::BigInt(template.created_at),
+      ),
+    ]);
+    if let Some(document) = &template.document {
+      map.insert(TEMPLATE_DOCUMENT.to_string(), document.as_str().into());
+    }
+    Any::from(map)
+  }
+}
+
+impl TryFrom<&Any> for RowTemplate {
+  type Error = anyhow::Error;
+
+  fn try_from(value: &Any) -> Result<Self, Self::Error> {
+    let template_map = match value {
+      Any::Map(template_map) => template_map,
+      _ => bail!("invalid row template"),
+    };
+    let id: String = template_map
+      .get_as(TEMPLATE_ID)
+      .ok_or_else(|| anyhow::anyhow!("invalid row template"))?;
+    let name: String = template_map.get_as(TEMPLATE_NAME).unwrap_or_default();
+    let mut cells = HashMap::new();
+    if let Some(Any::Map(cells_map)) = template_map.get(TEMPLATE_CELLS) {
+      for (field_id, content) in cells_map.iter() {
+        if let Any::String(content) = content {
+          cells.insert(field_id.clone(), content.to_string());
+        }
+      }
+    }
+    let document: Option<String> = template_map.get_as(TEMPLATE_DOCUMENT);
+    let created_at: i64 = template_map.get_as(TEMPLATE_CREATED_AT).unwrap_or_default();
+
+    Ok(Self {
+      id,
+      name,
+      cells,
+      document,
+      created_at,
+    })
+  }
+}
+
+fn row_template_key(template_id: &str) -> String {
+  format!("{}:{}", ROW_TEMPLATES, template_id)
+}
+
+/// Returns the row templates stored in the database, in the order of creation
+pub fn get_row_templates(database: &Database) -> Vec<RowTemplate> {
+  let prefix = row_template_key("");
+  let txn = database.collab.transact();
+  let mut templates = vec![];
+  for (key, value) in database.body.root.iter(&txn) {
+    if !key.starts_with(&prefix) {
+      continue;
+    }
+    let result = match value {
+      Out::Any(value) => RowTemplate::try_from(&value),
+      _ => Err(anyhow::anyhow!("invalid row template")),
+    };
+    match result {
+      Ok(template) => templates.push(template),
+      Err(err) => tracing::error!("Failed to deserialize row template {}: {:?}", key, err),
+    }
+  }
+  templates.sort_by(|left, right| {
+    left
+      .created_at
+      .cmp(&right.created_at)
+      .then_with(|| left.id.cmp(&right.id))
+  });
+  templates
+}
+
+/// Returns the row template with the given id
+pub fn get_row_template(database: &Database, template_id: &str) -> Option<RowTemplate> {
+  let txn = database.collab.transact();
+  match database.body.root.get(&txn, &row_template_key(template_id)) {
+    Some(Out::Any(value)) => RowTemplate::try_from(&value).ok(),
+    _ => None,
+  }
+}
+
+/// Inserts the template or replaces the template with the same id
+pub fn upsert_row_template(database: &mut Database, template: &RowTemplate) {
+  let mut txn = database.collab.transact_mut();
+  database.body.root.insert(
+    &mut txn,
+    row_template_key(&template.id),
+    Any::from(template),
+  );
+}
+
+pub fn delete_row_template(database: &mut Database, template_id: &str) {
+  let mut txn = database.collab.transact_mut();
+  database
+    .body
+    .root
+    .remove(&mut txn, &row_template_key(template_id));
+}
+
+/// The key of the row templates in the raw data of a database. The raw data is the json of the
+/// [DatabaseData] of the database, which doesn't contain the row templates, with the templates
+/// added under this key.
+const RAW_DATA_ROW_TEMPLATES: &str = "row_templates";
+
+/// Returns the raw data of the database, used by the raw export and the snapshots of the database
+pub fn database_raw_data_to_json(
+  data: &DatabaseData,
+  templates: &[RowTemplate],
+) -> FlowyResult<serde_json::Value> {
+  let mut value = serde_json::to_value(data)?;
+  if let serde_json::Value::Object(map) = &mut value {
+    map.insert(
+      RAW_DATA_ROW_TEMPLATES.to_string(),
+      serde_json::to_value(templates)?,
+    );
+  }
+  Ok(value)
+}
+
+/// Parses the raw data returned by [database_raw_data_to_json]. The templates are None if the
+/// data doesn't contain them, e.g. the data exported before the templates were added to it.
+pub fn database_raw_data_from_json(
+  data: Vec<u8>,
+) -> FlowyResult<(DatabaseData, Option<Vec<RowTemplate>>)> {
+  let mut value = serde_json::from_slice::<serde_json::Value>(&data)?;
+  let templates = match &mut value {
+    serde_json::Value::Object(map) => map
+      .remove(RAW_DATA_ROW_TEMPLATES)
+      .map(serde_json::from_value::<Vec<RowTemplate>>)
+      .transpose()?,
+    _ => None,
+  };
+  let data = DatabaseData::from_json_bytes(serde_json::to_vec(&value)?)?;
+  Ok((data, templates))
+}
@@ -0,0 +1,3 @@
+mod entities;
+
+pub use entities::*;
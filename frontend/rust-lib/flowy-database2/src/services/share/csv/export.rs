@@ -1,14 +1,16 @@
+use std::io::Write;
+use std::sync::Arc;
+
 use collab_database::database::Database;
 use collab_database::fields::Field;
-use collab_database::rows::Cell;
-use collab_database::template::timestamp_parse::TimestampCellData;
-use futures::StreamExt;
-use indexmap::IndexMap;
+use collab_database::rows::Row;
+use futures::{pin_mut, StreamExt};
 
 use flowy_error::{FlowyError, FlowyResult};
 
-use crate::entities::FieldType;
 use crate::services::cell::stringify_cell;
+use crate::services::share::csv::{CSVProgress, CSV_ROWS_PER_BATCH};
+use crate::services::share::util::get_exported_cell;
 
 #[derive(Debug, Clone, Copy)]
 pub enum CSVFormat {
@@ -20,6 +22,13 @@ pub enum CSVFormat {
   META,
 }
 
+/// The rows of a group of the exported view. The name of the group is written as a header
+/// record above its rows.
+pub struct CSVGroup {
+  pub name: String,
+  pub rows: Vec<Arc<Row>>,
+}
+
 pub struct CSVExport;
 impl CSVExport {
   pub async fn export_database(
@@ -27,71 +36,172 @@ impl CSVExport {
     database: &Database,
     style: CSVFormat,
   ) -> FlowyResult<String> {
-    let mut wtr = csv::Writer::from_writer(vec![]);
+    let data = self
+      .export_database_to_writer(database, style, vec![], None)
+      .await?;
+    csv_to_string(data)
+  }
+
+  /// Writes the csv of the database to the `writer` row by row, so the rows are never held in
+  /// memory at once. When `progress` is provided, the progress is reported every
+  /// [CSV_ROWS_PER_BATCH] rows and the export stops with an error once it is cancelled.
+  pub async fn export_database_to_writer<W: Write>(
+    &self,
+    database: &Database,
+    style: CSVFormat,
+    writer: W,
+    progress: Option<&CSVProgress>,
+  ) -> FlowyResult<W> {
+    let mut wtr = csv::Writer::from_writer(writer);
     let inline_view_id = database.get_inline_view_id();
     let fields = database.get_fields_in_view(&inline_view_id, None);
-
-    // Write fields
-    let field_records = fields
-      .iter()
-      .map(|field| match &style {
-        CSVFormat::Original => field.name.clone(),
-        CSVFormat::META => serde_json::to_string(&field).unwrap(),
-      })
-      .collect::<Vec<String>>();
-    wtr
-      .write_record(&field_records)
-      .map_err(|e| FlowyError::internal().with_context(e))?;
+    write_field_record(&mut wtr, &fields, style)?;
 
     // Write rows
-    let mut field_by_field_id = IndexMap::new();
-    fields.into_iter().for_each(|field| {
-      field_by_field_id.insert(field.id.clone(), field);
-    });
+    let total_rows = database.get_row_orders_for_view(&inline_view_id).len();
     let rows = database
       .get_rows_for_view(&inline_view_id, 20, None)
       .await
-      .filter_map(|result| async { result.ok() })
-      .collect::<Vec<_>>()
-      .await;
+      .filter_map(|result| async { result.ok() });
+    pin_mut!(rows);
 
-    let stringify = |cell: &Cell, field: &Field, style: CSVFormat| match style {
-      CSVFormat::Original => stringify_cell(cell, field),
-      CSVFormat::META => serde_json::to_string(cell).unwrap_or_else(|_| "".to_string()),
-    };
+    let mut written_rows = 0;
+    while let Some(row) = rows.next().await {
+      write_row_record(&mut wtr, &row, &fields, style);
 
-    for row in rows {
-      let cells = field_by_field_id
-        .iter()
-        .map(|(field_id, field)| {
-          let field_type = FieldType::from(field.field_type);
-          match field_type {
-            FieldType::LastEditedTime | FieldType::CreatedTime => {
-              let cell_data = if field_type.is_created_time() {
-                TimestampCellData::new(row.created_at)
-              } else {
-                TimestampCellData::new(row.modified_at)
-              };
-              let cell = cell_data.to_cell(field.field_type);
-              stringify(&cell, field, style)
-            },
-            _ => match row.cells.get(field_id) {
-              None => "".to_string(),
-              Some(cell) => stringify(cell, field, style),
-            },
-          }
-        })
-        .collect::<Vec<_>>();
-
-      if let Err(e) = wtr.write_record(&cells) {
-        tracing::warn!("CSV failed to write record: {}", e);
+      written_rows += 1;
+      if let Some(progress) = progress {
+        if written_rows % CSV_ROWS_PER_BATCH == 0 {
+          progress.check_cancelled()?;
+          progress.notify(written_rows, written_rows as f64 / total_rows.max(1) as f64);
+        }
       }
     }
 
+    if let Some(progress) = progress {
+      progress.check_cancelled()?;
+      progress.finish(written_rows);
+    }
+
+    let writer = wtr
+      .into_inner()
+      .map_err(|e| FlowyError::internal().with_context(e))?;
+    Ok(writer)
+  }
+
+  /// Returns the header record of the csv of the fields
+  pub fn export_field_record(&self, fields: &[Field], style: CSVFormat) -> FlowyResult<Vec<u8>> {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    write_field_record(&mut wtr, fields, style)?;
+    wtr
+      .into_inner()
+      .map_err(|e| FlowyError::internal().with_context(e))
+  }
+
+  /// Returns the records of the rows without the header record. It's used to write the csv of a
+  /// database in batches, after [CSVExport::export_field_record].
+  pub fn export_row_records(
+    &self,
+    fields: &[Field],
+    rows: &[Arc<Row>],
+    style: CSVFormat,
+  ) -> FlowyResult<Vec<u8>> {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    for row in rows {
+      write_row_record(&mut wtr, row, fields, style);
+    }
+    wtr
+      .into_inner()
+      .map_err(|e| FlowyError::internal().with_context(e))
+  }
+
+  /// Exports the fields and rows in the given order. It's used to export a database view after
+  /// its filters, sorts and field settings are applied.
+  pub fn export_rows(
+    &self,
+    fields: &[Field],
+    rows: &[Arc<Row>],
+    style: CSVFormat,
+  ) -> FlowyResult<String> {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    write_field_record(&mut wtr, fields, style)?;
+    for row in rows {
+      write_row_record(&mut wtr, row, fields, style);
+    }
+    let data = wtr
+      .into_inner()
+      .map_err(|e| FlowyError::internal().with_context(e))?;
+    csv_to_string(data)
+  }
+
+  /// Same as [CSVExport::export_rows], but the rows are exported group by group. Each group
+  /// starts with a record that contains the group name in its first column.
+  pub fn export_groups(
+    &self,
+    fields: &[Field],
+    groups: &[CSVGroup],
+    style: CSVFormat,
+  ) -> FlowyResult<String> {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    write_field_record(&mut wtr, fields, style)?;
+    for group in groups {
+      let mut header = vec![String::new(); fields.len().max(1)];
+      header[0] = group.name.clone();
+      if let Err(e) = wtr.write_record(&header) {
+        tracing::warn!("CSV failed to write group header: {}", e);
+      }
+
+      for row in group.rows.iter() {
+        write_row_record(&mut wtr, row, fields, style);
+      }
+    }
     let data = wtr
       .into_inner()
       .map_err(|e| FlowyError::internal().with_context(e))?;
-    let csv = String::from_utf8(data).map_err(|e| FlowyError::internal().with_context(e))?;
-    Ok(csv)
+    csv_to_string(data)
   }
 }
+
+fn write_field_record<W: Write>(
+  wtr: &mut csv::Writer<W>,
+  fields: &[Field],
+  style: CSVFormat,
+) -> FlowyResult<()> {
+  let field_records = fields
+    .iter()
+    .map(|field| match &style {
+      CSVFormat::Original => field.name.clone(),
+      CSVFormat::META => serde_json::to_string(&field).unwrap(),
+    })
+    .collect::<Vec<String>>();
+  wtr
+    .write_record(&field_records)
+    .map_err(|e| FlowyError::internal().with_context(e))?;
+  Ok(())
+}
+
+fn write_row_record<W: Write>(
+  wtr: &mut csv::Writer<W>,
+  row: &Row,
+  fields: &[Field],
+  style: CSVFormat,
+) {
+  let cells = fields
+    .iter()
+    .map(|field| match get_exported_cell(row, field) {
+      None => "".to_string(),
+      Some(cell) => match style {
+        CSVFormat::Original => stringify_cell(&cell, field),
+        CSVFormat::META => serde_json::to_string(&cell).unwrap_or_else(|_| "".to_string()),
+      },
+    })
+    .collect::<Vec<_>>();
+
+  if let Err(e) = wtr.write_record(&cells) {
+    tracing::warn!("CSV failed to write record: {}", e);
+  }
+}
+
+fn csv_to_string(data: Vec<u8>) -> FlowyResult<String> {
+  String::from_utf8(data).map_err(|e| FlowyError::internal().with_context(e))
+}
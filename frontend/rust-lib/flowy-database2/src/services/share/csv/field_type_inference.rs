@@ -0,0 +1,261 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use collab_database::fields::select_type_option::SELECTION_IDS_SEPARATOR;
+use fancy_regex::Regex;
+use lazy_static::lazy_static;
+
+use crate::entities::FieldType;
+
+/// The maximum number of non-empty values of a column that are sampled to infer its field type.
+pub(crate) const MAX_SAMPLE_SIZE: usize = 100;
+
+/// A column is only imported as a select field if it has at most this many distinct options.
+const MAX_SELECT_OPTIONS: usize = 20;
+
+const DATE_FORMATS: [&str; 6] = [
+  "%Y-%m-%d",
+  "%Y/%m/%d",
+  "%m/%d/%Y",
+  "%d.%m.%Y",
+  "%B %d, %Y",
+  "%d %B %Y",
+];
+
+const DATE_TIME_FORMATS: [&str; 8] = [
+  "%Y-%m-%d %H:%M",
+  "%Y-%m-%d %H:%M:%S",
+  "%Y-%m-%dT%H:%M:%S",
+  "%Y/%m/%d %H:%M",
+  "%m/%d/%Y %H:%M",
+  "%B %d, %Y %H:%M",
+  "%B %d, %Y %I:%M %p",
+  "%m/%d/%Y %I:%M %p",
+];
+
+lazy_static! {
+  static ref URL_REGEX: Regex =
+    Regex::new(r"^(https?://)?([\w-]+\.)+[A-Za-z]{2,}(:\d+)?([/?#]\S*)?$").unwrap();
+}
+
+/// Returns true if the cells of the field type can be created from the content of a csv
+/// column.
+pub fn is_importable_field_type(field_type: &FieldType) -> bool {
+  matches!(
+    field_type,
+    FieldType::RichText
+      | FieldType::Number
+      | FieldType::DateTime
+      | FieldType::Checkbox
+      | FieldType::URL
+      | FieldType::SingleSelect
+      | FieldType::MultiSelect
+  )
+}
+
+/// Infers the field type of a csv column. Empty values are ignored. The field type is picked
+/// by sampling the first [MAX_SAMPLE_SIZE] values, and the column falls back to text if any of
+/// its values can't be imported as that field type, so no content is lost.
+///
+/// Columns whose values repeat are imported as select fields. If the values contain the
+/// select option separator, the column is imported as a multi-select field.
+pub fn infer_field_type<'a>(values: impl IntoIterator<Item = &'a str>) -> FieldType {
+  let values = values
+    .into_iter()
+    .map(|value| value.trim())
+    .filter(|value| !value.is_empty())
+    .collect::<Vec<&str>>();
+  let field_type = infer_field_type_from_samples(&values[..values.len().min(MAX_SAMPLE_SIZE)]);
+  if values
+    .iter()
+    .all(|value| is_value_of_field_type(value, &field_type))
+  {
+    field_type
+  } else {
+    FieldType::RichText
+  }
+}
+
+fn infer_field_type_from_samples(samples: &[&str]) -> FieldType {
+  if samples.is_empty() {
+    return FieldType::RichText;
+  }
+
+  for field_type in [
+    FieldType::Checkbox,
+    FieldType::Number,
+    FieldType::DateTime,
+    FieldType::URL,
+  ] {
+    if samples
+      .iter()
+      .all(|value| is_value_of_field_type(value, &field_type))
+    {
+      return field_type;
+    }
+  }
+
+  let is_multi_select = samples
+    .iter()
+    .any(|value| value.contains(SELECTION_IDS_SEPARATOR));
+  let options = samples
+    .iter()
+    .flat_map(|value| {
+      if is_multi_select {
+        split_select_options(value)
+      } else {
+        vec![*value]
+      }
+    })
+    .collect::<Vec<&str>>();
+  if is_select_options(&options) {
+    if is_multi_select {
+      return FieldType::MultiSelect;
+    }
+    return FieldType::SingleSelect;
+  }
+
+  FieldType::RichText
+}
+
+/// Returns true if the non-empty csv value can be imported as a cell of the field type without
+/// losing its content. The values of the select fields are added as options, so they always
+/// match.
+pub(crate) fn is_value_of_field_type(value: &str, field_type: &FieldType) -> bool {
+  match field_type {
+    FieldType::Checkbox => parse_checkbox(value).is_some(),
+    FieldType::Number => parse_number(value).is_some() && !is_zero_padded_integer(value),
+    FieldType::DateTime => parse_date(value).is_some(),
+    FieldType::URL => is_url(value),
+    _ => true,
+  }
+}
+
+/// Returns true for the integers with leading zeros, like zip codes or ids, whose zeros are lost
+/// if they are imported as numbers.
+fn is_zero_padded_integer(value: &str) -> bool {
+  let digits = value.trim().trim_start_matches(['-', '+']);
+  digits.len() > 1 && digits.starts_with('0') && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Returns true if the values look like the options of a select field: there are only a few
+/// distinct values and each of them is used more than once on average.
+fn is_select_options(options: &[&str]) -> bool {
+  let distinct = options.iter().collect::<HashSet<_>>().len();
+  distinct > 0 && distinct <= MAX_SELECT_OPTIONS && distinct * 2 <= options.len()
+}
+
+/// Splits the content of a multi-select cell into the names of its options.
+pub(crate) fn split_select_options(content: &str) -> Vec<&str> {
+  content
+    .split(SELECTION_IDS_SEPARATOR)
+    .map(|name| name.trim())
+    .filter(|name| !name.is_empty())
+    .collect()
+}
+
+pub(crate) fn parse_checkbox(content: &str) -> Option<bool> {
+  match content.trim().to_lowercase().as_str() {
+    "yes" | "true" => Some(true),
+    "no" | "false" => Some(false),
+    _ => None,
+  }
+}
+
+pub(crate) fn parse_number(content: &str) -> Option<f64> {
+  content
+    .trim()
+    .parse::<f64>()
+    .ok()
+    .filter(|number| number.is_finite())
+}
+
+/// Parses the content into a timestamp in seconds. The returned flag indicates whether the
+/// content contains the time of the day. Contents without time zone are treated as UTC.
+pub(crate) fn parse_date(content: &str) -> Option<(i64, bool)> {
+  let content = content.trim();
+  if let Ok(date_time) = DateTime::parse_from_rfc3339(content) {
+    return Some((date_time.timestamp(), true));
+  }
+
+  for format in DATE_TIME_FORMATS {
+    if let Ok(date_time) = NaiveDateTime::parse_from_str(content, format) {
+      return Some((date_time.and_utc().timestamp(), true));
+    }
+  }
+
+  for format in DATE_FORMATS {
+    if let Ok(date) = NaiveDate::parse_from_str(content, format) {
+      let date_time = date.and_hms_opt(0, 0, 0)?;
+      return Some((date_time.and_utc().timestamp(), false));
+    }
+  }
+  None
+}
+
+pub(crate) fn is_url(content: &str) -> bool {
+  URL_REGEX.is_match(content.trim()).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::entities::FieldType;
+  use crate::services::share::csv::{infer_field_type, parse_date, MAX_SAMPLE_SIZE};
+
+  #[test]
+  fn infer_primitive_field_type_test() {
+    assert_eq!(infer_field_type(["1", "2.5", "", "-3"]), FieldType::Number);
+    assert_eq!(infer_field_type(["Yes", "no", "TRUE"]), FieldType::Checkbox);
+    assert_eq!(
+      infer_field_type(["May 26, 2023", "2023-05-22", ""]),
+      FieldType::DateTime
+    );
+    assert_eq!(
+      infer_field_type(["appflowy.io", "https://github.com/AppFlowy-IO"]),
+      FieldType::URL
+    );
+    assert_eq!(infer_field_type(["", " "]), FieldType::RichText);
+    assert_eq!(infer_field_type(["1", "abc"]), FieldType::RichText);
+  }
+
+  #[test]
+  fn infer_field_type_keeps_content_test() {
+    // Zero-padded integers lose their zeros as numbers
+    assert_eq!(infer_field_type(["007", "0123"]), FieldType::RichText);
+    assert_eq!(infer_field_type(["0", "0.5", "10"]), FieldType::Number);
+
+    // A value after the samples that isn't a number turns the column into text
+    let mut values = vec!["1"; MAX_SAMPLE_SIZE];
+    values.push("n/a");
+    assert_eq!(infer_field_type(values), FieldType::RichText);
+  }
+
+  #[test]
+  fn infer_select_field_type_test() {
+    assert_eq!(
+      infer_field_type(["Done", "Todo", "Done", "Todo"]),
+      FieldType::SingleSelect
+    );
+    assert_eq!(
+      infer_field_type(["Rust, Dart", "Dart", "Rust"]),
+      FieldType::MultiSelect
+    );
+    // Every value is unique, so the column is treated as plain text.
+    assert_eq!(
+      infer_field_type(["tag 1", "tag 2", "tag 3"]),
+      FieldType::RichText
+    );
+  }
+
+  #[test]
+  fn parse_date_test() {
+    assert_eq!(parse_date("2023-05-26"), Some((1685059200, false)));
+    assert_eq!(parse_date("May 26, 2023"), Some((1685059200, false)));
+    assert_eq!(parse_date("2023-05-26 08:30"), Some((1685089800, true)));
+    assert_eq!(
+      parse_date("2023-05-26T08:30:00+00:00"),
+      Some((1685089800, true))
+    );
+    assert_eq!(parse_date("yesterday"), None);
+  }
+}
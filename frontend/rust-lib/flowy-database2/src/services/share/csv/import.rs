@@ -1,16 +1,35 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::io::BufReader;
+use std::{fs::File, io::prelude::*};
+
 use collab_database::database::{gen_database_id, gen_field_id, gen_row_id, timestamp};
 use collab_database::entity::{CreateDatabaseParams, CreateViewParams, EncodedCollabInfo};
+use collab_database::fields::select_type_option::{
+  MultiSelectTypeOption, SelectOption, SingleSelectTypeOption,
+};
 use collab_database::fields::Field;
 use collab_database::rows::{new_cell_builder, Cell, CreateRowParams};
 use collab_database::views::DatabaseLayout;
 use flowy_error::{FlowyError, FlowyResult};
-use std::fmt::Display;
-use std::{fs::File, io::prelude::*};
+use lib_infra::box_any::BoxAny;
+use tracing::warn;
 
 use crate::entities::FieldType;
-use crate::services::field::{default_type_option_data_from_type, CELL_DATA};
+use crate::services::cell::{
+  apply_cell_changeset, insert_checkbox_cell, insert_date_cell, insert_select_option_cell,
+  insert_url_cell,
+};
+use crate::services::field::date_filter::DateCellChangeset;
+use crate::services::field::{
+  default_type_option_data_from_type, new_select_option_color, select_type_option_from_field,
+  SelectOptionCellChangeset, SelectTypeOptionSharedAction, CELL_DATA, CHECK, UNCHECK,
+};
 use crate::services::field_settings::default_field_settings_for_fields;
-use crate::services::share::csv::CSVFormat;
+use crate::services::share::csv::{
+  infer_field_type, is_importable_field_type, parse_checkbox, parse_date, parse_number,
+  split_select_options, CSVFormat, MAX_SAMPLE_SIZE,
+};
 
 #[derive(Default)]
 pub struct CSVImporter;
@@ -26,7 +45,8 @@ impl CSVImporter {
     let mut content = String::new();
     file.read_to_string(&mut content)?;
     let fields_with_rows = self.get_fields_and_rows(content)?;
-    let database_data = database_from_fields_and_rows(view_id, fields_with_rows, &style);
+    let database_data =
+      database_from_fields_and_rows(view_id, fields_with_rows, &style, &HashMap::new());
     Ok(database_data)
   }
 
@@ -35,12 +55,80 @@ impl CSVImporter {
     view_id: String,
     content: String,
     format: CSVFormat,
+  ) -> FlowyResult<CreateDatabaseParams> {
+    self.import_csv_from_string_with_field_types(view_id, content, format, &HashMap::new())
+  }
+
+  /// Imports the csv content. In [CSVFormat::Original], the field type of a column is looked up
+  /// by the column name in `field_types`, the field type of the other columns is inferred from
+  /// their values. The first column is always imported as the primary text field.
+  pub fn import_csv_from_string_with_field_types(
+    &self,
+    view_id: String,
+    content: String,
+    format: CSVFormat,
+    field_types: &HashMap<String, FieldType>,
   ) -> FlowyResult<CreateDatabaseParams> {
     let fields_with_rows = self.get_fields_and_rows(content)?;
-    let database_data = database_from_fields_and_rows(&view_id, fields_with_rows, &format);
+    let database_data =
+      database_from_fields_and_rows(&view_id, fields_with_rows, &format, field_types);
     Ok(database_data)
   }
 
+  /// Opens the csv file for a streaming import in [CSVFormat::Original]. Only the first
+  /// [MAX_SAMPLE_SIZE] rows are read to infer the field types, they are returned in the
+  /// [CreateDatabaseParams] together with the fields. The remaining rows are read with
+  /// [CSVFileImport::next_rows].
+  pub fn open_csv_file(
+    &self,
+    view_id: &str,
+    path: &str,
+    field_types: &HashMap<String, FieldType>,
+  ) -> FlowyResult<(CSVFileImport, CreateDatabaseParams)> {
+    let file = File::open(path)?;
+    let total_bytes = file.metadata()?.len();
+    if total_bytes == 0 {
+      return Err(FlowyError::invalid_data().with_context("Import content is empty"));
+    }
+
+    let mut reader = csv::Reader::from_reader(BufReader::new(file));
+    let fields = match reader.headers() {
+      Ok(headers) => headers
+        .iter()
+        .map(|header| header.to_string())
+        .collect::<Vec<String>>(),
+      Err(_) => return Err(FlowyError::invalid_data().with_context("Header not found")),
+    };
+
+    let mut file_import = CSVFileImport {
+      reader,
+      database_id: String::new(),
+      fields: vec![],
+      updated_field_ids: HashSet::new(),
+      num_of_unparsed_cells: 0,
+      total_bytes,
+    };
+    let rows = file_import
+      .next_records(MAX_SAMPLE_SIZE)?
+      .iter()
+      .map(|record| {
+        record
+          .iter()
+          .map(|s| s.to_string())
+          .collect::<Vec<String>>()
+      })
+      .collect();
+    let params = database_from_fields_and_rows(
+      view_id,
+      FieldsRows { fields, rows },
+      &CSVFormat::Original,
+      field_types,
+    );
+    file_import.database_id = params.database_id.clone();
+    file_import.fields = params.fields.clone();
+    Ok((file_import, params))
+  }
+
   fn get_fields_and_rows(&self, content: String) -> Result<FieldsRows, FlowyError> {
     let mut fields: Vec<String> = vec![];
     if content.is_empty() {
@@ -75,6 +163,7 @@ fn database_from_fields_and_rows(
   view_id: &str,
   fields_and_rows: FieldsRows,
   format: &CSVFormat,
+  field_types: &HashMap<String, FieldType>,
 ) -> CreateDatabaseParams {
   let (fields, rows) = fields_and_rows.split();
   let database_id = gen_database_id();
@@ -83,7 +172,28 @@ fn database_from_fields_and_rows(
     .into_iter()
     .enumerate()
     .map(|(index, field_meta)| match format {
-      CSVFormat::Original => default_field(field_meta, index == 0),
+      CSVFormat::Original => {
+        if index == 0 {
+          return default_field(field_meta, true);
+        }
+        let values = rows
+          .iter()
+          .filter_map(|cells| cells.get(index))
+          .map(|cell_content| cell_content.as_str())
+          .collect::<Vec<&str>>();
+        let field_type = match field_types.get(&field_meta) {
+          Some(field_type) if is_importable_field_type(field_type) => *field_type,
+          Some(field_type) => {
+            warn!(
+              "Can't import the column:{} as {:?}, fallback to text",
+              field_meta, field_type
+            );
+            FieldType::RichText
+          },
+          None => infer_field_type(values.iter().copied()),
+        };
+        typed_field(field_meta, field_type, &values)
+      },
       CSVFormat::META => {
         //
         match serde_json::from_str(&field_meta) {
@@ -99,6 +209,7 @@ fn database_from_fields_and_rows(
 
   let field_settings = default_field_settings_for_fields(&fields, DatabaseLayout::Grid);
 
+  let mut num_of_unparsed_cells = 0;
   let rows = rows
     .iter()
     .map(|cells| {
@@ -108,24 +219,31 @@ fn database_from_fields_and_rows(
           let field_type = FieldType::from(field.field_type);
 
           // Make the cell based on the style.
-          let mut cell = new_cell_builder(field_type);
-          match format {
+          let cell = match format {
             CSVFormat::Original => {
-              cell.insert(CELL_DATA.into(), cell_content.as_str().into());
+              let (cell, is_parsed) = original_cell(cell_content, field, field_type);
+              if !is_parsed {
+                num_of_unparsed_cells += 1;
+              }
+              cell
             },
             CSVFormat::META => match serde_json::from_str::<Cell>(cell_content) {
-              Ok(cell_json) => cell = cell_json,
-              Err(_) => {
-                cell.insert(CELL_DATA.into(), "".into());
-              },
+              Ok(cell_json) => cell_json,
+              Err(_) => text_cell(field_type, ""),
             },
-          }
+          };
           params.cells.insert(field.id.clone(), cell);
         }
       }
       params
     })
     .collect::<Vec<CreateRowParams>>();
+  if num_of_unparsed_cells > 0 {
+    warn!(
+      "[CSV]: {} values don't match the type of their field, they are imported as text",
+      num_of_unparsed_cells
+    );
+  }
 
   let timestamp = timestamp();
 
@@ -153,6 +271,296 @@ fn default_field(field_str: String, is_primary: bool) -> Field {
     .with_type_option_data(field_type, type_option_data)
 }
 
+/// Creates the field of the column. The options of the select fields are created from the
+/// values of the column.
+fn typed_field(name: String, field_type: FieldType, values: &[&str]) -> Field {
+  let type_option_data = match field_type {
+    FieldType::SingleSelect => {
+      let mut type_option = SingleSelectTypeOption::default();
+      type_option.options = select_options_from_values(values.iter().map(|value| value.trim()));
+      type_option.into()
+    },
+    FieldType::MultiSelect => {
+      let mut type_option = MultiSelectTypeOption::default();
+      type_option.options =
+        select_options_from_values(values.iter().flat_map(|value| split_select_options(value)));
+      type_option.into()
+    },
+    _ => default_type_option_data_from_type(field_type),
+  };
+  Field::new(gen_field_id(), name, field_type.into(), false)
+    .with_type_option_data(field_type, type_option_data)
+}
+
+fn select_options_from_values<'a>(names: impl Iterator<Item = &'a str>) -> Vec<SelectOption> {
+  let mut options: Vec<SelectOption> = vec![];
+  for name in names {
+    if !name.is_empty() && !options.iter().any(|option| option.name == name) {
+      let color = new_select_option_color(&options);
+      options.push(SelectOption::with_color(name, color));
+    }
+  }
+  options
+}
+
+/// Creates the cell of the field from the csv content. The content that can't be converted to
+/// the field type is kept in a text cell, so it's not lost and shows up again if the field is
+/// changed to text. The returned flag is false in that case.
+fn original_cell(content: &str, field: &Field, field_type: FieldType) -> (Cell, bool) {
+  if field_type == FieldType::RichText {
+    return (text_cell(field_type, content), true);
+  }
+  if content.trim().is_empty() {
+    return (text_cell(field_type, ""), true);
+  }
+  match typed_cell(content, field, field_type) {
+    Some(cell) => (cell, true),
+    None => (text_cell(FieldType::RichText, content), false),
+  }
+}
+
+/// Creates the cell of the typed field from the csv content. Returns None if the content is
+/// empty or can't be converted to the field type.
+fn typed_cell(content: &str, field: &Field, field_type: FieldType) -> Option<Cell> {
+  let content = content.trim();
+  if content.is_empty() {
+    return None;
+  }
+
+  match field_type {
+    FieldType::Number => {
+      parse_number(content)?;
+      apply_cell_changeset(BoxAny::new(content.to_string()), None, field, None).ok()
+    },
+    FieldType::DateTime => {
+      let (timestamp, include_time) = parse_date(content)?;
+      Some(insert_date_cell(timestamp, None, Some(include_time), field))
+    },
+    FieldType::Checkbox => {
+      let is_checked = parse_checkbox(content)?;
+      Some(insert_checkbox_cell(is_checked, field))
+    },
+    FieldType::URL => Some(insert_url_cell(content.to_string(), field)),
+    FieldType::SingleSelect | FieldType::MultiSelect => {
+      let names = match field_type {
+        FieldType::SingleSelect => vec![content],
+        _ => split_select_options(content),
+      };
+      let options = select_type_option_from_field(field).ok()?.options().clone();
+      let option_ids = names
+        .into_iter()
+        .filter_map(|name| options.iter().find(|option| option.name == name))
+        .map(|option| option.id.clone())
+        .collect::<Vec<String>>();
+      Some(insert_select_option_cell(option_ids, field))
+    },
+    _ => None,
+  }
+}
+
+/// Creates the changeset of the field from a string pasted into the grid, e.g. copied from a
+/// spreadsheet. The content is parsed like the content of a csv cell, and the cell is written
+/// through the [CellDataChangeset] of the field. Returns `Some(None)` if the content is empty,
+/// which clears the cell, and None if the cells of the field can't be written from a string or
+/// the content doesn't match the field type. The missing select options must be created before,
+/// see [insert_missing_select_options].
+pub(crate) fn pasted_cell_changeset(content: &str, field: &Field) -> Option<Option<BoxAny>> {
+  let field_type = FieldType::from(field.field_type);
+  let is_pastable = is_importable_field_type(&field_type)
+    || matches!(
+      field_type,
+      FieldType::Rating
+        | FieldType::Email
+        | FieldType::Phone
+        | FieldType::Time
+        | FieldType::Translate
+        | FieldType::Summary
+    );
+  if !is_pastable {
+    return None;
+  }
+
+  let content = content.trim();
+  if content.is_empty() {
+    return Some(None);
+  }
+  let changeset = match field_type {
+    FieldType::DateTime => {
+      let (timestamp, include_time) = parse_date(content)?;
+      BoxAny::new(DateCellChangeset {
+        timestamp: Some(timestamp),
+        include_time: Some(include_time),
+        ..Default::default()
+      })
+    },
+    FieldType::Checkbox => {
+      let is_checked = parse_checkbox(content)?;
+      BoxAny::new(if is_checked { CHECK } else { UNCHECK }.to_string())
+    },
+    FieldType::SingleSelect | FieldType::MultiSelect => {
+      let names = match field_type {
+        FieldType::SingleSelect => vec![content],
+        _ => split_select_options(content),
+      };
+      let options = select_type_option_from_field(field).ok()?.options().clone();
+      let insert_option_ids = names
+        .into_iter()
+        .filter_map(|name| options.iter().find(|option| option.name == name))
+        .map(|option| option.id.clone())
+        .collect::<Vec<String>>();
+      // The pasted options replace the options of the cell
+      let delete_option_ids = options
+        .into_iter()
+        .map(|option| option.id)
+        .filter(|id| !insert_option_ids.contains(id))
+        .collect();
+      BoxAny::new(SelectOptionCellChangeset {
+        insert_option_ids,
+        delete_option_ids,
+      })
+    },
+    FieldType::Number
+    | FieldType::Rating
+    | FieldType::Email
+    | FieldType::Phone
+    | FieldType::Time => {
+      if field_type == FieldType::Number {
+        parse_number(content)?;
+      }
+      // Skip the content that the changeset of the field rejects
+      apply_cell_changeset(BoxAny::new(content.to_string()), None, field, None).ok()?;
+      BoxAny::new(content.to_string())
+    },
+    _ => BoxAny::new(content.to_string()),
+  };
+  Some(Some(changeset))
+}
+
+fn text_cell(field_type: FieldType, content: &str) -> Cell {
+  let mut cell = new_cell_builder(field_type);
+  cell.insert(CELL_DATA.into(), content.into());
+  cell
+}
+
+/// A csv file that is imported row by row. See [CSVImporter::open_csv_file].
+pub struct CSVFileImport {
+  reader: csv::Reader<BufReader<File>>,
+  database_id: String,
+  fields: Vec<Field>,
+  updated_field_ids: HashSet<String>,
+  num_of_unparsed_cells: usize,
+  total_bytes: u64,
+}
+
+impl CSVFileImport {
+  /// Reads up to `batch_size` rows. Returns an empty list once the whole file is read.
+  ///
+  /// The options of the select fields are created from the sampled rows. The options that
+  /// only appear in the later rows are added to the fields returned by
+  /// [CSVFileImport::updated_fields]. The values that don't match the field type inferred from
+  /// the sampled rows are imported as text cells.
+  pub fn next_rows(&mut self, batch_size: usize) -> FlowyResult<Vec<CreateRowParams>> {
+    let records = self.next_records(batch_size)?;
+    let rows = records
+      .iter()
+      .map(|record| {
+        let mut params = CreateRowParams::new(gen_row_id(), self.database_id.clone());
+        for (index, cell_content) in record.iter().enumerate() {
+          if let Some(field) = self.fields.get_mut(index) {
+            let field_type = FieldType::from(field.field_type);
+            if insert_missing_select_options(field, field_type, cell_content) {
+              self.updated_field_ids.insert(field.id.clone());
+            }
+            let (cell, is_parsed) = original_cell(cell_content, field, field_type);
+            if !is_parsed {
+              self.num_of_unparsed_cells += 1;
+            }
+            params.cells.insert(field.id.clone(), cell);
+          }
+        }
+        params
+      })
+      .collect();
+    Ok(rows)
+  }
+
+  /// Returns the fields whose type option changed while reading the rows.
+  pub fn updated_fields(&self) -> Vec<Field> {
+    self
+      .fields
+      .iter()
+      .filter(|field| self.updated_field_ids.contains(&field.id))
+      .cloned()
+      .collect()
+  }
+
+  /// Returns the number of values read by [CSVFileImport::next_rows] that don't match the type
+  /// of their field. They are imported as text cells.
+  pub fn num_of_unparsed_cells(&self) -> usize {
+    self.num_of_unparsed_cells
+  }
+
+  /// Returns the fraction of the file that has been read.
+  pub fn progress(&self) -> f64 {
+    self.reader.position().byte() as f64 / self.total_bytes as f64
+  }
+
+  fn next_records(&mut self, batch_size: usize) -> FlowyResult<Vec<csv::StringRecord>> {
+    let mut records = vec![];
+    while records.len() < batch_size {
+      let mut record = csv::StringRecord::new();
+      match self.reader.read_record(&mut record) {
+        Ok(true) => records.push(record),
+        Ok(false) => break,
+        Err(err) if err.is_io_error() => {
+          return Err(FlowyError::internal().with_context(err));
+        },
+        Err(err) => warn!("Skip the invalid csv record: {}", err),
+      }
+    }
+    Ok(records)
+  }
+}
+
+/// Adds the options of the content that the select field doesn't have yet. Returns true if
+/// any option was added.
+pub(crate) fn insert_missing_select_options(
+  field: &mut Field,
+  field_type: FieldType,
+  content: &str,
+) -> bool {
+  let names = match field_type {
+    FieldType::SingleSelect => vec![content.trim()],
+    FieldType::MultiSelect => split_select_options(content),
+    _ => return false,
+  };
+  let mut type_option = match select_type_option_from_field(field) {
+    Ok(type_option) => type_option,
+    Err(_) => return false,
+  };
+
+  let mut is_updated = false;
+  for name in names {
+    if !name.is_empty()
+      && !type_option
+        .options()
+        .iter()
+        .any(|option| option.name == name)
+    {
+      let option = type_option.create_option(name);
+      type_option.mut_options().push(option);
+      is_updated = true;
+    }
+  }
+
+  if is_updated {
+    field
+      .type_options
+      .insert(field_type.to_string(), type_option.to_type_option_data());
+  }
+  is_updated
+}
+
 struct FieldsRows {
   fields: Vec<String>,
   rows: Vec<Vec<String>>,
@@ -188,9 +596,14 @@ impl Display for ImportResult {
 }
 #[cfg(test)]
 mod tests {
+  use std::collections::HashMap;
+
   use collab_database::database::gen_database_view_id;
+  use collab_database::fields::select_type_option::SingleSelectTypeOption;
 
-  use crate::services::share::csv::{CSVFormat, CSVImporter};
+  use crate::entities::FieldType;
+  use crate::services::field::StringCellData;
+  use crate::services::share::csv::{CSVFormat, CSVImporter, MAX_SAMPLE_SIZE};
 
   #[test]
   fn test_import_csv_from_str() {
@@ -219,6 +632,135 @@ mod tests {
     println!("{:?}", result);
   }
 
+  #[test]
+  fn import_csv_infer_field_types_test() {
+    let s = r#"Name,Tags,Number,Date,Checkbox,URL
+1,tag 1,1,"May 26, 2023",Yes,appflowy.io
+2,tag 2,2,"May 22, 2023",No,
+,,,,Yes,"#;
+    let result = CSVImporter
+      .import_csv_from_string(gen_database_view_id(), s.to_string(), CSVFormat::Original)
+      .unwrap();
+    let field_types = result
+      .fields
+      .iter()
+      .map(|field| FieldType::from(field.field_type))
+      .collect::<Vec<FieldType>>();
+    assert_eq!(
+      field_types,
+      vec![
+        FieldType::RichText,
+        FieldType::RichText,
+        FieldType::Number,
+        FieldType::DateTime,
+        FieldType::Checkbox,
+        FieldType::URL,
+      ]
+    );
+    assert!(result.fields[0].is_primary);
+  }
+
+  #[test]
+  fn import_csv_with_explicit_field_types_test() {
+    let s = r#"Name,Status,Estimate
+Design,Done,3
+Build,In progress,5"#;
+    let field_types = HashMap::from([
+      ("Status".to_string(), FieldType::SingleSelect),
+      ("Estimate".to_string(), FieldType::RichText),
+    ]);
+    let result = CSVImporter
+      .import_csv_from_string_with_field_types(
+        gen_database_view_id(),
+        s.to_string(),
+        CSVFormat::Original,
+        &field_types,
+      )
+      .unwrap();
+    assert_eq!(
+      FieldType::from(result.fields[1].field_type),
+      FieldType::SingleSelect
+    );
+    assert_eq!(
+      FieldType::from(result.fields[2].field_type),
+      FieldType::RichText
+    );
+
+    let type_option = result.fields[1]
+      .get_type_option::<SingleSelectTypeOption>(FieldType::SingleSelect)
+      .unwrap();
+    let option_names = type_option
+      .options
+      .iter()
+      .map(|option| option.name.as_str())
+      .collect::<Vec<&str>>();
+    assert_eq!(option_names, vec!["Done", "In progress"]);
+    assert_eq!(result.rows[1].cells.len(), 3);
+  }
+
+  #[test]
+  fn import_csv_keeps_unparseable_values_test() {
+    let s = r#"Name,Zip,Estimate
+Design,007,3
+Build,0123,unknown"#;
+    let field_types = HashMap::from([("Estimate".to_string(), FieldType::Number)]);
+    let result = CSVImporter
+      .import_csv_from_string_with_field_types(
+        gen_database_view_id(),
+        s.to_string(),
+        CSVFormat::Original,
+        &field_types,
+      )
+      .unwrap();
+
+    // The zero-padded integers are imported as text
+    assert_eq!(
+      FieldType::from(result.fields[1].field_type),
+      FieldType::RichText
+    );
+    let zip_cell = result.rows[0].cells.get(&result.fields[1].id).unwrap();
+    assert_eq!(StringCellData::from(zip_cell).0, "007");
+
+    // The value that isn't a number keeps its text in the number field
+    assert_eq!(
+      FieldType::from(result.fields[2].field_type),
+      FieldType::Number
+    );
+    let estimate_cell = result.rows[1].cells.get(&result.fields[2].id).unwrap();
+    assert_eq!(StringCellData::from(estimate_cell).0, "unknown");
+  }
+
+  #[test]
+  fn import_csv_file_keeps_values_after_samples_test() {
+    let mut content = "Name,Estimate\n".to_string();
+    for index in 0..MAX_SAMPLE_SIZE {
+      content.push_str(&format!("Task {},{}\n", index, index));
+    }
+    content.push_str("Last task,unknown\n");
+    let path = std::env::temp_dir().join(format!("{}.csv", uuid::Uuid::new_v4()));
+    std::fs::write(&path, content).unwrap();
+
+    let (mut file_import, params) = CSVImporter
+      .open_csv_file(
+        &gen_database_view_id(),
+        path.to_str().unwrap(),
+        &HashMap::new(),
+      )
+      .unwrap();
+    let rows = file_import.next_rows(10).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    // The field type is inferred from the sampled rows
+    assert_eq!(
+      FieldType::from(params.fields[1].field_type),
+      FieldType::Number
+    );
+    assert_eq!(rows.len(), 1);
+    let cell = rows[0].cells.get(&params.fields[1].id).unwrap();
+    assert_eq!(StringCellData::from(cell).0, "unknown");
+    assert_eq!(file_import.num_of_unparsed_cells(), 1);
+  }
+
   #[test]
   fn import_empty_csv_data_test() {
     let s = r#""#;
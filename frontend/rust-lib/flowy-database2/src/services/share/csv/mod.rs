@@ -1,5 +1,9 @@
 mod export;
+mod field_type_inference;
 mod import;
+mod progress;
 
 pub use export::*;
+pub use field_type_inference::*;
 pub use import::*;
+pub use progress::*;
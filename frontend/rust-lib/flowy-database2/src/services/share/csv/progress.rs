@@ -0,0 +1,71 @@
+use flowy_error::{FlowyError, FlowyResult};
+use tokio_util::sync::CancellationToken;
+
+use crate::entities::CSVProgressPB;
+use crate::notification::{database_notification_builder, DatabaseNotification};
+
+/// The number of rows that are imported or exported between two progress notifications.
+pub const CSV_ROWS_PER_BATCH: usize = 500;
+
+/// Tracks a streaming csv import or export of a database view. The progress is sent to the
+/// client through [DatabaseNotification::DidUpdateCSVProgress] and the task stops at the next
+/// batch once the cancellation token is cancelled.
+#[derive(Clone)]
+pub struct CSVProgress {
+  view_id: String,
+  cancellation: CancellationToken,
+}
+
+impl CSVProgress {
+  pub fn new(view_id: &str, cancellation: CancellationToken) -> Self {
+    Self {
+      view_id: view_id.to_string(),
+      cancellation,
+    }
+  }
+
+  pub fn check_cancelled(&self) -> FlowyResult<()> {
+    if self.cancellation.is_cancelled() {
+      return Err(
+        FlowyError::task_cancelled().with_context(format!("csv task of {}", self.view_id)),
+      );
+    }
+    Ok(())
+  }
+
+  pub fn notify(&self, processed_rows: usize, progress: f64) {
+    self.send(CSVProgressPB {
+      view_id: self.view_id.clone(),
+      processed_rows: processed_rows as i64,
+      progress: progress.clamp(0.0, 1.0),
+      is_finished: false,
+      is_cancelled: false,
+    });
+  }
+
+  pub fn finish(&self, processed_rows: usize) {
+    self.send(CSVProgressPB {
+      view_id: self.view_id.clone(),
+      processed_rows: processed_rows as i64,
+      progress: 1.0,
+      is_finished: true,
+      is_cancelled: false,
+    });
+  }
+
+  pub fn cancelled(&self, processed_rows: usize) {
+    self.send(CSVProgressPB {
+      view_id: self.view_id.clone(),
+      processed_rows: processed_rows as i64,
+      progress: 0.0,
+      is_finished: true,
+      is_cancelled: true,
+    });
+  }
+
+  fn send(&self, progress: CSVProgressPB) {
+    database_notification_builder(&self.view_id, DatabaseNotification::DidUpdateCSVProgress)
+      .payload(progress)
+      .send();
+  }
+}
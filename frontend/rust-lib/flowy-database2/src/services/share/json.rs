@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use collab_database::fields::Field;
+use collab_database::rows::Row;
+use flowy_error::FlowyResult;
+use serde_json::{json, Map, Value};
+
+use crate::services::cell::json_cell;
+use crate::services::share::util::get_exported_cell;
+
+/// Exports the rows of a database as structured json. The value of each cell is typed
+/// according to the field type, for example numbers for the number fields and booleans for
+/// the checkbox fields. The cells are keyed by field id.
+pub struct JSONExport;
+impl JSONExport {
+  pub fn export_rows(
+    &self,
+    database_id: &str,
+    fields: &[Field],
+    rows: &[Arc<Row>],
+  ) -> FlowyResult<String> {
+    let fields_json = fields
+      .iter()
+      .map(|field| {
+        json!({
+          "id": field.id,
+          "name": field.name,
+          "field_type": field.field_type,
+          "is_primary": field.is_primary,
+        })
+      })
+      .collect::<Vec<Value>>();
+
+    let rows_json = rows
+      .iter()
+      .map(|row| {
+        let cells = fields
+          .iter()
+          .map(|field| {
+            let value = get_exported_cell(row, field)
+              .map(|cell| json_cell(&cell, field))
+              .unwrap_or(Value::Null);
+            (field.id.clone(), value)
+          })
+          .collect::<Map<String, Value>>();
+        json!({
+          "id": row.id.to_string(),
+          "created_at": row.created_at,
+          "modified_at": row.modified_at,
+          "cells": cells,
+        })
+      })
+      .collect::<Vec<Value>>();
+
+    let database = json!({
+      "database_id": database_id,
+      "fields": fields_json,
+      "rows": rows_json,
+    });
+    Ok(serde_json::to_string_pretty(&database)?)
+  }
+}
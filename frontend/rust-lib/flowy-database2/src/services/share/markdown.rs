@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use collab_database::fields::Field;
+use collab_database::rows::Row;
+
+use crate::services::cell::stringify_cell;
+use crate::services::share::util::get_exported_cell;
+
+/// Exports the rows of a database view as a GitHub-flavoured Markdown table. The caller
+/// decides which fields and rows are exported, so the table can follow the filters, sorts and
+/// hidden fields of the view.
+pub struct MarkdownExport;
+impl MarkdownExport {
+  pub fn export_rows(&self, fields: &[Field], rows: &[Arc<Row>]) -> String {
+    let mut markdown = String::new();
+    let header = fields
+      .iter()
+      .map(|field| escape_markdown_cell(&field.name))
+      .collect::<Vec<String>>();
+    markdown.push_str(&table_line(&header));
+    markdown.push_str(&table_line(&vec!["---".to_string(); fields.len()]));
+
+    for row in rows {
+      let cells = fields
+        .iter()
+        .map(|field| {
+          get_exported_cell(row, field)
+            .map(|cell| escape_markdown_cell(&stringify_cell(&cell, field)))
+            .unwrap_or_default()
+        })
+        .collect::<Vec<String>>();
+      markdown.push_str(&table_line(&cells));
+    }
+    markdown
+  }
+}
+
+fn table_line(cells: &[String]) -> String {
+  format!("| {} |\n", cells.join(" | "))
+}
+
+/// Escapes the characters that would break the table layout. Line breaks are kept with `<br>`.
+fn escape_markdown_cell(content: &str) -> String {
+  content
+    .replace('\\', "\\\\")
+    .replace('|', "\\|")
+    .replace("\r\n", "<br>")
+    .replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::services::share::markdown::escape_markdown_cell;
+
+  #[test]
+  fn escape_markdown_cell_test() {
+    assert_eq!(escape_markdown_cell("a | b"), "a \\| b");
+    assert_eq!(escape_markdown_cell("line 1\nline 2"), "line 1<br>line 2");
+    assert_eq!(escape_markdown_cell("C:\\path"), "C:\\\\path");
+  }
+}
@@ -1 +1,10 @@
 pub mod csv;
+mod json;
+mod markdown;
+mod util;
+mod xlsx;
+
+pub use json::*;
+pub use markdown::*;
+pub(crate) use util::*;
+pub use xlsx::*;
@@ -0,0 +1,23 @@
+use collab_database::fields::Field;
+use collab_database::rows::{Cell, Row};
+use collab_database::template::timestamp_parse::TimestampCellData;
+
+use crate::entities::FieldType;
+
+/// Returns the cell of the row that is exported for the field. The cells of the
+/// [FieldType::CreatedTime] and [FieldType::LastEditedTime] fields are not stored in the row,
+/// they are built from the timestamps of the row.
+pub(crate) fn get_exported_cell(row: &Row, field: &Field) -> Option<Cell> {
+  let field_type = FieldType::from(field.field_type);
+  match field_type {
+    FieldType::LastEditedTime | FieldType::CreatedTime => {
+      let cell_data = if field_type.is_created_time() {
+        TimestampCellData::new(row.created_at)
+      } else {
+        TimestampCellData::new(row.modified_at)
+      };
+      Some(cell_data.to_cell(field.field_type))
+    },
+    _ => row.cells.get(&field.id).cloned(),
+  }
+}
@@ -0,0 +1,757 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{Cursor, Write};
+
+use collab_database::fields::date_type_option::DateCellData;
+use collab_database::fields::Field;
+use collab_database::rows::Row;
+use flowy_error::{FlowyError, FlowyResult};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::entities::{CheckboxCellDataPB, FieldType};
+use crate::services::cell::stringify_cell;
+use crate::services::field::{RatingCellData, RatingTypeOption, TypeOptionCellExt};
+use crate::services::share::util::get_exported_cell;
+
+/// The maximum length of a sheet name that Excel accepts.
+const MAX_SHEET_NAME_LEN: usize = 31;
+
+/// The number of days between the epoch of Excel, 1899-12-30, and the unix epoch.
+const UNIX_EPOCH_IN_EXCEL_DAYS: f64 = 25569.0;
+
+const DATE_STYLE: usize = 1;
+const DATE_TIME_STYLE: usize = 2;
+/// The index of the first cell format that is created for the number formats of the cells.
+const FIRST_NUMBER_STYLE: usize = 3;
+/// The first id of the custom number formats. The ids below are reserved for the built-in
+/// formats and the date formats of the style sheet.
+const FIRST_NUMBER_FORMAT_ID: usize = 166;
+
+const MIN_COLUMN_WIDTH: usize = 10;
+const MAX_COLUMN_WIDTH: usize = 60;
+
+const SPREADSHEET_NS: &str = "http://schemas.openxmlformats.org/spreadsheetml/2006/main";
+const RELATIONSHIPS_NS: &str = "http://schemas.openxmlformats.org/package/2006/relationships";
+const DOCUMENT_RELATIONSHIPS_NS: &str =
+  "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum XLSXCell {
+  Empty,
+  Text(String),
+  /// The format is the Excel number format code of the cell, for example `"$"#,##0.00`. The
+  /// cell uses the general format if it's None.
+  Number {
+    value: f64,
+    format: Option<String>,
+  },
+  Boolean(bool),
+  /// The timestamp is in seconds.
+  Date {
+    timestamp: i64,
+    include_time: bool,
+  },
+}
+
+impl XLSXCell {
+  /// Creates the typed cell of the row for the field. Number, checkbox and date fields are
+  /// exported as numbers, booleans and dates, the other fields are exported as text. The numbers
+  /// keep the formatting of the cell, for example the currency symbol or the percent sign.
+  pub fn from_row(row: &Row, field: &Field) -> Self {
+    let cell = match get_exported_cell(row, field) {
+      None => return XLSXCell::Empty,
+      Some(cell) => cell,
+    };
+
+    let field_type = FieldType::from(field.field_type);
+    match field_type {
+      FieldType::Number | FieldType::Formula | FieldType::Rollup => {
+        let content = stringify_cell(&cell, field);
+        let number = TypeOptionCellExt::new(field, None)
+          .get_type_option_cell_data_handler()
+          .and_then(|handler| handler.handle_numeric_cell(&cell));
+        match number {
+          Some(number) => {
+            XLSXCell::formatted_number(&content).unwrap_or_else(|| XLSXCell::number(number, None))
+          },
+          None => XLSXCell::text(content),
+        }
+      },
+      FieldType::Rating => {
+        let stars = RatingCellData::from(&cell).0;
+        if stars <= 0 {
+          return XLSXCell::Empty;
+        }
+        let max = field
+          .get_type_option::<RatingTypeOption>(field_type)
+          .unwrap_or_default()
+          .max;
+        XLSXCell::number(stars as f64, Some(format!("0\" / {}\"", max)))
+      },
+      FieldType::Checkbox => XLSXCell::Boolean(CheckboxCellDataPB::from(&cell).is_checked),
+      FieldType::DateTime => {
+        let cell_data = DateCellData::from(&cell);
+        match cell_data.timestamp {
+          Some(timestamp) => XLSXCell::Date {
+            timestamp,
+            include_time: cell_data.include_time,
+          },
+          None => XLSXCell::Empty,
+        }
+      },
+      FieldType::CreatedTime => XLSXCell::Date {
+        timestamp: row.created_at,
+        include_time: true,
+      },
+      FieldType::LastEditedTime => XLSXCell::Date {
+        timestamp: row.modified_at,
+        include_time: true,
+      },
+      _ => XLSXCell::text(stringify_cell(&cell, field)),
+    }
+  }
+
+  fn text(content: String) -> Self {
+    if content.is_empty() {
+      XLSXCell::Empty
+    } else {
+      XLSXCell::Text(content)
+    }
+  }
+
+  fn number(value: f64, format: Option<String>) -> Self {
+    XLSXCell::Number { value, format }
+  }
+
+  /// Parses a formatted number, for example `$1,234.50`, `-12.5` or `50%`, into a number and
+  /// the number format that displays it the same way. The text around the number is kept as
+  /// literal text of the format and a trailing percent sign is converted into a percentage.
+  /// Returns None if the content doesn't contain exactly one number.
+  fn formatted_number(content: &str) -> Option<Self> {
+    let start = content.find(|c: char| c.is_ascii_digit())?;
+    let end = content.rfind(|c: char| c.is_ascii_digit())? + 1;
+    let digits = &content[start..end];
+    if digits
+      .chars()
+      .any(|c| !c.is_ascii_digit() && c != '.' && c != ',')
+    {
+      return None;
+    }
+
+    // The minus sign may come before or after the currency symbol, for example `-$5` or `$-5`.
+    let is_negative = content[..start].contains('-');
+    let prefix = content[..start].replacen('-', "", 1);
+    let (suffix, is_percent) = match content[end..].trim_start().strip_prefix('%') {
+      Some(suffix) => (suffix, true),
+      None => (&content[end..], false),
+    };
+
+    let number = parse_grouped_number(digits)?;
+    let mut value = number.value;
+    if is_negative {
+      value = -value;
+    }
+    if is_percent {
+      value /= 100.0;
+    }
+
+    let mut format = literal_format(&prefix);
+    format.push_str(if number.is_grouped { "#,##0" } else { "0" });
+    if number.decimals > 0 {
+      format.push('.');
+      format.push_str(&"0".repeat(number.decimals));
+    }
+    if is_percent {
+      format.push('%');
+    }
+    format.push_str(&literal_format(suffix));
+
+    let format = if format == "0" { None } else { Some(format) };
+    Some(XLSXCell::number(value, format))
+  }
+
+  /// Returns the approximate number of characters that the cell takes when it's displayed.
+  fn display_width(&self) -> usize {
+    match self {
+      XLSXCell::Empty => 0,
+      XLSXCell::Text(content) => content
+        .lines()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or_default(),
+      XLSXCell::Number { value, format } => {
+        let literal_width = format
+          .as_ref()
+          .map(|format| format.chars().filter(|c| *c != '"').count())
+          .unwrap_or_default();
+        value.to_string().len() + literal_width
+      },
+      XLSXCell::Boolean(_) => 5,
+      XLSXCell::Date { include_time, .. } => {
+        if *include_time {
+          16
+        } else {
+          10
+        }
+      },
+    }
+  }
+}
+
+struct GroupedNumber {
+  value: f64,
+  decimals: usize,
+  is_grouped: bool,
+}
+
+/// Parses the digits of a formatted number that may use either `.` or `,` as the decimal
+/// separator. If both separators are used, the last one is the decimal separator. If only one
+/// of them is used, it's the group separator when it appears more than once or is followed by
+/// exactly three digits.
+fn parse_grouped_number(digits: &str) -> Option<GroupedNumber> {
+  let last_dot = digits.rfind('.');
+  let last_comma = digits.rfind(',');
+  let decimal_separator = match (last_dot, last_comma) {
+    (None, None) => None,
+    (Some(dot), Some(comma)) => Some(if dot > comma { '.' } else { ',' }),
+    (Some(index), None) | (None, Some(index)) => {
+      let separator = digits[index..].chars().next()?;
+      let is_group = digits.matches(separator).count() > 1 || digits.len() - index - 1 == 3;
+      if is_group {
+        None
+      } else {
+        Some(separator)
+      }
+    },
+  };
+
+  let (integer, fraction) = match decimal_separator {
+    Some(separator) => {
+      let index = digits.rfind(separator)?;
+      (&digits[..index], &digits[index + 1..])
+    },
+    None => (digits, ""),
+  };
+  if fraction.contains(['.', ',']) {
+    return None;
+  }
+
+  let integer_digits = integer.replace(['.', ','], "");
+  let value = format!("{}.{}", integer_digits, fraction)
+    .trim_end_matches('.')
+    .parse::<f64>()
+    .ok()?;
+  Some(GroupedNumber {
+    value,
+    decimals: fraction.len(),
+    is_grouped: integer_digits.len() != integer.len(),
+  })
+}
+
+/// Returns the text as a literal of a number format. The quotes are removed because they can't
+/// be escaped inside of a literal.
+fn literal_format(text: &str) -> String {
+  let text = text.replace('"', "");
+  if text.is_empty() {
+    text
+  } else {
+    format!("\"{}\"", text)
+  }
+}
+
+pub struct XLSXSheet {
+  pub name: String,
+  pub rows: Vec<Vec<XLSXCell>>,
+}
+
+/// Writes the sheets into an XLSX workbook. The text of the cells is stored in the shared
+/// string table of the workbook and each number format is stored once in the style sheet. The
+/// width of each column fits the content of its cells.
+pub struct XLSXExport;
+impl XLSXExport {
+  pub fn export_sheets(&self, sheets: Vec<XLSXSheet>) -> FlowyResult<Vec<u8>> {
+    let sheet_names = unique_sheet_names(sheets.iter().map(|sheet| sheet.name.as_str()));
+    let mut shared_strings = SharedStrings::default();
+    let mut number_formats = NumberFormats::default();
+    let worksheets = sheets
+      .iter()
+      .map(|sheet| worksheet_xml(&sheet.rows, &mut shared_strings, &mut number_formats))
+      .collect::<Vec<_>>();
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = FileOptions::<()>::default().compression_method(CompressionMethod::Deflated);
+    let mut write_part = |name: &str, content: String| -> FlowyResult<()> {
+      zip.start_file(name, options).map_err(internal_zip_error)?;
+      zip.write_all(content.as_bytes())?;
+      Ok(())
+    };
+
+    write_part("[Content_Types].xml", content_types_xml(sheets.len()))?;
+    write_part("_rels/.rels", root_relationships_xml())?;
+    write_part("xl/workbook.xml", workbook_xml(&sheet_names))?;
+    write_part(
+      "xl/_rels/workbook.xml.rels",
+      workbook_relationships_xml(sheets.len()),
+    )?;
+    write_part("xl/styles.xml", styles_xml(&number_formats))?;
+    write_part("xl/sharedStrings.xml", shared_strings.to_xml())?;
+    for (index, worksheet) in worksheets.into_iter().enumerate() {
+      write_part(&format!("xl/worksheets/sheet{}.xml", index + 1), worksheet)?;
+    }
+
+    let cursor = zip.finish().map_err(internal_zip_error)?;
+    Ok(cursor.into_inner())
+  }
+}
+
+fn internal_zip_error(err: zip::result::ZipError) -> FlowyError {
+  FlowyError::internal().with_context(err)
+}
+
+/// The shared string table of the workbook. Each distinct text is stored once and the cells
+/// refer to it by its index.
+#[derive(Default)]
+struct SharedStrings {
+  indexes: HashMap<String, usize>,
+  strings: Vec<String>,
+  /// The number of cells that refer to the table, including the duplicated texts.
+  count: usize,
+}
+
+impl SharedStrings {
+  fn index_of(&mut self, content: &str) -> usize {
+    self.count += 1;
+    if let Some(index) = self.indexes.get(content) {
+      return *index;
+    }
+    let index = self.strings.len();
+    self.indexes.insert(content.to_string(), index);
+    self.strings.push(content.to_string());
+    index
+  }
+
+  fn to_xml(&self) -> String {
+    let strings = self
+      .strings
+      .iter()
+      .map(|content| {
+        format!(
+          r#"<si><t xml:space="preserve">{}</t></si>"#,
+          escape_xml(content)
+        )
+      })
+      .collect::<String>();
+    format!(
+      r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><sst xmlns="{}" count="{}" uniqueCount="{}">{}</sst>"#,
+      SPREADSHEET_NS,
+      self.count,
+      self.strings.len(),
+      strings
+    )
+  }
+}
+
+/// The custom number formats of the workbook. Each format gets its own cell format in the
+/// style sheet, starting at [FIRST_NUMBER_STYLE].
+#[derive(Default)]
+struct NumberFormats {
+  formats: Vec<String>,
+}
+
+impl NumberFormats {
+  fn style_of(&mut self, format: &str) -> usize {
+    let index = match self.formats.iter().position(|f| f == format) {
+      Some(index) => index,
+      None => {
+        self.formats.push(format.to_string());
+        self.formats.len() - 1
+      },
+    };
+    FIRST_NUMBER_STYLE + index
+  }
+}
+
+fn content_types_xml(num_of_sheets: usize) -> String {
+  let sheets = (1..=num_of_sheets)
+    .map(|index| {
+      format!(
+        r#"<Override PartName="/xl/worksheets/sheet{}.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/>"#,
+        index
+      )
+    })
+    .collect::<String>();
+  format!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml"/><Override PartName="/xl/sharedStrings.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sharedStrings+xml"/>{}</Types>"#,
+    sheets
+  )
+}
+
+fn root_relationships_xml() -> String {
+  format!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><Relationships xmlns="{}"><Relationship Id="rId1" Type="{}/officeDocument" Target="xl/workbook.xml"/></Relationships>"#,
+    RELATIONSHIPS_NS, DOCUMENT_RELATIONSHIPS_NS
+  )
+}
+
+fn workbook_xml(sheet_names: &[String]) -> String {
+  let sheets = sheet_names
+    .iter()
+    .enumerate()
+    .map(|(index, name)| {
+      format!(
+        r#"<sheet name="{}" sheetId="{}" r:id="rId{}"/>"#,
+        escape_xml(name),
+        index + 1,
+        index + 1
+      )
+    })
+    .collect::<String>();
+  format!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><workbook xmlns="{}" xmlns:r="{}"><sheets>{}</sheets></workbook>"#,
+    SPREADSHEET_NS, DOCUMENT_RELATIONSHIPS_NS, sheets
+  )
+}
+
+fn workbook_relationships_xml(num_of_sheets: usize) -> String {
+  let sheets = (1..=num_of_sheets)
+    .map(|index| {
+      format!(
+        r#"<Relationship Id="rId{}" Type="{}/worksheet" Target="worksheets/sheet{}.xml"/>"#,
+        index, DOCUMENT_RELATIONSHIPS_NS, index
+      )
+    })
+    .collect::<String>();
+  format!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><Relationships xmlns="{}">{}<Relationship Id="rId{}" Type="{}/styles" Target="styles.xml"/><Relationship Id="rId{}" Type="{}/sharedStrings" Target="sharedStrings.xml"/></Relationships>"#,
+    RELATIONSHIPS_NS,
+    sheets,
+    num_of_sheets + 1,
+    DOCUMENT_RELATIONSHIPS_NS,
+    num_of_sheets + 2,
+    DOCUMENT_RELATIONSHIPS_NS
+  )
+}
+
+/// The cell formats are referenced by [DATE_STYLE], [DATE_TIME_STYLE] and the styles of the
+/// [NumberFormats].
+fn styles_xml(number_formats: &NumberFormats) -> String {
+  let custom_formats = number_formats
+    .formats
+    .iter()
+    .enumerate()
+    .map(|(index, format)| {
+      format!(
+        r#"<numFmt numFmtId="{}" formatCode="{}"/>"#,
+        FIRST_NUMBER_FORMAT_ID + index,
+        escape_xml(format)
+      )
+    })
+    .collect::<String>();
+  let custom_cell_formats = (0..number_formats.formats.len())
+    .map(|index| {
+      format!(
+        r#"<xf numFmtId="{}" fontId="0" fillId="0" borderId="0" xfId="0" applyNumberFormat="1"/>"#,
+        FIRST_NUMBER_FORMAT_ID + index
+      )
+    })
+    .collect::<String>();
+  format!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><styleSheet xmlns="{}"><numFmts count="{}"><numFmt numFmtId="164" formatCode="yyyy-mm-dd"/><numFmt numFmtId="165" formatCode="yyyy-mm-dd hh:mm"/>{}</numFmts><fonts count="1"><font><sz val="11"/><name val="Calibri"/></font></fonts><fills count="2"><fill><patternFill patternType="none"/></fill><fill><patternFill patternType="gray125"/></fill></fills><borders count="1"><border><left/><right/><top/><bottom/><diagonal/></border></borders><cellStyleXfs count="1"><xf numFmtId="0" fontId="0" fillId="0" borderId="0"/></cellStyleXfs><cellXfs count="{}"><xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0"/><xf numFmtId="164" fontId="0" fillId="0" borderId="0" xfId="0" applyNumberFormat="1"/><xf numFmtId="165" fontId="0" fillId="0" borderId="0" xfId="0" applyNumberFormat="1"/>{}</cellXfs><cellStyles count="1"><cellStyle name="Normal" xfId="0" builtinId="0"/></cellStyles></styleSheet>"#,
+    SPREADSHEET_NS,
+    2 + number_formats.formats.len(),
+    custom_formats,
+    FIRST_NUMBER_STYLE + number_formats.formats.len(),
+    custom_cell_formats
+  )
+}
+
+fn worksheet_xml(
+  rows: &[Vec<XLSXCell>],
+  shared_strings: &mut SharedStrings,
+  number_formats: &mut NumberFormats,
+) -> String {
+  let mut column_widths = Vec::<usize>::new();
+  let mut sheet_data = String::new();
+  for (row_index, row) in rows.iter().enumerate() {
+    sheet_data.push_str(&format!(r#"<row r="{}">"#, row_index + 1));
+    for (column_index, cell) in row.iter().enumerate() {
+      if column_widths.len() <= column_index {
+        column_widths.resize(column_index + 1, 0);
+      }
+      column_widths[column_index] = column_widths[column_index].max(cell.display_width());
+
+      let reference = format!("{}{}", column_name(column_index), row_index + 1);
+      sheet_data.push_str(&cell_xml(&reference, cell, shared_strings, number_formats));
+    }
+    sheet_data.push_str("</row>");
+  }
+
+  let columns = if column_widths.is_empty() {
+    String::new()
+  } else {
+    let columns = column_widths
+      .iter()
+      .enumerate()
+      .map(|(index, width)| {
+        format!(
+          r#"<col min="{}" max="{}" width="{}" customWidth="1"/>"#,
+          index + 1,
+          index + 1,
+          (width + 2).clamp(MIN_COLUMN_WIDTH, MAX_COLUMN_WIDTH)
+        )
+      })
+      .collect::<String>();
+    format!("<cols>{}</cols>", columns)
+  };
+  format!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><worksheet xmlns="{}">{}<sheetData>{}</sheetData></worksheet>"#,
+    SPREADSHEET_NS, columns, sheet_data
+  )
+}
+
+fn cell_xml(
+  reference: &str,
+  cell: &XLSXCell,
+  shared_strings: &mut SharedStrings,
+  number_formats: &mut NumberFormats,
+) -> String {
+  match cell {
+    XLSXCell::Empty => String::new(),
+    XLSXCell::Text(content) => format!(
+      r#"<c r="{}" t="s"><v>{}</v></c>"#,
+      reference,
+      shared_strings.index_of(content)
+    ),
+    XLSXCell::Number {
+      value,
+      format: None,
+    } => format!(r#"<c r="{}"><v>{}</v></c>"#, reference, value),
+    XLSXCell::Number {
+      value,
+      format: Some(format),
+    } => format!(
+      r#"<c r="{}" s="{}"><v>{}</v></c>"#,
+      reference,
+      number_formats.style_of(format),
+      value
+    ),
+    XLSXCell::Boolean(value) => format!(
+      r#"<c r="{}" t="b"><v>{}</v></c>"#,
+      reference,
+      if *value { 1 } else { 0 }
+    ),
+    XLSXCell::Date {
+      timestamp,
+      include_time,
+    } => {
+      let style = if *include_time {
+        DATE_TIME_STYLE
+      } else {
+        DATE_STYLE
+      };
+      let serial = *timestamp as f64 / 86400.0 + UNIX_EPOCH_IN_EXCEL_DAYS;
+      format!(
+        r#"<c r="{}" s="{}"><v>{}</v></c>"#,
+        reference, style, serial
+      )
+    },
+  }
+}
+
+/// Returns the name of the column in the A1 reference style, for example `A`, `Z` or `AA`.
+fn column_name(index: usize) -> String {
+  let mut name = String::new();
+  let mut index = index + 1;
+  while index > 0 {
+    let remainder = (index - 1) % 26;
+    name.insert(0, (b'A' + remainder as u8) as char);
+    index = (index - 1) / 26;
+  }
+  name
+}
+
+/// Returns the sheet names that Excel accepts: the names must be unique, at most
+/// [MAX_SHEET_NAME_LEN] characters long and must not contain any of `[]:*?/\`.
+fn unique_sheet_names<'a>(names: impl Iterator<Item = &'a str>) -> Vec<String> {
+  let mut used_names = HashSet::new();
+  names
+    .enumerate()
+    .map(|(index, name)| {
+      let name = name
+        .chars()
+        .map(|c| match c {
+          '[' | ']' | ':' | '*' | '?' | '/' | '\\' => '_',
+          _ => c,
+        })
+        .take(MAX_SHEET_NAME_LEN)
+        .collect::<String>();
+      let name = match name.trim() {
+        "" => format!("Sheet{}", index + 1),
+        name => name.to_string(),
+      };
+
+      let mut unique_name = name.clone();
+      let mut suffix = 2;
+      while !used_names.insert(unique_name.to_lowercase()) {
+        let suffix_str = format!(" ({})", suffix);
+        let prefix = name
+          .chars()
+          .take(MAX_SHEET_NAME_LEN - suffix_str.len())
+          .collect::<String>();
+        unique_name = format!("{}{}", prefix, suffix_str);
+        suffix += 1;
+      }
+      unique_name
+    })
+    .collect()
+}
+
+/// Escapes the xml special characters and removes the control characters that are not allowed
+/// in xml documents.
+fn escape_xml(content: &str) -> String {
+  let mut escaped = String::with_capacity(content.len());
+  for c in content.chars() {
+    match c {
+      '&' => escaped.push_str("&amp;"),
+      '<' => escaped.push_str("&lt;"),
+      '>' => escaped.push_str("&gt;"),
+      '"' => escaped.push_str("&quot;"),
+      '\'' => escaped.push_str("&apos;"),
+      '\t' | '\n' | '\r' => escaped.push(c),
+      c if c.is_control() => {},
+      c => escaped.push(c),
+    }
+  }
+  escaped
+}
+
+#[cfg(test)]
+mod tests {
+  use std::io::{Cursor, Read};
+
+  use crate::services::share::xlsx::{
+    column_name, escape_xml, unique_sheet_names, XLSXCell, XLSXExport, XLSXSheet,
+  };
+
+  #[test]
+  fn xlsx_column_name_test() {
+    assert_eq!(column_name(0), "A");
+    assert_eq!(column_name(25), "Z");
+    assert_eq!(column_name(26), "AA");
+    assert_eq!(column_name(701), "ZZ");
+    assert_eq!(column_name(702), "AAA");
+  }
+
+  #[test]
+  fn xlsx_sheet_names_test() {
+    let names = unique_sheet_names(
+      vec![
+        "Grid",
+        "grid",
+        "",
+        "a/b",
+        "A very long view name that exceeds the limit",
+      ]
+      .into_iter(),
+    );
+    assert_eq!(names[0], "Grid");
+    assert_eq!(names[1], "grid (2)");
+    assert_eq!(names[2], "Sheet3");
+    assert_eq!(names[3], "a_b");
+    assert_eq!(names[4].chars().count(), 31);
+  }
+
+  #[test]
+  fn xlsx_escape_test() {
+    assert_eq!(escape_xml("a < b & \"c\""), "a &lt; b &amp; &quot;c&quot;");
+    assert_eq!(escape_xml("a\u{1}b"), "ab");
+  }
+
+  #[test]
+  fn xlsx_formatted_number_test() {
+    let number = |value: f64, format: &str| XLSXCell::Number {
+      value,
+      format: Some(format.to_string()),
+    };
+    assert_eq!(
+      XLSXCell::formatted_number("$1,234.50"),
+      Some(number(1234.5, "\"$\"#,##0.00"))
+    );
+    assert_eq!(
+      XLSXCell::formatted_number("-€12,5"),
+      Some(number(-12.5, "\"€\"0.0"))
+    );
+    assert_eq!(
+      XLSXCell::formatted_number("1.234.567 kr"),
+      Some(number(1234567.0, "#,##0\" kr\""))
+    );
+    assert_eq!(XLSXCell::formatted_number("50%"), Some(number(0.5, "0%")));
+    assert_eq!(
+      XLSXCell::formatted_number("1.25"),
+      Some(number(1.25, "0.00"))
+    );
+    assert_eq!(
+      XLSXCell::formatted_number("42"),
+      Some(XLSXCell::Number {
+        value: 42.0,
+        format: None
+      })
+    );
+    assert_eq!(XLSXCell::formatted_number("2024-01-01"), None);
+    assert_eq!(XLSXCell::formatted_number("abc"), None);
+  }
+
+  fn read_part(archive: &mut zip::ZipArchive<Cursor<Vec<u8>>>, name: &str) -> String {
+    let mut content = String::new();
+    archive
+      .by_name(name)
+      .unwrap()
+      .read_to_string(&mut content)
+      .unwrap();
+    content
+  }
+
+  #[test]
+  fn xlsx_export_test() {
+    let sheets = vec![XLSXSheet {
+      name: "Grid".to_string(),
+      rows: vec![
+        vec![
+          XLSXCell::Text("Name".to_string()),
+          XLSXCell::Text("Price".to_string()),
+        ],
+        vec![
+          XLSXCell::Text("A very long name of the first row".to_string()),
+          XLSXCell::formatted_number("$1.50").unwrap(),
+        ],
+        vec![
+          XLSXCell::Text("Name".to_string()),
+          XLSXCell::formatted_number("$2.00").unwrap(),
+        ],
+      ],
+    }];
+    let data = XLSXExport.export_sheets(sheets).unwrap();
+    let mut archive = zip::ZipArchive::new(Cursor::new(data)).unwrap();
+
+    let shared_strings = read_part(&mut archive, "xl/sharedStrings.xml");
+    assert!(shared_strings.contains(r#"count="4" uniqueCount="3""#));
+    assert!(shared_strings.contains(r#"<si><t xml:space="preserve">Name</t></si>"#));
+
+    let sheet = read_part(&mut archive, "xl/worksheets/sheet1.xml");
+    assert!(sheet.contains(r#"<c r="A1" t="s"><v>0</v></c>"#));
+    assert!(sheet.contains(r#"<c r="A3" t="s"><v>0</v></c>"#));
+    assert!(sheet.contains(r#"<c r="B2" s="3"><v>1.5</v></c>"#));
+    assert!(sheet.contains(r#"<c r="B3" s="3"><v>2</v></c>"#));
+    assert!(sheet.contains(r#"<col min="1" max="1" width="35" customWidth="1"/>"#));
+
+    let styles = read_part(&mut archive, "xl/styles.xml");
+    assert!(styles.contains(r#"<numFmt numFmtId="166" formatCode="&quot;$&quot;0.00"/>"#));
+    assert!(styles.contains(r#"<cellXfs count="4">"#));
+
+    let content_types = read_part(&mut archive, "[Content_Types].xml");
+    assert!(content_types.contains("/xl/sharedStrings.xml"));
+    let relationships = read_part(&mut archive, "xl/_rels/workbook.xml.rels");
+    assert!(relationships.contains(r#"Target="sharedStrings.xml""#));
+    assert!(archive.by_name("xl/workbook.xml").is_ok());
+  }
+}
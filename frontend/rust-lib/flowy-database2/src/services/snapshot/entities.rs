@@ -1 +1,16 @@
+pub struct DatabaseSnapshotMeta {
+  pub snapshot_id: String,
+  pub database_id: String,
+  pub desc: String,
+  /// The hash of the data of the snapshot, used to skip the snapshots of an unchanged database
+  /// without loading the data.
+  pub data_hash: String,
+  pub created_at: i64,
+}
 
+pub struct DatabaseSnapshotData {
+  pub database_id: String,
+  /// The json bytes of the raw data of the database, which contains the fields, the rows, the
+  /// views and the row templates of the database.
+  pub data: Vec<u8>,
+}
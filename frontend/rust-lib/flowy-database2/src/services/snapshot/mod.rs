@@ -1 +1,62 @@
 pub mod entities;
+
+use flowy_error::FlowyResult;
+use lib_infra::util::{md5, timestamp};
+use tracing::trace;
+
+use crate::manager::DatabaseSnapshotService;
+
+/// The maximum number of local snapshots kept per database. The oldest snapshots are removed
+/// when a new snapshot is written.
+pub(crate) const MAX_DATABASE_SNAPSHOTS: usize = 20;
+
+/// The snapshots taken before the operations that remove data are skipped if the latest
+/// snapshot of the database is more recent than this number of seconds, so that deleting rows
+/// one by one doesn't serialize the whole database each time.
+const PRE_OPERATION_SNAPSHOT_THROTTLE_SECS: i64 = 60;
+
+/// Returns true if a snapshot of the database was taken less than
+/// [PRE_OPERATION_SNAPSHOT_THROTTLE_SECS] seconds ago.
+pub(crate) fn has_recent_database_snapshot(
+  snapshot_service: &dyn DatabaseSnapshotService,
+  database_id: &str,
+) -> FlowyResult<bool> {
+  let latest_snapshot = snapshot_service.get_latest_database_snapshot_meta(database_id)?;
+  Ok(latest_snapshot.is_some_and(|snapshot| {
+    timestamp() - snapshot.created_at < PRE_OPERATION_SNAPSHOT_THROTTLE_SECS
+  }))
+}
+
+/// Writes a local snapshot of the raw data of the database, see
+/// [crate::services::row_template::database_raw_data_to_json]. Nothing is written when the
+/// database didn't change since its latest snapshot, which is checked by comparing the hash of
+/// the data.
+pub(crate) fn write_database_snapshot(
+  snapshot_service: &dyn DatabaseSnapshotService,
+  database_id: &str,
+  data: &serde_json::Value,
+  desc: &str,
+) -> FlowyResult<()> {
+  // The keys of the json objects are sorted, so the same data always gives the same bytes
+  let bytes = serde_json::to_vec(data)?;
+  let data_hash = md5(&bytes);
+  let latest_snapshot = snapshot_service.get_latest_database_snapshot_meta(database_id)?;
+  if let Some(latest_snapshot) = latest_snapshot {
+    if latest_snapshot.data_hash == data_hash {
+      trace!(
+        "[Database]: {} is unchanged since the snapshot {}",
+        database_id,
+        latest_snapshot.snapshot_id
+      );
+      return Ok(());
+    }
+  }
+
+  snapshot_service.create_database_snapshot(
+    database_id,
+    desc,
+    &data_hash,
+    bytes,
+    MAX_DATABASE_SNAPSHOTS,
+  )
+}
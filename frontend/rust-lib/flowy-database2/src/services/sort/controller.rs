@@ -98,6 +98,15 @@ impl SortController {
     }
   }
 
+  /// Sorts all the rows again after many rows changed, instead of moving the rows one by one
+  pub async fn did_receive_rows_changed(&self) {
+    if !self.sorts.is_empty() {
+      self
+        .gen_task(SortEvent::SortDidChanged, QualityOfService::UserInteractive)
+        .await;
+    }
+  }
+
   pub async fn did_create_row(&mut self, row: &Row) -> Option<u32> {
     if !self.delegate.filter_row(row).await {
       return None;
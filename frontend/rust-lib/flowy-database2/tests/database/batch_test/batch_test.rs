@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use flowy_database2::entities::{
+  FieldType, FilterDataPB, NumberFilterConditionPB, NumberFilterPB, PasteCellsPayloadPB,
+  PastedRowPB,
+};
+use flowy_database2::services::cell::stringify_cell;
+use flowy_database2::services::filter::FilterInner;
+use lib_infra::box_any::BoxAny;
+use protobuf::ProtobufError;
+
+use crate::database::database_editor::DatabaseEditorTest;
+
+#[tokio::test]
+async fn grid_update_cells_in_batch_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let field = test.get_first_field(FieldType::RichText).await;
+  let changesets = test.rows[0..3]
+    .iter()
+    .map(|row| {
+      (
+        row.id.clone(),
+        field.id.clone(),
+        BoxAny::new("batch".to_string()),
+      )
+    })
+    .collect();
+  test
+    .editor
+    .update_cells_with_changesets(&test.view_id, changesets)
+    .await
+    .unwrap();
+
+  for row in test.rows[0..3].iter() {
+    let cell = test.editor.get_cell(&field.id, &row.id).await.unwrap();
+    assert_eq!(stringify_cell(&cell, &field), "batch");
+  }
+
+  // The batch is undone in one step
+  test.editor.undo().await.unwrap();
+  assert!(!test.editor.can_undo());
+  let cell = test.editor.get_cell(&field.id, &test.rows[0].id).await;
+  assert_eq!(cell, test.rows[0].cells.get(&field.id).cloned());
+}
+
+#[tokio::test]
+async fn grid_create_rows_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let field = test.get_first_field(FieldType::RichText).await;
+  let rows = ["first", "second", "third"]
+    .iter()
+    .map(|text| HashMap::from([(field.id.clone(), text.to_string())]))
+    .collect();
+  let row_orders = test.editor.create_rows(&test.view_id, rows).await.unwrap();
+  assert_eq!(row_orders.len(), 3);
+
+  // The rows are appended to the view in the given order
+  let rows = test.get_rows().await;
+  assert_eq!(rows.len(), test.rows.len() + 3);
+  let created_row_ids = rows[test.rows.len()..]
+    .iter()
+    .map(|row| row.id.clone())
+    .collect::<Vec<_>>();
+  let row_ids = row_orders
+    .iter()
+    .map(|row_order| row_order.id.clone())
+    .collect::<Vec<_>>();
+  assert_eq!(created_row_ids, row_ids);
+  let cell = test
+    .editor
+    .get_cell(&field.id, &row_orders[1].id)
+    .await
+    .unwrap();
+  assert_eq!(stringify_cell(&cell, &field), "second");
+
+  test.editor.undo().await.unwrap();
+  assert_eq!(test.get_rows().await.len(), test.rows.len());
+}
+
+#[tokio::test]
+async fn grid_delete_rows_by_filter_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let field = test.get_first_field(FieldType::Number).await;
+  let filter = NumberFilterPB {
+    condition: NumberFilterConditionPB::GreaterThan,
+    content: "2".to_string(),
+  };
+  let bytes: Result<Bytes, ProtobufError> = filter.try_into();
+  let filter = FilterDataPB {
+    field_id: field.id.clone(),
+    field_type: FieldType::Number,
+    data: bytes.unwrap().to_vec(),
+    comparison: None,
+  };
+  let row_ids = test
+    .editor
+    .delete_rows_by_filter(&test.view_id, FilterInner::try_from(filter).unwrap())
+    .await
+    .unwrap();
+  assert_eq!(row_ids.len(), 3);
+  assert_eq!(test.get_rows().await.len(), test.rows.len() - 3);
+
+  test.editor.undo().await.unwrap();
+  assert_eq!(test.get_rows().await.len(), test.rows.len());
+}
+
+#[tokio::test]
+async fn grid_paste_text_cells_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let field = test.get_first_field(FieldType::RichText).await;
+  let params = PasteCellsPayloadPB {
+    view_id: test.view_id.clone(),
+    row_id: test.rows[1].id.to_string(),
+    field_id: field.id.clone(),
+    rows: vec![
+      PastedRowPB {
+        cells: vec!["pasted 1".to_string()],
+      },
+      PastedRowPB {
+        cells: vec!["pasted 2".to_string()],
+      },
+    ],
+  };
+  test.editor.paste_cells(params).await.unwrap();
+
+  let rows = test.get_rows().await;
+  assert_eq!(
+    stringify_cell(rows[1].cells.get(&field.id).unwrap(), &field),
+    "pasted 1"
+  );
+  assert_eq!(
+    stringify_cell(rows[2].cells.get(&field.id).unwrap(), &field),
+    "pasted 2"
+  );
+  // The rows outside of the range are unchanged
+  assert_eq!(
+    rows[0].cells.get(&field.id),
+    test.rows[0].cells.get(&field.id)
+  );
+}
+
+#[tokio::test]
+async fn grid_paste_creates_missing_select_options_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let field = test.get_first_field(FieldType::SingleSelect).await;
+  let num_of_options = test.get_single_select_type_option(&field.id).await.len();
+  let params = PasteCellsPayloadPB {
+    view_id: test.view_id.clone(),
+    row_id: test.rows[0].id.to_string(),
+    field_id: field.id.clone(),
+    rows: vec![PastedRowPB {
+      cells: vec!["Pasted option".to_string()],
+    }],
+  };
+  test.editor.paste_cells(params).await.unwrap();
+
+  let options = test.get_single_select_type_option(&field.id).await;
+  assert_eq!(options.len(), num_of_options + 1);
+  assert!(options.iter().any(|option| option.name == "Pasted option"));
+
+  // The new option and the pasted cell are undone together
+  test.editor.undo().await.unwrap();
+  let options = test.get_single_select_type_option(&field.id).await;
+  assert_eq!(options.len(), num_of_options);
+}
@@ -193,3 +193,96 @@ async fn calculations_count_test() {
   tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
   test.assert_calculation_value("8").await;
 }
+
+#[tokio::test]
+async fn calculations_count_unique_test() {
+  let mut test = DatabaseCalculationTest::new().await;
+  let view_id = test.view_id();
+  let field_id = first_field_id(&test, FieldType::RichText);
+
+  test
+    .insert_calculation(UpdateCalculationChangesetPB {
+      view_id,
+      field_id,
+      calculation_id: Some("calc_id".to_owned()),
+      calculation_type: CalculationType::CountUnique,
+    })
+    .await;
+  // The empty cell is not counted and "AE" is counted once
+  test.assert_calculation_value("5").await;
+}
+
+#[tokio::test]
+async fn calculations_percent_test() {
+  let mut test = DatabaseCalculationTest::new().await;
+  let view_id = test.view_id();
+  let field_id = first_field_id(&test, FieldType::Checkbox);
+  let calculation_id = "calc_id".to_owned();
+
+  test
+    .insert_calculation(UpdateCalculationChangesetPB {
+      view_id: view_id.clone(),
+      field_id: field_id.clone(),
+      calculation_id: Some(calculation_id.clone()),
+      calculation_type: CalculationType::PercentChecked,
+    })
+    .await;
+  test.assert_calculation_float_value(50.0).await;
+
+  let text_field_id = first_field_id(&test, FieldType::RichText);
+  test
+    .insert_calculation(UpdateCalculationChangesetPB {
+      view_id,
+      field_id: text_field_id,
+      calculation_id: Some(calculation_id),
+      calculation_type: CalculationType::PercentEmpty,
+    })
+    .await;
+  test.assert_calculation_float_value(100.0 / 7.0).await;
+}
+
+#[tokio::test]
+async fn calculations_date_range_test() {
+  let mut test = DatabaseCalculationTest::new().await;
+  let view_id = test.view_id();
+  let field_id = first_field_id(&test, FieldType::DateTime);
+
+  test
+    .insert_calculation(UpdateCalculationChangesetPB {
+      view_id,
+      field_id,
+      calculation_id: Some("calc_id".to_owned()),
+      calculation_type: CalculationType::DateRange,
+    })
+    .await;
+  // From Mar 14, 2022 to Dec 25, 2022
+  test.assert_calculation_value("285").await;
+}
+
+#[tokio::test]
+async fn calculations_checklist_completion_test() {
+  let mut test = DatabaseCalculationTest::new().await;
+  let view_id = test.view_id();
+  let field_id = first_field_id(&test, FieldType::Checklist);
+
+  test
+    .insert_calculation(UpdateCalculationChangesetPB {
+      view_id,
+      field_id,
+      calculation_id: Some("calc_id".to_owned()),
+      calculation_type: CalculationType::ChecklistCompletion,
+    })
+    .await;
+  // 6 of the 10 tasks are completed
+  test.assert_calculation_float_value(60.0).await;
+}
+
+fn first_field_id(test: &DatabaseCalculationTest, field_type: FieldType) -> String {
+  test
+    .fields
+    .iter()
+    .find(|field| field.field_type == field_type as i64)
+    .unwrap()
+    .id
+    .clone()
+}
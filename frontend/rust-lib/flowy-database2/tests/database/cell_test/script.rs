@@ -1,6 +1,8 @@
 use crate::database::database_editor::DatabaseEditorTest;
 use collab_database::rows::RowId;
 use lib_infra::box_any::BoxAny;
+use std::time::Duration;
+use tokio::time::timeout;
 
 pub struct DatabaseCellTest {
   inner: DatabaseEditorTest,
@@ -25,6 +27,29 @@ impl DatabaseCellTest {
       .await
       .unwrap();
   }
+
+  /// Updates the cell and waits until the computed cells, the formula and rollup cells, of the
+  /// `computed_row_id` row are written.
+  pub async fn update_cell_and_wait_computed_cells(
+    &self,
+    view_id: &str,
+    field_id: &str,
+    row_id: &RowId,
+    changeset: BoxAny,
+    computed_row_id: &RowId,
+  ) {
+    let mut rx = self.editor.subscribe_computed_cells();
+    self.update_cell(view_id, field_id, row_id, changeset).await;
+    timeout(Duration::from_secs(5), async {
+      while let Ok(row_ids) = rx.recv().await {
+        if row_ids.contains(computed_row_id) {
+          break;
+        }
+      }
+    })
+    .await
+    .unwrap();
+  }
 }
 
 impl std::ops::Deref for DatabaseCellTest {
@@ -1,6 +1,7 @@
 use crate::database::cell_test::script::DatabaseCellTest;
 use collab_database::fields::date_type_option::DateCellData;
 use collab_database::fields::media_type_option::{MediaFile, MediaFileType, MediaUploadType};
+use collab_database::fields::relation_type_option::RelationTypeOption;
 use collab_database::fields::select_type_option::{MultiSelectTypeOption, SingleSelectTypeOption};
 use collab_database::fields::url_type_option::URLCellData;
 use collab_database::template::time_parse::TimeCellData;
@@ -10,7 +11,9 @@ use flowy_database2::services::field::checklist_filter::{
 };
 use flowy_database2::services::field::date_filter::DateCellChangeset;
 use flowy_database2::services::field::{
-  RelationCellChangeset, SelectOptionCellChangeset, StringCellData,
+  EmailCellData, FormulaCellData, Person, PersonCellChangeset, PhoneCellData, RatingCellData,
+  RelationCellChangeset, RollupCalculation, RollupCellData, RollupTypeOption,
+  SelectOptionCellChangeset, StringCellData,
 };
 use lib_infra::box_any::BoxAny;
 use std::time::Duration;
@@ -24,7 +27,15 @@ async fn grid_cell_update() {
   for row in rows.iter() {
     for field in &fields {
       let field_type = FieldType::from(field.field_type);
-      if field_type == FieldType::LastEditedTime || field_type == FieldType::CreatedTime {
+      // The cells of these fields are computed, they can't be updated directly.
+      if matches!(
+        field_type,
+        FieldType::LastEditedTime
+          | FieldType::CreatedTime
+          | FieldType::Formula
+          | FieldType::Rollup
+          | FieldType::AutoNumber
+      ) {
         continue;
       }
       let cell_changeset = match field_type {
@@ -73,6 +84,17 @@ async fn grid_cell_update() {
           }],
           removed_ids: vec![],
         }),
+        FieldType::Rating => BoxAny::new("3".to_string()),
+        FieldType::Email => BoxAny::new("nathan@appflowy.io".to_string()),
+        FieldType::Phone => BoxAny::new("+1 555 0100".to_string()),
+        FieldType::Person => BoxAny::new(PersonCellChangeset {
+          inserted_persons: vec![Person {
+            email: "nathan@appflowy.io".to_string(),
+            name: "Nathan".to_string(),
+            avatar_url: None,
+          }],
+          removed_emails: vec![],
+        }),
         _ => BoxAny::new("".to_string()),
       };
 
@@ -204,3 +226,204 @@ async fn time_cell_data_test() {
     assert_eq!(cell.0.unwrap_or_default(), 75);
   }
 }
+
+#[tokio::test]
+async fn formula_cell_recompute_on_referenced_cell_update_test() {
+  let test = DatabaseCellTest::new().await;
+  let number_field = test.get_first_field(FieldType::Number).await;
+  let formula_field = test.get_first_field(FieldType::Formula).await;
+  let row_id = test.rows[0].id.clone();
+
+  test
+    .update_cell_and_wait_computed_cells(
+      &test.view_id,
+      &number_field.id,
+      &row_id,
+      BoxAny::new("21".to_string()),
+      &row_id,
+    )
+    .await;
+
+  let cell = test
+    .editor
+    .get_cell(&formula_field.id, &row_id)
+    .await
+    .unwrap();
+  assert_eq!(FormulaCellData::from(&cell).0, "42");
+
+  // The cells of a formula field can't be edited directly
+  let result = test
+    .editor
+    .update_cell_with_changeset(
+      &test.view_id,
+      &row_id,
+      &formula_field.id,
+      BoxAny::new("1".to_string()),
+    )
+    .await;
+  assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn rollup_cell_recompute_on_related_cell_update_test() {
+  let test = DatabaseCellTest::new().await;
+  let database_id = test
+    .sdk
+    .database_manager
+    .get_database_id_with_view_id(&test.view_id)
+    .await
+    .unwrap();
+  let number_field = test.get_first_field(FieldType::Number).await;
+  let relation_field = test.get_first_field(FieldType::Relation).await;
+  let rollup_field = test.get_first_field(FieldType::Rollup).await;
+
+  // Relate the database to itself and sum up the numbers of the related rows
+  test
+    .editor
+    .update_field_type_option(
+      &relation_field.id,
+      RelationTypeOption {
+        database_id: database_id.clone(),
+      }
+      .into(),
+      relation_field.clone(),
+    )
+    .await
+    .unwrap();
+  test
+    .editor
+    .update_field_type_option(
+      &rollup_field.id,
+      RollupTypeOption::new(&relation_field.id, &number_field.id, RollupCalculation::Sum).into(),
+      rollup_field.clone(),
+    )
+    .await
+    .unwrap();
+
+  let row_id = test.rows[0].id.clone();
+  let related_row_ids = vec![test.rows[1].id.clone(), test.rows[2].id.clone()];
+  for (related_row_id, number) in related_row_ids.iter().zip(["10", "5"]) {
+    test
+      .update_cell(
+        &test.view_id,
+        &number_field.id,
+        related_row_id,
+        BoxAny::new(number.to_string()),
+      )
+      .await;
+  }
+  test
+    .update_cell_and_wait_computed_cells(
+      &test.view_id,
+      &relation_field.id,
+      &row_id,
+      BoxAny::new(RelationCellChangeset {
+        inserted_row_ids: related_row_ids.clone(),
+        ..Default::default()
+      }),
+      &row_id,
+    )
+    .await;
+
+  let cell = test
+    .editor
+    .get_cell(&rollup_field.id, &row_id)
+    .await
+    .unwrap();
+  assert_eq!(RollupCellData::from(&cell).0, "15");
+
+  // Updating the number of a related row refreshes the rollup cell
+  test
+    .update_cell_and_wait_computed_cells(
+      &test.view_id,
+      &number_field.id,
+      &related_row_ids[1],
+      BoxAny::new("20".to_string()),
+      &row_id,
+    )
+    .await;
+
+  let cell = test
+    .editor
+    .get_cell(&rollup_field.id, &row_id)
+    .await
+    .unwrap();
+  assert_eq!(RollupCellData::from(&cell).0, "30");
+}
+
+#[tokio::test]
+async fn rating_email_and_phone_cell_normalization_test() {
+  let test = DatabaseCellTest::new().await;
+  let row_id = test.rows[1].id.clone();
+  let rating_field = test.get_first_field(FieldType::Rating).await;
+  let email_field = test.get_first_field(FieldType::Email).await;
+  let phone_field = test.get_first_field(FieldType::Phone).await;
+
+  // The rating is clamped to the max stars of the field
+  test
+    .update_cell(
+      &test.view_id,
+      &rating_field.id,
+      &row_id,
+      BoxAny::new("7".to_string()),
+    )
+    .await;
+  let cell = test
+    .editor
+    .get_cell(&rating_field.id, &row_id)
+    .await
+    .unwrap();
+  assert_eq!(RatingCellData::from(&cell).0, 5);
+
+  test
+    .update_cell(
+      &test.view_id,
+      &email_field.id,
+      &row_id,
+      BoxAny::new("mailto:Nathan@AppFlowy.IO".to_string()),
+    )
+    .await;
+  let cell = test
+    .editor
+    .get_cell(&email_field.id, &row_id)
+    .await
+    .unwrap();
+  assert_eq!(EmailCellData::from(&cell).0, "Nathan@appflowy.io");
+
+  test
+    .update_cell(
+      &test.view_id,
+      &phone_field.id,
+      &row_id,
+      BoxAny::new("+1 (555) 010-0199".to_string()),
+    )
+    .await;
+  let cell = test
+    .editor
+    .get_cell(&phone_field.id, &row_id)
+    .await
+    .unwrap();
+  assert_eq!(PhoneCellData::from(&cell).0, "+15550100199");
+
+  // Invalid email addresses and phone numbers are rejected
+  let result = test
+    .editor
+    .update_cell_with_changeset(
+      &test.view_id,
+      &row_id,
+      &email_field.id,
+      BoxAny::new("nathan@".to_string()),
+    )
+    .await;
+  assert!(result.is_err());
+  let result = test
+    .editor
+    .update_cell_with_changeset(
+      &test.view_id,
+      &row_id,
+      &phone_field.id,
+      BoxAny::new("call me".to_string()),
+    )
+    .await;
+  assert!(result.is_err());
+}
@@ -1,7 +1,11 @@
 use collab_database::database::gen_option_id;
 use collab_database::fields::select_type_option::{SelectOption, SelectTypeOption};
-use flowy_database2::entities::{FieldChangesetPB, FieldType};
-use flowy_database2::services::field::{CHECK, UNCHECK};
+use collab_database::fields::Field;
+use collab_database::rows::Row;
+use flowy_database2::entities::{CreateRowPayloadPB, FieldChangesetPB, FieldType};
+use flowy_database2::services::cell::stringify_cell;
+use flowy_database2::services::field::{AutoNumberTypeOption, CHECK, UNCHECK};
+use std::collections::HashSet;
 
 use crate::database::field_test::script::DatabaseFieldTest;
 use crate::database::field_test::util::*;
@@ -255,3 +259,90 @@ async fn grid_switch_from_checklist_to_text_test() {
     .assert_cell_content(field_rev.id.clone(), 0, "First thing".to_string())
     .await;
 }
+
+#[tokio::test]
+async fn auto_number_field_numbers_new_rows_test() {
+  let test = DatabaseFieldTest::new().await;
+  let field = test.get_first_field(FieldType::AutoNumber).await;
+
+  let row_detail = test
+    .editor
+    .create_row(CreateRowPayloadPB {
+      view_id: test.view_id(),
+      ..Default::default()
+    })
+    .await
+    .unwrap()
+    .unwrap();
+  assert_eq!(number_of_row(&row_detail.row, &field), "TASK-1");
+
+  // The duplicated row gets a new number
+  test
+    .editor
+    .duplicate_row(&test.view_id(), &row_detail.row.id)
+    .await
+    .unwrap();
+  let rows = test.editor.get_all_rows(&test.view_id()).await.unwrap();
+  let duplicated_row = rows
+    .iter()
+    .find(|row| number_of_row(row, &field) == "TASK-2")
+    .unwrap();
+
+  // The number of a deleted row is not reused
+  test
+    .editor
+    .delete_rows(&test.view_id(), &[duplicated_row.id.clone()])
+    .await;
+  let row_detail = test
+    .editor
+    .create_row(CreateRowPayloadPB {
+      view_id: test.view_id(),
+      ..Default::default()
+    })
+    .await
+    .unwrap()
+    .unwrap();
+  assert_eq!(number_of_row(&row_detail.row, &field), "TASK-3");
+
+  let type_option = test
+    .editor
+    .get_field(&field.id)
+    .await
+    .unwrap()
+    .get_type_option::<AutoNumberTypeOption>(FieldType::AutoNumber)
+    .unwrap();
+  assert_eq!(type_option.next_number, 4);
+}
+
+#[tokio::test]
+async fn grid_switch_from_url_to_auto_number_test() {
+  let mut test = DatabaseFieldTest::new().await;
+  let field = test.get_first_field(FieldType::URL).await;
+
+  test
+    .switch_to_field(test.view_id(), field.id.clone(), FieldType::AutoNumber)
+    .await;
+
+  // Every row gets a unique number
+  let field = test.editor.get_field(&field.id).await.unwrap();
+  let rows = test.editor.get_all_rows(&test.view_id()).await.unwrap();
+  let numbers = rows
+    .iter()
+    .map(|row| stringify_cell(row.cells.get(&field.id).unwrap(), &field))
+    .collect::<HashSet<String>>();
+  assert_eq!(numbers.len(), rows.len());
+  assert!(!numbers.contains(""));
+
+  let type_option = field
+    .get_type_option::<AutoNumberTypeOption>(FieldType::AutoNumber)
+    .unwrap();
+  assert_eq!(type_option.next_number, rows.len() as i64 + 1);
+}
+
+fn number_of_row(row: &Row, field: &Field) -> String {
+  row
+    .cells
+    .get(&field.id)
+    .map(|cell| stringify_cell(cell, field))
+    .unwrap_or_default()
+}
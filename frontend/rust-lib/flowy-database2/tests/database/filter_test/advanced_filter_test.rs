@@ -88,6 +88,7 @@ async fn create_advanced_filter_test() {
             field_id: "".to_string(),
             field_type: FieldType::Checkbox,
             data: checkbox_filter_bytes.clone(),
+            comparison: None,
           }),
         },
         FilterPB {
@@ -144,6 +145,7 @@ async fn create_advanced_filter_test() {
             field_id: "".to_string(),
             field_type: FieldType::Checkbox,
             data: checkbox_filter_bytes,
+            comparison: None,
           }),
         },
         FilterPB {
@@ -158,6 +160,7 @@ async fn create_advanced_filter_test() {
                 field_id: "".to_string(),
                 field_type: FieldType::DateTime,
                 data: date_filter_bytes,
+                comparison: None,
               }),
             },
             FilterPB {
@@ -168,6 +171,7 @@ async fn create_advanced_filter_test() {
                 field_id: "".to_string(),
                 field_type: FieldType::Number,
                 data: number_filter_bytes,
+                comparison: None,
               }),
             },
           ],
@@ -276,6 +280,7 @@ async fn create_advanced_filter_with_conversion_test() {
             field_id: "".to_string(),
             field_type: FieldType::Checkbox,
             data: checkbox_filter_bytes,
+            comparison: None,
           }),
         },
         FilterPB {
@@ -290,6 +295,7 @@ async fn create_advanced_filter_with_conversion_test() {
                 field_id: "".to_string(),
                 field_type: FieldType::DateTime,
                 data: date_filter_bytes,
+                comparison: None,
               }),
             },
             FilterPB {
@@ -300,6 +306,7 @@ async fn create_advanced_filter_with_conversion_test() {
                 field_id: "".to_string(),
                 field_type: FieldType::Number,
                 data: number_filter_bytes,
+                comparison: None,
               }),
             },
           ],
@@ -312,3 +319,72 @@ async fn create_advanced_filter_with_conversion_test() {
 
   test.assert_number_of_visible_rows(4).await;
 }
+
+/// Create a NOT filter:
+///
+/// 1. Add a NOT filter, which doesn't hide any rows while it's empty
+/// 2. Add a Checkbox filter to its children, which hides the checked rows
+///
+#[tokio::test]
+async fn create_not_filter_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let row_count = test.rows.len();
+
+  let create_checkbox_filter = || -> CheckboxFilterPB {
+    CheckboxFilterPB {
+      condition: CheckboxFilterConditionPB::IsChecked,
+    }
+  };
+
+  // Create NOT Filter
+  test.create_not_filter(None, None).await;
+  test.wait(100).await;
+  test.assert_number_of_visible_rows(row_count).await;
+
+  let not_filter = test.get_filter(FilterType::Not, None).await.unwrap();
+
+  // Create Checkbox Filter
+  test
+    .create_data_filter(
+      Some(not_filter.id.clone()),
+      FieldType::Checkbox,
+      BoxAny::new(create_checkbox_filter()),
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: 3,
+      }),
+    )
+    .await;
+  test.wait(100).await;
+
+  let checkbox_filter_bytes: Result<Bytes, ProtobufError> = create_checkbox_filter().try_into();
+  let checkbox_filter_bytes = checkbox_filter_bytes.unwrap().to_vec();
+
+  test
+    .assert_filters(vec![FilterPB {
+      id: "".to_string(),
+      filter_type: FilterType::Not,
+      children: vec![FilterPB {
+        id: "".to_string(),
+        filter_type: FilterType::Data,
+        children: vec![],
+        data: Some(FilterDataPB {
+          field_id: "".to_string(),
+          field_type: FieldType::Checkbox,
+          data: checkbox_filter_bytes,
+          comparison: None,
+        }),
+      }],
+      data: None,
+    }])
+    .await;
+
+  test.assert_number_of_visible_rows(4).await;
+
+  // Convert the NOT filter to an AND filter
+  test
+    .update_filter_type(not_filter.id, FilterType::And, None)
+    .await;
+  test.wait(100).await;
+  test.assert_number_of_visible_rows(3).await;
+}
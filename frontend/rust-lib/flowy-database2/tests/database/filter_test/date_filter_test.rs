@@ -1,5 +1,7 @@
 use crate::database::filter_test::script::{DatabaseFilterTest, FilterRowChanged};
-use flowy_database2::entities::{DateFilterConditionPB, DateFilterPB, FieldType};
+use flowy_database2::entities::{
+  DateFilterConditionPB, DateFilterPB, FieldType, RelativeDateRangePB,
+};
 use lib_infra::box_any::BoxAny;
 
 #[tokio::test]
@@ -18,6 +20,8 @@ async fn grid_filter_date_is_test() {
         start: None,
         end: None,
         timestamp: Some(1647251762),
+        relative_range: None,
+        num_of_days: None,
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -46,6 +50,8 @@ async fn grid_filter_date_after_test() {
         start: None,
         end: None,
         timestamp: Some(1647251762),
+        relative_range: None,
+        num_of_days: None,
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -74,6 +80,8 @@ async fn grid_filter_date_on_or_after_test() {
         start: None,
         end: None,
         timestamp: Some(1668359085),
+        relative_range: None,
+        num_of_days: None,
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -102,6 +110,8 @@ async fn grid_filter_date_on_or_before_test() {
         start: None,
         end: None,
         timestamp: Some(1668359085),
+        relative_range: None,
+        num_of_days: None,
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -130,6 +140,8 @@ async fn grid_filter_date_within_test() {
         start: Some(1647251762),
         end: Some(1668704685),
         timestamp: None,
+        relative_range: None,
+        num_of_days: None,
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -141,3 +153,52 @@ async fn grid_filter_date_within_test() {
   // Assert the number of visible rows
   test.assert_number_of_visible_rows(expected).await;
 }
+
+#[tokio::test]
+async fn grid_filter_created_today_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let row_count = test.rows.len();
+
+  // All the rows were created today
+  test
+    .create_data_filter(
+      None,
+      FieldType::CreatedTime,
+      BoxAny::new(DateFilterPB {
+        condition: DateFilterConditionPB::DateStartsInRelativeRange,
+        relative_range: Some(RelativeDateRangePB::Today),
+        ..Default::default()
+      }),
+      None,
+    )
+    .await;
+
+  // Assert the number of visible rows
+  test.assert_number_of_visible_rows(row_count).await;
+}
+
+#[tokio::test]
+async fn grid_filter_created_yesterday_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let row_count = test.rows.len();
+
+  // Create "Created yesterday" filter
+  test
+    .create_data_filter(
+      None,
+      FieldType::CreatedTime,
+      BoxAny::new(DateFilterPB {
+        condition: DateFilterConditionPB::DateStartsInRelativeRange,
+        relative_range: Some(RelativeDateRangePB::Yesterday),
+        ..Default::default()
+      }),
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: row_count,
+      }),
+    )
+    .await;
+
+  // Assert the number of visible rows
+  test.assert_number_of_visible_rows(0).await;
+}
@@ -0,0 +1,110 @@
+use crate::database::filter_test::script::{DatabaseFilterTest, FilterRowChanged};
+use flowy_database2::entities::{
+  FieldComparisonConditionPB, FieldComparisonPB, FieldType, FilterDataPB, FilterPB, FilterType,
+};
+
+#[tokio::test]
+async fn grid_filter_number_less_than_rating_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let row_count = test.rows.len();
+  let expected = 1;
+
+  // The rows whose Number or Rating is empty are hidden
+  test
+    .create_field_comparison_filter(
+      None,
+      FieldType::Number,
+      FieldType::Rating,
+      FieldComparisonConditionPB::LessThan,
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: row_count - expected,
+      }),
+    )
+    .await;
+
+  test.assert_number_of_visible_rows(expected).await;
+
+  let rating_field = test.get_first_field(FieldType::Rating).await;
+  test
+    .assert_filters(vec![FilterPB {
+      id: "".to_string(),
+      filter_type: FilterType::Data,
+      children: vec![],
+      data: Some(FilterDataPB {
+        field_id: "".to_string(),
+        field_type: FieldType::Number,
+        data: vec![],
+        comparison: Some(FieldComparisonPB {
+          other_field_id: rating_field.id,
+          condition: FieldComparisonConditionPB::LessThan,
+        }),
+      }),
+    }])
+    .await;
+}
+
+#[tokio::test]
+async fn grid_filter_date_before_created_time_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let row_count = test.rows.len();
+  let expected = 6;
+
+  test
+    .create_field_comparison_filter(
+      None,
+      FieldType::DateTime,
+      FieldType::CreatedTime,
+      FieldComparisonConditionPB::LessThan,
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: row_count - expected,
+      }),
+    )
+    .await;
+
+  test.assert_number_of_visible_rows(expected).await;
+}
+
+#[tokio::test]
+async fn grid_filter_not_number_greater_than_rating_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let row_count = test.rows.len();
+
+  test.create_not_filter(None, None).await;
+  let not_filter = test.get_filter(FilterType::Not, None).await.unwrap();
+
+  // Everything except the rows whose Number is greater than or equal to their Rating
+  test
+    .create_field_comparison_filter(
+      Some(not_filter.id),
+      FieldType::Number,
+      FieldType::Rating,
+      FieldComparisonConditionPB::GreaterThanOrEqualTo,
+      None,
+    )
+    .await;
+
+  test.assert_number_of_visible_rows(row_count).await;
+}
+
+#[tokio::test]
+async fn grid_filter_field_comparison_delete_other_field_test() {
+  let mut test = DatabaseFilterTest::new().await;
+
+  test
+    .create_field_comparison_filter(
+      None,
+      FieldType::Number,
+      FieldType::Rating,
+      FieldComparisonConditionPB::LessThan,
+      None,
+    )
+    .await;
+  test.assert_filter_count(1).await;
+
+  // Deleting the field on the right side of the comparison deletes the filter
+  let rating_field = test.get_first_field(FieldType::Rating).await;
+  test.editor.delete_field(&rating_field.id).await.unwrap();
+  test.assert_filter_count(0).await;
+}
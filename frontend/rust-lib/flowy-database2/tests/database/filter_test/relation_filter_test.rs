@@ -0,0 +1,147 @@
+use crate::database::filter_test::script::{DatabaseFilterTest, FilterRowChanged};
+use flowy_database2::entities::{FieldType, RelationFilterConditionPB, RelationFilterPB};
+use lib_infra::box_any::BoxAny;
+
+#[tokio::test]
+async fn grid_filter_relation_is_empty_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let row_count = test.rows.len();
+  let related_row_id = test.rows[0].id.clone();
+  test
+    .update_relation_cell(test.rows[1].id.clone(), vec![related_row_id])
+    .await;
+
+  test
+    .create_data_filter(
+      None,
+      FieldType::Relation,
+      BoxAny::new(RelationFilterPB {
+        condition: RelationFilterConditionPB::RelationIsEmpty,
+        row_ids: vec![],
+      }),
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: 1,
+      }),
+    )
+    .await;
+  test.assert_number_of_visible_rows(row_count - 1).await;
+}
+
+#[tokio::test]
+async fn grid_filter_relation_contains_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let row_count = test.rows.len();
+  let project_a = test.rows[0].id.clone();
+  let project_b = test.rows[1].id.clone();
+  test
+    .update_relation_cell(test.rows[2].id.clone(), vec![project_a.clone()])
+    .await;
+  test
+    .update_relation_cell(
+      test.rows[3].id.clone(),
+      vec![project_a.clone(), project_b.clone()],
+    )
+    .await;
+
+  test
+    .create_data_filter(
+      None,
+      FieldType::Relation,
+      BoxAny::new(RelationFilterPB {
+        condition: RelationFilterConditionPB::RelationContains,
+        row_ids: vec![project_b.to_string()],
+      }),
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: row_count - 1,
+      }),
+    )
+    .await;
+  test.assert_number_of_visible_rows(1).await;
+}
+
+#[tokio::test]
+async fn grid_filter_relation_does_not_contain_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let row_count = test.rows.len();
+  let project_a = test.rows[0].id.clone();
+  test
+    .update_relation_cell(test.rows[2].id.clone(), vec![project_a.clone()])
+    .await;
+
+  test
+    .create_data_filter(
+      None,
+      FieldType::Relation,
+      BoxAny::new(RelationFilterPB {
+        condition: RelationFilterConditionPB::RelationDoesNotContain,
+        row_ids: vec![project_a.to_string()],
+      }),
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: 1,
+      }),
+    )
+    .await;
+  test.assert_number_of_visible_rows(row_count - 1).await;
+}
+
+#[tokio::test]
+async fn grid_filter_relation_contains_any_and_all_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let row_count = test.rows.len();
+  let project_a = test.rows[0].id.clone();
+  let project_b = test.rows[1].id.clone();
+  test
+    .update_relation_cell(test.rows[2].id.clone(), vec![project_a.clone()])
+    .await;
+  test
+    .update_relation_cell(
+      test.rows[3].id.clone(),
+      vec![project_a.clone(), project_b.clone()],
+    )
+    .await;
+
+  test
+    .create_data_filter(
+      None,
+      FieldType::Relation,
+      BoxAny::new(RelationFilterPB {
+        condition: RelationFilterConditionPB::RelationContainsAny,
+        row_ids: vec![project_a.to_string(), project_b.to_string()],
+      }),
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: row_count - 2,
+      }),
+    )
+    .await;
+  test.assert_number_of_visible_rows(2).await;
+
+  let filter = test.get_all_filters().await.pop().unwrap();
+  test
+    .delete_filter(
+      filter.id,
+      Some(FilterRowChanged {
+        showing_num_of_rows: row_count - 2,
+        hiding_num_of_rows: 0,
+      }),
+    )
+    .await;
+  test
+    .create_data_filter(
+      None,
+      FieldType::Relation,
+      BoxAny::new(RelationFilterPB {
+        condition: RelationFilterConditionPB::RelationContainsAll,
+        row_ids: vec![project_a.to_string(), project_b.to_string()],
+      }),
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: row_count - 1,
+      }),
+    )
+    .await;
+  test.assert_number_of_visible_rows(1).await;
+}
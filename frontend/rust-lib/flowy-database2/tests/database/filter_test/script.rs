@@ -8,9 +8,11 @@ use lib_infra::box_any::BoxAny;
 use tokio::sync::broadcast::Receiver;
 
 use flowy_database2::entities::{
-  DatabaseViewSettingPB, FieldType, FilterPB, FilterType, TextFilterConditionPB, TextFilterPB,
+  DatabaseViewSettingPB, FieldComparisonConditionPB, FieldType, FilterPB, FilterType,
+  TextFilterConditionPB, TextFilterPB,
 };
 use flowy_database2::services::database_view::DatabaseViewChanged;
+use flowy_database2::services::field::RelationCellChangeset;
 
 use crate::database::database_editor::DatabaseEditorTest;
 
@@ -60,8 +62,10 @@ impl DatabaseFilterTest {
     field_type: Option<FieldType>,
   ) -> Option<FilterPB> {
     match &filter.filter_type {
-      FilterType::And | FilterType::Or if filter.filter_type == filter_type => Some(filter.clone()),
-      FilterType::And | FilterType::Or => {
+      FilterType::And | FilterType::Or | FilterType::Not if filter.filter_type == filter_type => {
+        Some(filter.clone())
+      },
+      FilterType::And | FilterType::Or | FilterType::Not => {
         for child_filter in filter.children.iter() {
           if let Some(result) = Self::find_filter(child_filter, filter_type, field_type) {
             return Some(result);
@@ -99,6 +103,21 @@ impl DatabaseFilterTest {
       .unwrap();
   }
 
+  pub async fn update_relation_cell(&mut self, row_id: RowId, related_row_ids: Vec<RowId>) {
+    let field = self.get_first_field(FieldType::Relation).await;
+    self
+      .update_cell(
+        &field.id,
+        row_id,
+        BoxAny::new(RelationCellChangeset {
+          inserted_row_ids: related_row_ids,
+          ..Default::default()
+        }),
+      )
+      .await
+      .unwrap();
+  }
+
   pub async fn update_single_select_cell_with_change(
     &mut self,
     row_id: RowId,
@@ -153,7 +172,11 @@ impl DatabaseFilterTest {
       data: FilterInner::Data {
         field_id: current_filter.field_id,
         field_type: current_filter.field_type,
-        condition_and_content: BoxAny::new(TextFilterPB { condition, content }),
+        condition_and_content: BoxAny::new(TextFilterPB {
+          condition,
+          content,
+          ..Default::default()
+        }),
       },
     };
     self
@@ -199,6 +222,71 @@ impl DatabaseFilterTest {
       .unwrap();
   }
 
+  pub async fn create_not_filter(
+    &mut self,
+    parent_filter_id: Option<String>,
+    changed: Option<FilterRowChanged>,
+  ) {
+    self.subscribe_view_changed().await;
+    self.assert_future_changed(changed).await;
+    let params = FilterChangeset::Insert {
+      parent_filter_id,
+      data: FilterInner::Not { children: vec![] },
+    };
+    self
+      .editor
+      .modify_view_filters(&self.view_id, params)
+      .await
+      .unwrap();
+  }
+
+  pub async fn update_filter_type(
+    &mut self,
+    filter_id: String,
+    filter_type: FilterType,
+    changed: Option<FilterRowChanged>,
+  ) {
+    self.subscribe_view_changed().await;
+    self.assert_future_changed(changed).await;
+    let params = FilterChangeset::UpdateType {
+      filter_id,
+      filter_type,
+    };
+    self
+      .editor
+      .modify_view_filters(&self.view_id, params)
+      .await
+      .unwrap();
+  }
+
+  pub async fn create_field_comparison_filter(
+    &mut self,
+    parent_filter_id: Option<String>,
+    field_type: FieldType,
+    other_field_type: FieldType,
+    condition: FieldComparisonConditionPB,
+    changed: Option<FilterRowChanged>,
+  ) {
+    self.subscribe_view_changed().await;
+    self.assert_future_changed(changed).await;
+    let field = self.get_first_field(field_type).await;
+    let other_field = self.get_first_field(other_field_type).await;
+    let params = FilterChangeset::Insert {
+      parent_filter_id,
+      data: FilterInner::FieldComparison {
+        field_id: field.id,
+        field_type,
+        other_field_id: other_field.id,
+        condition,
+      },
+    };
+    self
+      .editor
+      .modify_view_filters(&self.view_id, params)
+      .await
+      .unwrap();
+  }
+
   pub async fn delete_filter(&mut self, filter_id: String, changed: Option<FilterRowChanged>) {
     self.subscribe_view_changed().await;
     self.assert_future_changed(changed).await;
@@ -298,8 +386,9 @@ impl DatabaseFilterTest {
         let expected_data = expected.data.clone().unwrap();
         assert_eq!(actual_data.field_type, expected_data.field_type);
         assert_eq!(actual_data.data, expected_data.data);
+        assert_eq!(actual_data.comparison, expected_data.comparison);
       },
-      FilterType::And | FilterType::Or => {
+      FilterType::And | FilterType::Or | FilterType::Not => {
         for (actual_child, expected_child) in actual.children.iter().zip(expected.children.iter()) {
           Self::assert_filter(actual_child, expected_child);
         }
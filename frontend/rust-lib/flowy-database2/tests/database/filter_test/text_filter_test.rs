@@ -14,6 +14,7 @@ async fn grid_filter_text_is_empty_test() {
       BoxAny::new(TextFilterPB {
         condition: TextFilterConditionPB::TextIsEmpty,
         content: "".to_string(),
+        ..Default::default()
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -38,6 +39,7 @@ async fn grid_filter_text_is_not_empty_test() {
       BoxAny::new(TextFilterPB {
         condition: TextFilterConditionPB::TextIsNotEmpty,
         content: "".to_string(),
+        ..Default::default()
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -77,6 +79,7 @@ async fn grid_filter_is_text_test() {
       BoxAny::new(TextFilterPB {
         condition: TextFilterConditionPB::TextIs,
         content: "A".to_string(),
+        ..Default::default()
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -98,6 +101,7 @@ async fn grid_filter_contain_text_test() {
       BoxAny::new(TextFilterPB {
         condition: TextFilterConditionPB::TextContains,
         content: "A".to_string(),
+        ..Default::default()
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -120,6 +124,7 @@ async fn grid_filter_contain_text_test2() {
       BoxAny::new(TextFilterPB {
         condition: TextFilterConditionPB::TextContains,
         content: "A".to_string(),
+        ..Default::default()
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -153,6 +158,7 @@ async fn grid_filter_does_not_contain_text_test() {
       BoxAny::new(TextFilterPB {
         condition: TextFilterConditionPB::TextDoesNotContain,
         content: "AB".to_string(),
+        ..Default::default()
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -174,6 +180,7 @@ async fn grid_filter_start_with_text_test() {
       BoxAny::new(TextFilterPB {
         condition: TextFilterConditionPB::TextStartsWith,
         content: "A".to_string(),
+        ..Default::default()
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -195,6 +202,7 @@ async fn grid_filter_ends_with_text_test() {
       BoxAny::new(TextFilterPB {
         condition: TextFilterConditionPB::TextEndsWith,
         content: "A".to_string(),
+        ..Default::default()
       }),
       None,
     )
@@ -216,6 +224,7 @@ async fn grid_update_text_filter_test() {
       BoxAny::new(TextFilterPB {
         condition: TextFilterConditionPB::TextEndsWith,
         content: "A".to_string(),
+        ..Default::default()
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -258,6 +267,7 @@ async fn grid_filter_delete_test() {
       BoxAny::new(TextFilterPB {
         condition: TextFilterConditionPB::TextIsEmpty,
         content: "".to_string(),
+        ..Default::default()
       }),
       None,
     )
@@ -289,6 +299,7 @@ async fn grid_filter_update_empty_text_cell_test() {
       BoxAny::new(TextFilterPB {
         condition: TextFilterConditionPB::TextIsEmpty,
         content: "".to_string(),
+        ..Default::default()
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -312,3 +323,89 @@ async fn grid_filter_update_empty_text_cell_test() {
     )
     .await;
 }
+
+#[tokio::test]
+async fn grid_filter_text_matches_regex_test() {
+  let mut test = DatabaseFilterTest::new().await;
+
+  // Create Text "Matches Regex" filter, which is case-insensitive by default
+  test
+    .create_data_filter(
+      None,
+      FieldType::RichText,
+      BoxAny::new(TextFilterPB {
+        condition: TextFilterConditionPB::TextMatchesRegex,
+        content: "^a".to_string(),
+        ..Default::default()
+      }),
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: 4,
+      }),
+    )
+    .await;
+
+  test.assert_number_of_visible_rows(3).await;
+}
+
+#[tokio::test]
+async fn grid_filter_text_case_sensitive_test() {
+  let mut test = DatabaseFilterTest::new().await;
+
+  // Create case-sensitive Text "Contains" filter
+  test
+    .create_data_filter(
+      None,
+      FieldType::RichText,
+      BoxAny::new(TextFilterPB {
+        condition: TextFilterConditionPB::TextContains,
+        content: "a".to_string(),
+        case_sensitive: true,
+        ..Default::default()
+      }),
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: 7,
+      }),
+    )
+    .await;
+
+  test.assert_number_of_visible_rows(0).await;
+
+  // The option is saved with the filter
+  let filter = test.get_all_filters().await.pop().unwrap();
+  let filter = TextFilterPB::try_from(filter.data.unwrap().data.as_slice()).unwrap();
+  assert!(filter.case_sensitive);
+  assert!(!filter.trim_whitespace);
+}
+
+#[tokio::test]
+async fn grid_filter_text_matches_any_of_test() {
+  let mut test = DatabaseFilterTest::new().await;
+
+  // Create Text "Matches Any Of" filter
+  test
+    .create_data_filter(
+      None,
+      FieldType::RichText,
+      BoxAny::new(TextFilterPB {
+        condition: TextFilterConditionPB::TextMatchesAnyOf,
+        values: vec!["c".to_string(), " DA ".to_string()],
+        trim_whitespace: true,
+        ..Default::default()
+      }),
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: 5,
+      }),
+    )
+    .await;
+
+  test.assert_number_of_visible_rows(2).await;
+
+  // The values are saved with the filter
+  let filter = test.get_all_filters().await.pop().unwrap();
+  let filter = TextFilterPB::try_from(filter.data.unwrap().data.as_slice()).unwrap();
+  assert_eq!(filter.values, vec!["c".to_string(), " DA ".to_string()]);
+  assert!(filter.trim_whitespace);
+}
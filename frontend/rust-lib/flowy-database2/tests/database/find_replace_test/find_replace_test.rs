@@ -0,0 +1,101 @@
+use flowy_database2::entities::{CellMatchPB, FieldType};
+use flowy_database2::services::cell::stringify_cell;
+use flowy_database2::services::find_replace::TextMatcher;
+
+use crate::database::database_editor::DatabaseEditorTest;
+use crate::database::mock_data::{COMPLETED, PLANNED};
+
+async fn find_in_field(
+  test: &DatabaseEditorTest,
+  field_id: &str,
+  matcher: &TextMatcher,
+) -> Vec<CellMatchPB> {
+  test
+    .editor
+    .find_in_database(&test.view_id, matcher, false)
+    .await
+    .unwrap()
+    .into_iter()
+    .filter(|m| m.field_id == field_id)
+    .collect()
+}
+
+#[tokio::test]
+async fn grid_find_plain_text_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let field = test.get_first_field(FieldType::RichText).await;
+
+  // The text cells are A, "", C, DA, AE, AE and CB
+  let matcher = TextMatcher::new("a", false, false).unwrap();
+  let matches = find_in_field(&test, &field.id, &matcher).await;
+  assert_eq!(matches.len(), 4);
+  assert_eq!(matches[1].row_id, test.rows[3].id.to_string());
+  assert_eq!((matches[1].start, matches[1].end), (1, 2));
+
+  let matcher = TextMatcher::new("a", false, true).unwrap();
+  assert!(find_in_field(&test, &field.id, &matcher).await.is_empty());
+}
+
+#[tokio::test]
+async fn grid_find_regex_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let field = test.get_first_field(FieldType::RichText).await;
+  let matcher = TextMatcher::new("^A", true, true).unwrap();
+  assert_eq!(find_in_field(&test, &field.id, &matcher).await.len(), 3);
+
+  // The regex special characters are matched as they are in plain text
+  let matcher = TextMatcher::new("^A", false, true).unwrap();
+  assert!(find_in_field(&test, &field.id, &matcher).await.is_empty());
+
+  assert!(TextMatcher::new("(A", true, true).is_err());
+}
+
+#[tokio::test]
+async fn grid_replace_text_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let field = test.get_first_field(FieldType::RichText).await;
+  let matcher = TextMatcher::new("A", false, true).unwrap();
+  let result = test
+    .editor
+    .replace_in_database(&test.view_id, &matcher, "Z", false)
+    .await
+    .unwrap();
+  assert!(result.num_of_replaced_cells >= 4);
+
+  let row_id = test.rows[3].id.clone();
+  let cell = test.editor.get_cell(&field.id, &row_id).await.unwrap();
+  assert_eq!(stringify_cell(&cell, &field), "DZ");
+  assert!(find_in_field(&test, &field.id, &matcher).await.is_empty());
+
+  // All the replaced cells are restored in one step
+  test.editor.undo().await.unwrap();
+  let cell = test.editor.get_cell(&field.id, &row_id).await.unwrap();
+  assert_eq!(stringify_cell(&cell, &field), "DA");
+  assert_eq!(find_in_field(&test, &field.id, &matcher).await.len(), 4);
+}
+
+#[tokio::test]
+async fn grid_replace_renames_select_option_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let field = test.get_first_field(FieldType::SingleSelect).await;
+  let matcher = TextMatcher::new(COMPLETED, false, true).unwrap();
+  let result = test
+    .editor
+    .replace_in_database(&test.view_id, &matcher, "Done", false)
+    .await
+    .unwrap();
+  assert_eq!(result.num_of_renamed_options, 1);
+
+  let options = test.get_single_select_type_option(&field.id).await;
+  assert!(options.iter().any(|option| option.name == "Done"));
+  assert!(!options.iter().any(|option| option.name == COMPLETED));
+
+  // An option is not renamed to the name of another option
+  let matcher = TextMatcher::new("Done", false, true).unwrap();
+  let result = test
+    .editor
+    .replace_in_database(&test.view_id, &matcher, PLANNED, false)
+    .await
+    .unwrap();
+  assert_eq!(result.num_of_renamed_options, 0);
+}
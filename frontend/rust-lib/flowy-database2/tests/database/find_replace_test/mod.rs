@@ -0,0 +1 @@
+mod find_replace_test;
@@ -0,0 +1,85 @@
+use flowy_database2::entities::{
+  FieldType, FormattingTargetPB, NumberFilterConditionPB, SelectOptionColorPB,
+};
+
+use crate::database::formatting_test::script::DatabaseFormattingTest;
+
+#[tokio::test]
+async fn grid_formatting_rule_test() {
+  let mut test = DatabaseFormattingTest::new().await;
+  let rule_id = test
+    .insert_number_rule(
+      NumberFilterConditionPB::LessThan,
+      "3",
+      FormattingTargetPB::Row,
+      SelectOptionColorPB::Pink,
+    )
+    .await;
+
+  let rules = test.get_all_rules().await;
+  assert_eq!(rules.len(), 1);
+  assert_eq!(rules[0].target, FormattingTargetPB::Row);
+  assert_eq!(rules[0].color, SelectOptionColorPB::Pink);
+
+  // The rows with the numbers 1 and 2
+  test.assert_number_of_formatted_rows(&rule_id, 2).await;
+}
+
+#[tokio::test]
+async fn grid_formatting_rule_update_cell_test() {
+  let mut test = DatabaseFormattingTest::new().await;
+  let rule_id = test
+    .insert_number_rule(
+      NumberFilterConditionPB::LessThan,
+      "0",
+      FormattingTargetPB::Cell,
+      SelectOptionColorPB::Orange,
+    )
+    .await;
+  test.assert_number_of_formatted_rows(&rule_id, 0).await;
+
+  // A negative number matches the rule
+  let row_id = test.rows[3].id.clone();
+  test.update_number_cell(row_id.clone(), "-14").await;
+  let notification = test.next_formatting_notification().await;
+  assert_eq!(notification.rows.len(), 1);
+  assert_eq!(notification.rows[0].row_id, row_id.to_string());
+  assert_eq!(notification.rows[0].rule_ids, vec![rule_id.clone()]);
+  test.assert_number_of_formatted_rows(&rule_id, 1).await;
+}
+
+#[tokio::test]
+async fn grid_delete_formatting_rule_test() {
+  let mut test = DatabaseFormattingTest::new().await;
+  let rule_id = test
+    .insert_number_rule(
+      NumberFilterConditionPB::NumberIsNotEmpty,
+      "",
+      FormattingTargetPB::Row,
+      SelectOptionColorPB::Green,
+    )
+    .await;
+  test.assert_number_of_formatted_rows(&rule_id, 5).await;
+
+  test.delete_rule(&rule_id).await;
+  assert!(test.get_all_rules().await.is_empty());
+  test.assert_number_of_formatted_rows(&rule_id, 0).await;
+}
+
+#[tokio::test]
+async fn grid_formatting_rule_deleted_with_field_test() {
+  let mut test = DatabaseFormattingTest::new().await;
+  test
+    .insert_number_rule(
+      NumberFilterConditionPB::NumberIsNotEmpty,
+      "",
+      FormattingTargetPB::Cell,
+      SelectOptionColorPB::Blue,
+    )
+    .await;
+  assert_eq!(test.get_all_rules().await.len(), 1);
+
+  let field = test.get_first_field(FieldType::Number).await;
+  test.editor.delete_field(&field.id).await.unwrap();
+  assert!(test.get_all_rules().await.is_empty());
+}
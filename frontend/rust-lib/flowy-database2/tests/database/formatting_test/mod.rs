@@ -0,0 +1,2 @@
+mod formatting_rule_test;
+mod script;
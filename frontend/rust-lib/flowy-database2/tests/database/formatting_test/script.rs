@@ -0,0 +1,141 @@
+use std::time::Duration;
+
+use bytes::Bytes;
+use collab_database::rows::RowId;
+use flowy_database2::entities::{
+  DeleteFormattingRulePayloadPB, FieldType, FilterDataPB, FormattingRulePB, FormattingTargetPB,
+  NumberFilterConditionPB, NumberFilterPB, RowsFormattingChangesetPB, SelectOptionColorPB,
+  UpdateFormattingRulePayloadPB,
+};
+use flowy_database2::services::database_view::DatabaseViewChanged;
+use lib_infra::box_any::BoxAny;
+use protobuf::ProtobufError;
+use tokio::sync::broadcast::Receiver;
+
+use crate::database::database_editor::DatabaseEditorTest;
+
+pub struct DatabaseFormattingTest {
+  inner: DatabaseEditorTest,
+  recv: Option<Receiver<DatabaseViewChanged>>,
+}
+
+impl DatabaseFormattingTest {
+  pub async fn new() -> Self {
+    let editor_test = DatabaseEditorTest::new_grid().await;
+    Self {
+      inner: editor_test,
+      recv: None,
+    }
+  }
+
+  /// Colours the rows whose number matches the condition. Returns the id of the new rule.
+  pub async fn insert_number_rule(
+    &mut self,
+    condition: NumberFilterConditionPB,
+    content: &str,
+    target: FormattingTargetPB,
+    color: SelectOptionColorPB,
+  ) -> String {
+    let field = self.get_first_field(FieldType::Number).await;
+    let filter = NumberFilterPB {
+      condition,
+      content: content.to_string(),
+    };
+    let bytes: Result<Bytes, ProtobufError> = filter.try_into();
+    let payload = UpdateFormattingRulePayloadPB {
+      view_id: self.view_id.clone(),
+      rule_id: None,
+      condition: FilterDataPB {
+        field_id: field.id,
+        field_type: FieldType::Number,
+        data: bytes.unwrap().to_vec(),
+        comparison: None,
+      },
+      target,
+      color,
+    };
+    self
+      .editor
+      .modify_view_formatting_rules(&self.view_id, payload.try_into().unwrap())
+      .await
+      .unwrap();
+
+    self.get_all_rules().await.last().unwrap().id.clone()
+  }
+
+  pub async fn delete_rule(&self, rule_id: &str) {
+    let payload = DeleteFormattingRulePayloadPB {
+      view_id: self.view_id.clone(),
+      rule_id: rule_id.to_string(),
+    };
+    self
+      .editor
+      .modify_view_formatting_rules(&self.view_id, payload.into())
+      .await
+      .unwrap();
+  }
+
+  pub async fn get_all_rules(&self) -> Vec<FormattingRulePB> {
+    self
+      .editor
+      .get_all_formatting_rules(&self.view_id)
+      .await
+      .items
+  }
+
+  pub async fn update_number_cell(&mut self, row_id: RowId, content: &str) {
+    self.recv = Some(
+      self
+        .editor
+        .subscribe_view_changed(&self.view_id)
+        .await
+        .unwrap(),
+    );
+    let field = self.get_first_field(FieldType::Number).await;
+    self
+      .update_cell(&field.id, row_id, BoxAny::new(content.to_string()))
+      .await
+      .unwrap();
+  }
+
+  /// Asserts the number of rows matching the rule when the view is opened
+  pub async fn assert_number_of_formatted_rows(&self, rule_id: &str, expected: usize) {
+    let database = self.get_database_data(&self.view_id).await;
+    let num_of_rows = database
+      .rows
+      .iter()
+      .filter(|row| row.formatting_rule_ids.iter().any(|id| id == rule_id))
+      .count();
+    assert_eq!(num_of_rows, expected);
+  }
+
+  /// Waits for the formatting of the rows to be sent after updating a cell
+  pub async fn next_formatting_notification(&mut self) -> RowsFormattingChangesetPB {
+    let mut receiver = self.recv.take().unwrap();
+    let notification = tokio::time::timeout(Duration::from_secs(2), async {
+      loop {
+        if let DatabaseViewChanged::FormattingNotification(notification) =
+          receiver.recv().await.unwrap()
+        {
+          return notification;
+        }
+      }
+    })
+    .await;
+    notification.expect("Process formatting task timeout")
+  }
+}
+
+impl std::ops::Deref for DatabaseFormattingTest {
+  type Target = DatabaseEditorTest;
+
+  fn deref(&self) -> &Self::Target {
+    &self.inner
+  }
+}
+
+impl std::ops::DerefMut for DatabaseFormattingTest {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.inner
+  }
+}
@@ -0,0 +1,17 @@
+use flowy_database2::entities::FieldType;
+
+use crate::database::group_test::script::DatabaseGroupTest;
+
+#[tokio::test]
+async fn group_by_checklist_test() {
+  let test = DatabaseGroupTest::new().await;
+  let checklist_field = test.get_field(FieldType::Checklist).await;
+  test.group_by_field(&checklist_field.id).await;
+
+  // The rows without tasks are in the no status group
+  test.assert_group_count(4).await;
+  test.assert_group_row_count(0, 5).await;
+  test.assert_group_id(1, "not_started").await;
+  test.assert_group_id(2, "in_progress").await;
+  test.assert_group_id(3, "completed").await;
+}
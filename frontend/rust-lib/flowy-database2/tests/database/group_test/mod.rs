@@ -1,4 +1,10 @@
+mod checklist_group_test;
 mod date_group_test;
+mod number_group_test;
+mod person_group_test;
+mod relation_group_test;
 mod script;
+mod sub_group_test;
 mod test;
+mod timestamp_group_test;
 mod url_group_test;
@@ -0,0 +1,91 @@
+use bytes::Bytes;
+use collab_database::rows::RowId;
+use flowy_database2::entities::{FieldType, NumberGroupConfigurationPB};
+use flowy_database2::services::cell::stringify_cell;
+
+use crate::database::group_test::script::DatabaseGroupTest;
+
+async fn group_by_number_with_bucket_width(test: &DatabaseGroupTest, bucket_width: f64) {
+  let number_field = test.get_field(FieldType::Number).await;
+  let configuration = NumberGroupConfigurationPB {
+    hide_empty: false,
+    bucket_width,
+  };
+  let data: Bytes = configuration.try_into().unwrap();
+  test
+    .editor
+    .set_group_by_field(&test.view_id, &number_field.id, data.to_vec())
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn group_by_number_test() {
+  let test = DatabaseGroupTest::new().await;
+  let number_field = test.get_field(FieldType::Number).await;
+  test.group_by_field(&number_field.id).await;
+
+  // The numbers 1, 2, 3 and 4 are in the range of the default width of 10
+  test.assert_group_count(2).await;
+  test.assert_group_row_count(0, 1).await; // No status group
+  test.assert_group_id(1, "0").await;
+  test.assert_group_row_count(1, 4).await;
+}
+
+#[tokio::test]
+async fn group_by_number_with_bucket_width_test() {
+  let test = DatabaseGroupTest::new().await;
+  group_by_number_with_bucket_width(&test, 2.0).await;
+
+  test.assert_group_count(4).await;
+  test.assert_group_row_count(0, 1).await; // No status group
+  test.assert_group_id(1, "0").await;
+  test.assert_group_row_count(1, 1).await; // 1
+  test.assert_group_id(2, "2").await;
+  test.assert_group_row_count(2, 2).await; // 2, 3
+  test.assert_group_id(3, "4").await;
+  test.assert_group_row_count(3, 1).await; // 4
+}
+
+#[tokio::test]
+async fn group_alter_number_to_new_range_test() {
+  let test = DatabaseGroupTest::new().await;
+  group_by_number_with_bucket_width(&test, 2.0).await;
+
+  // The group of the range from 0 to 2 is deleted with its only row
+  test
+    .update_grouped_cell_with_data(1, 0, "25".to_string())
+    .await;
+  test.assert_group_count(4).await;
+  test.assert_group_id(1, "2").await;
+  test.assert_group_id(2, "4").await;
+  test.assert_group_id(3, "24").await;
+  test.assert_group_row_count(3, 1).await;
+
+  // The row without number is moved out of the no status group
+  test
+    .update_grouped_cell_with_data(0, 0, "5".to_string())
+    .await;
+  test.assert_group_row_count(0, 0).await;
+  test.assert_group_row_count(2, 2).await;
+}
+
+#[tokio::test]
+async fn group_move_number_group_row_test() {
+  let test = DatabaseGroupTest::new().await;
+  group_by_number_with_bucket_width(&test, 2.0).await;
+
+  // The number of the row becomes the lower bound of the range of the group
+  let row = test.row_at_index(3, 0).await;
+  test.move_row(3, 0, 2, 0).await;
+  test.assert_group_row_count(2, 3).await;
+  test.assert_row(2, 0, row.clone()).await;
+
+  let number_field = test.get_field(FieldType::Number).await;
+  let cell = test
+    .editor
+    .get_cell(&number_field.id, &RowId::from(row.id))
+    .await
+    .unwrap();
+  assert_eq!(stringify_cell(&cell, &number_field), "2");
+}
@@ -0,0 +1,49 @@
+use crate::database::group_test::script::DatabaseGroupTest;
+
+#[tokio::test]
+async fn group_group_by_person() {
+  let test = DatabaseGroupTest::new().await;
+  let person_field = test.get_person_field().await;
+
+  // Group by person field
+  test.group_by_field(&person_field.id).await;
+
+  // A row assigned to several persons is in each of their groups
+  test.assert_group_row_count(0, 2).await; // No status group
+  test.assert_group_row_count(1, 2).await; // lucas@appflowy.io group
+  test.assert_group_row_count(2, 2).await; // nathan@appflowy.io group
+  test.assert_group_count(3).await;
+  test.assert_group_id(1, "lucas@appflowy.io").await;
+  test.assert_group_id(2, "nathan@appflowy.io").await;
+}
+
+#[tokio::test]
+async fn group_assign_new_person_test() {
+  let test = DatabaseGroupTest::new().await;
+  let person_field = test.get_person_field().await;
+  test.group_by_field(&person_field.id).await;
+
+  // Assign a row without persons to a new member
+  test
+    .update_grouped_cell_with_data(0, 0, "annie@appflowy.io".to_string())
+    .await;
+
+  test.assert_group_row_count(0, 1).await; // No status group
+  test.assert_group_row_count(3, 1).await; // annie@appflowy.io group
+  test.assert_group_count(4).await;
+}
+
+#[tokio::test]
+async fn group_move_person_group_row_test() {
+  let test = DatabaseGroupTest::new().await;
+  let person_field = test.get_person_field().await;
+  test.group_by_field(&person_field.id).await;
+
+  // Move the row only assigned to nathan to the group of lucas
+  let row = test.row_at_index(2, 1).await;
+  test.move_row(2, 1, 1, 0).await;
+
+  test.assert_group_row_count(1, 3).await; // lucas@appflowy.io group
+  test.assert_group_row_count(2, 1).await; // nathan@appflowy.io group
+  test.assert_row(1, 0, row).await;
+}
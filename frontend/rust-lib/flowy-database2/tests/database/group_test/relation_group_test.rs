@@ -0,0 +1,52 @@
+use flowy_database2::entities::FieldType;
+
+use crate::database::group_test::script::DatabaseGroupTest;
+
+#[tokio::test]
+async fn group_by_relation_test() {
+  let test = DatabaseGroupTest::new().await;
+  let relation_field = test.get_field(FieldType::Relation).await;
+  test.group_by_field(&relation_field.id).await;
+
+  // None of the rows is related to another row
+  test.assert_group_count(1).await;
+  test.assert_group_row_count(0, 5).await;
+
+  // One group is created for each related row
+  test
+    .update_grouped_cell_with_data(0, 0, "related_row_1".to_string())
+    .await;
+  test
+    .update_grouped_cell_with_data(0, 0, "related_row_1".to_string())
+    .await;
+  test
+    .update_grouped_cell_with_data(0, 0, "related_row_2".to_string())
+    .await;
+  test.assert_group_count(3).await;
+  test.assert_group_row_count(0, 2).await;
+  test.assert_group_id(1, "related_row_1").await;
+  test.assert_group_row_count(1, 2).await;
+  test.assert_group_id(2, "related_row_2").await;
+  test.assert_group_row_count(2, 1).await;
+}
+
+#[tokio::test]
+async fn group_move_relation_group_row_test() {
+  let test = DatabaseGroupTest::new().await;
+  let relation_field = test.get_field(FieldType::Relation).await;
+  test.group_by_field(&relation_field.id).await;
+  test
+    .update_grouped_cell_with_data(0, 0, "related_row_1".to_string())
+    .await;
+  test
+    .update_grouped_cell_with_data(0, 0, "related_row_2".to_string())
+    .await;
+  test
+    .update_grouped_cell_with_data(0, 0, "related_row_2".to_string())
+    .await;
+
+  // The moved row is related to the row of the group
+  test.move_row(2, 0, 1, 0).await;
+  test.assert_group_row_count(1, 2).await;
+  test.assert_group_row_count(2, 1).await;
+}
@@ -2,12 +2,15 @@ use crate::database::database_editor::DatabaseEditorTest;
 use collab_database::fields::select_type_option::{SelectOption, SingleSelectTypeOption};
 use collab_database::fields::Field;
 use collab_database::rows::RowId;
-use flowy_database2::entities::{CreateRowPayloadPB, FieldType, GroupPB, RowMetaPB};
+use flowy_database2::entities::{
+  CalculationType, CreateRowPayloadPB, FieldType, GroupPB, RowMetaPB, UpdateCalculationChangesetPB,
+};
 use flowy_database2::services::cell::{
-  delete_select_option_cell, insert_date_cell, insert_select_option_cell, insert_url_cell,
+  delete_select_option_cell, insert_date_cell, insert_person_cell, insert_relation_cell,
+  insert_select_option_cell, insert_text_cell, insert_url_cell,
 };
 use flowy_database2::services::field::{
-  edit_single_select_type_option, SelectTypeOptionSharedAction,
+  edit_single_select_type_option, Person, SelectTypeOptionSharedAction,
 };
 use std::time::Duration;
 
@@ -52,6 +55,7 @@ impl DatabaseGroupTest {
         &self.view_id,
         &from_group.group_id,
         &to_group.group_id,
+        None,
         from_row,
         Some(to_row),
       )
@@ -59,6 +63,34 @@ impl DatabaseGroupTest {
       .unwrap();
   }
 
+  pub async fn move_row_to_sub_group(
+    &self,
+    from_group_index: usize,
+    from_row_index: usize,
+    to_group_index: usize,
+    to_sub_group_index: usize,
+  ) {
+    let groups: Vec<GroupPB> = self.editor.load_groups(&self.view_id).await.unwrap().items;
+    let from_group = groups.get(from_group_index).unwrap();
+    let from_row = from_group.rows.get(from_row_index).unwrap();
+    let to_group = groups.get(to_group_index).unwrap();
+    let to_sub_group = to_group.sub_groups.get(to_sub_group_index).unwrap();
+    let from_row = RowId::from(from_row.id.clone());
+
+    self
+      .editor
+      .move_group_row(
+        &self.view_id,
+        &from_group.group_id,
+        &to_group.group_id,
+        Some(&to_sub_group.group_id),
+        from_row,
+        None,
+      )
+      .await
+      .unwrap();
+  }
+
   pub async fn assert_row(&self, group_index: usize, row_index: usize, row: RowMetaPB) {
     let group = self.group_at_index(group_index).await;
     let compare_row = group.rows.get(row_index).unwrap().clone();
@@ -72,6 +104,8 @@ impl DatabaseGroupTest {
       row_position: Default::default(),
       group_id: Some(group.group_id),
       data: Default::default(),
+      sub_group_id: None,
+      template_id: None,
     };
     self.editor.create_row(params).await.unwrap();
   }
@@ -79,7 +113,7 @@ impl DatabaseGroupTest {
   pub async fn delete_row(&self, group_index: usize, row_index: usize) {
     let row = self.row_at_index(group_index, row_index).await;
     let row_ids = vec![RowId::from(row.id)];
-    self.editor.delete_rows(&row_ids).await;
+    self.editor.delete_rows(&self.view_id, &row_ids).await;
     tokio::time::sleep(Duration::from_secs(1)).await; // Sleep to allow deletion to propagate
   }
 
@@ -108,6 +142,9 @@ impl DatabaseGroupTest {
           insert_select_option_cell(vec![to_group.group_id.clone()], &field)
         },
         FieldType::URL => insert_url_cell(to_group.group_id.clone(), &field),
+        FieldType::Person => {
+          insert_person_cell(vec![Person::from_email(&to_group.group_id)], &field)
+        },
         _ => panic!("Unsupported group field type"),
       }
     };
@@ -132,9 +169,12 @@ impl DatabaseGroupTest {
     let field_type = FieldType::from(field.field_type);
     let cell = match field_type {
       FieldType::URL => insert_url_cell(cell_data, &field),
+      FieldType::Person => insert_person_cell(vec![Person::from_email(&cell_data)], &field),
       FieldType::DateTime => {
         insert_date_cell(cell_data.parse::<i64>().unwrap(), None, Some(true), &field)
       },
+      FieldType::Number => insert_text_cell(cell_data, &field),
+      FieldType::Relation => insert_relation_cell(vec![RowId::from(cell_data)], &field),
       _ => panic!("Unsupported group field type"),
     };
 
@@ -156,6 +196,27 @@ impl DatabaseGroupTest {
       .unwrap();
   }
 
+  pub async fn insert_calculation(&self, field_type: FieldType, calculation_type: CalculationType) {
+    let field = self.get_field(field_type).await;
+    self
+      .editor
+      .update_calculation(UpdateCalculationChangesetPB {
+        view_id: self.view_id.clone(),
+        field_id: field.id,
+        calculation_id: None,
+        calculation_type,
+      })
+      .await
+      .unwrap();
+  }
+
+  pub async fn assert_group_calculation(&self, group_index: usize, expected: &str) {
+    tokio::time::sleep(Duration::from_secs(1)).await; // Sleep to allow updates to complete
+    let group = self.group_at_index(group_index).await;
+    let calculation = group.calculations.first().unwrap();
+    assert_eq!(calculation.value, expected, "group index: {}", group_index);
+  }
+
   pub async fn assert_group(&self, group_index: usize, expected_group: GroupPB) {
     let group = self.group_at_index(group_index).await;
     assert_eq!(group.group_id, expected_group.group_id);
@@ -179,6 +240,32 @@ impl DatabaseGroupTest {
       .unwrap();
   }
 
+  pub async fn sub_group_by_field(&self, field_id: Option<&str>) {
+    self
+      .editor
+      .set_sub_group_by_field(&self.view_id, field_id, vec![])
+      .await
+      .unwrap();
+  }
+
+  pub async fn assert_sub_group_row_count(
+    &self,
+    group_index: usize,
+    sub_group_index: usize,
+    row_count: usize,
+  ) {
+    tokio::time::sleep(Duration::from_secs(1)).await; // Sleep to allow updates to complete
+    let group = self.group_at_index(group_index).await;
+    let sub_group = group.sub_groups.get(sub_group_index).unwrap();
+    assert_eq!(
+      row_count,
+      sub_group.rows.len(),
+      "group index: {}, sub group index: {}",
+      group_index,
+      sub_group_index
+    );
+  }
+
   pub async fn assert_group_id(&self, group_index: usize, group_id: &str) {
     let group = self.group_at_index(group_index).await;
     assert_eq!(group_id, group.group_id, "group index: {}", group_index);
@@ -221,6 +308,10 @@ impl DatabaseGroupTest {
     self.get_field(FieldType::URL).await
   }
 
+  pub async fn get_person_field(&self) -> Field {
+    self.get_field(FieldType::Person).await
+  }
+
   pub async fn get_field(&self, field_type: FieldType) -> Field {
     self
       .inner
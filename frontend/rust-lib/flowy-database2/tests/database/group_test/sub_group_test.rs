@@ -0,0 +1,103 @@
+use collab_database::rows::RowId;
+use flowy_database2::entities::FieldType;
+use flowy_database2::services::cell::insert_checkbox_cell;
+
+use crate::database::group_test::script::DatabaseGroupTest;
+
+#[tokio::test]
+async fn sub_group_by_checkbox_test() {
+  let test = DatabaseGroupTest::new().await;
+  let checkbox_field = test.get_field(FieldType::Checkbox).await;
+  test.sub_group_by_field(Some(&checkbox_field.id)).await;
+
+  // Every group contains the checked and the unchecked sub groups
+  let group = test.group_at_index(1).await;
+  assert_eq!(group.sub_groups.len(), 2);
+  assert_eq!(group.sub_groups[0].field_id, checkbox_field.id);
+  test.assert_sub_group_row_count(1, 0, 2).await;
+  test.assert_sub_group_row_count(1, 1, 0).await;
+  test.assert_sub_group_row_count(2, 0, 0).await;
+  test.assert_sub_group_row_count(2, 1, 2).await;
+  test.assert_sub_group_row_count(3, 0, 0).await;
+  test.assert_sub_group_row_count(3, 1, 1).await;
+}
+
+#[tokio::test]
+async fn remove_sub_group_test() {
+  let test = DatabaseGroupTest::new().await;
+  let checkbox_field = test.get_field(FieldType::Checkbox).await;
+  test.sub_group_by_field(Some(&checkbox_field.id)).await;
+  test.sub_group_by_field(None).await;
+
+  let group = test.group_at_index(1).await;
+  assert!(group.sub_groups.is_empty());
+  test.assert_group_count(4).await;
+}
+
+#[tokio::test]
+async fn sub_group_by_grouping_field_test() {
+  let test = DatabaseGroupTest::new().await;
+  let single_select_field = test.get_single_select_field().await;
+  let result = test
+    .editor
+    .set_sub_group_by_field(&test.view_id, Some(&single_select_field.id), vec![])
+    .await;
+  assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn move_row_to_sub_group_test() {
+  let test = DatabaseGroupTest::new().await;
+  let checkbox_field = test.get_field(FieldType::Checkbox).await;
+  test.sub_group_by_field(Some(&checkbox_field.id)).await;
+
+  // Move the first checked row of the first group to the unchecked sub group of the second group
+  test.move_row_to_sub_group(1, 0, 2, 1).await;
+  test.assert_group_row_count(1, 1).await;
+  test.assert_group_row_count(2, 3).await;
+  test.assert_sub_group_row_count(1, 0, 1).await;
+  test.assert_sub_group_row_count(2, 1, 3).await;
+
+  // Both cells of the row are written, so the groups are the same after regrouping the rows
+  let single_select_field = test.get_single_select_field().await;
+  test.group_by_field(&single_select_field.id).await;
+  test.sub_group_by_field(Some(&checkbox_field.id)).await;
+  test.assert_group_row_count(2, 3).await;
+  test.assert_sub_group_row_count(2, 1, 3).await;
+  test.assert_sub_group_row_count(2, 0, 0).await;
+}
+
+#[tokio::test]
+async fn update_sub_grouped_cell_test() {
+  let test = DatabaseGroupTest::new().await;
+  let checkbox_field = test.get_field(FieldType::Checkbox).await;
+  test.sub_group_by_field(Some(&checkbox_field.id)).await;
+
+  // Unchecking the row moves it to the unchecked sub group of the same group
+  let row_id = RowId::from(test.row_at_index(1, 0).await.id);
+  test
+    .editor
+    .update_cell(
+      &test.view_id,
+      &row_id,
+      &checkbox_field.id,
+      insert_checkbox_cell(false, &checkbox_field),
+    )
+    .await
+    .unwrap();
+  test.assert_group_row_count(1, 2).await;
+  test.assert_sub_group_row_count(1, 0, 1).await;
+  test.assert_sub_group_row_count(1, 1, 1).await;
+}
+
+#[tokio::test]
+async fn delete_sub_grouped_row_test() {
+  let test = DatabaseGroupTest::new().await;
+  let checkbox_field = test.get_field(FieldType::Checkbox).await;
+  test.sub_group_by_field(Some(&checkbox_field.id)).await;
+
+  test.delete_row(1, 0).await;
+  test.assert_group_row_count(1, 1).await;
+  test.assert_sub_group_row_count(1, 0, 1).await;
+  test.assert_sub_group_row_count(1, 1, 0).await;
+}
@@ -1,5 +1,6 @@
 use crate::database::group_test::script::DatabaseGroupTest;
 use collab_database::fields::select_type_option::SelectOption;
+use flowy_database2::entities::{CalculationType, FieldType};
 
 #[tokio::test]
 async fn group_init_test() {
@@ -11,6 +12,24 @@ async fn group_init_test() {
   test.assert_group_row_count(0, 0).await;
 }
 
+#[tokio::test]
+async fn group_calculation_test() {
+  let test = DatabaseGroupTest::new().await;
+  test
+    .insert_calculation(FieldType::Number, CalculationType::Sum)
+    .await;
+
+  // The sum of the prices of each group
+  test.assert_group_calculation(1, "3.00").await;
+  test.assert_group_calculation(2, "7.00").await;
+  test.assert_group_calculation(3, "").await;
+
+  // Move the first row of the first group to the second group
+  test.move_row(1, 0, 2, 0).await;
+  test.assert_group_calculation(1, "2.00").await;
+  test.assert_group_calculation(2, "8.00").await;
+}
+
 #[tokio::test]
 async fn group_move_row_test() {
   let test = DatabaseGroupTest::new().await;
@@ -0,0 +1,34 @@
+use flowy_database2::entities::FieldType;
+
+use crate::database::group_test::script::DatabaseGroupTest;
+
+#[tokio::test]
+async fn group_by_created_time_test() {
+  let test = DatabaseGroupTest::new().await;
+  let created_time_field = test.get_field(FieldType::CreatedTime).await;
+  test.group_by_field(&created_time_field.id).await;
+
+  // All the rows are created today
+  test.assert_group_count(2).await;
+  test.assert_group_row_count(0, 0).await; // No status group
+  test.assert_group_row_count(1, 5).await;
+
+  // A new row is created today too
+  test.create_row(1).await;
+  test.assert_group_row_count(1, 6).await;
+}
+
+#[tokio::test]
+async fn group_by_last_edited_time_test() {
+  let test = DatabaseGroupTest::new().await;
+  let last_edited_time_field = test.get_field(FieldType::LastEditedTime).await;
+  test.group_by_field(&last_edited_time_field.id).await;
+  test.assert_group_count(2).await;
+  test.assert_group_row_count(1, 5).await;
+
+  // The row can be reordered in its group
+  let row = test.row_at_index(1, 0).await;
+  test.move_row(1, 0, 1, 1).await;
+  test.assert_group_row_count(1, 5).await;
+  test.assert_row(1, 1, row).await;
+}
@@ -14,7 +14,7 @@ use strum::IntoEnumIterator;
 use crate::database::mock_data::{COMPLETED, FACEBOOK, GOOGLE, PAUSED, PLANNED, TWITTER};
 use event_integration_test::database_event::TestRowBuilder;
 use flowy_database2::entities::FieldType;
-use flowy_database2::services::field::FieldBuilder;
+use flowy_database2::services::field::{FieldBuilder, Person};
 use flowy_database2::services::field_settings::default_field_settings_for_fields;
 use flowy_database2::services::setting::BoardLayoutSetting;
 
@@ -145,7 +145,20 @@ pub fn make_test_board() -> DatabaseData {
           .build();
         fields.push(time_field);
       },
-      FieldType::Translate | FieldType::Media => {},
+      FieldType::Translate
+      | FieldType::Media
+      | FieldType::Formula
+      | FieldType::Rollup
+      | FieldType::Rating
+      | FieldType::Email
+      | FieldType::Phone
+      | FieldType::AutoNumber => {},
+      FieldType::Person => {
+        let person_field = FieldBuilder::from_field_type(field_type)
+          .name("Assignee")
+          .build();
+        fields.push(person_field);
+      },
     }
   }
 
@@ -171,6 +184,7 @@ pub fn make_test_board() -> DatabaseData {
               .insert_multi_select_cell(|mut options| vec![options.remove(0), options.remove(0)]),
             FieldType::Checkbox => row_builder.insert_checkbox_cell("true"),
             FieldType::URL => row_builder.insert_url_cell("https://appflowy.io"),
+            FieldType::Person => row_builder.insert_person_cell(vec![lucas(), nathan()]),
             _ => "".to_owned(),
           };
         }
@@ -188,6 +202,7 @@ pub fn make_test_board() -> DatabaseData {
             FieldType::MultiSelect => row_builder
               .insert_multi_select_cell(|mut options| vec![options.remove(0), options.remove(0)]),
             FieldType::Checkbox => row_builder.insert_checkbox_cell("true"),
+            FieldType::Person => row_builder.insert_person_cell(vec![nathan()]),
             _ => "".to_owned(),
           };
         }
@@ -209,6 +224,7 @@ pub fn make_test_board() -> DatabaseData {
             FieldType::URL => {
               row_builder.insert_url_cell("https://github.com/AppFlowy-IO/AppFlowy")
             },
+            FieldType::Person => row_builder.insert_person_cell(vec![lucas()]),
             _ => "".to_owned(),
           };
         }
@@ -276,3 +292,19 @@ pub fn make_test_board() -> DatabaseData {
     rows,
   }
 }
+
+fn lucas() -> Person {
+  Person {
+    email: "lucas@appflowy.io".to_string(),
+    name: "Lucas".to_string(),
+    avatar_url: None,
+  }
+}
+
+fn nathan() -> Person {
+  Person {
+    email: "nathan@appflowy.io".to_string(),
+    name: "Nathan".to_string(),
+    avatar_url: None,
+  }
+}
@@ -20,7 +20,9 @@ use crate::database::mock_data::{COMPLETED, FACEBOOK, GOOGLE, PAUSED, PLANNED, T
 use event_integration_test::database_event::TestRowBuilder;
 use flowy_database2::entities::FieldType;
 use flowy_database2::services::field::checklist_filter::ChecklistCellInsertChangeset;
-use flowy_database2::services::field::FieldBuilder;
+use flowy_database2::services::field::{
+  AutoNumberTypeOption, FieldBuilder, FormulaTypeOption, RatingTypeOption,
+};
 use flowy_database2::services::field_settings::default_field_settings_for_fields;
 
 pub fn make_test_grid() -> DatabaseData {
@@ -170,6 +172,49 @@ pub fn make_test_grid() -> DatabaseData {
           .build();
         fields.push(media_field);
       },
+      FieldType::Formula => {
+        let type_option = FormulaTypeOption::new("{Price} * 2");
+        let formula_field = FieldBuilder::new(field_type, type_option)
+          .name("Formula")
+          .build();
+        fields.push(formula_field);
+      },
+      FieldType::Rollup => {
+        let rollup_field = FieldBuilder::from_field_type(field_type)
+          .name("Rollup")
+          .build();
+        fields.push(rollup_field);
+      },
+      FieldType::Rating => {
+        let rating_field = FieldBuilder::new(field_type, RatingTypeOption::new(5))
+          .name("Rating")
+          .build();
+        fields.push(rating_field);
+      },
+      FieldType::Email => {
+        let email_field = FieldBuilder::from_field_type(field_type)
+          .name("Email")
+          .build();
+        fields.push(email_field);
+      },
+      FieldType::Phone => {
+        let phone_field = FieldBuilder::from_field_type(field_type)
+          .name("Phone")
+          .build();
+        fields.push(phone_field);
+      },
+      FieldType::AutoNumber => {
+        let auto_number_field = FieldBuilder::new(field_type, AutoNumberTypeOption::new("TASK-"))
+          .name("ID")
+          .build();
+        fields.push(auto_number_field);
+      },
+      FieldType::Person => {
+        let person_field = FieldBuilder::from_field_type(field_type)
+          .name("Assignee")
+          .build();
+        fields.push(person_field);
+      },
     }
   }
 
@@ -197,6 +242,9 @@ pub fn make_test_grid() -> DatabaseData {
               )])
             },
             FieldType::Time => row_builder.insert_time_cell(75),
+            FieldType::Rating => row_builder.insert_rating_cell("4"),
+            FieldType::Email => row_builder.insert_email_cell("Lucas@AppFlowy.io"),
+            FieldType::Phone => row_builder.insert_phone_cell("+1 (555) 010-0100"),
             _ => "".to_owned(),
           };
         }
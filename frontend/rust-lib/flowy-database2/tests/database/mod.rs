@@ -1,3 +1,4 @@
+mod batch_test;
 mod block_test;
 mod calculations_test;
 mod cell_test;
@@ -5,9 +6,14 @@ mod database_editor;
 mod field_settings_test;
 mod field_test;
 mod filter_test;
+mod find_replace_test;
+mod formatting_test;
 mod group_test;
 mod layout_test;
 mod mock_data;
 mod pre_fill_cell_test;
+mod row_template_test;
 mod share_test;
+mod snapshot_test;
 mod sort_test;
+mod undo_test;
@@ -1,8 +1,8 @@
 use crate::database::pre_fill_cell_test::script::DatabasePreFillRowCellTest;
 use flowy_database2::entities::{
   CheckboxFilterConditionPB, CheckboxFilterPB, DateFilterConditionPB, DateFilterPB, FieldType,
-  FilterDataPB, SelectOptionFilterConditionPB, SelectOptionFilterPB, TextFilterConditionPB,
-  TextFilterPB,
+  FilterDataPB, RelationFilterConditionPB, RelationFilterPB, SelectOptionFilterConditionPB,
+  SelectOptionFilterPB, TextFilterConditionPB, TextFilterPB,
 };
 
 #[tokio::test]
@@ -17,9 +17,11 @@ async fn according_to_text_contains_filter_test() {
       data: TextFilterPB {
         condition: TextFilterConditionPB::TextContains,
         content: "sample".to_string(),
+        ..Default::default()
       }
       .try_into()
       .unwrap(),
+      comparison: None,
     })
     .await;
 
@@ -47,9 +49,11 @@ async fn according_to_empty_text_contains_filter_test() {
       data: TextFilterPB {
         condition: TextFilterConditionPB::TextContains,
         content: "".to_string(),
+        ..Default::default()
       }
       .try_into()
       .unwrap(),
+      comparison: None,
     })
     .await;
 
@@ -76,9 +80,11 @@ async fn according_to_text_is_not_empty_filter_test() {
       data: TextFilterPB {
         condition: TextFilterConditionPB::TextIsNotEmpty,
         content: "".to_string(),
+        ..Default::default()
       }
       .try_into()
       .unwrap(),
+      comparison: None,
     })
     .await;
 
@@ -105,6 +111,7 @@ async fn according_to_checkbox_is_unchecked_filter_test() {
       }
       .try_into()
       .unwrap(),
+      comparison: None,
     })
     .await;
 
@@ -135,6 +142,7 @@ async fn according_to_checkbox_is_checked_filter_test() {
       }
       .try_into()
       .unwrap(),
+      comparison: None,
     })
     .await;
 
@@ -170,6 +178,7 @@ async fn according_to_date_time_is_filter_test() {
       }
       .try_into()
       .unwrap(),
+      comparison: None,
     })
     .await;
 
@@ -205,6 +214,7 @@ async fn according_to_invalid_date_time_is_filter_test() {
       }
       .try_into()
       .unwrap(),
+      comparison: None,
     })
     .await;
 
@@ -249,6 +259,7 @@ async fn according_to_select_option_is_filter_test() {
       }
       .try_into()
       .unwrap(),
+      comparison: None,
     })
     .await;
 
@@ -293,6 +304,7 @@ async fn according_to_select_option_contains_filter_test() {
       }
       .try_into()
       .unwrap(),
+      comparison: None,
     })
     .await;
 
@@ -332,6 +344,7 @@ async fn according_to_select_option_is_not_empty_filter_test() {
       }
       .try_into()
       .unwrap(),
+      comparison: None,
     })
     .await;
 
@@ -348,3 +361,36 @@ async fn according_to_select_option_is_not_empty_filter_test() {
     .assert_cell_content(multi_select_field.id, 5, stringified_expected)
     .await;
 }
+
+#[tokio::test]
+async fn according_to_relation_contains_filter_test() {
+  let mut test = DatabasePreFillRowCellTest::new().await;
+  let relation_field = test.get_first_field(FieldType::Relation).await;
+  let related_row_id = test.rows[0].id.to_string();
+
+  test.assert_row_count(7).await;
+
+  test
+    .insert_filter(FilterDataPB {
+      field_id: relation_field.id.clone(),
+      field_type: FieldType::Relation,
+      data: RelationFilterPB {
+        condition: RelationFilterConditionPB::RelationContains,
+        row_ids: vec![related_row_id.clone()],
+      }
+      .try_into()
+      .unwrap(),
+      comparison: None,
+    })
+    .await;
+
+  test.wait(100).await;
+  test.assert_row_count(0).await;
+  test.create_empty_row().await;
+  test.wait(100).await;
+  test.assert_row_count(1).await;
+
+  test
+    .assert_relation_cell_strict(relation_field.id, 0, vec![related_row_id])
+    .await;
+}
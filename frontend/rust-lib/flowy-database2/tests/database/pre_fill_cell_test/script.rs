@@ -1,5 +1,6 @@
 use crate::database::database_editor::DatabaseEditorTest;
 use collab_database::fields::select_type_option::{SelectOptionIds, SELECTION_IDS_SEPARATOR};
+use collab_database::template::relation_parse::RelationCellData;
 use flowy_database2::entities::{CreateRowPayloadPB, FilterDataPB, InsertFilterPB};
 use flowy_database2::services::cell::stringify_cell;
 use std::ops::{Deref, DerefMut};
@@ -90,6 +91,23 @@ impl DatabasePreFillRowCellTest {
     assert_eq!(content, expected_content);
   }
 
+  pub async fn assert_relation_cell_strict(
+    &self,
+    field_id: String,
+    row_index: usize,
+    expected_row_ids: Vec<String>,
+  ) {
+    let rows = self.editor.get_all_rows(&self.view_id).await.unwrap();
+    let row = rows.get(row_index).unwrap();
+    let cell = row.cells.get(&field_id).cloned().unwrap_or_default();
+    let row_ids = RelationCellData::from(&cell)
+      .row_ids
+      .into_iter()
+      .map(|row_id| row_id.to_string())
+      .collect::<Vec<String>>();
+    assert_eq!(row_ids, expected_row_ids);
+  }
+
   pub async fn wait(&self, milliseconds: u64) {
     tokio::time::sleep(Duration::from_millis(milliseconds)).await;
   }
@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+
+use collab_document::document_data::default_document_data;
+use flowy_database2::entities::{
+  CreateRowPayloadPB, DeleteRowTemplatePayloadPB, FieldType, RowTemplatePB,
+  UpdateRowTemplatePayloadPB,
+};
+use flowy_database2::services::cell::stringify_cell;
+use uuid::Uuid;
+
+use crate::database::database_editor::DatabaseEditorTest;
+
+async fn insert_template(
+  test: &DatabaseEditorTest,
+  name: &str,
+  cells: HashMap<String, String>,
+) -> RowTemplatePB {
+  let document = default_document_data("row_template");
+  let payload = UpdateRowTemplatePayloadPB {
+    view_id: test.view_id.clone(),
+    template_id: None,
+    name: name.to_string(),
+    cells,
+    document: Some(serde_json::to_string(&document).unwrap()),
+  };
+  test
+    .editor
+    .modify_row_templates(payload.try_into().unwrap())
+    .await
+    .unwrap();
+  get_templates(test).await.pop().unwrap()
+}
+
+async fn get_templates(test: &DatabaseEditorTest) -> Vec<RowTemplatePB> {
+  test.editor.get_row_templates().await.items
+}
+
+#[tokio::test]
+async fn grid_create_row_from_template_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let text_field = test.get_first_field(FieldType::RichText).await;
+  let number_field = test.get_first_field(FieldType::Number).await;
+  let template = insert_template(
+    &test,
+    "Bug report",
+    HashMap::from([
+      (text_field.id.clone(), "Steps to reproduce".to_string()),
+      (number_field.id.clone(), "3".to_string()),
+    ]),
+  )
+  .await;
+  assert_eq!(template.name, "Bug report");
+  assert!(template.document.is_some());
+
+  // The cells of the payload take precedence over the cells of the template
+  let row_detail = test
+    .editor
+    .create_row(CreateRowPayloadPB {
+      view_id: test.view_id.clone(),
+      data: HashMap::from([(text_field.id.clone(), "Crash on start".to_string())]),
+      template_id: Some(template.id.clone()),
+      ..Default::default()
+    })
+    .await
+    .unwrap()
+    .unwrap();
+  let row = row_detail.row;
+  let text = stringify_cell(row.cells.get(&text_field.id).unwrap(), &text_field);
+  assert_eq!(text, "Crash on start");
+  let number = stringify_cell(row.cells.get(&number_field.id).unwrap(), &number_field);
+  assert!(number.contains('3'));
+}
+
+#[tokio::test]
+async fn grid_create_row_with_template_document_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let template = insert_template(&test, "Meeting notes", HashMap::new()).await;
+  let row_detail = test
+    .editor
+    .create_row(CreateRowPayloadPB {
+      view_id: test.view_id.clone(),
+      template_id: Some(template.id.clone()),
+      ..Default::default()
+    })
+    .await
+    .unwrap()
+    .unwrap();
+
+  // The document of the row is created from the document of the template
+  let row_meta = test
+    .editor
+    .get_row_meta(&test.view_id, &row_detail.row.id)
+    .await
+    .unwrap();
+  let document_id = Uuid::parse_str(&row_meta.document_id.unwrap()).unwrap();
+  let document = test
+    .sdk
+    .document_manager
+    .get_document_data(&document_id)
+    .await
+    .unwrap();
+  let expected = default_document_data("row_template");
+  assert_eq!(document.page_id, expected.page_id);
+  assert_eq!(document.blocks.len(), expected.blocks.len());
+}
+
+#[tokio::test]
+async fn grid_create_row_from_unknown_template_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let result = test
+    .editor
+    .create_row(CreateRowPayloadPB {
+      view_id: test.view_id.clone(),
+      template_id: Some("t:123456".to_string()),
+      ..Default::default()
+    })
+    .await;
+  assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn grid_update_and_delete_template_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let template = insert_template(&test, "Meeting notes", HashMap::new()).await;
+
+  let payload = UpdateRowTemplatePayloadPB {
+    view_id: test.view_id.clone(),
+    template_id: Some(template.id.clone()),
+    name: "Weekly meeting".to_string(),
+    cells: HashMap::new(),
+    document: None,
+  };
+  test
+    .editor
+    .modify_row_templates(payload.try_into().unwrap())
+    .await
+    .unwrap();
+  let templates = get_templates(&test).await;
+  assert_eq!(templates.len(), 1);
+  assert_eq!(templates[0].name, "Weekly meeting");
+  assert!(templates[0].document.is_none());
+
+  let payload = DeleteRowTemplatePayloadPB {
+    view_id: test.view_id.clone(),
+    template_id: template.id,
+  };
+  test
+    .editor
+    .modify_row_templates(payload.into())
+    .await
+    .unwrap();
+  assert!(get_templates(&test).await.is_empty());
+}
+
+#[tokio::test]
+async fn grid_row_templates_are_kept_after_reopen_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let template = insert_template(&test, "Bug report", HashMap::new()).await;
+  let database_manager = &test.sdk.database_manager;
+  database_manager
+    .close_database_view(&test.view_id)
+    .await
+    .unwrap();
+
+  let editor = database_manager
+    .get_database_editor_with_view_id(&test.view_id)
+    .await
+    .unwrap();
+  let templates = editor.get_row_templates().await.items;
+  assert_eq!(templates.len(), 1);
+  assert_eq!(templates[0].id, template.id);
+  assert!(templates[0].document.is_some());
+}
+
+#[tokio::test]
+async fn grid_row_templates_are_kept_after_duplicate_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let template = insert_template(&test, "Bug report", HashMap::new()).await;
+  let database_manager = &test.sdk.database_manager;
+  let new_view_id = Uuid::new_v4().to_string();
+  database_manager
+    .duplicate_database(&test.view_id, &new_view_id)
+    .await
+    .unwrap();
+
+  let editor = database_manager
+    .get_database_editor_with_view_id(&new_view_id)
+    .await
+    .unwrap();
+  let templates = editor.get_row_templates().await.items;
+  assert_eq!(templates.len(), 1);
+  assert_eq!(templates[0].id, template.id);
+  assert_eq!(templates[0].name, template.name);
+  assert!(templates[0].document.is_some());
+}
+
+#[tokio::test]
+async fn grid_row_templates_are_kept_after_raw_export_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let template = insert_template(&test, "Bug report", HashMap::new()).await;
+  let database_manager = &test.sdk.database_manager;
+  let json = database_manager
+    .get_database_json_string(&test.view_id)
+    .await
+    .unwrap();
+
+  let new_view_id = Uuid::new_v4().to_string();
+  database_manager
+    .create_database_with_data(&new_view_id, json.into_bytes())
+    .await
+    .unwrap();
+  let editor = database_manager
+    .get_database_editor_with_view_id(&new_view_id)
+    .await
+    .unwrap();
+  let templates = editor.get_row_templates().await.items;
+  assert_eq!(templates.len(), 1);
+  assert_eq!(templates[0].id, template.id);
+  assert_eq!(templates[0].name, template.name);
+}
+
+#[tokio::test]
+async fn grid_row_templates_are_restored_with_snapshot_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let template = insert_template(&test, "Bug report", HashMap::new()).await;
+  let database_manager = &test.sdk.database_manager;
+  database_manager
+    .create_database_snapshot(&test.view_id, "")
+    .await
+    .unwrap();
+  let snapshot_id = database_manager
+    .get_database_snapshots(&test.view_id, 10)
+    .await
+    .unwrap()[0]
+    .snapshot_id
+    .clone();
+
+  let payload = DeleteRowTemplatePayloadPB {
+    view_id: test.view_id.clone(),
+    template_id: template.id.clone(),
+  };
+  test
+    .editor
+    .modify_row_templates(payload.into())
+    .await
+    .unwrap();
+  assert!(get_templates(&test).await.is_empty());
+
+  database_manager
+    .restore_database_snapshot(&test.view_id, &snapshot_id)
+    .await
+    .unwrap();
+  let templates = get_templates(&test).await;
+  assert_eq!(templates.len(), 1);
+  assert_eq!(templates[0].id, template.id);
+}
@@ -1,7 +1,15 @@
-use flowy_database2::entities::FieldType;
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+
+use collab_database::database::gen_database_view_id;
+use flowy_database2::entities::{
+  FieldSettingsChangesetPB, FieldType, FieldVisibility, TextFilterConditionPB, TextFilterPB,
+};
 use flowy_database2::services::cell::stringify_cell;
 use flowy_database2::services::field::CHECK;
+use flowy_database2::services::filter::{FilterChangeset, FilterInner};
 use flowy_database2::services::share::csv::CSVFormat;
+use lib_infra::box_any::BoxAny;
 
 use crate::database::database_editor::DatabaseEditorTest;
 
@@ -85,7 +93,14 @@ async fn export_and_then_import_meta_csv_test() {
           | FieldType::Summary
           | FieldType::Time
           | FieldType::Translate
-          | FieldType::Media => {},
+          | FieldType::Media
+          | FieldType::Formula
+          | FieldType::Rollup
+          | FieldType::Rating
+          | FieldType::Email
+          | FieldType::Phone
+          | FieldType::AutoNumber
+          | FieldType::Person => {},
         }
       } else {
         panic!(
@@ -164,6 +179,21 @@ async fn history_database_import_test() {
               assert_eq!(s, "AppFlowy website - https://www.appflowy.io");
             }
           },
+          FieldType::Rating => {
+            if index == 0 {
+              assert_eq!(s, "4");
+            }
+          },
+          FieldType::Email => {
+            if index == 0 {
+              assert_eq!(s, "Lucas@appflowy.io");
+            }
+          },
+          FieldType::Phone => {
+            if index == 0 {
+              assert_eq!(s, "+15550100100");
+            }
+          },
           FieldType::Checklist
           | FieldType::LastEditedTime
           | FieldType::CreatedTime
@@ -171,7 +201,11 @@ async fn history_database_import_test() {
           | FieldType::Summary
           | FieldType::Time
           | FieldType::Translate
-          | FieldType::Media => {},
+          | FieldType::Media
+          | FieldType::Formula
+          | FieldType::Rollup
+          | FieldType::AutoNumber
+          | FieldType::Person => {},
         }
       } else {
         panic!(
@@ -182,3 +216,178 @@ async fn history_database_import_test() {
     }
   }
 }
+
+#[tokio::test]
+async fn export_and_then_import_csv_file_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let manager = test.sdk.database_manager.clone();
+  let path = std::env::temp_dir().join(format!("{}.csv", uuid::Uuid::new_v4()));
+  let path = path.to_str().unwrap().to_string();
+  manager
+    .export_csv_to_file(&test.view_id, &path, CSVFormat::Original)
+    .await
+    .unwrap();
+
+  let view_id = gen_database_view_id();
+  let database_id = manager
+    .import_csv_from_file(view_id.clone(), path.clone(), HashMap::new())
+    .await
+    .unwrap();
+  let _ = std::fs::remove_file(&path);
+
+  let database = test.get_database(&database_id).await.unwrap();
+  let fields = database.get_fields(&view_id, None).await;
+  let rows = database.get_all_rows(&view_id).await.unwrap();
+  assert_eq!(fields.len(), test.fields.len());
+  assert_eq!(rows.len(), test.rows.len());
+  assert_eq!(fields[0].name, "Name");
+}
+
+#[tokio::test]
+async fn export_json_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let json = test.editor.export_json().await.unwrap();
+  let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+  let fields = value["fields"].as_array().unwrap();
+  let rows = value["rows"].as_array().unwrap();
+  assert_eq!(fields.len(), test.fields.len());
+  assert_eq!(rows.len(), test.rows.len());
+
+  let primary_field_id = fields[0]["id"].as_str().unwrap();
+  assert_eq!(fields[0]["is_primary"], true);
+  assert_eq!(rows[0]["cells"][primary_field_id], "A");
+}
+
+#[tokio::test]
+async fn export_markdown_without_hidden_fields_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let hidden_field = test.fields[1].clone();
+  test
+    .editor
+    .update_field_settings_with_changeset(FieldSettingsChangesetPB {
+      view_id: test.view_id.clone(),
+      field_id: hidden_field.id.clone(),
+      visibility: Some(FieldVisibility::AlwaysHidden),
+      width: None,
+      wrap_cell_content: None,
+    })
+    .await
+    .unwrap();
+
+  let markdown = test.editor.export_markdown(&test.view_id).await.unwrap();
+  let lines = markdown.lines().collect::<Vec<&str>>();
+  assert_eq!(lines.len(), test.rows.len() + 2);
+  assert!(lines[0].starts_with("| Name |"));
+  assert!(!lines[0].contains(&format!("| {} |", hidden_field.name)));
+  assert!(lines[2].starts_with("| A |"));
+}
+
+#[tokio::test]
+async fn export_xlsx_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let bytes = test.editor.export_xlsx().await.unwrap();
+
+  let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+  let mut read_part = |name: &str| {
+    let mut content = String::new();
+    archive
+      .by_name(name)
+      .unwrap()
+      .read_to_string(&mut content)
+      .unwrap();
+    content
+  };
+  let sheet = read_part("xl/worksheets/sheet1.xml");
+  assert_eq!(sheet.matches("<row ").count(), test.rows.len() + 1);
+  assert!(sheet.contains("<cols>"));
+
+  let shared_strings = read_part("xl/sharedStrings.xml");
+  assert!(shared_strings.contains(r#"<t xml:space="preserve">Name</t>"#));
+
+  // The currency of the number field and the maximum of the rating field are kept.
+  let styles = read_part("xl/styles.xml");
+  assert!(styles.contains(r#"formatCode="&quot;$&quot;"#));
+  assert!(styles.contains(r#"formatCode="0&quot; / 5&quot;""#));
+}
+
+#[tokio::test]
+async fn export_view_csv_with_filter_and_hidden_field_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let primary_field = test.fields[0].clone();
+  let hidden_field = test.fields[1].clone();
+  test
+    .editor
+    .modify_view_filters(
+      &test.view_id,
+      FilterChangeset::Insert {
+        parent_filter_id: None,
+        data: FilterInner::Data {
+          field_id: primary_field.id.clone(),
+          field_type: FieldType::RichText,
+          condition_and_content: BoxAny::new(TextFilterPB {
+            condition: TextFilterConditionPB::TextContains,
+            content: "A".to_string(),
+            ..Default::default()
+          }),
+        },
+      },
+    )
+    .await
+    .unwrap();
+  test
+    .editor
+    .update_field_settings_with_changeset(FieldSettingsChangesetPB {
+      view_id: test.view_id.clone(),
+      field_id: hidden_field.id.clone(),
+      visibility: Some(FieldVisibility::AlwaysHidden),
+      width: None,
+      wrap_cell_content: None,
+    })
+    .await
+    .unwrap();
+
+  let s = test
+    .editor
+    .export_view_csv(&test.view_id, CSVFormat::Original, false)
+    .await
+    .unwrap();
+  let mut reader = csv::Reader::from_reader(s.as_bytes());
+  let headers = reader.headers().unwrap().clone();
+  assert_eq!(headers.len(), test.fields.len() - 1);
+  assert!(!headers.iter().any(|header| header == hidden_field.name));
+
+  let visible_rows = test.editor.get_all_rows(&test.view_id).await.unwrap();
+  let records = reader
+    .records()
+    .map(|record| record.unwrap())
+    .collect::<Vec<_>>();
+  assert_eq!(records.len(), visible_rows.len());
+  assert!(records.len() < test.rows.len());
+  for record in records {
+    assert!(record[0].contains('A'));
+  }
+}
+
+#[tokio::test]
+async fn export_board_view_csv_with_group_headers_test() {
+  let test = DatabaseEditorTest::new_board().await;
+  let groups = test.editor.load_groups(&test.view_id).await.unwrap().items;
+  let visible_groups = groups
+    .iter()
+    .filter(|group| group.is_visible)
+    .collect::<Vec<_>>();
+  let grouped_rows = visible_groups
+    .iter()
+    .map(|group| group.rows.len())
+    .sum::<usize>();
+
+  let s = test
+    .editor
+    .export_view_csv(&test.view_id, CSVFormat::Original, true)
+    .await
+    .unwrap();
+  let mut reader = csv::Reader::from_reader(s.as_bytes());
+  let records = reader.records().count();
+  assert_eq!(records, grouped_rows + visible_groups.len());
+}
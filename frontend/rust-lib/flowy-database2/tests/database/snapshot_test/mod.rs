@@ -0,0 +1 @@
+mod snapshot_test;
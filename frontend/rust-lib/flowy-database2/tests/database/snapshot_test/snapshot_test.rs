@@ -0,0 +1,159 @@
+use flowy_database2::entities::{FieldChangesetPB, FieldType};
+
+use crate::database::database_editor::DatabaseEditorTest;
+
+#[tokio::test]
+async fn grid_create_snapshot_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let manager = &test.sdk.database_manager;
+  manager
+    .create_database_snapshot(&test.view_id, "")
+    .await
+    .unwrap();
+  // The database didn't change, so no snapshot is written
+  manager
+    .create_database_snapshot(&test.view_id, "")
+    .await
+    .unwrap();
+
+  let snapshots = manager
+    .get_database_snapshots(&test.view_id, 10)
+    .await
+    .unwrap();
+  assert_eq!(snapshots.len(), 1);
+  assert!(snapshots[0].is_local);
+}
+
+#[tokio::test]
+async fn grid_restore_deleted_field_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let manager = &test.sdk.database_manager;
+  manager
+    .create_database_snapshot(&test.view_id, "")
+    .await
+    .unwrap();
+  let snapshot_id = manager
+    .get_database_snapshots(&test.view_id, 10)
+    .await
+    .unwrap()
+    .remove(0)
+    .snapshot_id;
+
+  let field = test.get_first_field(FieldType::Number).await;
+  let cell = test.editor.get_cell(&field.id, &test.rows[0].id).await;
+  test.editor.delete_field(&field.id).await.unwrap();
+  assert_eq!(test.get_fields().await.len(), test.fields.len() - 1);
+
+  manager
+    .restore_database_snapshot(&test.view_id, &snapshot_id)
+    .await
+    .unwrap();
+  assert_eq!(test.get_fields().await.len(), test.fields.len());
+  let restored_field = test.get_first_field(FieldType::Number).await;
+  assert_eq!(restored_field.id, field.id);
+  assert_eq!(restored_field.name, field.name);
+  assert_eq!(
+    test.editor.get_cell(&field.id, &test.rows[0].id).await,
+    cell
+  );
+
+  // The state before restoring is saved, so the restore can be rolled back
+  let snapshots = manager
+    .get_database_snapshots(&test.view_id, 10)
+    .await
+    .unwrap();
+  assert_eq!(snapshots.len(), 2);
+}
+
+#[tokio::test]
+async fn grid_restore_deleted_rows_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let manager = &test.sdk.database_manager;
+  manager
+    .create_database_snapshot(&test.view_id, "")
+    .await
+    .unwrap();
+  let snapshot_id = manager
+    .get_database_snapshots(&test.view_id, 10)
+    .await
+    .unwrap()
+    .remove(0)
+    .snapshot_id;
+
+  let row_ids = vec![test.rows[0].id.clone(), test.rows[1].id.clone()];
+  test.editor.delete_rows(&test.view_id, &row_ids).await;
+  assert!(test
+    .editor
+    .get_row(&test.view_id, &row_ids[0])
+    .await
+    .is_none());
+
+  manager
+    .restore_database_snapshot(&test.view_id, &snapshot_id)
+    .await
+    .unwrap();
+  for row_id in row_ids.iter() {
+    assert!(test.editor.get_row(&test.view_id, row_id).await.is_some());
+  }
+  let rows = test.editor.get_all_rows(&test.view_id).await.unwrap();
+  assert_eq!(rows.len(), test.rows.len());
+}
+
+#[tokio::test]
+async fn grid_snapshot_before_delete_is_throttled_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let manager = &test.sdk.database_manager;
+  manager
+    .create_database_snapshot_before(&test.view_id, "deleting rows")
+    .await;
+  test
+    .editor
+    .delete_rows(&test.view_id, &[test.rows[0].id.clone()])
+    .await;
+
+  // The previous snapshot was just taken, so the database is not serialized again
+  manager
+    .create_database_snapshot_before(&test.view_id, "deleting rows")
+    .await;
+  test
+    .editor
+    .delete_rows(&test.view_id, &[test.rows[1].id.clone()])
+    .await;
+
+  let snapshots = manager
+    .get_database_snapshots(&test.view_id, 10)
+    .await
+    .unwrap();
+  assert_eq!(snapshots.len(), 1);
+  assert_eq!(snapshots[0].snapshot_desc, "Before deleting rows");
+}
+
+#[tokio::test]
+async fn grid_snapshots_are_pruned_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let manager = &test.sdk.database_manager;
+  let field = test.get_first_field(FieldType::RichText).await;
+  for i in 0..25 {
+    test
+      .editor
+      .update_field(FieldChangesetPB {
+        field_id: field.id.clone(),
+        view_id: test.view_id.clone(),
+        name: Some(format!("Name {}", i)),
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+    manager
+      .create_database_snapshot(&test.view_id, "")
+      .await
+      .unwrap();
+  }
+
+  // Only the latest snapshots of the database are kept
+  let snapshots = manager
+    .get_database_snapshots(&test.view_id, 100)
+    .await
+    .unwrap();
+  assert_eq!(snapshots.len(), 20);
+}
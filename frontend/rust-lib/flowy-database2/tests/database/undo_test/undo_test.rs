@@ -0,0 +1,299 @@
+use collab_database::database::gen_database_view_id;
+use collab_database::views::DatabaseLayout;
+use flowy_database2::entities::{
+  CreateRowPayloadPB, FieldSettingsChangesetPB, FieldType, FieldVisibility,
+  NumberFilterConditionPB, NumberFilterPB, SortConditionPB, UpdateSortPayloadPB,
+};
+use flowy_database2::services::filter::{FilterChangeset, FilterInner};
+use lib_infra::box_any::BoxAny;
+
+use crate::database::database_editor::DatabaseEditorTest;
+
+#[tokio::test]
+async fn grid_undo_redo_cell_test() {
+  let mut test = DatabaseEditorTest::new_grid().await;
+  assert!(!test.editor.undo().await.unwrap());
+
+  let field = test.get_first_field(FieldType::RichText).await;
+  let row_id = test.rows[0].id.clone();
+  let old_cell = test.editor.get_cell(&field.id, &row_id).await;
+  test
+    .update_text_cell(row_id.clone(), "hello world")
+    .await
+    .unwrap();
+  let new_cell = test.editor.get_cell(&field.id, &row_id).await;
+  assert!(test.editor.can_undo());
+  assert!(!test.editor.can_redo());
+
+  assert!(test.editor.undo().await.unwrap());
+  assert_eq!(test.editor.get_cell(&field.id, &row_id).await, old_cell);
+  assert!(!test.editor.can_undo());
+  assert!(test.editor.can_redo());
+
+  assert!(test.editor.redo().await.unwrap());
+  assert_eq!(test.editor.get_cell(&field.id, &row_id).await, new_cell);
+  assert!(test.editor.can_undo());
+  assert!(!test.editor.can_redo());
+}
+
+#[tokio::test]
+async fn grid_new_edit_clears_redo_test() {
+  let mut test = DatabaseEditorTest::new_grid().await;
+  let row_id = test.rows[0].id.clone();
+  test.update_text_cell(row_id.clone(), "a").await.unwrap();
+  test.editor.undo().await.unwrap();
+  assert!(test.editor.can_redo());
+
+  test.update_text_cell(row_id, "b").await.unwrap();
+  assert!(!test.editor.can_redo());
+}
+
+#[tokio::test]
+async fn grid_undo_create_row_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let row_detail = test
+    .editor
+    .create_row(CreateRowPayloadPB {
+      view_id: test.view_id.clone(),
+      ..Default::default()
+    })
+    .await
+    .unwrap()
+    .unwrap();
+  assert_eq!(test.get_rows().await.len(), test.rows.len() + 1);
+
+  test.editor.undo().await.unwrap();
+  assert_eq!(test.get_rows().await.len(), test.rows.len());
+
+  test.editor.redo().await.unwrap();
+  assert!(test
+    .editor
+    .get_row(&test.view_id, &row_detail.row.id)
+    .await
+    .is_some());
+}
+
+#[tokio::test]
+async fn grid_undo_delete_rows_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let row_ids = vec![test.rows[1].id.clone(), test.rows[2].id.clone()];
+  test.editor.delete_rows(&test.view_id, &row_ids).await;
+  assert_eq!(test.get_rows().await.len(), test.rows.len() - 2);
+
+  test.editor.undo().await.unwrap();
+  // The rows are restored at their position
+  let rows = test.get_rows().await;
+  assert_eq!(rows.len(), test.rows.len());
+  assert_eq!(rows[1].id, row_ids[0]);
+  assert_eq!(rows[2].id, row_ids[1]);
+
+  test.editor.redo().await.unwrap();
+  assert_eq!(test.get_rows().await.len(), test.rows.len() - 2);
+}
+
+#[tokio::test]
+async fn grid_undo_delete_rows_in_linked_view_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let database_id = test.get_database_data(&test.view_id).await.id;
+  let linked_view_id = gen_database_view_id();
+  test
+    .sdk
+    .database_manager
+    .create_linked_view(
+      "linked grid".to_string(),
+      DatabaseLayout::Grid,
+      database_id,
+      linked_view_id.clone(),
+      test.view_id.clone(),
+    )
+    .await
+    .unwrap();
+
+  // Move the last row to the top of the linked view only
+  let rows = test.editor.get_all_rows(&linked_view_id).await.unwrap();
+  let row_id = rows.last().unwrap().id.clone();
+  test
+    .editor
+    .move_row(&linked_view_id, row_id.clone(), rows[0].id.clone())
+    .await
+    .unwrap();
+
+  test
+    .editor
+    .delete_rows(&linked_view_id, &[row_id.clone()])
+    .await;
+  test.editor.undo().await.unwrap();
+
+  // The row is restored at its position in the linked view
+  let rows = test.editor.get_all_rows(&linked_view_id).await.unwrap();
+  assert_eq!(rows[0].id, row_id);
+}
+
+#[tokio::test]
+async fn grid_undo_delete_field_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let field = test.get_first_field(FieldType::Number).await;
+  let row_id = test.rows[0].id.clone();
+  let cell = test.editor.get_cell(&field.id, &row_id).await;
+  test.editor.delete_field(&field.id).await.unwrap();
+  assert_eq!(test.get_fields().await.len(), test.fields.len() - 1);
+
+  test.editor.undo().await.unwrap();
+  assert_eq!(test.get_fields().await.len(), test.fields.len());
+  let restored_field = test.get_first_field(FieldType::Number).await;
+  assert_eq!(restored_field.id, field.id);
+  assert_eq!(test.editor.get_cell(&field.id, &row_id).await, cell);
+}
+
+#[tokio::test]
+async fn grid_undo_delete_field_restores_settings_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let field = test.get_first_field(FieldType::Number).await;
+  test
+    .editor
+    .update_field_settings_with_changeset(FieldSettingsChangesetPB {
+      view_id: test.view_id.clone(),
+      field_id: field.id.clone(),
+      visibility: Some(FieldVisibility::AlwaysHidden),
+      width: Some(320),
+      wrap_cell_content: Some(false),
+    })
+    .await
+    .unwrap();
+  test
+    .editor
+    .modify_view_filters(
+      &test.view_id,
+      FilterChangeset::Insert {
+        parent_filter_id: None,
+        data: FilterInner::Data {
+          field_id: field.id.clone(),
+          field_type: FieldType::Number,
+          condition_and_content: BoxAny::new(NumberFilterPB {
+            condition: NumberFilterConditionPB::NumberIsNotEmpty,
+            content: "".to_string(),
+          }),
+        },
+      },
+    )
+    .await
+    .unwrap();
+  test
+    .editor
+    .create_or_update_sort(UpdateSortPayloadPB {
+      view_id: test.view_id.clone(),
+      field_id: field.id.clone(),
+      sort_id: None,
+      condition: SortConditionPB::Descending,
+    })
+    .await
+    .unwrap();
+  let filters = test.database_filters().await;
+  let sorts = test.editor.get_all_sorts(&test.view_id).await.items;
+
+  test.editor.delete_field(&field.id).await.unwrap();
+  assert!(test.database_filters().await.is_empty());
+  assert!(test
+    .editor
+    .get_all_sorts(&test.view_id)
+    .await
+    .items
+    .is_empty());
+
+  test.editor.undo().await.unwrap();
+  let field_settings = test
+    .editor
+    .get_field_settings(&test.view_id, vec![field.id.clone()])
+    .await
+    .unwrap()
+    .remove(0);
+  assert_eq!(field_settings.visibility, FieldVisibility::AlwaysHidden);
+  assert_eq!(field_settings.width, 320);
+  assert!(!field_settings.wrap_cell_content);
+
+  let restored_filters = test.database_filters().await;
+  assert_eq!(restored_filters.len(), 1);
+  assert_eq!(restored_filters[0].id, filters[0].id);
+  let restored_sorts = test.editor.get_all_sorts(&test.view_id).await.items;
+  assert_eq!(restored_sorts.len(), 1);
+  assert_eq!(restored_sorts[0].id, sorts[0].id);
+}
+
+#[tokio::test]
+async fn grid_undo_switch_field_type_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let field = test.get_first_field(FieldType::Number).await;
+  test
+    .editor
+    .switch_to_field_type(&test.view_id, &field.id, FieldType::RichText, None)
+    .await
+    .unwrap();
+
+  test.editor.undo().await.unwrap();
+  let undone_field = test.editor.get_field(&field.id).await.unwrap();
+  assert_eq!(FieldType::from(undone_field.field_type), FieldType::Number);
+
+  test.editor.redo().await.unwrap();
+  let redone_field = test.editor.get_field(&field.id).await.unwrap();
+  assert_eq!(
+    FieldType::from(redone_field.field_type),
+    FieldType::RichText
+  );
+}
+
+#[tokio::test]
+async fn grid_redo_delete_field_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let field = test.get_first_field(FieldType::Number).await;
+  test.editor.delete_field(&field.id).await.unwrap();
+  test.editor.undo().await.unwrap();
+
+  // Deleting the field again is the redo itself, it isn't recorded as a new step
+  test.editor.redo().await.unwrap();
+  assert_eq!(test.get_fields().await.len(), test.fields.len() - 1);
+  assert!(test.editor.can_undo());
+  assert!(!test.editor.can_redo());
+
+  test.editor.undo().await.unwrap();
+  assert_eq!(test.get_fields().await.len(), test.fields.len());
+  assert!(!test.editor.can_undo());
+}
+
+#[tokio::test]
+async fn grid_edit_while_undoing_is_recorded_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let field = test.get_first_field(FieldType::RichText).await;
+  let first_row_id = test.rows[0].id.clone();
+  let second_row_id = test.rows[1].id.clone();
+  let old_cell = test.editor.get_cell(&field.id, &second_row_id).await;
+  test
+    .editor
+    .update_cell_with_changeset(
+      &test.view_id,
+      &first_row_id,
+      &field.id,
+      BoxAny::new("a".to_string()),
+    )
+    .await
+    .unwrap();
+
+  // The cell updated while the first edit is undone is recorded as a new step
+  let (undo_result, update_result) = tokio::join!(
+    test.editor.undo(),
+    test.editor.update_cell_with_changeset(
+      &test.view_id,
+      &second_row_id,
+      &field.id,
+      BoxAny::new("b".to_string()),
+    )
+  );
+  assert!(undo_result.unwrap());
+  update_result.unwrap();
+  assert!(test.editor.can_undo());
+
+  test.editor.undo().await.unwrap();
+  assert_eq!(
+    test.editor.get_cell(&field.id, &second_row_id).await,
+    old_cell
+  );
+}
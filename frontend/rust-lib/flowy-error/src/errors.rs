@@ -107,6 +107,10 @@ impl FlowyError {
     self.code == ErrorCode::AIMaxRequired
   }
 
+  pub fn is_task_cancelled(&self) -> bool {
+    self.code == ErrorCode::TaskCancelled
+  }
+
   static_flowy_error!(internal, ErrorCode::Internal);
   static_flowy_error!(record_not_found, ErrorCode::RecordNotFound);
   static_flowy_error!(workspace_initialize, ErrorCode::WorkspaceInitializeError);
@@ -161,6 +165,7 @@ impl FlowyError {
   static_flowy_error!(view_is_locked, ErrorCode::ViewIsLocked);
   static_flowy_error!(local_ai_not_ready, ErrorCode::LocalAINotReady);
   static_flowy_error!(local_ai_disabled, ErrorCode::LocalAIDisabled);
+  static_flowy_error!(task_cancelled, ErrorCode::TaskCancelled);
 }
 
 impl std::convert::From<ErrorCode> for FlowyError {
@@ -0,0 +1,10 @@
+-- Your SQL goes here
+CREATE TABLE database_snapshot_table (
+    id TEXT PRIMARY KEY NOT NULL,
+    database_id TEXT NOT NULL,
+    desc TEXT NOT NULL,
+    data_hash TEXT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    data BLOB NOT NULL
+);
+CREATE INDEX idx_database_snapshot_database_id ON database_snapshot_table (database_id, timestamp);
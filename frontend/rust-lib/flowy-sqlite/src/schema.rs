@@ -54,6 +54,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    database_snapshot_table (id) {
+        id -> Text,
+        database_id -> Text,
+        desc -> Text,
+        data_hash -> Text,
+        timestamp -> BigInt,
+        data -> Binary,
+    }
+}
+
 diesel::table! {
     upload_file_part (upload_id, e_tag) {
         upload_id -> Text,
@@ -133,6 +144,7 @@ diesel::allow_tables_to_appear_in_same_query!(
   chat_message_table,
   chat_table,
   collab_snapshot,
+  database_snapshot_table,
   upload_file_part,
   upload_file_table,
   user_data_migration_records,
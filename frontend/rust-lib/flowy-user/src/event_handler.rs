@@ -198,7 +198,7 @@ pub async fn get_appearance_setting(
   }
 }
 
-const DATE_TIME_SETTINGS_CACHE_KEY: &str = "date_time_settings";
+pub(crate) const DATE_TIME_SETTINGS_CACHE_KEY: &str = "date_time_settings";
 
 #[tracing::instrument(level = "debug", skip_all, err)]
 pub async fn set_date_time_settings(
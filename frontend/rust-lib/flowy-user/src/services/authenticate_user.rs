@@ -1,7 +1,9 @@
+use crate::entities::DateTimeSettingsPB;
+use crate::event_handler::DATE_TIME_SETTINGS_CACHE_KEY;
 use crate::migrations::session_migration::migrate_session_with_user_uuid;
 use crate::services::db::UserDB;
 use crate::services::entities::{UserConfig, UserPaths};
-use crate::services::sqlite_sql::user_sql::vacuum_database;
+use crate::services::sqlite_sql::user_sql::{select_user_profile, vacuum_database};
 use collab_integrate::CollabKVDB;
 
 use arc_swap::ArcSwapOption;
@@ -76,6 +78,22 @@ impl AuthenticateUser {
     Ok(workspace_uuid)
   }
 
+  pub fn user_email(&self) -> FlowyResult<String> {
+    let uid = self.user_id()?;
+    let profile = select_user_profile(uid, self.get_sqlite_connection(uid)?)?;
+    Ok(profile.email)
+  }
+
+  /// Returns the timezone of the date and time settings of the user. It's empty if the user
+  /// uses the timezone of the device.
+  pub fn timezone_id(&self) -> String {
+    self
+      .store_preferences
+      .get_object::<DateTimeSettingsPB>(DATE_TIME_SETTINGS_CACHE_KEY)
+      .map(|setting| setting.timezone_id)
+      .unwrap_or_default()
+  }
+
   pub fn workspace_database_object_id(&self) -> FlowyResult<Uuid> {
     let session = self.get_session()?;
     let id = Uuid::from_str(&session.user_workspace.workspace_database_id)?;